@@ -3,7 +3,8 @@ use noah_algebra::{
     collections::{hash_map::Iter, HashMap},
     prelude::*,
 };
-use noah_crypto::basic::anemoi_jive::{AnemoiJive, AnemoiJive381, ANEMOI_JIVE_381_SALTS};
+use noah_crypto::basic::{anemoi_jive::AnemoiJive381, arithmetic_hash::ArithmeticHash};
+use std::marker::PhantomData;
 use storage::db::MerkleDB;
 use storage::store::{ImmutablePrefixedStore, PrefixedStore, Stated, Store};
 
@@ -52,14 +53,28 @@ const ENTRY_COUNT_KEY: [u8; 4] = [0, 0, 0, 1];
 /// assert_eq!(1, v);
 ///
 /// ```
-pub struct PersistentMerkleTree<'a, D: MerkleDB> {
+///
+/// The nodes are hashed with Anemoi-Jive by default,
+/// and another hash function can be chosen with `new_with_hash`.
+pub struct PersistentMerkleTree<'a, D: MerkleDB, H: ArithmeticHash<BLSScalar> = AnemoiJive381> {
     entry_count: u64,
     store: PrefixedStore<'a, D>,
+    hash: PhantomData<H>,
 }
 
 impl<'a, D: MerkleDB> PersistentMerkleTree<'a, D> {
     /// Generates a new PersistentMerkleTree based on a sessioned KV store
-    pub fn new(mut store: PrefixedStore<'a, D>) -> Result<PersistentMerkleTree<'a, D>> {
+    pub fn new(store: PrefixedStore<'a, D>) -> Result<PersistentMerkleTree<'a, D>> {
+        Self::new_with_hash(store)
+    }
+}
+
+impl<'a, D: MerkleDB, H: ArithmeticHash<BLSScalar>> PersistentMerkleTree<'a, D, H> {
+    /// Generates a new PersistentMerkleTree based on a sessioned KV store,
+    /// whose nodes are hashed with `H`
+    pub fn new_with_hash(
+        mut store: PrefixedStore<'a, D>,
+    ) -> Result<PersistentMerkleTree<'a, D, H>> {
         let mut entry_count = 0;
 
         if let Some(bytes) = store.get(&ENTRY_COUNT_KEY)? {
@@ -80,7 +95,11 @@ impl<'a, D: MerkleDB> PersistentMerkleTree<'a, D> {
             store.state_mut().commit(0).c(d!())?;
         }
 
-        Ok(PersistentMerkleTree { entry_count, store })
+        Ok(PersistentMerkleTree {
+            entry_count,
+            store,
+            hash: PhantomData,
+        })
     }
 
     /// add a new leaf and return the leaf uid.
@@ -126,8 +145,7 @@ impl<'a, D: MerkleDB> PersistentMerkleTree<'a, D> {
                 ),
             };
 
-            let hash =
-                AnemoiJive381::eval_jive(&[sib0, sib1], &[sib2, ANEMOI_JIVE_381_SALTS[index]]);
+            let hash = H::compress(&[sib0, sib1, sib2], &H::salts()[index]);
             cache.set(keys[index + 1].0, BLSScalar::noah_to_bytes(&hash));
         }
 
@@ -410,6 +428,11 @@ impl<'a, D: MerkleDB> ImmutablePersistentMerkleTree<'a, D> {
 
 /// verify merkle proof.
 pub fn verify(leaf: BLSScalar, proof: &Proof) -> bool {
+    verify_with_hash::<AnemoiJive381>(leaf, proof)
+}
+
+/// verify merkle proof of a tree whose nodes are hashed with `H`.
+pub fn verify_with_hash<H: ArithmeticHash<BLSScalar>>(leaf: BLSScalar, proof: &Proof) -> bool {
     let mut next = leaf;
     if proof.nodes.len() != TREE_DEPTH {
         return false;
@@ -420,7 +443,7 @@ pub fn verify(leaf: BLSScalar, proof: &Proof) -> bool {
             TreePath::Middle => (node.siblings1, next, node.siblings2),
             TreePath::Right => (node.siblings1, node.siblings2, next),
         };
-        let hash = AnemoiJive381::eval_jive(&[s1, s2], &[s3, ANEMOI_JIVE_381_SALTS[i]]);
+        let hash = H::compress(&[s1, s2, s3], &H::salts()[i]);
         next = hash
     }
     next == proof.root
@@ -1,6 +1,7 @@
 use mem_db::MemoryDB;
-use noah_accumulators::merkle_tree::{verify, PersistentMerkleTree, TREE_DEPTH};
+use noah_accumulators::merkle_tree::{verify, verify_with_hash, PersistentMerkleTree, TREE_DEPTH};
 use noah_algebra::{bls12_381::BLSScalar, prelude::*};
+use noah_crypto::basic::poseidon::Poseidon381;
 use parking_lot::RwLock;
 use std::sync::Arc;
 use std::time::Instant;
@@ -46,3 +47,20 @@ fn test_merkle_tree() {
         root1
     );
 }
+
+#[test]
+fn test_merkle_tree_with_poseidon() {
+    let fdb = MemoryDB::new();
+    let cs = Arc::new(RwLock::new(ChainState::new(fdb, "test_db".to_string(), 0)));
+    let mut state = State::new(cs, false);
+    let store = PrefixedStore::new("my_store", &mut state);
+    let mut mt = PersistentMerkleTree::<_, Poseidon381>::new_with_hash(store).unwrap();
+
+    for i in 0..5u32 {
+        let uid = mt.add_commitment_hash(BLSScalar::from(i)).unwrap();
+        let proof = mt.generate_proof(uid).unwrap();
+        assert!(verify_with_hash::<Poseidon381>(BLSScalar::from(i), &proof));
+        assert!(!verify(BLSScalar::from(i), &proof));
+    }
+    mt.commit().unwrap();
+}
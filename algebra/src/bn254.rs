@@ -0,0 +1,934 @@
+use crate::{
+    errors::AlgebraError,
+    prelude::*,
+    traits::{Domain, Pairing},
+};
+use ark_bn254::{
+    fr::FrParameters, Bn254 as Bn254Pairing, Fq12Parameters, Fr, G1Affine, G1Projective, G2Affine,
+    G2Projective,
+};
+use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
+use ark_ff::{
+    BigInteger, BigInteger256, FftField, FftParameters, Field, Fp12, FpParameters, PrimeField,
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    fmt::{Debug, Display, Formatter},
+    result::Result as StdResult,
+    str::FromStr,
+};
+use digest::{generic_array::typenum::U64, Digest};
+use num_bigint::BigUint;
+use num_traits::Num;
+use wasm_bindgen::prelude::*;
+
+/// The number of bytes for a scalar value over BN254
+pub const BN254_SCALAR_LEN: usize = 32;
+
+/// The wrapped struct for [`ark_bn254::Fr`](https://docs.rs/ark-bn254/0.3.0/ark_bn254/fr/struct.FrParameters.html)
+#[wasm_bindgen]
+#[derive(Copy, Clone, PartialEq, Eq, Default, PartialOrd, Ord, Hash)]
+pub struct BN254Scalar(pub(crate) Fr);
+
+impl Debug for BN254Scalar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        <BigUint as Debug>::fmt(
+            &<BigInteger256 as Into<BigUint>>::into(self.0.into_repr()),
+            f,
+        )
+    }
+}
+
+/// The wrapped struct for [`ark_bn254::G1Projective`](https://docs.rs/ark-bn254/0.3.0/ark_bn254/g1/type.G1Projective.html)
+#[wasm_bindgen]
+#[derive(Copy, Default, Clone, PartialEq, Eq)]
+pub struct BN254G1(pub(crate) G1Projective);
+
+impl Debug for BN254G1 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        <G1Affine as Display>::fmt(&self.0.into_affine(), f)
+    }
+}
+
+/// The wrapped struct for [`ark_bn254::G2Projective`](https://docs.rs/ark-bn254/0.3.0/ark_bn254/g2/type.G2Projective.html)
+#[wasm_bindgen]
+#[derive(Copy, Default, Clone, PartialEq, Eq)]
+pub struct BN254G2(pub(crate) G2Projective);
+
+impl Debug for BN254G2 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        <G2Affine as Display>::fmt(&self.0.into_affine(), f)
+    }
+}
+
+/// The wrapped struct for [`Fp12<ark_bn254::Fq12Parameters>`](https://docs.rs/ark-bn254/0.3.0/ark_bn254/fq12/struct.Fq12Parameters.html),
+/// which is the pairing result
+#[wasm_bindgen]
+#[derive(Copy, Default, Clone, PartialEq, Eq, Debug)]
+pub struct BN254Gt(pub(crate) Fp12<Fq12Parameters>);
+
+impl FromStr for BN254Scalar {
+    type Err = AlgebraError;
+
+    fn from_str(string: &str) -> StdResult<Self, AlgebraError> {
+        let res = Fr::from_str(string);
+
+        if res.is_ok() {
+            Ok(Self(res.unwrap()))
+        } else {
+            Err(AlgebraError::DeserializationError)
+        }
+    }
+}
+
+impl BN254Scalar {
+    /// Create a new scalar element from the arkworks-rs representation.
+    pub const fn new(is_positive: bool, limbs: &[u64]) -> Self {
+        type Params = <Fr as PrimeField>::Params;
+        BN254Scalar(Fr::const_from_str(
+            &limbs,
+            is_positive,
+            Params::R2,
+            Params::MODULUS,
+            Params::INV,
+        ))
+    }
+}
+
+/// A convenient macro to initialize a field element over the BN254 curve.
+#[macro_export]
+macro_rules! new_bn254 {
+    ($c0:expr) => {{
+        let (is_positive, limbs) = ark_ff::ark_ff_macros::to_sign_and_limbs!($c0);
+        BN254Scalar::new(is_positive, &limbs)
+    }};
+}
+
+impl Into<BigUint> for BN254Scalar {
+    #[inline]
+    fn into(self) -> BigUint {
+        self.0.into_repr().into()
+    }
+}
+
+impl<'a> From<&'a BigUint> for BN254Scalar {
+    #[inline]
+    fn from(src: &BigUint) -> Self {
+        Self(Fr::from(src.clone()))
+    }
+}
+
+impl One for BN254Scalar {
+    #[inline]
+    fn one() -> Self {
+        Self(Fr::one())
+    }
+}
+
+impl Zero for BN254Scalar {
+    #[inline]
+    fn zero() -> Self {
+        Self(Fr::zero())
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+impl Add for BN254Scalar {
+    type Output = BN254Scalar;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0.add(&rhs.0))
+    }
+}
+
+impl Mul for BN254Scalar {
+    type Output = BN254Scalar;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self(self.0.mul(&rhs.0))
+    }
+}
+
+impl Sum<BN254Scalar> for BN254Scalar {
+    #[inline]
+    fn sum<I: Iterator<Item = BN254Scalar>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+
+impl<'a> Add<&'a BN254Scalar> for BN254Scalar {
+    type Output = BN254Scalar;
+
+    #[inline]
+    fn add(self, rhs: &Self) -> Self::Output {
+        Self(self.0.add(&rhs.0))
+    }
+}
+
+impl<'a> AddAssign<&'a BN254Scalar> for BN254Scalar {
+    #[inline]
+    fn add_assign(&mut self, rhs: &Self) {
+        (self.0).add_assign(&rhs.0);
+    }
+}
+
+impl<'a> Sub<&'a BN254Scalar> for BN254Scalar {
+    type Output = BN254Scalar;
+
+    #[inline]
+    fn sub(self, rhs: &Self) -> Self::Output {
+        Self(self.0.sub(&rhs.0))
+    }
+}
+
+impl<'a> SubAssign<&'a BN254Scalar> for BN254Scalar {
+    #[inline]
+    fn sub_assign(&mut self, rhs: &Self) {
+        (self.0).sub_assign(&rhs.0);
+    }
+}
+
+impl<'a> Mul<&'a BN254Scalar> for BN254Scalar {
+    type Output = BN254Scalar;
+
+    #[inline]
+    fn mul(self, rhs: &Self) -> Self::Output {
+        Self(self.0.mul(&rhs.0))
+    }
+}
+
+impl<'a> MulAssign<&'a BN254Scalar> for BN254Scalar {
+    #[inline]
+    fn mul_assign(&mut self, rhs: &Self) {
+        (self.0).mul_assign(&rhs.0);
+    }
+}
+
+impl<'a> Sum<&'a BN254Scalar> for BN254Scalar {
+    #[inline]
+    fn sum<I: Iterator<Item = &'a BN254Scalar>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+
+impl Neg for BN254Scalar {
+    type Output = BN254Scalar;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self(self.0.neg())
+    }
+}
+
+impl From<u32> for BN254Scalar {
+    #[inline]
+    fn from(value: u32) -> Self {
+        Self::from(value as u64)
+    }
+}
+
+impl From<u64> for BN254Scalar {
+    #[inline]
+    fn from(value: u64) -> Self {
+        Self(Fr::from(value))
+    }
+}
+
+impl Scalar for BN254Scalar {
+    #[inline]
+    fn random<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
+        Self(Fr::rand(rng))
+    }
+
+    #[inline]
+    fn from_hash<D>(hash: D) -> Self
+    where
+        D: Digest<OutputSize = U64> + Default,
+    {
+        let mut prng = derive_prng_from_hash::<D>(hash);
+        Self::random(&mut prng)
+    }
+
+    #[inline]
+    fn capacity() -> usize {
+        FrParameters::CAPACITY as usize
+    }
+
+    #[inline]
+    fn multiplicative_generator() -> Self {
+        Self(Fr::multiplicative_generator())
+    }
+
+    #[inline]
+    fn get_field_size_biguint() -> BigUint {
+        BigUint::from_str_radix(
+            "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+            10,
+        )
+        .unwrap()
+    }
+
+    #[inline]
+    fn get_field_size_le_bytes() -> Vec<u8> {
+        [
+            0x01, 0x00, 0x00, 0xf0, 0x93, 0xf5, 0xe1, 0x43, 0x91, 0x70, 0xb9, 0x79, 0x48, 0xe8,
+            0x33, 0x28, 0x5d, 0x58, 0x81, 0x81, 0xb6, 0x45, 0x50, 0xb8, 0x29, 0xa0, 0x31, 0xe1,
+            0x72, 0x4e, 0x64, 0x30,
+        ]
+        .to_vec()
+    }
+
+    #[inline]
+    fn get_little_endian_u64(&self) -> Vec<u64> {
+        let a = self.0.into_repr().to_bytes_le();
+        let a1 = u8_le_slice_to_u64(&a[0..8]);
+        let a2 = u8_le_slice_to_u64(&a[8..16]);
+        let a3 = u8_le_slice_to_u64(&a[16..24]);
+        let a4 = u8_le_slice_to_u64(&a[24..]);
+        vec![a1, a2, a3, a4]
+    }
+
+    #[inline]
+    fn bytes_len() -> usize {
+        BN254_SCALAR_LEN
+    }
+
+    #[inline]
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.into_repr().to_bytes_le()
+    }
+
+    #[inline]
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() > Self::bytes_len() {
+            return Err(eg!(AlgebraError::DeserializationError));
+        }
+        let mut array = vec![0u8; Self::bytes_len()];
+        array[0..bytes.len()].copy_from_slice(bytes);
+        Ok(Self(Fr::from_le_bytes_mod_order(bytes)))
+    }
+
+    #[inline]
+    fn inv(&self) -> Result<Self> {
+        let a = self.0.inverse();
+        if a.is_none() {
+            return Err(eg!(AlgebraError::GroupInversionError));
+        }
+        Ok(Self(a.unwrap()))
+    }
+
+    #[inline]
+    fn pow(&self, exponent: &[u64]) -> Self {
+        let len = exponent.len();
+        let mut array = [0u64; 4];
+        array[..len].copy_from_slice(exponent);
+        Self(self.0.pow(&array))
+    }
+
+    fn square(&self) -> Self {
+        Self(self.0.square())
+    }
+}
+
+impl Domain for BN254Scalar {
+    type Field = Fr;
+
+    #[inline]
+    fn get_field(&self) -> Self::Field {
+        self.0
+    }
+
+    #[inline]
+    fn from_field(field: Self::Field) -> Self {
+        Self(field)
+    }
+}
+
+impl Group for BN254G1 {
+    type ScalarType = BN254Scalar;
+    const COMPRESSED_LEN: usize = 32;
+
+    #[inline]
+    fn double(&self) -> Self {
+        Self(self.0.double())
+    }
+
+    #[inline]
+    fn get_identity() -> Self {
+        Self(G1Projective::zero())
+    }
+
+    #[inline]
+    fn get_base() -> Self {
+        Self(G1Projective::prime_subgroup_generator())
+    }
+
+    #[inline]
+    fn random<R: CryptoRng + RngCore>(prng: &mut R) -> Self {
+        Self::get_base().mul(&BN254Scalar::random(prng))
+    }
+
+    #[inline]
+    fn to_compressed_bytes(&self) -> Vec<u8> {
+        let affine = G1Affine::from(self.0);
+        let mut buf = Vec::new();
+        affine.serialize(&mut buf).unwrap();
+
+        buf
+    }
+
+    #[inline]
+    fn to_unchecked_bytes(&self) -> Vec<u8> {
+        let affine = G1Affine::from(self.0);
+        let mut buf = Vec::new();
+        affine.serialize_unchecked(&mut buf).unwrap();
+
+        buf
+    }
+
+    #[inline]
+    fn from_compressed_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = ark_std::io::BufReader::new(bytes);
+
+        let affine = G1Affine::deserialize(&mut reader);
+
+        if affine.is_ok() {
+            Ok(Self(G1Projective::from(affine.unwrap()))) // safe unwrap
+        } else {
+            Err(eg!(AlgebraError::DeserializationError))
+        }
+    }
+
+    #[inline]
+    fn from_unchecked_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = ark_std::io::BufReader::new(bytes);
+
+        let affine = G1Affine::deserialize_unchecked(&mut reader);
+
+        if affine.is_ok() {
+            Ok(Self(G1Projective::from(affine.unwrap()))) // safe unwrap
+        } else {
+            Err(eg!(AlgebraError::DeserializationError))
+        }
+    }
+
+    #[inline]
+    fn unchecked_size() -> usize {
+        let g = G1Affine::from(Self::get_base().0);
+        g.uncompressed_size()
+    }
+
+    #[inline]
+    fn from_hash<D>(hash: D) -> Self
+    where
+        D: Digest<OutputSize = U64> + Default,
+    {
+        let mut prng = derive_prng_from_hash::<D>(hash);
+        Self(G1Projective::rand(&mut prng))
+    }
+
+    #[inline]
+    fn multi_exp(scalars: &[&Self::ScalarType], points: &[&Self]) -> Self {
+        let scalars_raw = scalars
+            .iter()
+            .map(|r| r.0.into_repr())
+            .collect::<Vec<<FrParameters as FftParameters>::BigInt>>();
+        let points_raw = G1Projective::batch_normalization_into_affine(
+            &points.iter().map(|r| r.0).collect::<Vec<G1Projective>>(),
+        );
+
+        Self(ark_ec::msm::VariableBase::msm(&points_raw, &scalars_raw))
+    }
+}
+
+impl<'a> Add<&'a BN254G1> for BN254G1 {
+    type Output = BN254G1;
+
+    #[inline]
+    fn add(self, rhs: &Self) -> Self::Output {
+        Self(self.0.add(&rhs.0))
+    }
+}
+
+impl<'a> Sub<&'a BN254G1> for BN254G1 {
+    type Output = BN254G1;
+
+    #[inline]
+    fn sub(self, rhs: &Self) -> Self::Output {
+        Self(self.0.sub(&rhs.0))
+    }
+}
+
+impl<'a> Mul<&'a BN254Scalar> for BN254G1 {
+    type Output = BN254G1;
+
+    #[inline]
+    fn mul(self, rhs: &BN254Scalar) -> Self::Output {
+        Self(self.0.mul(&rhs.0.into_repr()))
+    }
+}
+
+impl<'a> AddAssign<&'a BN254G1> for BN254G1 {
+    #[inline]
+    fn add_assign(&mut self, rhs: &'a BN254G1) {
+        self.0.add_assign(&rhs.0)
+    }
+}
+
+impl<'a> SubAssign<&'a BN254G1> for BN254G1 {
+    #[inline]
+    fn sub_assign(&mut self, rhs: &'a BN254G1) {
+        self.0.sub_assign(&rhs.0)
+    }
+}
+
+impl<'a> MulAssign<&'a BN254Scalar> for BN254G1 {
+    #[inline]
+    fn mul_assign(&mut self, rhs: &'a BN254Scalar) {
+        self.0.mul_assign(rhs.0.clone())
+    }
+}
+
+impl Neg for BN254G1 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(self.0.neg())
+    }
+}
+
+impl Group for BN254G2 {
+    type ScalarType = BN254Scalar;
+    const COMPRESSED_LEN: usize = 64;
+
+    #[inline]
+    fn double(&self) -> Self {
+        Self(self.0.double())
+    }
+
+    #[inline]
+    fn get_identity() -> Self {
+        Self(G2Projective::zero())
+    }
+
+    #[inline]
+    fn get_base() -> Self {
+        Self(G2Projective::prime_subgroup_generator())
+    }
+
+    #[inline]
+    fn random<R: CryptoRng + RngCore>(prng: &mut R) -> Self {
+        Self::get_base().mul(&BN254Scalar::random(prng))
+    }
+
+    #[inline]
+    fn to_compressed_bytes(&self) -> Vec<u8> {
+        let affine = G2Affine::from(self.0);
+        let mut buf = Vec::new();
+        affine.serialize(&mut buf).unwrap();
+
+        buf
+    }
+
+    #[inline]
+    fn to_unchecked_bytes(&self) -> Vec<u8> {
+        let affine = G2Affine::from(self.0);
+        let mut buf = Vec::new();
+        affine.serialize_unchecked(&mut buf).unwrap();
+
+        buf
+    }
+
+    #[inline]
+    fn from_compressed_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = ark_std::io::BufReader::new(bytes);
+
+        let affine = G2Affine::deserialize(&mut reader);
+
+        if affine.is_ok() {
+            Ok(Self(affine.unwrap().into_projective()))
+        } else {
+            Err(eg!(AlgebraError::DeserializationError))
+        }
+    }
+
+    #[inline]
+    fn from_unchecked_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = ark_std::io::BufReader::new(bytes);
+
+        let affine = G2Affine::deserialize_unchecked(&mut reader);
+
+        if affine.is_ok() {
+            Ok(Self(affine.unwrap().into_projective()))
+        } else {
+            Err(eg!(AlgebraError::DeserializationError))
+        }
+    }
+
+    #[inline]
+    fn unchecked_size() -> usize {
+        let g = G2Affine::from(Self::get_base().0);
+        g.uncompressed_size()
+    }
+
+    #[inline]
+    fn from_hash<D>(hash: D) -> Self
+    where
+        D: Digest<OutputSize = U64> + Default,
+    {
+        let mut prng = derive_prng_from_hash::<D>(hash);
+        Self(G2Projective::rand(&mut prng))
+    }
+}
+
+impl Neg for BN254G2 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(self.0.neg())
+    }
+}
+
+impl<'a> Add<&'a BN254G2> for BN254G2 {
+    type Output = BN254G2;
+
+    #[inline]
+    fn add(self, rhs: &'a Self) -> Self::Output {
+        Self(self.0.add(&rhs.0))
+    }
+}
+
+impl<'a> Sub<&'a BN254G2> for BN254G2 {
+    type Output = BN254G2;
+
+    #[inline]
+    fn sub(self, rhs: &'a Self) -> Self::Output {
+        Self(self.0.sub(&rhs.0))
+    }
+}
+
+impl<'a> Mul<&'a BN254Scalar> for BN254G2 {
+    type Output = BN254G2;
+
+    #[inline]
+    fn mul(self, rhs: &'a BN254Scalar) -> Self::Output {
+        Self(self.0.mul(&rhs.0.into_repr()))
+    }
+}
+
+impl<'a> AddAssign<&'a BN254G2> for BN254G2 {
+    #[inline]
+    fn add_assign(&mut self, rhs: &BN254G2) {
+        self.0.add_assign(&rhs.0)
+    }
+}
+
+impl<'a> SubAssign<&'a BN254G2> for BN254G2 {
+    #[inline]
+    fn sub_assign(&mut self, rhs: &BN254G2) {
+        self.0.sub_assign(&rhs.0)
+    }
+}
+
+/// The pairing engine for BN254
+pub struct BN254PairingEngine;
+
+impl Pairing for BN254PairingEngine {
+    type ScalarField = BN254Scalar;
+    type G1 = BN254G1;
+    type G2 = BN254G2;
+    type Gt = BN254Gt;
+
+    #[inline]
+    fn pairing(a: &Self::G1, b: &Self::G2) -> Self::Gt {
+        BN254Gt(Bn254Pairing::pairing(a.0, b.0))
+    }
+}
+
+impl Neg for BN254Gt {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        let mut v = self.0;
+        v.conjugate();
+        Self(v)
+    }
+}
+
+impl<'a> Add<&'a BN254Gt> for BN254Gt {
+    type Output = BN254Gt;
+
+    #[inline]
+    fn add(self, rhs: &'a BN254Gt) -> Self::Output {
+        Self(self.0.mul(&rhs.0))
+    }
+}
+
+impl<'a> Sub<&'a BN254Gt> for BN254Gt {
+    type Output = BN254Gt;
+
+    #[inline]
+    fn sub(self, rhs: &'a BN254Gt) -> Self::Output {
+        let mut rhs_inverse = rhs.0.clone();
+        rhs_inverse.conjugate();
+
+        Self(self.0.mul(&rhs_inverse))
+    }
+}
+
+impl<'a> Mul<&'a BN254Scalar> for BN254Gt {
+    type Output = BN254Gt;
+
+    fn mul(self, rhs: &'a BN254Scalar) -> Self::Output {
+        let mut acc = Self::get_identity();
+
+        // This is a simple double-and-add implementation of group element
+        // multiplication, moving from most significant to least
+        // significant bit of the scalar.
+        //
+        // We skip the leading bits because they are always unset for Fr
+        // elements, which have 254 bits.
+        for bit in rhs
+            .0
+            .into_repr()
+            .to_bytes_le()
+            .iter()
+            .rev()
+            .flat_map(|byte| (0..8).rev().map(move |i| ((byte >> i) & 1u8) == 1u8))
+            .skip(2)
+        {
+            acc = acc.double();
+            if bit {
+                acc = acc.add(&self)
+            }
+        }
+
+        acc
+    }
+}
+
+impl<'a> AddAssign<&'a BN254Gt> for BN254Gt {
+    #[inline]
+    fn add_assign(&mut self, rhs: &'a BN254Gt) {
+        self.0.mul_assign(&rhs.0)
+    }
+}
+
+impl<'a> SubAssign<&'a BN254Gt> for BN254Gt {
+    #[inline]
+    fn sub_assign(&mut self, rhs: &'a BN254Gt) {
+        let mut rhs_inverse = rhs.0.clone();
+        rhs_inverse.conjugate();
+
+        self.0.mul_assign(&rhs_inverse)
+    }
+}
+
+impl Group for BN254Gt {
+    type ScalarType = BN254Scalar;
+
+    const COMPRESSED_LEN: usize = 384;
+
+    #[inline]
+    fn double(&self) -> Self {
+        Self(self.0.mul(&self.0))
+    }
+
+    #[inline]
+    fn get_identity() -> Self {
+        Self(Fp12::<Fq12Parameters>::one())
+    }
+
+    #[inline]
+    fn get_base() -> Self {
+        BN254PairingEngine::pairing(&BN254G1::get_base(), &BN254G2::get_base())
+    }
+
+    #[inline]
+    fn random<R: CryptoRng + RngCore>(prng: &mut R) -> Self {
+        Self::get_base().mul(&BN254Scalar::random(prng))
+    }
+
+    #[inline]
+    fn to_compressed_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.0.serialize(&mut buf).unwrap();
+
+        buf
+    }
+
+    #[inline]
+    fn to_unchecked_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.0.serialize_unchecked(&mut buf).unwrap();
+
+        buf
+    }
+
+    #[inline]
+    fn from_compressed_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = ark_std::io::BufReader::new(bytes);
+
+        let res = Fp12::<Fq12Parameters>::deserialize(&mut reader);
+
+        if res.is_ok() {
+            Ok(Self(res.unwrap()))
+        } else {
+            Err(eg!(AlgebraError::DeserializationError))
+        }
+    }
+
+    #[inline]
+    fn from_unchecked_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = ark_std::io::BufReader::new(bytes);
+
+        let res = Fp12::<Fq12Parameters>::deserialize_unchecked(&mut reader);
+
+        if res.is_ok() {
+            Ok(Self(res.unwrap()))
+        } else {
+            Err(eg!(AlgebraError::DeserializationError))
+        }
+    }
+
+    #[inline]
+    fn unchecked_size() -> usize {
+        let g = Self::get_base().0;
+        g.uncompressed_size()
+    }
+
+    #[inline]
+    fn from_hash<D>(hash: D) -> Self
+    where
+        D: Digest<OutputSize = U64> + Default,
+    {
+        let mut prng = derive_prng_from_hash::<D>(hash);
+        Self(Fp12::<Fq12Parameters>::rand(&mut prng))
+    }
+}
+
+#[cfg(test)]
+mod bn254_groups_test {
+    use crate::{
+        bn254::{BN254Gt, BN254PairingEngine, BN254Scalar, BN254G1, BN254G2},
+        prelude::*,
+        traits::{
+            group_tests::{test_scalar_operations, test_scalar_serialization},
+            Pairing,
+        },
+    };
+    use ark_std::test_rng;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn test_scalar_ops() {
+        test_scalar_operations::<BN254Scalar>();
+    }
+
+    #[test]
+    fn scalar_deser() {
+        test_scalar_serialization::<BN254Scalar>();
+    }
+
+    #[test]
+    fn scalar_from_to_bytes() {
+        let small_value = BN254Scalar::from(165747u32);
+        let small_value_bytes = small_value.to_bytes();
+        let expected_small_value_bytes: [u8; 32] = [
+            115, 135, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0,
+        ];
+        assert_eq!(small_value_bytes, expected_small_value_bytes);
+
+        let small_value_from_bytes = BN254Scalar::from_bytes(&small_value_bytes).unwrap();
+        assert_eq!(small_value_from_bytes, small_value);
+    }
+
+    #[test]
+    fn field_size() {
+        assert_eq!(
+            BigUint::from_bytes_le(&BN254Scalar::get_field_size_le_bytes()),
+            BN254Scalar::get_field_size_biguint()
+        );
+        assert!(BN254Scalar::from(&BN254Scalar::get_field_size_biguint()).is_zero());
+        assert_eq!(
+            BN254Scalar::multiplicative_generator(),
+            BN254Scalar::from(5u32)
+        );
+    }
+
+    #[test]
+    fn hard_coded_group_elements() {
+        let base_bn254_gt = BN254Gt::get_base();
+        let expected_base = BN254PairingEngine::pairing(&BN254G1::get_base(), &BN254G2::get_base());
+        assert_eq!(base_bn254_gt, expected_base);
+    }
+
+    #[test]
+    fn bilinear_properties() {
+        let identity_g1 = BN254G1::get_identity();
+        let identity_g2 = BN254G2::get_identity();
+        let identity_gt_computed = BN254PairingEngine::pairing(&identity_g1, &identity_g2);
+        let identity_gt = BN254Gt::get_identity();
+        assert_eq!(identity_gt, identity_gt_computed);
+
+        let mut prng = test_rng();
+
+        let s1 = BN254Scalar::random(&mut prng);
+        let s2 = BN254Scalar::random(&mut prng);
+
+        let base_g1 = BN254G1::get_base();
+        let base_g2 = BN254G2::get_base();
+
+        let s1_base_g1 = base_g1.mul(&s1);
+        let s2_base_g2 = base_g2.mul(&s2);
+
+        let gt_mapped_element = BN254PairingEngine::pairing(&s1_base_g1, &s2_base_g2);
+        let gt_base = BN254Gt::get_base();
+
+        assert_eq!(
+            gt_mapped_element,
+            BN254PairingEngine::pairing(&base_g1, &s2_base_g2).mul(&s1)
+        );
+        assert_eq!(
+            gt_mapped_element,
+            BN254PairingEngine::pairing(&s1_base_g1, &base_g2).mul(&s2)
+        );
+        assert_eq!(gt_mapped_element, gt_base.mul(&s1).mul(&s2));
+        assert_eq!(gt_mapped_element, gt_base.mul(&s1.mul(&s2)));
+    }
+
+    #[test]
+    fn serialization_of_points() {
+        let mut prng = test_rng();
+
+        let g1 = BN254G1::random(&mut prng);
+        let g1_bytes = g1.to_compressed_bytes();
+        assert_eq!(g1_bytes.len(), BN254G1::COMPRESSED_LEN);
+        assert_eq!(BN254G1::from_compressed_bytes(&g1_bytes).unwrap(), g1);
+        assert_eq!(
+            BN254G1::from_unchecked_bytes(&g1.to_unchecked_bytes()).unwrap(),
+            g1
+        );
+
+        let g2 = BN254G2::random(&mut prng);
+        let g2_bytes = g2.to_compressed_bytes();
+        assert_eq!(g2_bytes.len(), BN254G2::COMPRESSED_LEN);
+        assert_eq!(BN254G2::from_compressed_bytes(&g2_bytes).unwrap(), g2);
+        assert_eq!(
+            BN254G2::from_unchecked_bytes(&g2.to_unchecked_bytes()).unwrap(),
+            g2
+        );
+
+        let gt = BN254Gt::random(&mut prng);
+        let gt_bytes = gt.to_compressed_bytes();
+        assert_eq!(gt_bytes.len(), BN254Gt::COMPRESSED_LEN);
+        assert_eq!(BN254Gt::from_compressed_bytes(&gt_bytes).unwrap(), gt);
+    }
+}
@@ -103,9 +103,15 @@ pub enum NoahError {
     AssetTracingExtractionError,
     IdentityTracingExtractionError,
     AnonymousCredentialSignError,
+    CredentialRevokedError,
+    CredentialExpiredError,
+    AttributePredicateError,
+    CredentialIssuanceRequestError,
     R1CSProofError,
     NoMemoInAssetTracerMemo,
     BogusAssetTracerMemo,
+    MissingTracerMemoError,
+    TracingPolicyMismatchError,
     MissingURSError,
     MissingSRSError,
     MissingVerifierParamsError,
@@ -160,9 +166,15 @@ impl fmt::Display for NoahError {
             AssetTracingExtractionError => "Cannot extract correct data from tracing ciphertext",
             IdentityTracingExtractionError => "Cannot extract identity attributes from tracing ciphertext",
             AnonymousCredentialSignError => "The number of attributes passed as parameter differs from the number of attributes of the AC issuer public key",
+            CredentialRevokedError => "The credential has been revoked by the issuer",
+            CredentialExpiredError => "The credential has expired",
+            AttributePredicateError => "The attribute does not satisfy the predicate",
+            CredentialIssuanceRequestError => "The credential issuance request is malformed or its proof is invalid",
             R1CSProofError =>  "Could not create R1CSProof",
             NoMemoInAssetTracerMemo => "Cannot decrypt asset tracer memo, try brute force decoding",
             BogusAssetTracerMemo => "AssetTracerMemo decryption yields inconsistent data, try brute force decoding",
+            MissingTracerMemoError => "The record lacks a tracer memo required by the tracing policy registry",
+            TracingPolicyMismatchError => "The tracing policies of the record differ from the tracing policy registry",
             MissingURSError => "The Noah library is compiled without URS. Such parameters must be created first",
             MissingSRSError => "The Noah library is compiled without SRS, which prevents proof generation",
             MissingVerifierParamsError => "The program is loading verifier parameters that are not hardcoded. Such parameters must be created first",
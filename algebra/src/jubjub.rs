@@ -22,6 +22,29 @@ pub const JUBJUB_SCALAR_LEN: usize = 32;
 #[derive(Copy, Clone, PartialEq, Eq, Default, PartialOrd, Ord, Debug, Hash)]
 pub struct JubjubScalar(pub(crate) Fr);
 
+impl JubjubScalar {
+    /// Create a new scalar element from the arkworks-rs representation.
+    pub const fn new(is_positive: bool, limbs: &[u64]) -> Self {
+        type Params = <Fr as PrimeField>::Params;
+        JubjubScalar(Fr::const_from_str(
+            &limbs,
+            is_positive,
+            Params::R2,
+            Params::MODULUS,
+            Params::INV,
+        ))
+    }
+}
+
+/// A convenient macro to initialize a scalar over the Jubjub curve.
+#[macro_export]
+macro_rules! new_jubjub {
+    ($c0:expr) => {{
+        let (is_positive, limbs) = ark_ff::ark_ff_macros::to_sign_and_limbs!($c0);
+        JubjubScalar::new(is_positive, &limbs)
+    }};
+}
+
 /// The wrapped struct for `ark_ed_on_bls12_381::EdwardsProjective`
 #[derive(Clone, PartialEq, Debug, Copy)]
 pub struct JubjubPoint(pub EdwardsProjective);
@@ -292,6 +315,13 @@ impl JubjubPoint {
     pub fn mul_by_cofactor(&self) -> Self {
         Self(self.0.into_affine().mul_by_cofactor_to_projective())
     }
+
+    /// Check if the point is in the prime-order subgroup, i.e., if it vanishes when multiplied by
+    /// the order of the subgroup.
+    #[inline]
+    pub fn is_in_prime_order_subgroup(&self) -> bool {
+        ProjectiveCurve::mul(self.0, <Fr as PrimeField>::Params::MODULUS).is_zero()
+    }
 }
 
 impl Group for JubjubPoint {
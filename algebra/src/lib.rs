@@ -25,6 +25,9 @@
 /// Module for the BLS12-381 curve
 pub mod bls12_381;
 
+/// Module for the BN254 curve
+pub mod bn254;
+
 /// Module for the secq256k1 curve
 pub mod secq256k1;
 
@@ -1,6 +1,7 @@
 use crate::errors::AlgebraError;
 use crate::prelude::*;
 use crate::secq256k1::SECQ256K1Scalar;
+use crate::traits::Domain;
 use ark_bulletproofs_secq256k1::curve::secp256k1::{Fr, FrParameters, G1Affine, G1Projective};
 use ark_ec::short_weierstrass_jacobian::GroupProjective;
 use ark_ec::{AffineCurve, ProjectiveCurve};
@@ -302,6 +303,20 @@ impl Scalar for SECP256K1Scalar {
     }
 }
 
+impl Domain for SECP256K1Scalar {
+    type Field = Fr;
+
+    #[inline]
+    fn get_field(&self) -> Self::Field {
+        self.0
+    }
+
+    #[inline]
+    fn from_field(field: Self::Field) -> Self {
+        Self(field)
+    }
+}
+
 impl SECP256K1Scalar {
     /// Get the raw data.
     pub fn get_raw(&self) -> Fr {
@@ -2,6 +2,7 @@ use crate::secp256k1::{SECP256K1Scalar, SECP256K1G1};
 use crate::secq256k1::SECQ256K1G1;
 use crate::{
     bls12_381::{BLSGt, BLSScalar, BLSG1, BLSG2},
+    bn254::{BN254Gt, BN254Scalar, BN254G1, BN254G2},
     jubjub::{JubjubPoint, JubjubScalar},
     prelude::*,
     ristretto::{CompressedEdwardsY, CompressedRistretto, RistrettoPoint, RistrettoScalar},
@@ -30,6 +31,7 @@ macro_rules! to_from_bytes_scalar {
 
 to_from_bytes_scalar!(RistrettoScalar);
 to_from_bytes_scalar!(BLSScalar);
+to_from_bytes_scalar!(BN254Scalar);
 to_from_bytes_scalar!(JubjubScalar);
 to_from_bytes_scalar!(SECQ256K1Scalar);
 to_from_bytes_scalar!(SECP256K1Scalar);
@@ -64,6 +66,7 @@ serialize_deserialize!(CompressedRistretto);
 serialize_deserialize!(CompressedEdwardsY);
 serialize_deserialize!(RistrettoScalar);
 serialize_deserialize!(BLSScalar);
+serialize_deserialize!(BN254Scalar);
 serialize_deserialize!(JubjubScalar);
 serialize_deserialize!(SECQ256K1Scalar);
 serialize_deserialize!(SECP256K1Scalar);
@@ -86,6 +89,9 @@ to_from_bytes_group!(RistrettoPoint);
 to_from_bytes_group!(BLSG1);
 to_from_bytes_group!(BLSG2);
 to_from_bytes_group!(BLSGt);
+to_from_bytes_group!(BN254G1);
+to_from_bytes_group!(BN254G2);
+to_from_bytes_group!(BN254Gt);
 to_from_bytes_group!(JubjubPoint);
 to_from_bytes_group!(SECQ256K1G1);
 to_from_bytes_group!(SECP256K1G1);
@@ -94,6 +100,9 @@ serialize_deserialize!(RistrettoPoint);
 serialize_deserialize!(BLSG1);
 serialize_deserialize!(BLSG2);
 serialize_deserialize!(BLSGt);
+serialize_deserialize!(BN254G1);
+serialize_deserialize!(BN254G2);
+serialize_deserialize!(BN254Gt);
 serialize_deserialize!(JubjubPoint);
 serialize_deserialize!(SECQ256K1G1);
 serialize_deserialize!(SECP256K1G1);
@@ -0,0 +1,177 @@
+use ark_std::test_rng;
+use criterion::{criterion_group, criterion_main, Criterion};
+use digest::Digest;
+use merlin::Transcript;
+use noah::anon_xfr::{
+    address_folding::{
+        create_address_folding, prepare_verifier_input, prove_address_folding_in_cs,
+        prove_public_key_derivation_in_cs, AXfrAddressFoldingWitness,
+    },
+    keys::AXfrKeyPair,
+};
+use noah_algebra::{bls12_381::BLSScalar, prelude::*};
+use noah_plonk::{
+    plonk::{
+        constraint_system::{TurboCS, VarIndex},
+        indexer::indexer,
+        prover::prover,
+        verifier::verifier,
+    },
+    poly_commit::kzg_poly_com::KZGCommitmentSchemeBLS,
+};
+use sha2::Sha512;
+
+// Compare the two ways of proving the ownership of a secp256k1 address in a TurboPlonk circuit:
+// address folding, which delegates the scalar multiplication to a Bulletproofs proof over
+// secq256k1, and the native in-circuit public key derivation with field simulation.
+// Both circuits are also proven and verified with TurboPlonk over a KZG test setup.
+criterion_group!(benches, bench_address_folding, bench_public_key_derivation);
+criterion_main!(benches);
+
+fn test_hash<R: CryptoRng + RngCore>(prng: &mut R) -> Sha512 {
+    let mut hasher = Sha512::new();
+    let mut random_bytes = [0u8; 32];
+    prng.fill_bytes(&mut random_bytes);
+    hasher.update(&random_bytes);
+    hasher
+}
+
+fn alloc_keypair_scalars(
+    cs: &mut TurboCS<BLSScalar>,
+    keypair: &AXfrKeyPair,
+) -> ([VarIndex; 3], [VarIndex; 2]) {
+    let public_key_scalars = keypair.get_public_key().get_public_key_scalars().unwrap();
+    let secret_key_scalars = keypair.get_secret_key().get_secret_key_scalars().unwrap();
+
+    let public_key_scalars_vars = [
+        cs.new_variable(public_key_scalars[0]),
+        cs.new_variable(public_key_scalars[1]),
+        cs.new_variable(public_key_scalars[2]),
+    ];
+    let secret_key_scalars_vars = [
+        cs.new_variable(secret_key_scalars[0]),
+        cs.new_variable(secret_key_scalars[1]),
+    ];
+    (public_key_scalars_vars, secret_key_scalars_vars)
+}
+
+fn address_folding_cs(
+    keypair: &AXfrKeyPair,
+    witness: &AXfrAddressFoldingWitness,
+) -> TurboCS<BLSScalar> {
+    let mut cs = TurboCS::<BLSScalar>::new();
+    let (public_key_scalars_vars, secret_key_scalars_vars) =
+        alloc_keypair_scalars(&mut cs, keypair);
+    prove_address_folding_in_cs(
+        &mut cs,
+        &public_key_scalars_vars,
+        &secret_key_scalars_vars,
+        witness,
+    )
+    .unwrap();
+    cs
+}
+
+fn public_key_derivation_cs(keypair: &AXfrKeyPair) -> TurboCS<BLSScalar> {
+    let mut cs = TurboCS::<BLSScalar>::new();
+    let (public_key_scalars_vars, secret_key_scalars_vars) =
+        alloc_keypair_scalars(&mut cs, keypair);
+    prove_public_key_derivation_in_cs(
+        &mut cs,
+        &public_key_scalars_vars,
+        &secret_key_scalars_vars,
+        keypair,
+    )
+    .unwrap();
+    cs
+}
+
+fn bench_plonk<R: CryptoRng + RngCore>(
+    c: &mut Criterion,
+    group_name: &str,
+    prng: &mut R,
+    mut cs: TurboCS<BLSScalar>,
+    online_inputs: &[BLSScalar],
+) {
+    cs.pad();
+    let witness = cs.get_and_clear_witness();
+    assert!(cs.verify_witness(&witness, online_inputs).is_ok());
+
+    let pcs = KZGCommitmentSchemeBLS::new(cs.size + 3, prng);
+    let prover_params = indexer(&cs, &pcs).unwrap();
+    let verifier_params = prover_params.get_verifier_params_ref();
+
+    let proof = {
+        let mut transcript = Transcript::new(b"Address folding bench");
+        prover(prng, &mut transcript, &pcs, &cs, &prover_params, &witness).unwrap()
+    };
+
+    let mut group = c.benchmark_group(group_name);
+    group.sample_size(10);
+    group.bench_function("plonk prover", |b| {
+        b.iter(|| {
+            let mut transcript = Transcript::new(b"Address folding bench");
+            prover(prng, &mut transcript, &pcs, &cs, &prover_params, &witness).unwrap()
+        });
+    });
+    group.bench_function("plonk verifier", |b| {
+        b.iter(|| {
+            let mut transcript = Transcript::new(b"Address folding bench");
+            verifier(
+                &mut transcript,
+                &pcs,
+                &cs,
+                verifier_params,
+                online_inputs,
+                &proof,
+            )
+            .unwrap()
+        });
+    });
+    group.finish();
+}
+
+fn bench_address_folding(c: &mut Criterion) {
+    let mut prng = test_rng();
+    let keypair = AXfrKeyPair::generate(&mut prng);
+
+    let mut transcript = Transcript::new(b"Address folding bench");
+    let (instance, witness) =
+        create_address_folding(&mut prng, test_hash(&mut prng), &mut transcript, &keypair).unwrap();
+    let cs = address_folding_cs(&keypair, &witness);
+    println!("address folding: {} constraints", cs.size);
+
+    let mut group = c.benchmark_group("address_folding");
+    group.sample_size(10);
+    group.bench_function("bulletproofs side proof", |b| {
+        b.iter(|| {
+            let mut transcript = Transcript::new(b"Address folding bench");
+            create_address_folding(&mut prng, test_hash(&mut prng), &mut transcript, &keypair)
+                .unwrap()
+        });
+    });
+    group.bench_function("constraints", |b| {
+        b.iter(|| address_folding_cs(&keypair, &witness));
+    });
+    group.finish();
+
+    let online_inputs = prepare_verifier_input(&instance, &witness.beta, &witness.lambda);
+    bench_plonk(c, "address_folding", &mut prng, cs, &online_inputs);
+}
+
+fn bench_public_key_derivation(c: &mut Criterion) {
+    let mut prng = test_rng();
+    let keypair = AXfrKeyPair::generate(&mut prng);
+
+    let cs = public_key_derivation_cs(&keypair);
+    println!("in-circuit public key derivation: {} constraints", cs.size);
+
+    let mut group = c.benchmark_group("public_key_derivation");
+    group.sample_size(10);
+    group.bench_function("constraints", |b| {
+        b.iter(|| public_key_derivation_cs(&keypair));
+    });
+    group.finish();
+
+    bench_plonk(c, "public_key_derivation", &mut prng, cs, &[]);
+}
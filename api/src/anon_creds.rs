@@ -1,12 +1,15 @@
 use noah_algebra::{
     bls12_381::{BLSPairingEngine, BLSScalar, BLSG1, BLSG2},
+    ops::Range,
     prelude::*,
     traits::Pairing,
 };
 use noah_crypto::{
     anon_creds::{Attribute, CommOutput},
     basic::elgamal::elgamal_key_gen,
+    credential_predicates::AttributePredicate,
 };
+use sha2::{Digest, Sha256};
 
 type G1 = BLSG1;
 type G2 = BLSG2;
@@ -36,6 +39,95 @@ pub type Credential = noah_crypto::anon_creds::Credential<G1, G2, Attr>;
 pub type ACRevealProof = noah_crypto::anon_creds::CredentialCommOpenProof<G2, S>;
 /// The confidential opening proof.
 pub type ACConfidentialRevealProof = noah_crypto::confidential_anon_creds::CACPoK<G1, G2, S>;
+/// The issuer's revocation list.
+pub type ACRevocationList = noah_crypto::anon_creds::CredentialRevocationList<S>;
+/// The non-revocation proof.
+pub type ACNonRevocationProof = noah_crypto::anon_creds::CredentialNonRevocationProof<G2, S>;
+/// A predicate over a hidden attribute.
+pub type ACAttributePredicate = AttributePredicate<Attr>;
+/// The expiry policy of a credential, where the attribute at `attr_index` holds the expiry date
+/// of the credential, encoded by `ac_attr_from_date`. The credential is valid before this date.
+/// A proof does not reveal the expiry but a bound chosen by the prover, namely the expiry rounded
+/// down to a multiple of `granularity` days, and proves that the expiry is at least this bound.
+/// The verifier compares the bound with its own time, see `ACExpiryCheck`, so that the proof
+/// remains valid until the bound.
+/// The bound is shared by the credentials expiring in the same period, while a finer granularity
+/// would reveal the expiry and link the proofs of a credential, so the granularity must be at
+/// least `AC_EXPIRY_MIN_GRANULARITY` days.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ACExpiryPolicy {
+    /// The slot of the expiry attribute.
+    pub attr_index: usize,
+    /// The granularity of the published bound, in days.
+    pub granularity: Attr,
+}
+
+/// The minimum granularity of an expiry policy, in days.
+pub const AC_EXPIRY_MIN_GRANULARITY: Attr = 7;
+
+impl ACExpiryPolicy {
+    /// Create an expiry policy, checking that the granularity is at least
+    /// `AC_EXPIRY_MIN_GRANULARITY` days.
+    pub fn new(attr_index: usize, granularity: Attr) -> Result<Self> {
+        let policy = ACExpiryPolicy {
+            attr_index,
+            granularity,
+        };
+        policy.check().c(d!())?;
+        Ok(policy)
+    }
+
+    /// Check that the granularity is at least `AC_EXPIRY_MIN_GRANULARITY` days.
+    pub fn check(&self) -> Result<()> {
+        if self.granularity < AC_EXPIRY_MIN_GRANULARITY {
+            return Err(eg!(NoahError::ParameterError));
+        }
+        Ok(())
+    }
+
+    /// Return the bound published for a credential with the given expiry attribute.
+    pub fn bound(&self, expiry: Attr) -> Result<Attr> {
+        self.check().c(d!())?;
+        Ok(expiry - expiry % self.granularity)
+    }
+
+    /// Return the expiry check of the verifier at time `timestamp`.
+    pub fn check_at(&self, timestamp: u64) -> ACExpiryCheck {
+        ACExpiryCheck {
+            attr_index: self.attr_index,
+            timestamp,
+        }
+    }
+}
+
+/// The check that a credential has not expired, where the attribute at `attr_index` holds the
+/// expiry date of the credential, encoded by `ac_attr_from_date`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ACExpiryCheck {
+    /// The slot of the expiry attribute.
+    pub attr_index: usize,
+    /// The current time of the verifier, in seconds since the Unix epoch, whose date the revealed
+    /// expiry or the published bound must exceed.
+    pub timestamp: u64,
+}
+
+impl ACExpiryCheck {
+    /// Check if a credential whose expiry date is at least `expiry` is still valid.
+    pub fn is_valid(&self, expiry: u64) -> bool {
+        expiry > day_of_timestamp(self.timestamp)
+    }
+}
+
+/// The presentation of several credentials bound to the same user secret key.
+pub type ACPresentation = noah_crypto::anon_creds::CredentialPresentation<G1, G2, S>;
+/// The issuer key for blind issuance.
+pub type ACBlindIssuanceKey = noah_crypto::credential_issuance::CredentialBlindIssuanceKey<G1>;
+/// The user request for blind issuance.
+pub type ACIssuanceRequest = noah_crypto::credential_issuance::CredentialIssuanceRequest<G1, S>;
+/// The user secret kept until the blind signature is unblinded.
+pub type ACIssuanceSecret = noah_crypto::credential_issuance::CredentialIssuanceSecret<S>;
+/// The blind signature returned by the issuer.
+pub type ACBlindSignature = noah_crypto::credential_issuance::CredentialBlindSig<G1, S>;
 /// The attribute types.
 pub type Attr = u32;
 
@@ -103,6 +195,99 @@ pub fn ac_sign<R: CryptoRng + RngCore>(
     .c(d!())
 }
 
+/// Derive the blind issuance key of an issuer, which the issuer publishes next to its public key.
+pub fn ac_blind_issuance_key(issuer_sk: &ACIssuerSecretKey) -> ACBlindIssuanceKey {
+    noah_crypto::credential_issuance::blind_issuance_key::<BLSPairingEngine>(issuer_sk)
+}
+
+/// Check that a blind issuance key matches the issuer public key.
+pub fn ac_verify_blind_issuance_key(
+    issuer_pk: &ACIssuerPublicKey,
+    blind_key: &ACBlindIssuanceKey,
+) -> Result<()> {
+    noah_crypto::credential_issuance::verify_blind_issuance_key::<BLSPairingEngine>(
+        issuer_pk, blind_key,
+    )
+    .c(d!())
+}
+
+/// Request a credential whose attributes `attrs[i] = Some(a)` are hidden from the issuer, while
+/// the issuer sets the attributes `attrs[i] = None`.
+pub fn ac_blind_request<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    issuer_pk: &ACIssuerPublicKey,
+    blind_key: &ACBlindIssuanceKey,
+    user_sk: &ACUserSecretKey,
+    attrs: &[Option<Attr>],
+) -> Result<(ACIssuanceRequest, ACIssuanceSecret)> {
+    let attrs_scalar = attrs.iter().map(|a| a.map(BLSScalar::from)).collect_vec();
+    noah_crypto::credential_issuance::blind_issuance_request::<_, BLSPairingEngine>(
+        prng,
+        issuer_pk,
+        blind_key,
+        user_sk,
+        attrs_scalar.as_slice(),
+    )
+    .c(d!())
+}
+
+/// Verify a blind issuance request and sign it, where the issuer sets the attributes
+/// `attrs[i] = Some(a)` and leaves `None` for the attributes hidden in the request.
+pub fn ac_blind_sign<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    issuer_sk: &ACIssuerSecretKey,
+    issuer_pk: &ACIssuerPublicKey,
+    request: &ACIssuanceRequest,
+    attrs: &[Option<Attr>],
+) -> Result<ACBlindSignature> {
+    let attrs_scalar = attrs.iter().map(|a| a.map(BLSScalar::from)).collect_vec();
+    noah_crypto::credential_issuance::blind_grant_credential::<_, BLSPairingEngine>(
+        prng,
+        issuer_sk,
+        issuer_pk,
+        request,
+        attrs_scalar.as_slice(),
+    )
+    .c(d!())
+}
+
+/// Unblind the signature returned by the issuer, which then signs the hidden attributes of the
+/// request together with the attributes set by the issuer.
+/// # Example
+/// ```
+/// use rand_core::SeedableRng;
+/// use rand_chacha::ChaChaRng;
+/// use noah::anon_creds::{ac_keygen_issuer, ac_keygen_user, ac_blind_issuance_key, ac_verify_blind_issuance_key, ac_blind_request, ac_blind_sign, ac_unblind, ac_reveal, ac_verify, Credential};
+/// let mut prng = ChaChaRng::from_seed([0u8;32]);
+/// let (issuer_sk, issuer_pk) = ac_keygen_issuer::<ChaChaRng>(&mut prng, 2);
+/// let blind_key = ac_blind_issuance_key(&issuer_sk);
+/// let (user_sk, _) = ac_keygen_user::<ChaChaRng>(&mut prng, &issuer_pk);
+/// assert!(ac_verify_blind_issuance_key(&issuer_pk, &blind_key).is_ok());
+/// // the first attribute is hidden from the issuer, which sets the second one
+/// let (request, secret) = ac_blind_request::<ChaChaRng>(&mut prng, &issuer_pk, &blind_key, &user_sk, &[Some(10u32), None]).unwrap();
+/// let blind_sig = ac_blind_sign::<ChaChaRng>(&mut prng, &issuer_sk, &issuer_pk, &request, &[None, Some(20u32)]).unwrap();
+/// let signature = ac_unblind(&issuer_pk, &user_sk, &secret, &blind_sig).unwrap();
+/// let credential = Credential{
+///   sig: signature,
+///   attrs: vec![10u32, 20],
+///   ipk: issuer_pk.clone(),
+/// };
+/// let reveal_sig = ac_reveal::<ChaChaRng>(&mut prng, &user_sk, &credential, &[false, true]).unwrap();
+/// assert!(ac_verify(&issuer_pk, &[None, Some(20u32)], &reveal_sig.cm, &reveal_sig.proof_open, None).is_ok());
+/// ```
+pub fn ac_unblind(
+    issuer_pk: &ACIssuerPublicKey,
+    user_sk: &ACUserSecretKey,
+    secret: &ACIssuanceSecret,
+    blind_sig: &ACBlindSignature,
+) -> Result<ACSignature> {
+    noah_crypto::credential_issuance::unblind_credential::<BLSPairingEngine>(
+        issuer_pk, user_sk, secret, blind_sig,
+    )
+    .map(|credential| credential.sig)
+    .c(d!())
+}
+
 /// Produce an opening key for credential commitment creation and attribute opening
 /// # Example
 /// ```
@@ -259,6 +444,76 @@ pub fn ac_open_commitment<R: CryptoRng + RngCore>(
     rand: &ACCommitmentKey,
     reveal_map: &[bool],
 ) -> Result<ACRevealProof> {
+    ac_open_commitment_with_predicates(prng, usk, credential, rand, reveal_map, &[], None, None)
+        .c(d!())
+}
+
+/// Produce an AttrsRevealProof for a committed credential. A hidden attribute can be proven to
+/// satisfy a predicate by giving a `predicate_map`, which is either empty or has one entry per
+/// attribute, and the credential can be proven unrevoked by giving a `revocation_list`.
+/// ```
+/// use rand_core::SeedableRng;
+/// use rand_chacha::ChaChaRng;
+/// use noah::anon_creds::{ac_keygen_issuer, ac_keygen_user, ac_sign, ac_open_commitment_with_predicates, ac_commit, ac_verify_with_predicates, ACAttributePredicate, Credential};
+/// let mut prng = ChaChaRng::from_seed([0u8;32]);
+/// let (issuer_sk, issuer_pk) = ac_keygen_issuer(&mut prng, 2);
+/// let (user_sk, user_pk) = ac_keygen_user(&mut prng, &issuer_pk);
+/// let attributes = vec![10, 20]; // the second attribute is the age
+/// let signature = ac_sign::<ChaChaRng>(&mut prng, &issuer_sk, &user_pk, &attributes[..]).unwrap();
+/// let credential = Credential {
+///   sig:signature,
+///   attrs:attributes,
+///   ipk:issuer_pk.clone(),
+/// };
+/// let (commitment,_,key) = ac_commit::<ChaChaRng>(&mut prng, &user_sk, &credential, b"Some message").unwrap();
+/// let predicate_map = [None, Some(ACAttributePredicate::AtLeast(18))];
+/// let proof = ac_open_commitment_with_predicates::<ChaChaRng>(&mut prng, &user_sk, &credential, &key.unwrap(), &[true, false], &predicate_map, None, None).unwrap();
+/// assert!(ac_verify_with_predicates(&issuer_pk, &[Some(10), None], &commitment, &proof, &predicate_map, None, None).is_ok());
+/// ```
+/// A credential with an expiry attribute can be proven unexpired at the time of the verifier,
+/// while only revealing the expiry rounded down to the granularity of the expiry policy.
+/// ```
+/// use rand_core::SeedableRng;
+/// use rand_chacha::ChaChaRng;
+/// use noah::anon_creds::{ac_attr_from_date, ac_keygen_issuer, ac_keygen_user, ac_sign, ac_open_commitment_with_predicates, ac_commit, ac_verify_with_predicates, ACExpiryPolicy, Credential};
+/// let mut prng = ChaChaRng::from_seed([0u8;32]);
+/// let (issuer_sk, issuer_pk) = ac_keygen_issuer(&mut prng, 2);
+/// let (user_sk, user_pk) = ac_keygen_user(&mut prng, &issuer_pk);
+/// let expiry = ac_attr_from_date(2023, 11, 14).unwrap();
+/// let attributes = vec![10, expiry]; // the second attribute is the expiry
+/// let signature = ac_sign::<ChaChaRng>(&mut prng, &issuer_sk, &user_pk, &attributes[..]).unwrap();
+/// let credential = Credential {
+///   sig:signature,
+///   attrs:attributes,
+///   ipk:issuer_pk.clone(),
+/// };
+/// let (commitment,_,key) = ac_commit::<ChaChaRng>(&mut prng, &user_sk, &credential, b"Some message").unwrap();
+/// let key = key.unwrap();
+/// let policy = ACExpiryPolicy::new(1, 7).unwrap(); // bounds rounded to weeks
+/// let proof = ac_open_commitment_with_predicates::<ChaChaRng>(&mut prng, &user_sk, &credential, &key, &[true, false], &[], Some(&policy), None).unwrap();
+/// assert_eq!(proof.expiry_bound(), Some(u64::from(ac_attr_from_date(2023, 11, 13).unwrap())));
+/// let check = policy.check_at(1_650_000_000); // 2022-04-15
+/// assert!(ac_verify_with_predicates(&issuer_pk, &[Some(10), None], &commitment, &proof, &[], Some(&check), None).is_ok());
+/// let check = policy.check_at(1_699_900_000); // 2023-11-13
+/// assert!(ac_verify_with_predicates(&issuer_pk, &[Some(10), None], &commitment, &proof, &[], Some(&check), None).is_err());
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn ac_open_commitment_with_predicates<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    usk: &ACUserSecretKey,
+    credential: &Credential,
+    rand: &ACCommitmentKey,
+    reveal_map: &[bool],
+    predicate_map: &[Option<ACAttributePredicate>],
+    expiry: Option<&ACExpiryPolicy>,
+    revocation_list: Option<&ACRevocationList>,
+) -> Result<ACRevealProof> {
+    // a revealed expiry is checked directly by the verifier
+    let expiry =
+        expiry.filter(|expiry| !reveal_map.get(expiry.attr_index).copied().unwrap_or(false));
+    let (predicates, bound) =
+        prover_expiry_predicates(predicate_map, expiry, &credential.attrs).c(d!())?;
+
     let c = noah_crypto::anon_creds::Credential {
         sig: credential.sig.clone(),
         attrs: credential
@@ -271,8 +526,44 @@ pub fn ac_open_commitment<R: CryptoRng + RngCore>(
 
     let cm = ACCommitment::new(&credential.sig, &rand);
 
-    noah_crypto::anon_creds::open_comm::<_, BLSPairingEngine>(prng, usk, &c, &cm, &rand, reveal_map)
-        .c(d!())
+    noah_crypto::anon_creds::open_comm_with_predicates::<_, BLSPairingEngine>(
+        prng,
+        usk,
+        &c,
+        &cm,
+        &rand,
+        reveal_map,
+        &predicates,
+        revocation_list,
+    )
+    .map(|proof| match bound {
+        Some(bound) => proof.with_expiry_bound(bound),
+        None => proof,
+    })
+    .c(d!())
+}
+
+/// Produce an AttrsRevealProof for a committed credential, together with a proof that the
+/// credential is not revoked in `revocation_list`.
+pub fn ac_open_commitment_with_revocation<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    usk: &ACUserSecretKey,
+    credential: &Credential,
+    rand: &ACCommitmentKey,
+    reveal_map: &[bool],
+    revocation_list: &ACRevocationList,
+) -> Result<ACRevealProof> {
+    ac_open_commitment_with_predicates(
+        prng,
+        usk,
+        credential,
+        rand,
+        reveal_map,
+        &[],
+        None,
+        Some(revocation_list),
+    )
+    .c(d!())
 }
 
 /// Produce a ACRevealSig for a credential.
@@ -321,18 +612,61 @@ pub fn ac_reveal<R: CryptoRng + RngCore>(
 /// let bitmap = [true,false]; // Reveal first attribute and hide the second one
 /// let reveal_sig = ac_reveal::<ChaChaRng>(&mut prng, &user_sk, &credential, &bitmap).unwrap();
 /// let attr_map = [Some(10u32), None];
-/// let result_verification_ok = ac_verify(&issuer_pk, &attr_map, &reveal_sig.cm, &reveal_sig.proof_open);
+/// let result_verification_ok = ac_verify(&issuer_pk, &attr_map, &reveal_sig.cm, &reveal_sig.proof_open, None);
 /// assert!(result_verification_ok.is_ok());
 /// let attr_map = [None, Some(20)];
-/// let result_verification_err = ac_verify(&issuer_pk, &attr_map, &reveal_sig.cm, &reveal_sig.proof_open);
+/// let result_verification_err = ac_verify(&issuer_pk, &attr_map, &reveal_sig.cm, &reveal_sig.proof_open, None);
 /// assert!(result_verification_err.is_err());
 /// ```
+/// A credential with an expiry attribute is checked against the time of the verifier, which
+/// requires the expiry to be revealed or proven by `ac_open_commitment_with_predicates`.
+/// ```
+/// use rand_core::SeedableRng;
+/// use rand_chacha::ChaChaRng;
+/// use noah::anon_creds::{ac_attr_from_date, ac_keygen_issuer, ac_keygen_user, ac_sign, ac_verify, ac_reveal, ACExpiryPolicy, Credential};
+/// let mut prng = ChaChaRng::from_seed([0u8;32]);
+/// let (issuer_sk, issuer_pk) = ac_keygen_issuer::<ChaChaRng>(&mut prng, 2);
+/// let (user_sk, user_pk) = ac_keygen_user::<ChaChaRng>(&mut prng, &issuer_pk);
+/// let expiry = ac_attr_from_date(2023, 11, 14).unwrap();
+/// let attributes = vec![10u32, expiry]; // the second attribute is the expiry
+/// let signature = ac_sign::<ChaChaRng>(&mut prng, &issuer_sk, &user_pk, &attributes[..]).unwrap();
+/// let credential = Credential{
+///   sig:signature,
+///   attrs:attributes,
+///   ipk: issuer_pk.clone(),
+/// };
+/// let reveal_sig = ac_reveal::<ChaChaRng>(&mut prng, &user_sk, &credential, &[false, true]).unwrap();
+/// let policy = ACExpiryPolicy::new(1, 7).unwrap();
+/// let check = policy.check_at(1_650_000_000); // 2022-04-15
+/// assert!(ac_verify(&issuer_pk, &[None, Some(expiry)], &reveal_sig.cm, &reveal_sig.proof_open, Some(&check)).is_ok());
+/// let check = policy.check_at(1_700_000_000); // 2023-11-14
+/// assert!(ac_verify(&issuer_pk, &[None, Some(expiry)], &reveal_sig.cm, &reveal_sig.proof_open, Some(&check)).is_err());
+/// ```
 pub fn ac_verify(
     issuer_pub_key: &ACIssuerPublicKey,
     attrs: &[Option<Attr>],
     cm: &ACCommitment,
     proof_open: &ACRevealProof,
+    expiry: Option<&ACExpiryCheck>,
+) -> Result<()> {
+    ac_verify_with_predicates(issuer_pub_key, attrs, cm, proof_open, &[], expiry, None).c(d!())
+}
+
+/// Verifies an anonymous credential reveal proof, together with the proofs that the attributes
+/// with an entry in `predicate_map` satisfy the predicates, that the credential passes the `expiry`
+/// check and, if `revocation_list` is given, that the credential is not revoked.
+/// See `ac_open_commitment_with_predicates` for examples.
+pub fn ac_verify_with_predicates(
+    issuer_pub_key: &ACIssuerPublicKey,
+    attrs: &[Option<Attr>],
+    cm: &ACCommitment,
+    proof_open: &ACRevealProof,
+    predicate_map: &[Option<ACAttributePredicate>],
+    expiry: Option<&ACExpiryCheck>,
+    revocation_list: Option<&ACRevocationList>,
 ) -> Result<()> {
+    let predicates =
+        verifier_expiry_predicates(predicate_map, expiry, attrs, proof_open).c(d!())?;
     let attrs_scalar: Vec<Attribute<S>> = attrs
         .iter()
         .map(|attr| match attr {
@@ -341,11 +675,247 @@ pub fn ac_verify(
         })
         .collect();
 
-    noah_crypto::anon_creds::verify_open::<BLSPairingEngine>(
+    noah_crypto::anon_creds::verify_open_with_predicates::<BLSPairingEngine>(
         issuer_pub_key,
         &cm,
         &proof_open,
         attrs_scalar.as_slice(),
+        &predicates,
+        revocation_list,
+    )
+    .c(d!())
+}
+
+/// Add the predicate that the expiry attribute is at least the bound of the `expiry` policy to the
+/// predicate map, returning the bound to be published with the proof.
+fn prover_expiry_predicates(
+    predicate_map: &[Option<ACAttributePredicate>],
+    expiry: Option<&ACExpiryPolicy>,
+    attrs: &[Attr],
+) -> Result<(Vec<Option<AttributePredicate<S>>>, Option<u64>)> {
+    let mut predicates = predicates_to_scalar(predicate_map);
+    let expiry = match expiry {
+        Some(expiry) => expiry,
+        None => return Ok((predicates, None)),
+    };
+    let expiry_attr = attrs
+        .get(expiry.attr_index)
+        .c(d!(NoahError::ParameterError))?;
+    let bound = expiry.bound(*expiry_attr).c(d!())?;
+    add_expiry_predicate(&mut predicates, attrs.len(), expiry.attr_index, bound).c(d!())?;
+    Ok((predicates, Some(u64::from(bound))))
+}
+
+/// Add the predicate that the expiry attribute is at least the bound published with `proof` to
+/// the predicate map, after checking that the bound passes the `expiry` check.
+/// A revealed expiry attribute is checked directly instead, and a published bound without the
+/// `expiry` check is rejected.
+fn verifier_expiry_predicates(
+    predicate_map: &[Option<ACAttributePredicate>],
+    expiry: Option<&ACExpiryCheck>,
+    revealed: &[Option<Attr>],
+    proof: &ACPoK,
+) -> Result<Vec<Option<AttributePredicate<S>>>> {
+    let mut predicates = predicates_to_scalar(predicate_map);
+    let expiry = match expiry {
+        Some(expiry) => expiry,
+        None if proof.expiry_bound().is_some() => return Err(eg!(NoahError::ParameterError)),
+        None => return Ok(predicates),
+    };
+    if let Some(Some(expiry_attr)) = revealed.get(expiry.attr_index) {
+        if !expiry.is_valid(u64::from(*expiry_attr)) {
+            return Err(eg!(NoahError::CredentialExpiredError));
+        }
+        return Ok(predicates);
+    }
+    let bound = proof.expiry_bound().c(d!(NoahError::ParameterError))?;
+    if !expiry.is_valid(bound) {
+        return Err(eg!(NoahError::CredentialExpiredError));
+    }
+    let bound = Attr::try_from(bound).map_err(|_| eg!(NoahError::ParameterError))?;
+    add_expiry_predicate(&mut predicates, revealed.len(), expiry.attr_index, bound).c(d!())?;
+    Ok(predicates)
+}
+
+fn add_expiry_predicate(
+    predicates: &mut Vec<Option<AttributePredicate<S>>>,
+    num_attrs: usize,
+    attr_index: usize,
+    bound: Attr,
+) -> Result<()> {
+    if predicates.is_empty() {
+        *predicates = vec![None; num_attrs];
+    }
+    let slot = predicates
+        .get_mut(attr_index)
+        .c(d!(NoahError::ParameterError))?;
+    if slot.is_some() {
+        return Err(eg!(NoahError::ParameterError));
+    }
+    *slot = Some(AttributePredicate::AtLeast(BLSScalar::from(bound)));
+    Ok(())
+}
+
+fn predicates_to_scalar(
+    predicate_map: &[Option<ACAttributePredicate>],
+) -> Vec<Option<AttributePredicate<S>>> {
+    predicate_map
+        .iter()
+        .map(|p| p.as_ref().map(|p| p.map(|v| BLSScalar::from(*v))))
+        .collect()
+}
+
+/// Derive the public key of an existing user secret key for another issuer, so that credentials
+/// from several issuers can later be presented together.
+pub fn ac_user_pk_for_issuer(
+    user_sk: &ACUserSecretKey,
+    issuer_pk: &ACIssuerPublicKey,
+) -> ACUserPublicKey {
+    noah_crypto::anon_creds::user_pk_for_issuer::<BLSPairingEngine>(user_sk, issuer_pk)
+}
+
+/// Selectively reveal the attributes of several credentials, possibly from different issuers, in a
+/// single presentation proving that all of them belong to `user_sk`.
+pub fn ac_present<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    user_sk: &ACUserSecretKey,
+    credentials: &[&Credential],
+    reveal_maps: &[&[bool]],
+    msg: &[u8],
+) -> Result<ACPresentation> {
+    let cs = credentials
+        .iter()
+        .map(|credential| noah_crypto::anon_creds::Credential {
+            sig: credential.sig.clone(),
+            attrs: credential
+                .attrs
+                .iter()
+                .map(|a| BLSScalar::from(*a))
+                .collect_vec(),
+            ipk: credential.ipk.clone(),
+        })
+        .collect_vec();
+    noah_crypto::anon_creds::present_credentials::<_, BLSPairingEngine>(
+        prng,
+        user_sk,
+        &cs.iter().collect_vec(),
+        reveal_maps,
+        msg,
+    )
+    .c(d!())
+}
+
+/// Verifies a presentation of several credentials, where `attrs[i]` lists the revealed attributes
+/// of the credential granted by `issuer_pks[i]`.
+/// # Example
+/// ```
+/// use rand_core::SeedableRng;
+/// use rand_chacha::ChaChaRng;
+/// use noah::anon_creds::{ac_keygen_issuer, ac_keygen_user, ac_user_pk_for_issuer, ac_sign, ac_present, ac_verify_presentation, Credential};
+/// let mut prng = ChaChaRng::from_seed([0u8;32]);
+/// let (issuer1_sk, issuer1_pk) = ac_keygen_issuer::<ChaChaRng>(&mut prng, 2);
+/// let (issuer2_sk, issuer2_pk) = ac_keygen_issuer::<ChaChaRng>(&mut prng, 1);
+/// let (user_sk, user_pk1) = ac_keygen_user::<ChaChaRng>(&mut prng, &issuer1_pk);
+/// let user_pk2 = ac_user_pk_for_issuer(&user_sk, &issuer2_pk);
+/// let credential1 = Credential{
+///   sig: ac_sign::<ChaChaRng>(&mut prng, &issuer1_sk, &user_pk1, &[10u32, 20]).unwrap(),
+///   attrs: vec![10u32, 20],
+///   ipk: issuer1_pk.clone(),
+/// };
+/// let credential2 = Credential{
+///   sig: ac_sign::<ChaChaRng>(&mut prng, &issuer2_sk, &user_pk2, &[30u32]).unwrap(),
+///   attrs: vec![30u32],
+///   ipk: issuer2_pk.clone(),
+/// };
+/// let presentation = ac_present::<ChaChaRng>(&mut prng, &user_sk, &[&credential1, &credential2], &[&[true, false][..], &[true][..]], b"Some message").unwrap();
+/// let attrs: [&[Option<u32>]; 2] = [&[Some(10), None], &[Some(30)]];
+/// assert!(ac_verify_presentation(&[&issuer1_pk, &issuer2_pk], &attrs, &presentation, b"Some message").is_ok());
+/// let attrs: [&[Option<u32>]; 2] = [&[Some(10), None], &[Some(31)]];
+/// assert!(ac_verify_presentation(&[&issuer1_pk, &issuer2_pk], &attrs, &presentation, b"Some message").is_err());
+/// ```
+pub fn ac_verify_presentation(
+    issuer_pks: &[&ACIssuerPublicKey],
+    attrs: &[&[Option<Attr>]],
+    presentation: &ACPresentation,
+    msg: &[u8],
+) -> Result<()> {
+    let attrs_scalar = attrs
+        .iter()
+        .map(|attrs| {
+            attrs
+                .iter()
+                .map(|attr| match attr {
+                    Some(x) => Attribute::Revealed(BLSScalar::from(*x)),
+                    None => Attribute::Hidden(None),
+                })
+                .collect_vec()
+        })
+        .collect_vec();
+
+    noah_crypto::anon_creds::verify_presentation::<BLSPairingEngine>(
+        issuer_pks,
+        &attrs_scalar.iter().map(|a| a.as_slice()).collect_vec(),
+        presentation,
+        msg,
+    )
+    .c(d!())
+}
+
+/// Create an empty revocation list, where the revocation handle of a credential is the attribute
+/// at `handle_index`.
+pub fn ac_new_revocation_list(handle_index: usize) -> ACRevocationList {
+    ACRevocationList::new(handle_index)
+}
+
+/// Revoke the credentials whose revocation handle is `handle`.
+/// The list holds at most `noah_crypto::anon_creds::MAX_REVOKED_HANDLES` handles,
+/// since the non-revocation proofs grow with it.
+pub fn ac_revoke(revocation_list: &mut ACRevocationList, handle: Attr) -> Result<()> {
+    revocation_list.revoke(&BLSScalar::from(handle)).c(d!())
+}
+
+/// Verifies an anonymous credential reveal proof, and that the credential is not revoked in
+/// `revocation_list`.
+/// # Example
+/// ```
+/// use rand_core::SeedableRng;
+/// use rand_chacha::ChaChaRng;
+/// use noah::anon_creds::{ac_keygen_issuer, ac_keygen_user, ac_sign, ac_commit, ac_new_revocation_list, ac_revoke, ac_open_commitment_with_revocation, ac_verify_with_revocation, Credential};
+/// let mut prng = ChaChaRng::from_seed([0u8;32]);
+/// let (issuer_sk, issuer_pk) = ac_keygen_issuer::<ChaChaRng>(&mut prng, 2);
+/// let (user_sk, user_pk) = ac_keygen_user::<ChaChaRng>(&mut prng, &issuer_pk);
+/// let attributes = vec![10u32, 1234]; // the second attribute is the revocation handle
+/// let signature = ac_sign::<ChaChaRng>(&mut prng, &issuer_sk, &user_pk, &attributes[..]).unwrap();
+/// let credential = Credential{
+///   sig:signature,
+///   attrs:attributes,
+///   ipk: issuer_pk.clone(),
+/// };
+/// let (cm, _, key) = ac_commit::<ChaChaRng>(&mut prng, &user_sk, &credential, b"Some message").unwrap();
+/// let key = key.unwrap();
+/// let mut revocation_list = ac_new_revocation_list(1);
+/// ac_revoke(&mut revocation_list, 4321).unwrap();
+/// let proof = ac_open_commitment_with_revocation::<ChaChaRng>(&mut prng, &user_sk, &credential, &key, &[true, false], &revocation_list).unwrap();
+/// assert!(ac_verify_with_revocation(&issuer_pk, &[Some(10u32), None], &cm, &proof, &revocation_list).is_ok());
+/// ac_revoke(&mut revocation_list, 1234).unwrap();
+/// assert!(ac_verify_with_revocation(&issuer_pk, &[Some(10u32), None], &cm, &proof, &revocation_list).is_err());
+/// assert!(ac_open_commitment_with_revocation::<ChaChaRng>(&mut prng, &user_sk, &credential, &key, &[true, false], &revocation_list).is_err());
+/// ```
+pub fn ac_verify_with_revocation(
+    issuer_pub_key: &ACIssuerPublicKey,
+    attrs: &[Option<Attr>],
+    cm: &ACCommitment,
+    proof_open: &ACRevealProof,
+    revocation_list: &ACRevocationList,
+) -> Result<()> {
+    ac_verify_with_predicates(
+        issuer_pub_key,
+        attrs,
+        cm,
+        proof_open,
+        &[],
+        None,
+        Some(revocation_list),
     )
     .c(d!())
 }
@@ -356,6 +926,13 @@ pub type AttributeEncKey = noah_crypto::basic::elgamal::ElGamalEncKey<G1>;
 pub type AttributeDecKey = noah_crypto::basic::elgamal::ElGamalDecKey<S>;
 /// The ciphertext of an attribute.
 pub type AttributeCiphertext = noah_crypto::basic::elgamal::ElGamalCiphertext<G1>;
+/// A share of the attribute decryption key.
+pub type AttributeDecKeyShare = noah_crypto::basic::threshold_elgamal::ElGamalDecKeyShare<S>;
+/// A decryption share of an attribute ciphertext.
+pub type AttributeDecShare = noah_crypto::basic::threshold_elgamal::ElGamalDecShare<S, G1>;
+/// The public parameters of a shared attribute decryption key.
+pub type AttributeThresholdParams =
+    noah_crypto::basic::threshold_elgamal::ThresholdElGamalParams<G1>;
 
 /// Confidential anonymous credential
 pub type ConfidentialAC = noah_crypto::confidential_anon_creds::ConfidentialAC<G1, G2, S>;
@@ -434,9 +1011,761 @@ pub fn ac_confidential_verify(
     .c(d!())
 }
 
+/// Produce a confidential anonymous credential revealing proof, together with a zero-knowledge
+/// proof that the credential is not revoked in `revocation_list`.
+/// This is `ac_confidential_open_commitment_with_predicates` without predicates.
+#[allow(clippy::too_many_arguments)]
+pub fn ac_confidential_open_commitment_with_revocation<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    usk: &ACUserSecretKey,
+    credential: &Credential,
+    rand: &ACCommitmentKey,
+    enc_key: &AttributeEncKey,
+    reveal_map: &[bool],
+    msg: &[u8],
+    revocation_list: &ACRevocationList,
+) -> Result<ConfidentialAC> {
+    ac_confidential_open_commitment_with_predicates(
+        prng,
+        usk,
+        credential,
+        rand,
+        enc_key,
+        reveal_map,
+        msg,
+        &[],
+        None,
+        Some(revocation_list),
+    )
+    .c(d!())
+}
+
+/// Verify a confidential anonymous credential reveal proof, and that the credential is not
+/// revoked in `revocation_list`.
+/// This is `ac_confidential_verify_with_predicates` without predicates.
+#[allow(clippy::too_many_arguments)]
+pub fn ac_confidential_verify_with_revocation(
+    issuer_pk: &ACIssuerPublicKey,
+    enc_key: &AttributeEncKey,
+    reveal_map: &[bool],
+    sig_commitment: &ACCommitment,
+    attr_ctext: &[AttributeCiphertext],
+    cac_proof: &ACConfidentialRevealProof,
+    msg: &[u8],
+    revocation_list: &ACRevocationList,
+) -> Result<()> {
+    ac_confidential_verify_with_predicates(
+        issuer_pk,
+        enc_key,
+        reveal_map,
+        sig_commitment,
+        attr_ctext,
+        cac_proof,
+        msg,
+        &[],
+        None,
+        Some(revocation_list),
+    )
+    .c(d!())
+}
+
+/// Produce a confidential anonymous credential revealing proof, together with zero-knowledge
+/// proofs that the attributes with an entry in `predicate_map` satisfy the predicates, that the
+/// expiry attribute is at least the bound of the `expiry` policy and, if `revocation_list` is
+/// given, that the credential is not revoked.
+#[allow(clippy::too_many_arguments)]
+pub fn ac_confidential_open_commitment_with_predicates<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    usk: &ACUserSecretKey,
+    credential: &Credential,
+    rand: &ACCommitmentKey,
+    enc_key: &AttributeEncKey,
+    reveal_map: &[bool],
+    msg: &[u8],
+    predicate_map: &[Option<ACAttributePredicate>],
+    expiry: Option<&ACExpiryPolicy>,
+    revocation_list: Option<&ACRevocationList>,
+) -> Result<ConfidentialAC> {
+    ac_confidential_open_commitment_with_linked(
+        prng,
+        usk,
+        credential,
+        rand,
+        enc_key,
+        reveal_map,
+        msg,
+        predicate_map,
+        expiry,
+        revocation_list,
+        &[],
+    )
+    .c(d!())
+}
+
+/// Produce a confidential anonymous credential revealing proof as in
+/// `ac_confidential_open_commitment_with_predicates`, which also reveals to `enc_key` the
+/// attributes of the `linked` credentials, possibly granted by other issuers, and proves that all
+/// credentials belong to `usk`. Each linked credential is given together with its reveal map, and
+/// the ciphertexts of its revealed attributes follow those of `credential`.
+#[allow(clippy::too_many_arguments)]
+pub fn ac_confidential_open_commitment_with_linked<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    usk: &ACUserSecretKey,
+    credential: &Credential,
+    rand: &ACCommitmentKey,
+    enc_key: &AttributeEncKey,
+    reveal_map: &[bool],
+    msg: &[u8],
+    predicate_map: &[Option<ACAttributePredicate>],
+    expiry: Option<&ACExpiryPolicy>,
+    revocation_list: Option<&ACRevocationList>,
+    linked: &[(&Credential, &[bool])],
+) -> Result<ConfidentialAC> {
+    // the attributes are only revealed to the tracer, so the expiry is always proven
+    let (predicates, bound) =
+        prover_expiry_predicates(predicate_map, expiry, &credential.attrs).c(d!())?;
+    let to_scalar_credential = |credential: &Credential| noah_crypto::anon_creds::Credential {
+        sig: credential.sig.clone(),
+        attrs: credential
+            .attrs
+            .iter()
+            .map(|x| BLSScalar::from(*x))
+            .collect_vec(),
+        ipk: credential.ipk.clone(),
+    };
+    let c = to_scalar_credential(credential);
+    let linked_cs = linked
+        .iter()
+        .map(|(credential, _)| to_scalar_credential(credential))
+        .collect_vec();
+    let cm = ACCommitment::new(&credential.sig, &rand);
+    noah_crypto::confidential_anon_creds::confidential_open_comm_with_linked::<R, BLSPairingEngine>(
+        prng,
+        usk,
+        &c,
+        &cm,
+        rand,
+        reveal_map,
+        enc_key,
+        msg,
+        &predicates,
+        revocation_list,
+        &linked_cs
+            .iter()
+            .zip(linked.iter())
+            .map(|(c, (_, reveal_map))| (c, *reveal_map))
+            .collect_vec(),
+    )
+    .map(|mut cac| {
+        if let Some(bound) = bound {
+            cac.pok.pok = cac.pok.pok.with_expiry_bound(bound);
+        }
+        cac
+    })
+    .c(d!())
+}
+
+/// Verify a confidential anonymous credential reveal proof, together with the proofs that the
+/// attributes with an entry in `predicate_map` satisfy the predicates, that the credential passes
+/// the `expiry` check and, if `revocation_list` is given, that the credential is not revoked.
+#[allow(clippy::too_many_arguments)]
+pub fn ac_confidential_verify_with_predicates(
+    issuer_pk: &ACIssuerPublicKey,
+    enc_key: &AttributeEncKey,
+    reveal_map: &[bool],
+    sig_commitment: &ACCommitment,
+    attr_ctext: &[AttributeCiphertext],
+    cac_proof: &ACConfidentialRevealProof,
+    msg: &[u8],
+    predicate_map: &[Option<ACAttributePredicate>],
+    expiry: Option<&ACExpiryCheck>,
+    revocation_list: Option<&ACRevocationList>,
+) -> Result<()> {
+    ac_confidential_verify_with_linked(
+        issuer_pk,
+        enc_key,
+        reveal_map,
+        sig_commitment,
+        attr_ctext,
+        cac_proof,
+        msg,
+        predicate_map,
+        expiry,
+        revocation_list,
+        &[],
+    )
+    .c(d!())
+}
+
+/// Verify a confidential anonymous credential reveal proof as in
+/// `ac_confidential_verify_with_predicates`, together with the openings of the linked credentials,
+/// each given by the public key of its issuer and its reveal map.
+#[allow(clippy::too_many_arguments)]
+pub fn ac_confidential_verify_with_linked(
+    issuer_pk: &ACIssuerPublicKey,
+    enc_key: &AttributeEncKey,
+    reveal_map: &[bool],
+    sig_commitment: &ACCommitment,
+    attr_ctext: &[AttributeCiphertext],
+    cac_proof: &ACConfidentialRevealProof,
+    msg: &[u8],
+    predicate_map: &[Option<ACAttributePredicate>],
+    expiry: Option<&ACExpiryCheck>,
+    revocation_list: Option<&ACRevocationList>,
+    linked: &[(&ACIssuerPublicKey, &[bool])],
+) -> Result<()> {
+    let predicates = verifier_expiry_predicates(
+        predicate_map,
+        expiry,
+        &vec![None; issuer_pk.num_attrs()],
+        &cac_proof.pok,
+    )
+    .c(d!())?;
+    noah_crypto::confidential_anon_creds::confidential_verify_open_with_linked::<BLSPairingEngine>(
+        issuer_pk,
+        enc_key,
+        reveal_map,
+        sig_commitment,
+        attr_ctext,
+        cac_proof,
+        msg,
+        &predicates,
+        revocation_list,
+        linked,
+    )
+    .c(d!())
+}
+
 /// Generate encryptiion key for confidential anonymous credentials.
 pub fn ac_confidential_gen_encryption_keys<R: CryptoRng + RngCore>(
     prng: &mut R,
 ) -> (AttributeDecKey, AttributeEncKey) {
     elgamal_key_gen::<_, G1>(prng)
 }
+
+/// The current version of the credential wallet format.
+pub const AC_WALLET_VERSION: u32 = 1;
+
+/// The number of attribute slots holding a string, which together store its 256-bit hash.
+pub const AC_STRING_ATTR_SLOTS: usize = 8;
+
+/// The type of the value held by an attribute.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ACAttributeType {
+    /// An unsigned integer stored as is.
+    Integer,
+    /// A string, stored as a hash split over `AC_STRING_ATTR_SLOTS` slots by `ac_attrs_from_str`.
+    String,
+    /// A date, stored as a day number by `ac_attr_from_date`, as the expiry of a credential.
+    Date,
+}
+
+impl ACAttributeType {
+    /// Return the number of attribute slots taken by a value of this type.
+    pub fn num_slots(&self) -> usize {
+        match self {
+            ACAttributeType::String => AC_STRING_ATTR_SLOTS,
+            ACAttributeType::Integer | ACAttributeType::Date => 1,
+        }
+    }
+}
+
+/// The name and the type of an attribute, which takes consecutive attribute slots.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ACAttributeSchema {
+    /// The name of the attribute.
+    pub name: String,
+    /// The type of the attribute.
+    pub attr_type: ACAttributeType,
+}
+
+/// A credential together with the issuer metadata, in a versioned format for storage.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ACWalletCredential {
+    /// The version of the format.
+    pub version: u32,
+    /// The fingerprint of the issuer public key.
+    pub issuer_fingerprint: [u8; 32],
+    /// The schema of the attributes, in the order of their slots.
+    pub schema: Vec<ACAttributeSchema>,
+    /// The issuance time, in seconds since the Unix epoch.
+    pub issued_at: u64,
+    /// The name of the date attribute holding the expiry of the credential, if any.
+    pub expiry_attr: Option<String>,
+    /// The credential.
+    pub credential: Credential,
+}
+
+impl ACWalletCredential {
+    /// Wrap a credential with its schema and issuance metadata.
+    pub fn new(
+        credential: Credential,
+        schema: Vec<ACAttributeSchema>,
+        issued_at: u64,
+        expiry_attr: Option<String>,
+    ) -> Result<Self> {
+        let wallet_credential = ACWalletCredential {
+            version: AC_WALLET_VERSION,
+            issuer_fingerprint: ac_issuer_fingerprint(&credential.ipk),
+            schema,
+            issued_at,
+            expiry_attr,
+            credential,
+        };
+        wallet_credential.check().c(d!())?;
+        Ok(wallet_credential)
+    }
+
+    /// Check the version, the issuer fingerprint, the schema and the signed expiry, e.g., after
+    /// deserialization.
+    pub fn check(&self) -> Result<()> {
+        if self.version != AC_WALLET_VERSION {
+            return Err(eg!(NoahError::DeserializationError));
+        }
+        if self.issuer_fingerprint != ac_issuer_fingerprint(&self.credential.ipk) {
+            return Err(eg!(NoahError::ParameterError));
+        }
+        let n = self.credential.ipk.num_attrs();
+        let num_slots: usize = self
+            .schema
+            .iter()
+            .map(|attr| attr.attr_type.num_slots())
+            .sum();
+        if num_slots != n || self.credential.attrs.len() != n {
+            return Err(eg!(NoahError::ParameterError));
+        }
+        for (i, attr) in self.schema.iter().enumerate() {
+            if self.schema[..i].iter().any(|other| other.name == attr.name) {
+                return Err(eg!(NoahError::ParameterError));
+            }
+        }
+        if let Some(name) = &self.expiry_attr {
+            let is_date = self
+                .schema
+                .iter()
+                .any(|attr| &attr.name == name && attr.attr_type == ACAttributeType::Date);
+            let expiry = self.expiry().c(d!(NoahError::ParameterError))?;
+            if !is_date || u64::from(expiry) <= day_of_timestamp(self.issued_at) {
+                return Err(eg!(NoahError::ParameterError));
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the expiry date of the credential, as signed by the issuer.
+    pub fn expiry(&self) -> Option<Attr> {
+        self.expiry_attr
+            .as_ref()
+            .and_then(|name| self.attribute(name))
+            .and_then(|attr| attr.first().copied())
+    }
+
+    /// Return the slots of the attribute with the given name.
+    pub fn attribute_slots(&self, name: &str) -> Option<Range<usize>> {
+        let mut start = 0;
+        for attr in self.schema.iter() {
+            let end = start + attr.attr_type.num_slots();
+            if attr.name == name {
+                return Some(start..end);
+            }
+            start = end;
+        }
+        None
+    }
+
+    /// Return the value of the attribute with the given name, one entry per slot.
+    pub fn attribute(&self, name: &str) -> Option<&[Attr]> {
+        self.attribute_slots(name)
+            .and_then(|slots| self.credential.attrs.get(slots))
+    }
+
+    /// Build the reveal map that reveals exactly the attributes with the given names.
+    pub fn reveal_map(&self, names: &[&str]) -> Result<Vec<bool>> {
+        let mut reveal_map = vec![false; self.credential.attrs.len()];
+        for name in names {
+            let slots = self
+                .attribute_slots(name)
+                .c(d!(NoahError::ParameterError))?;
+            reveal_map
+                .get_mut(slots)
+                .c(d!(NoahError::ParameterError))?
+                .iter_mut()
+                .for_each(|b| *b = true);
+        }
+        Ok(reveal_map)
+    }
+
+    /// Check if the credential has expired at time `now`, in seconds since the Unix epoch.
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expiry()
+            .map_or(false, |expiry| u64::from(expiry) <= day_of_timestamp(now))
+    }
+}
+
+/// Compute the fingerprint of an issuer public key, which identifies the issuer of a credential.
+pub fn ac_issuer_fingerprint(issuer_pk: &ACIssuerPublicKey) -> [u8; 32] {
+    let mut hash = Sha256::new();
+    hash.update(b"Noah AC issuer fingerprint");
+    hash.update(issuer_pk.gen2.to_compressed_bytes());
+    hash.update(issuer_pk.xx2.to_compressed_bytes());
+    hash.update(issuer_pk.zz1.to_compressed_bytes());
+    hash.update(issuer_pk.zz2.to_compressed_bytes());
+    for yy2i in issuer_pk.yy2.iter() {
+        hash.update(yy2i.to_compressed_bytes());
+    }
+    let mut fingerprint = [0u8; 32];
+    fingerprint.copy_from_slice(&hash.finalize());
+    fingerprint
+}
+
+/// Encode a string into `AC_STRING_ATTR_SLOTS` attributes holding its hash, so that the string
+/// can be revealed and compared but not recovered from the attributes.
+/// # Example
+/// ```
+/// use noah::anon_creds::{ac_attrs_from_str, AC_STRING_ATTR_SLOTS};
+/// let name = ac_attrs_from_str("Alice");
+/// assert_eq!(name.len(), AC_STRING_ATTR_SLOTS);
+/// assert_ne!(name, ac_attrs_from_str("Bob"));
+/// ```
+pub fn ac_attrs_from_str(s: &str) -> [Attr; AC_STRING_ATTR_SLOTS] {
+    let mut hash = Sha256::new();
+    hash.update(b"Noah AC string attribute");
+    hash.update(s.as_bytes());
+    let digest = hash.finalize();
+    let mut attrs = [0; AC_STRING_ATTR_SLOTS];
+    for (attr, bytes) in attrs.iter_mut().zip(digest.chunks_exact(4)) {
+        *attr = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+    attrs
+}
+
+/// Encode a date of the Gregorian calendar into an attribute, as the number of days since
+/// 0001-01-01. The encoding preserves the order of dates, so they can be used in predicates.
+/// It is the encoding of all the times in attributes, including the expiry of credentials.
+/// # Example
+/// ```
+/// use noah::anon_creds::{ac_attr_from_date, ac_attr_to_date};
+/// let birth_date = ac_attr_from_date(1990, 2, 28).unwrap();
+/// assert!(birth_date < ac_attr_from_date(1990, 3, 1).unwrap());
+/// assert_eq!(ac_attr_to_date(birth_date).unwrap(), (1990, 2, 28));
+/// assert!(ac_attr_from_date(1990, 2, 29).is_err());
+/// ```
+pub fn ac_attr_from_date(year: u32, month: u32, day: u32) -> Result<Attr> {
+    if !(1..=9999).contains(&year)
+        || !(1..=12).contains(&month)
+        || day == 0
+        || day > days_in_month(year, month)
+    {
+        return Err(eg!(NoahError::ParameterError));
+    }
+    let days_before_month: u32 = (1..month).map(|m| days_in_month(year, m)).sum();
+    Ok(days_before_year(year) + days_before_month + day - 1)
+}
+
+/// Encode the date of a time in seconds since the Unix epoch into an attribute,
+/// following `ac_attr_from_date`.
+/// # Example
+/// ```
+/// use noah::anon_creds::{ac_attr_from_date, ac_attr_from_timestamp};
+/// let date = ac_attr_from_timestamp(1_700_000_000).unwrap(); // 2023-11-14 22:13:20 UTC
+/// assert_eq!(date, ac_attr_from_date(2023, 11, 14).unwrap());
+/// ```
+pub fn ac_attr_from_timestamp(timestamp: u64) -> Result<Attr> {
+    let day = day_of_timestamp(timestamp);
+    if day >= u64::from(days_before_year(10000)) {
+        return Err(eg!(NoahError::ParameterError));
+    }
+    Ok(day as Attr)
+}
+
+/// Decode a date encoded by `ac_attr_from_date` into `(year, month, day)`.
+pub fn ac_attr_to_date(attr: Attr) -> Result<(u32, u32, u32)> {
+    if attr >= days_before_year(10000) {
+        return Err(eg!(NoahError::ParameterError));
+    }
+    let mut year = attr / 366 + 1;
+    while days_before_year(year + 1) <= attr {
+        year += 1;
+    }
+    let mut day = attr - days_before_year(year);
+    let mut month = 1;
+    while day >= days_in_month(year, month) {
+        day -= days_in_month(year, month);
+        month += 1;
+    }
+    Ok((year, month, day + 1))
+}
+
+/// Return the day number of a time in seconds since the Unix epoch, as in `ac_attr_from_date`.
+fn day_of_timestamp(timestamp: u64) -> u64 {
+    u64::from(days_before_year(1970)) + timestamp / 86_400
+}
+
+fn days_before_year(year: u32) -> u32 {
+    let y = year - 1;
+    y * 365 + y / 4 - y / 100 + y / 400
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use noah_algebra::collections::HashMap;
+    use rand_chacha::ChaChaRng;
+    use rand_core::SeedableRng;
+
+    fn wallet_credential(prng: &mut ChaChaRng) -> (ACUserSecretKey, ACWalletCredential) {
+        let (issuer_sk, issuer_pk) = ac_keygen_issuer(prng, AC_STRING_ATTR_SLOTS + 3);
+        let (user_sk, user_pk) = ac_keygen_user(prng, &issuer_pk);
+        let mut attrs = ac_attrs_from_str("Alice").to_vec();
+        attrs.push(ac_attr_from_date(1990, 2, 28).unwrap());
+        attrs.push(42);
+        attrs.push(ac_attr_from_date(2023, 11, 14).unwrap());
+        let sig = ac_sign(prng, &issuer_sk, &user_pk, &attrs).unwrap();
+        let schema = vec![
+            ACAttributeSchema {
+                name: "name".to_string(),
+                attr_type: ACAttributeType::String,
+            },
+            ACAttributeSchema {
+                name: "birth_date".to_string(),
+                attr_type: ACAttributeType::Date,
+            },
+            ACAttributeSchema {
+                name: "score".to_string(),
+                attr_type: ACAttributeType::Integer,
+            },
+            ACAttributeSchema {
+                name: "expiry".to_string(),
+                attr_type: ACAttributeType::Date,
+            },
+        ];
+        let credential = Credential {
+            sig,
+            attrs,
+            ipk: issuer_pk,
+        };
+        let wallet_credential = ACWalletCredential::new(
+            credential,
+            schema,
+            1_600_000_000,
+            Some("expiry".to_string()),
+        )
+        .unwrap();
+        (user_sk, wallet_credential)
+    }
+
+    #[test]
+    fn test_wallet_credential() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let (user_sk, wallet_credential) = wallet_credential(&mut prng);
+
+        let json = serde_json::to_string(&wallet_credential).unwrap();
+        let decoded: ACWalletCredential = serde_json::from_str(&json).unwrap();
+        assert!(decoded.check().is_ok());
+        assert_eq!(decoded, wallet_credential);
+
+        assert_eq!(decoded.attribute("score"), Some(&[42][..]));
+        assert_eq!(
+            decoded.attribute("name"),
+            Some(&ac_attrs_from_str("Alice")[..])
+        );
+        assert_eq!(decoded.attribute("unknown"), None);
+        assert_eq!(decoded.expiry(), ac_attr_from_date(2023, 11, 14).ok());
+        assert!(!decoded.is_expired(1_650_000_000));
+        assert!(!decoded.is_expired(1_699_919_999)); // 2023-11-13 23:59:59 UTC
+        assert!(decoded.is_expired(1_699_920_000)); // 2023-11-14 00:00:00 UTC
+
+        let reveal_map = decoded.reveal_map(&["score", "name"]).unwrap();
+        let mut expected = vec![true; AC_STRING_ATTR_SLOTS];
+        expected.extend([false, true, false]);
+        assert_eq!(reveal_map, expected);
+        assert!(decoded.reveal_map(&["unknown"]).is_err());
+
+        let reveal_sig = ac_reveal(&mut prng, &user_sk, &decoded.credential, &reveal_map).unwrap();
+        let attrs = decoded
+            .credential
+            .attrs
+            .iter()
+            .zip(reveal_map.iter())
+            .map(|(attr, b)| if *b { Some(*attr) } else { None })
+            .collect_vec();
+        assert!(ac_verify(
+            &decoded.credential.ipk,
+            &attrs,
+            &reveal_sig.cm,
+            &reveal_sig.proof_open,
+            None,
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_expiry_policy() {
+        let mut prng = ChaChaRng::from_seed([2u8; 32]);
+        let (user_sk, wallet_credential) = wallet_credential(&mut prng);
+        let credential = &wallet_credential.credential;
+        let attr_index = wallet_credential.attribute_slots("expiry").unwrap().start;
+
+        msg_eq!(
+            NoahError::ParameterError,
+            ACExpiryPolicy::new(attr_index, AC_EXPIRY_MIN_GRANULARITY - 1).unwrap_err()
+        );
+        let (cm, _, key) = ac_commit(&mut prng, &user_sk, credential, b"message").unwrap();
+        let key = key.unwrap();
+        let reveal_map = wallet_credential.reveal_map(&["score"]).unwrap();
+        let fine_policy = ACExpiryPolicy {
+            attr_index,
+            granularity: 1,
+        };
+        msg_eq!(
+            NoahError::ParameterError,
+            ac_open_commitment_with_predicates(
+                &mut prng,
+                &user_sk,
+                credential,
+                &key,
+                &reveal_map,
+                &[],
+                Some(&fine_policy),
+                None,
+            )
+            .unwrap_err()
+        );
+
+        let policy = ACExpiryPolicy::new(attr_index, AC_EXPIRY_MIN_GRANULARITY).unwrap();
+        let proof = ac_open_commitment_with_predicates(
+            &mut prng,
+            &user_sk,
+            credential,
+            &key,
+            &reveal_map,
+            &[],
+            Some(&policy),
+            None,
+        )
+        .unwrap();
+        let attrs = credential
+            .attrs
+            .iter()
+            .zip(reveal_map.iter())
+            .map(|(attr, b)| if *b { Some(*attr) } else { None })
+            .collect_vec();
+        let check = policy.check_at(1_650_000_000);
+        pnk!(ac_verify(
+            &credential.ipk,
+            &attrs,
+            &cm,
+            &proof,
+            Some(&check)
+        ));
+        msg_eq!(
+            NoahError::CredentialExpiredError,
+            ac_verify(
+                &credential.ipk,
+                &attrs,
+                &cm,
+                &proof,
+                Some(&policy.check_at(1_700_000_000))
+            )
+            .unwrap_err()
+        );
+        // the published bound cannot be ignored by the verifier
+        msg_eq!(
+            NoahError::ParameterError,
+            ac_verify(&credential.ipk, &attrs, &cm, &proof, None).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_malformed_wallet_credential() {
+        let mut prng = ChaChaRng::from_seed([1u8; 32]);
+        let (_, wallet_credential) = wallet_credential(&mut prng);
+        let (_, other_issuer_pk) = ac_keygen_issuer(&mut prng, 3);
+
+        let mut decoded = wallet_credential.clone();
+        decoded.version += 1;
+        assert!(decoded.check().is_err());
+
+        let mut decoded = wallet_credential.clone();
+        decoded.credential.ipk = other_issuer_pk;
+        assert!(decoded.check().is_err());
+
+        let mut decoded = wallet_credential.clone();
+        decoded.schema[2].name = "name".to_string();
+        assert!(decoded.check().is_err());
+
+        let mut decoded = wallet_credential.clone();
+        decoded.schema.pop();
+        assert!(decoded.check().is_err());
+
+        let mut decoded = wallet_credential.clone();
+        decoded.schema[0].attr_type = ACAttributeType::Integer;
+        assert!(decoded.check().is_err());
+
+        // the expiry must be a signed date after the issuance
+        for name in ["birth_date", "score", "unknown"] {
+            let mut decoded = wallet_credential.clone();
+            decoded.expiry_attr = Some(name.to_string());
+            assert!(decoded.check().is_err());
+        }
+
+        let mut decoded = wallet_credential;
+        decoded.issued_at = 1_699_920_000;
+        assert!(decoded.check().is_err());
+    }
+
+    #[test]
+    fn test_string_attributes() {
+        let alice = ac_attrs_from_str("Alice");
+        assert_eq!(alice, ac_attrs_from_str("Alice"));
+        // strings agreeing on a truncated hash still differ in the other slots
+        let mut words = HashMap::new();
+        for i in 0..10_000u32 {
+            let word = format!("word{}", i);
+            let attrs = ac_attrs_from_str(&word);
+            if let Some(other) = words.insert(attrs[0] & 0xff, word.clone()) {
+                assert_ne!(attrs, ac_attrs_from_str(&other));
+            }
+        }
+    }
+
+    #[test]
+    fn test_date_attributes() {
+        assert_eq!(ac_attr_from_date(1, 1, 1).unwrap(), 0);
+        assert_eq!(ac_attr_from_date(1970, 1, 1).unwrap(), 719_162);
+        let mut prev = None;
+        for (year, month, day) in [
+            (1899, 12, 31),
+            (1900, 2, 28),
+            (1900, 3, 1),
+            (2000, 2, 29),
+            (2024, 12, 31),
+            (9999, 12, 31),
+        ] {
+            let attr = ac_attr_from_date(year, month, day).unwrap();
+            assert_eq!(ac_attr_to_date(attr).unwrap(), (year, month, day));
+            assert!(prev.map_or(true, |prev| prev < attr));
+            prev = Some(attr);
+        }
+        assert!(ac_attr_from_date(1900, 2, 29).is_err());
+        assert!(ac_attr_from_date(2023, 13, 1).is_err());
+        assert!(ac_attr_from_date(0, 1, 1).is_err());
+        assert!(ac_attr_to_date(ac_attr_from_date(9999, 12, 31).unwrap() + 1).is_err());
+
+        assert_eq!(ac_attr_from_timestamp(0).unwrap(), 719_162);
+        assert_eq!(
+            ac_attr_from_timestamp(951_868_799).unwrap(), // 2000-02-29 23:59:59 UTC
+            ac_attr_from_date(2000, 2, 29).unwrap()
+        );
+        assert!(ac_attr_from_timestamp(u64::MAX).is_err());
+    }
+}
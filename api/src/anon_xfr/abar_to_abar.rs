@@ -11,6 +11,7 @@ use crate::anon_xfr::{
         AccElemVars, AnonAssetRecord, AxfrOwnerMemo, Commitment, MTNode, MTPath, Nullifier,
         OpenAnonAssetRecord, PayeeWitness, PayeeWitnessVars, PayerWitness, PayerWitnessVars,
     },
+    tracing::{gen_anon_xfr_tracing, verify_anon_xfr_tracing, AXfrTracerPubKey, AXfrTracing},
     AXfrPlonkPf, TurboPlonkCS, AMOUNT_LEN, FEE_TYPE,
 };
 use crate::errors::NoahError;
@@ -47,6 +48,9 @@ pub struct AXfrNote {
     pub proof: AXfrPlonkPf,
     /// The address folding instance.
     pub folding_instance: AXfrAddressFoldingInstance,
+    /// The optional tracing extension of the outputs.
+    #[serde(default)]
+    pub tracing: Option<AXfrTracing>,
 }
 
 /// Anonymous transfer pre-note without proofs and signatures.
@@ -236,15 +240,47 @@ pub fn finish_anon_xfr_note<R: CryptoRng + RngCore, D: Digest<OutputSize = U64>
         body: body,
         proof,
         folding_instance,
+        tracing: None,
     })
 }
 
+/// Build an anonymous transfer note as in `finish_anon_xfr_note`, together with the tracing
+/// extension that locks the amount and the asset type of every output to `tracer_pub_key`.
+pub fn finish_anon_xfr_note_with_tracing<
+    R: CryptoRng + RngCore,
+    D: Digest<OutputSize = U64> + Default,
+>(
+    prng: &mut R,
+    params: &ProverParams,
+    pre_note: AXfrPreNote,
+    hash: D,
+    tracer_pub_key: &AXfrTracerPubKey,
+) -> Result<AXfrNote> {
+    let tracing_params = ProverParams::abar_tracing_params().c(d!())?;
+    let tracing = gen_anon_xfr_tracing(prng, tracing_params, &pre_note, tracer_pub_key).c(d!())?;
+    let mut note = finish_anon_xfr_note(prng, params, pre_note, hash).c(d!())?;
+    note.tracing = Some(tracing);
+    Ok(note)
+}
+
 /// Verify an anonymous transfer note.
 pub fn verify_anon_xfr_note<D: Digest<OutputSize = U64> + Default>(
     params: &VerifierParams,
     note: &AXfrNote,
     merkle_root: &BLSScalar,
     hash: D,
+) -> Result<()> {
+    verify_anon_xfr_note_with_tracing(params, note, merkle_root, hash, None).c(d!())
+}
+
+/// Verify an anonymous transfer note, and if a tracing policy applies, that every output is
+/// traced by `tracer_pub_key`.
+pub fn verify_anon_xfr_note_with_tracing<D: Digest<OutputSize = U64> + Default>(
+    params: &VerifierParams,
+    note: &AXfrNote,
+    merkle_root: &BLSScalar,
+    hash: D,
+    tracer_pub_key: Option<&AXfrTracerPubKey>,
 ) -> Result<()> {
     if *merkle_root != note.body.merkle_root {
         return Err(eg!(NoahError::AXfrVerificationError));
@@ -274,7 +310,13 @@ pub fn verify_anon_xfr_note<D: Digest<OutputSize = U64> + Default>(
         &note.proof,
         &address_folding_public_input,
     )
-    .c(d!(NoahError::AXfrVerificationError))
+    .c(d!(NoahError::AXfrVerificationError))?;
+
+    if let Some(tracer_pub_key) = tracer_pub_key {
+        let tracing_params = VerifierParams::abar_tracing_params().c(d!())?;
+        verify_anon_xfr_tracing(&tracing_params, note, tracer_pub_key).c(d!())?;
+    }
+    Ok(())
 }
 
 /// Batch verify the anonymous transfer notes.
@@ -915,7 +957,8 @@ pub(crate) fn add_payees_witnesses(
 #[cfg(test)]
 mod tests {
     use crate::anon_xfr::abar_to_abar::{
-        finish_anon_xfr_note, init_anon_xfr_note, AXfrNote, ANON_XFR_FOLDING_PROOF_TRANSCRIPT,
+        finish_anon_xfr_note, finish_anon_xfr_note_with_tracing, init_anon_xfr_note,
+        verify_anon_xfr_note_with_tracing, AXfrNote, ANON_XFR_FOLDING_PROOF_TRANSCRIPT,
     };
     use crate::anon_xfr::address_folding::{
         create_address_folding, prepare_verifier_input, verify_address_folding,
@@ -931,6 +974,7 @@ mod tests {
             AccElemVars, AnonAssetRecord, MTLeafInfo, MTNode, MTPath, OpenAnonAssetRecord,
             OpenAnonAssetRecordBuilder, PayeeWitness, PayerWitness,
         },
+        tracing::{trace_anon_assets, AXfrTracerKeyPair},
         FEE_TYPE,
     };
     use crate::setup::{ProverParams, VerifierParams};
@@ -1121,7 +1165,10 @@ mod tests {
             hasher
         };
 
-        let (note, merkle_root) = {
+        // sample a tracer for testing.
+        let tracer_keypair = AXfrTracerKeyPair::generate(&mut prng);
+
+        let (note, traced_note, merkle_root) = {
             // prover scope
             let owner_memo = oabar.get_owner_memo().unwrap();
             let oabar_in = OpenAnonAssetRecordBuilder::from_abar(&abar, owner_memo, &keypair)
@@ -1145,14 +1192,25 @@ mod tests {
             let note = gen_anon_xfr_note(
                 &mut prng,
                 &user_params,
-                &[oabar_in],
-                &[oabar_out],
+                &[oabar_in.clone()],
+                &[oabar_out.clone()],
                 fee_amount,
                 &keypair,
                 test_hash.clone(),
             )
             .unwrap();
-            (note, merkle_root)
+
+            let pre_note =
+                init_anon_xfr_note(&[oabar_in], &[oabar_out], fee_amount, &keypair).unwrap();
+            let traced_note = finish_anon_xfr_note_with_tracing(
+                &mut prng,
+                &user_params,
+                pre_note,
+                test_hash.clone(),
+                &tracer_keypair.get_public_key(),
+            )
+            .unwrap();
+            (note, traced_note, merkle_root)
         };
         {
             // owner scope
@@ -1174,6 +1232,41 @@ mod tests {
                 verify_anon_xfr_note(&verifier_params, &note, &merkle_root, test_hash.clone())
                     .is_ok()
             );
+
+            // the traced note verifies under the tracing policy, unlike the untraced note
+            let tracer_pub_key = tracer_keypair.get_public_key();
+            assert!(verify_anon_xfr_note_with_tracing(
+                &verifier_params,
+                &traced_note,
+                &merkle_root,
+                test_hash.clone(),
+                Some(&tracer_pub_key),
+            )
+            .is_ok());
+            assert!(verify_anon_xfr_note_with_tracing(
+                &verifier_params,
+                &note,
+                &merkle_root,
+                test_hash.clone(),
+                Some(&tracer_pub_key),
+            )
+            .is_err());
+            let other_tracer_pub_key = AXfrTracerKeyPair::generate(&mut prng).get_public_key();
+            assert!(verify_anon_xfr_note_with_tracing(
+                &verifier_params,
+                &traced_note,
+                &merkle_root,
+                test_hash.clone(),
+                Some(&other_tracer_pub_key),
+            )
+            .is_err());
+        }
+        {
+            // tracer scope
+            assert_eq!(
+                trace_anon_assets(&traced_note, &tracer_keypair, &[asset_type]).unwrap(),
+                vec![(output_amount, asset_type)]
+            );
         }
     }
 
@@ -283,6 +283,12 @@ pub fn verify_abar_to_bar_note<D: Digest<OutputSize = U64> + Default>(
                 .ok_or(NoahError::DecompressElementError)
                 .c(d!())?,
         ),
+        XfrAmount::ConfidentialU64(com) => (
+            com.decompress()
+                .ok_or(NoahError::DecompressElementError)
+                .c(d!())?,
+            RistrettoPoint::get_identity(),
+        ),
         XfrAmount::NonConfidential(amount) => {
             // Use a trivial commitment
             let (l, h) = u64_to_u32_pair(amount);
@@ -411,6 +417,12 @@ pub fn batch_verify_abar_to_bar_note<D: Digest<OutputSize = U64> + Default + Syn
                         .ok_or(NoahError::DecompressElementError)
                         .c(d!())?,
                 ),
+                XfrAmount::ConfidentialU64(com) => (
+                    com.decompress()
+                        .ok_or(NoahError::DecompressElementError)
+                        .c(d!())?,
+                    RistrettoPoint::get_identity(),
+                ),
                 XfrAmount::NonConfidential(amount) => {
                     // Use a trivial commitment
                     let (l, h) = u64_to_u32_pair(amount);
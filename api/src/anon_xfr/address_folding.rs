@@ -14,8 +14,13 @@ use noah_crypto::delegated_schnorr::{
     prove_delegated_schnorr, verify_delegated_schnorr, DelegatedSchnorrInspection,
     DelegatedSchnorrProof,
 };
-use noah_crypto::field_simulation::{SimFr, SimFrParams, SimFrParamsSecq256k1};
+use noah_crypto::field_simulation::{
+    SimFr, SimFrParams, SimFrParamsSecp256k1, SimFrParamsSecq256k1,
+};
 use noah_plonk::plonk::constraint_system::field_simulation::SimFrVar;
+use noah_plonk::plonk::constraint_system::secp256k1::{
+    secp256k1_scalar_to_sim_fr, SECP256K1PointVar,
+};
 use noah_plonk::plonk::constraint_system::VarIndex;
 use num_bigint::BigUint;
 use rand_core::{CryptoRng, RngCore};
@@ -559,6 +564,66 @@ pub fn prove_address_folding_in_cs(
     Ok(())
 }
 
+/// Generate the constraints that prove, natively in the circuit, that the secret key
+/// derives the public key, as an alternative to address folding that needs no
+/// Bulletproofs side proof.
+pub fn prove_public_key_derivation_in_cs(
+    cs: &mut TurboPlonkCS,
+    public_key_scalars_vars: &[VarIndex; 3],
+    secret_key_scalars_vars: &[VarIndex; 2],
+    keypair: &AXfrKeyPair,
+) -> Result<()> {
+    // 1. decompose the scalar inputs.
+    let mut public_key_bits_vars = cs.range_check(public_key_scalars_vars[0], 248);
+    public_key_bits_vars.extend_from_slice(&cs.range_check(public_key_scalars_vars[1], 248));
+    public_key_bits_vars.extend_from_slice(&cs.range_check(public_key_scalars_vars[2], 16));
+
+    let mut secret_key_bits_vars = cs.range_check(secret_key_scalars_vars[0], 248);
+    secret_key_bits_vars.extend_from_slice(&cs.range_check(secret_key_scalars_vars[1], 8));
+
+    // 2. allocate the simulated field elements and obtain their bit representations.
+    let public_key = keypair.get_public_key();
+    let x_sim_fr = SimFr::<SimFrParamsSecq256k1>::from(&public_key.0.get_x().into());
+    let (x_sim_fr_var, x_sim_bits_vars) = SimFrVar::alloc_witness(cs, &x_sim_fr);
+    let y_sim_fr = SimFr::<SimFrParamsSecq256k1>::from(&public_key.0.get_y().into());
+    let (y_sim_fr_var, y_sim_bits_vars) = SimFrVar::alloc_witness(cs, &y_sim_fr);
+
+    let s_sim_fr = secp256k1_scalar_to_sim_fr(&keypair.get_secret_key().0);
+    let (s_sim_fr_var, s_sim_bits_vars) =
+        SimFrVar::<SimFrParamsSecp256k1>::alloc_witness(cs, &s_sim_fr);
+
+    // 3. check that the bit representations are the same as the one provided through scalars.
+    let mut public_key_sim_bits_vars = x_sim_bits_vars;
+    public_key_sim_bits_vars.extend_from_slice(&y_sim_bits_vars);
+
+    assert_eq!(public_key_sim_bits_vars.len(), public_key_bits_vars.len());
+    assert_eq!(s_sim_bits_vars.len(), secret_key_bits_vars.len());
+
+    for (sim_bit, scalar_bit) in public_key_sim_bits_vars
+        .iter()
+        .zip(public_key_bits_vars.iter())
+    {
+        cs.equal(*sim_bit, *scalar_bit);
+    }
+
+    for (sim_bit, scalar_bit) in s_sim_bits_vars.iter().zip(secret_key_bits_vars.iter()) {
+        cs.equal(*sim_bit, *scalar_bit);
+    }
+
+    // 4. check that the encodings are canonical.
+    x_sim_fr_var.enforce_less_than_modulus(cs);
+    y_sim_fr_var.enforce_less_than_modulus(cs);
+    s_sim_fr_var.enforce_less_than_modulus(cs);
+
+    // 5. check that the public key is the secret key times the generator.
+    let public_key_var = SECP256K1PointVar {
+        x: x_sim_fr_var,
+        y: y_sim_fr_var,
+    };
+    cs.secp256k1_public_key_derivation(&public_key_var, &secret_key_bits_vars)
+        .c(d!())
+}
+
 /// Convert the instance into input to the Plonk verifier.
 pub fn prepare_verifier_input(
     instance: &AXfrAddressFoldingInstance,
@@ -597,3 +662,118 @@ pub fn prepare_verifier_input(
 
     v
 }
+
+#[cfg(test)]
+mod test {
+    use crate::anon_xfr::{
+        address_folding::{
+            create_address_folding, prepare_verifier_input, prove_address_folding_in_cs,
+            prove_public_key_derivation_in_cs, verify_address_folding,
+        },
+        keys::AXfrKeyPair,
+        TurboPlonkCS,
+    };
+    use ark_std::test_rng;
+    use digest::Digest;
+    use merlin::Transcript;
+    use noah_plonk::plonk::constraint_system::{TurboCS, VarIndex};
+    use sha2::Sha512;
+
+    fn alloc_keypair_scalars(
+        cs: &mut TurboPlonkCS,
+        public_key_keypair: &AXfrKeyPair,
+        secret_key_keypair: &AXfrKeyPair,
+    ) -> ([VarIndex; 3], [VarIndex; 2]) {
+        let public_key_scalars = public_key_keypair
+            .get_public_key()
+            .get_public_key_scalars()
+            .unwrap();
+        let secret_key_scalars = secret_key_keypair
+            .get_secret_key()
+            .get_secret_key_scalars()
+            .unwrap();
+
+        let public_key_scalars_vars = [
+            cs.new_variable(public_key_scalars[0]),
+            cs.new_variable(public_key_scalars[1]),
+            cs.new_variable(public_key_scalars[2]),
+        ];
+        let secret_key_scalars_vars = [
+            cs.new_variable(secret_key_scalars[0]),
+            cs.new_variable(secret_key_scalars[1]),
+        ];
+        (public_key_scalars_vars, secret_key_scalars_vars)
+    }
+
+    #[test]
+    fn test_address_folding_in_cs() {
+        let mut prng = test_rng();
+        let keypair = AXfrKeyPair::generate(&mut prng);
+
+        let mut hash = Sha512::new();
+        hash.update(b"address folding test");
+
+        let mut transcript = Transcript::new(b"Address folding test");
+        let (instance, witness) =
+            create_address_folding(&mut prng, hash.clone(), &mut transcript, &keypair).unwrap();
+
+        let mut transcript = Transcript::new(b"Address folding test");
+        let (beta, lambda) = verify_address_folding(hash, &mut transcript, &instance).unwrap();
+
+        let mut cs = TurboCS::new();
+        let (public_key_scalars_vars, secret_key_scalars_vars) =
+            alloc_keypair_scalars(&mut cs, &keypair, &keypair);
+        prove_address_folding_in_cs(
+            &mut cs,
+            &public_key_scalars_vars,
+            &secret_key_scalars_vars,
+            &witness,
+        )
+        .unwrap();
+
+        let online_inputs = prepare_verifier_input(&instance, &beta, &lambda);
+        let cs_witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&cs_witness, &online_inputs).is_ok());
+    }
+
+    #[test]
+    fn test_public_key_derivation_in_cs() {
+        let mut prng = test_rng();
+        let keypair = AXfrKeyPair::generate(&mut prng);
+
+        let mut cs = TurboCS::new();
+        let (public_key_scalars_vars, secret_key_scalars_vars) =
+            alloc_keypair_scalars(&mut cs, &keypair, &keypair);
+        prove_public_key_derivation_in_cs(
+            &mut cs,
+            &public_key_scalars_vars,
+            &secret_key_scalars_vars,
+            &keypair,
+        )
+        .unwrap();
+
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness, &[]).is_ok());
+    }
+
+    #[test]
+    fn test_public_key_derivation_in_cs_with_wrong_public_key() {
+        let mut prng = test_rng();
+        let keypair = AXfrKeyPair::generate(&mut prng);
+        let other_keypair = AXfrKeyPair::generate(&mut prng);
+
+        let mut cs = TurboCS::new();
+        let (public_key_scalars_vars, secret_key_scalars_vars) =
+            alloc_keypair_scalars(&mut cs, &other_keypair, &keypair);
+        prove_public_key_derivation_in_cs(
+            &mut cs,
+            &public_key_scalars_vars,
+            &secret_key_scalars_vars,
+            &keypair,
+        )
+        .unwrap();
+
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness, &[]).is_err());
+    }
+}
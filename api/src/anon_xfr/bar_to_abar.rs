@@ -247,6 +247,12 @@ pub(crate) fn verify_bar_to_abar(
                 .ok_or(NoahError::DecompressElementError)
                 .c(d!())?,
         ),
+        XfrAmount::ConfidentialU64(com) => (
+            com.decompress()
+                .ok_or(NoahError::DecompressElementError)
+                .c(d!())?,
+            RistrettoPoint::get_identity(),
+        ),
         XfrAmount::NonConfidential(amount) => {
             // a trivial commitment
             let (l, h) = u64_to_u32_pair(amount);
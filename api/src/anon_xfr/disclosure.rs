@@ -0,0 +1,296 @@
+use crate::anon_xfr::{
+    commit, commit_in_cs,
+    keys::AXfrPubKey,
+    structs::{AnonAssetRecord, OpenAnonAssetRecord, PayeeWitness, PayeeWitnessVars},
+    AXfrPlonkPf, TurboPlonkCS,
+};
+use crate::setup::{ProverParams, VerifierParams};
+use crate::xfr::structs::AssetType;
+use merlin::Transcript;
+use noah_algebra::{bls12_381::BLSScalar, prelude::*};
+use noah_crypto::basic::anemoi_jive::{AnemoiJive381, AnemoiVLHTrace};
+use noah_plonk::plonk::{
+    constraint_system::TurboCS, prover::prover_with_lagrange, verifier::verifier,
+};
+
+/// The domain separator for disclosure receipts of anonymous asset records, for the Plonk proof.
+const ABAR_DISCLOSURE_PLONK_PROOF_TRANSCRIPT: &[u8] = b"ABAR Disclosure Plonk Proof";
+
+/// A standalone proof that an anonymous asset record belongs to the public key
+/// and carries the disclosed amount and/or asset type, without revealing the blinding factor.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AbarDisclosureReceipt {
+    /// The public key of the owner.
+    pub pub_key: AXfrPubKey,
+    /// The disclosed amount, if any.
+    pub amount: Option<u64>,
+    /// The disclosed asset type, if any.
+    pub asset_type: Option<AssetType>,
+    /// The proof that the commitment opens to the disclosed values.
+    pub proof: AXfrPlonkPf,
+}
+
+/// Generate a receipt that discloses the owner and the amount and/or the asset type
+/// of an open anonymous asset record, bound to the given context.
+pub fn gen_abar_disclosure_receipt<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    params: &ProverParams,
+    oabar: &OpenAnonAssetRecord,
+    disclose_amount: bool,
+    disclose_asset_type: bool,
+    context: &[u8],
+) -> Result<AbarDisclosureReceipt> {
+    if !disclose_amount && !disclose_asset_type {
+        return Err(eg!(NoahError::ParameterError));
+    }
+
+    let payee_witness = PayeeWitness {
+        amount: oabar.get_amount(),
+        blind: oabar.get_blind(),
+        asset_type: oabar.get_asset_type().as_scalar(),
+        public_key: oabar.pub_key_ref().clone(),
+    };
+
+    let (_, output_trace) = commit(
+        &payee_witness.public_key,
+        payee_witness.blind,
+        payee_witness.amount,
+        payee_witness.asset_type,
+    )
+    .c(d!())?;
+
+    let mut transcript = Transcript::new(ABAR_DISCLOSURE_PLONK_PROOF_TRANSCRIPT);
+    transcript.append_message(b"context", context);
+    let (mut cs, _) = build_abar_disclosure_cs(
+        payee_witness,
+        disclose_amount,
+        disclose_asset_type,
+        &output_trace,
+    );
+    let witness = cs.get_and_clear_witness();
+
+    let proof = prover_with_lagrange(
+        prng,
+        &mut transcript,
+        &params.pcs,
+        params.lagrange_pcs.as_ref(),
+        &params.cs,
+        &params.prover_params,
+        &witness,
+    )
+    .c(d!(NoahError::AXfrProofError))?;
+
+    Ok(AbarDisclosureReceipt {
+        pub_key: oabar.pub_key_ref().clone(),
+        amount: if disclose_amount {
+            Some(oabar.get_amount())
+        } else {
+            None
+        },
+        asset_type: if disclose_asset_type {
+            Some(oabar.get_asset_type())
+        } else {
+            None
+        },
+        proof,
+    })
+}
+
+/// Verify a disclosure receipt against an anonymous asset record and the context it is bound to.
+pub fn verify_abar_disclosure_receipt(
+    params: &VerifierParams,
+    abar: &AnonAssetRecord,
+    receipt: &AbarDisclosureReceipt,
+    context: &[u8],
+) -> Result<()> {
+    if receipt.amount.is_none() && receipt.asset_type.is_none() {
+        return Err(eg!(NoahError::ParameterError));
+    }
+
+    let zero = BLSScalar::zero();
+    let one = BLSScalar::one();
+    let mut online_inputs: Vec<BLSScalar> = vec![];
+    match receipt.amount {
+        Some(amount) => online_inputs.extend_from_slice(&[one, BLSScalar::from(amount)]),
+        None => online_inputs.extend_from_slice(&[zero, zero]),
+    }
+    match receipt.asset_type {
+        Some(asset_type) => online_inputs.extend_from_slice(&[one, asset_type.as_scalar()]),
+        None => online_inputs.extend_from_slice(&[zero, zero]),
+    }
+    online_inputs.extend_from_slice(&receipt.pub_key.get_public_key_scalars().c(d!())?);
+    online_inputs.push(abar.commitment);
+
+    let mut transcript = Transcript::new(ABAR_DISCLOSURE_PLONK_PROOF_TRANSCRIPT);
+    transcript.append_message(b"context", context);
+    verifier(
+        &mut transcript,
+        &params.pcs,
+        &params.cs,
+        &params.verifier_params,
+        &online_inputs,
+        &receipt.proof,
+    )
+    .c(d!(NoahError::AXfrVerificationError))
+}
+
+/// Construct the constraint system proving that a commitment opens to the disclosed values.
+///
+/// For each of the amount and the asset type, the public inputs are a flag and the product
+/// of the flag and the value, so that one circuit covers all the disclosure choices.
+pub fn build_abar_disclosure_cs(
+    payee_data: PayeeWitness,
+    disclose_amount: bool,
+    disclose_asset_type: bool,
+    output_trace: &AnemoiVLHTrace<BLSScalar, 2, 12>,
+) -> (TurboPlonkCS, usize) {
+    let mut cs = TurboCS::new();
+    cs.load_anemoi_jive_parameters::<AnemoiJive381>();
+
+    let amount_var = cs.new_variable(BLSScalar::from(payee_data.amount));
+    let asset_var = cs.new_variable(payee_data.asset_type);
+    let blind = cs.new_variable(payee_data.blind);
+
+    let public_key_scalars = payee_data.public_key.get_public_key_scalars().unwrap();
+    let public_key_scalars_vars = [
+        cs.new_variable(public_key_scalars[0]),
+        cs.new_variable(public_key_scalars[1]),
+        cs.new_variable(public_key_scalars[2]),
+    ];
+
+    let payee = PayeeWitnessVars {
+        amount: amount_var,
+        blind,
+        asset_type: asset_var,
+        public_key_scalars: public_key_scalars_vars.clone(),
+    };
+
+    // the disclosed amount and asset type
+    let disclose_amount_var = cs.new_variable(BLSScalar::from(disclose_amount as u32));
+    cs.prepare_pi_variable(disclose_amount_var);
+    let disclosed_amount_var = cs.mul(disclose_amount_var, payee.amount);
+    cs.prepare_pi_variable(disclosed_amount_var);
+
+    let disclose_asset_type_var = cs.new_variable(BLSScalar::from(disclose_asset_type as u32));
+    cs.prepare_pi_variable(disclose_asset_type_var);
+    let disclosed_asset_type_var = cs.mul(disclose_asset_type_var, payee.asset_type);
+    cs.prepare_pi_variable(disclosed_asset_type_var);
+
+    // the public key
+    for public_key_scalar_var in public_key_scalars_vars.iter() {
+        cs.prepare_pi_variable(*public_key_scalar_var);
+    }
+
+    // commitment
+    let com_abar_out_var = commit_in_cs(
+        &mut cs,
+        payee.blind,
+        payee.amount,
+        payee.asset_type,
+        &public_key_scalars_vars,
+        output_trace,
+    );
+    cs.prepare_pi_variable(com_abar_out_var);
+
+    // pad the number of constraints to power of two
+    cs.pad();
+
+    let n_constraints = cs.size;
+    (cs, n_constraints)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::anon_xfr::{
+        disclosure::{gen_abar_disclosure_receipt, verify_abar_disclosure_receipt},
+        keys::AXfrKeyPair,
+        structs::{AnonAssetRecord, OpenAnonAssetRecordBuilder},
+    };
+    use crate::setup::{ProverParams, VerifierParams};
+    use crate::xfr::structs::AssetType;
+    use ark_std::test_rng;
+    use noah_algebra::{bls12_381::BLSScalar, prelude::*};
+
+    #[test]
+    fn test_abar_disclosure() {
+        let mut prng = test_rng();
+        let params = ProverParams::abar_disclosure_params().unwrap();
+        let verifier_params = VerifierParams::from(ProverParams::abar_disclosure_params().unwrap());
+
+        let keypair = AXfrKeyPair::generate(&mut prng);
+        let amount = prng.next_u64();
+        let asset_type = AssetType::from_identical_byte(7);
+        let oabar = OpenAnonAssetRecordBuilder::new()
+            .amount(amount)
+            .asset_type(asset_type)
+            .pub_key(&keypair.get_public_key())
+            .finalize(&mut prng)
+            .unwrap()
+            .build()
+            .unwrap();
+        let abar = AnonAssetRecord::from_oabar(&oabar);
+        let context = b"receipt for invoice #42";
+
+        for (disclose_amount, disclose_asset_type) in [(true, true), (true, false), (false, true)] {
+            let receipt = gen_abar_disclosure_receipt(
+                &mut prng,
+                &params,
+                &oabar,
+                disclose_amount,
+                disclose_asset_type,
+                context,
+            )
+            .unwrap();
+            assert!(
+                verify_abar_disclosure_receipt(&verifier_params, &abar, &receipt, context).is_ok()
+            );
+
+            // another context
+            assert!(verify_abar_disclosure_receipt(
+                &verifier_params,
+                &abar,
+                &receipt,
+                b"another context"
+            )
+            .is_err());
+
+            // wrong values
+            let mut bad_receipt = receipt.clone();
+            bad_receipt.amount = Some(amount.wrapping_add(1));
+            assert!(
+                verify_abar_disclosure_receipt(&verifier_params, &abar, &bad_receipt, context)
+                    .is_err()
+            );
+            let mut bad_receipt = receipt.clone();
+            bad_receipt.asset_type = Some(AssetType::from_identical_byte(8));
+            assert!(
+                verify_abar_disclosure_receipt(&verifier_params, &abar, &bad_receipt, context)
+                    .is_err()
+            );
+
+            // another owner
+            let mut bad_receipt = receipt.clone();
+            bad_receipt.pub_key = AXfrKeyPair::generate(&mut prng).get_public_key();
+            assert!(
+                verify_abar_disclosure_receipt(&verifier_params, &abar, &bad_receipt, context)
+                    .is_err()
+            );
+
+            // another record
+            let mut other_abar = abar.clone();
+            other_abar.commitment = BLSScalar::random(&mut prng);
+            assert!(verify_abar_disclosure_receipt(
+                &verifier_params,
+                &other_abar,
+                &receipt,
+                context
+            )
+            .is_err());
+        }
+
+        // nothing disclosed
+        assert!(
+            gen_abar_disclosure_receipt(&mut prng, &params, &oabar, false, false, context).is_err()
+        );
+    }
+}
@@ -1,5 +1,9 @@
-use aes_gcm::{aead::Aead, KeyInit};
+use aes_gcm::{
+    aead::{Aead, Payload},
+    KeyInit,
+};
 use digest::{generic_array::GenericArray, Digest};
+use hkdf::Hkdf;
 use noah_algebra::secp256k1::{SECP256K1Scalar, SECP256K1G1, SECP256K1_SCALAR_LEN};
 use noah_algebra::{bls12_381::BLSScalar, prelude::*};
 use serde::{Deserialize, Serialize};
@@ -10,6 +14,184 @@ pub const AXFR_SECRET_KEY_LENGTH: usize = SECP256K1_SCALAR_LEN;
 /// The length of the public key for anonymous transfer.
 pub const AXFR_PUBLIC_KEY_LENGTH: usize = SECP256K1G1::COMPRESSED_LEN;
 
+/// The version byte of owner memos encrypted under an HKDF-derived key with a random nonce
+/// and bound to the commitment of the anonymous asset record.
+pub const AXFR_MEMO_VERSION_1: u8 = 1;
+/// The version byte of version-1 owner memos that carry a view tag after the cipher suite.
+pub const AXFR_MEMO_VERSION_1_VIEW_TAG: u8 = 2;
+/// The length of the nonce used in the owner memo encryption.
+pub const AXFR_MEMO_NONCE_LENGTH: usize = 12;
+/// The length of the header of a versioned ciphertext without a view tag: version, cipher suite, and nonce.
+pub const AXFR_MEMO_HEADER_LENGTH: usize = 2 + AXFR_MEMO_NONCE_LENGTH;
+/// The domain separator of the key derivation for owner memos.
+const AXFR_MEMO_KDF_DOMAIN: &[u8] = b"Noah AXfr Owner Memo KDF v1";
+/// The domain separator of the view tags.
+const AXFR_VIEW_TAG_DOMAIN: &[u8] = b"Noah AXfr View Tag";
+
+/// The authenticated encryption scheme used for the owner memo.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub enum AXfrMemoCipherSuite {
+    /// AES-256-GCM.
+    #[default]
+    Aes256Gcm,
+    /// ChaCha20-Poly1305.
+    ChaCha20Poly1305,
+}
+
+impl AXfrMemoCipherSuite {
+    /// Return the byte that identifies the cipher suite in the ciphertext header.
+    pub fn to_byte(&self) -> u8 {
+        match self {
+            AXfrMemoCipherSuite::Aes256Gcm => 0,
+            AXfrMemoCipherSuite::ChaCha20Poly1305 => 1,
+        }
+    }
+
+    /// Parse the cipher suite from the byte in the ciphertext header.
+    pub fn from_byte(byte: u8) -> Result<Self> {
+        match byte {
+            0 => Ok(AXfrMemoCipherSuite::Aes256Gcm),
+            1 => Ok(AXfrMemoCipherSuite::ChaCha20Poly1305),
+            _ => Err(eg!(NoahError::DeserializationError)),
+        }
+    }
+
+    fn encrypt(&self, key: &[u8; 32], nonce: &[u8], msg: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+        let nonce = GenericArray::from_slice(nonce);
+        let payload = Payload { msg, aad };
+        match self {
+            AXfrMemoCipherSuite::Aes256Gcm => aes_gcm::Aes256Gcm::new_from_slice(key)
+                .map_err(|_| NoahError::EncryptionError)
+                .c(d!())?
+                .encrypt(nonce, payload)
+                .map_err(|_| NoahError::EncryptionError)
+                .c(d!()),
+            AXfrMemoCipherSuite::ChaCha20Poly1305 => {
+                chacha20poly1305::ChaCha20Poly1305::new_from_slice(key)
+                    .map_err(|_| NoahError::EncryptionError)
+                    .c(d!())?
+                    .encrypt(nonce, payload)
+                    .map_err(|_| NoahError::EncryptionError)
+                    .c(d!())
+            }
+        }
+    }
+
+    fn decrypt(&self, key: &[u8; 32], nonce: &[u8], ctext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+        let nonce = GenericArray::from_slice(nonce);
+        let payload = Payload { msg: ctext, aad };
+        match self {
+            AXfrMemoCipherSuite::Aes256Gcm => aes_gcm::Aes256Gcm::new_from_slice(key)
+                .map_err(|_| NoahError::DecryptionError)
+                .c(d!())?
+                .decrypt(nonce, payload)
+                .map_err(|_| NoahError::DecryptionError)
+                .c(d!()),
+            AXfrMemoCipherSuite::ChaCha20Poly1305 => {
+                chacha20poly1305::ChaCha20Poly1305::new_from_slice(key)
+                    .map_err(|_| NoahError::DecryptionError)
+                    .c(d!())?
+                    .decrypt(nonce, payload)
+                    .map_err(|_| NoahError::DecryptionError)
+                    .c(d!())
+            }
+        }
+    }
+}
+
+/// The header of a versioned ciphertext.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AXfrMemoHeader {
+    /// The cipher suite.
+    pub suite: AXfrMemoCipherSuite,
+    /// The view tag that lets the recipient skip the decryption of memos that are not theirs.
+    pub view_tag: Option<u8>,
+    /// The nonce.
+    pub nonce: [u8; AXFR_MEMO_NONCE_LENGTH],
+}
+
+impl AXfrMemoHeader {
+    /// Return the length of the header in bytes.
+    pub fn size(&self) -> usize {
+        if self.view_tag.is_some() {
+            AXFR_MEMO_HEADER_LENGTH + 1
+        } else {
+            AXFR_MEMO_HEADER_LENGTH
+        }
+    }
+
+    /// Parse the header at the beginning of the ciphertext.
+    pub fn parse(ctext: &[u8]) -> Result<Self> {
+        if ctext.len() < AXFR_MEMO_HEADER_LENGTH {
+            return Err(eg!(NoahError::DeserializationError));
+        }
+        let suite = AXfrMemoCipherSuite::from_byte(ctext[1]).c(d!())?;
+        let (view_tag, offset) = match ctext[0] {
+            AXFR_MEMO_VERSION_1 => (None, 2),
+            AXFR_MEMO_VERSION_1_VIEW_TAG if ctext.len() > AXFR_MEMO_HEADER_LENGTH => {
+                (Some(ctext[2]), 3)
+            }
+            _ => return Err(eg!(NoahError::DeserializationError)),
+        };
+        let mut nonce = [0u8; AXFR_MEMO_NONCE_LENGTH];
+        nonce.copy_from_slice(&ctext[offset..offset + AXFR_MEMO_NONCE_LENGTH]);
+
+        Ok(Self {
+            suite,
+            view_tag,
+            nonce,
+        })
+    }
+
+    /// Serialize the header.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.size());
+        match self.view_tag {
+            None => {
+                bytes.push(AXFR_MEMO_VERSION_1);
+                bytes.push(self.suite.to_byte());
+            }
+            Some(view_tag) => {
+                bytes.push(AXFR_MEMO_VERSION_1_VIEW_TAG);
+                bytes.push(self.suite.to_byte());
+                bytes.push(view_tag);
+            }
+        }
+        bytes.extend_from_slice(&self.nonce);
+        bytes
+    }
+}
+
+/// Compute the one-byte view tag from the Diffie-Hellman point.
+fn compute_view_tag(dh: &SECP256K1G1) -> u8 {
+    let mut hasher = sha2::Sha512::new();
+    hasher.update(AXFR_VIEW_TAG_DOMAIN);
+    hasher.update(&dh.to_compressed_bytes());
+    hasher.finalize()[0]
+}
+
+/// Derive the symmetric key of an owner memo from the Diffie-Hellman point.
+/// The key is bound to the ephemeral share, the recipient, and the cipher suite.
+fn derive_memo_key(
+    dh: &SECP256K1G1,
+    share: &SECP256K1G1,
+    recipient: &SECP256K1G1,
+    suite: AXfrMemoCipherSuite,
+) -> Result<[u8; 32]> {
+    let hkdf = Hkdf::<sha2::Sha512>::new(Some(AXFR_MEMO_KDF_DOMAIN), &dh.to_compressed_bytes());
+
+    let mut info = vec![];
+    info.extend_from_slice(&share.to_compressed_bytes());
+    info.extend_from_slice(&recipient.to_compressed_bytes());
+    info.push(suite.to_byte());
+
+    let mut key = [0u8; 32];
+    hkdf.expand(&info, &mut key)
+        .map_err(|_| NoahError::ParameterError)
+        .c(d!())?;
+    Ok(key)
+}
+
 /// The spending key.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default, Hash)]
 pub struct AXfrSecretKey(pub(crate) SECP256K1Scalar);
@@ -85,7 +267,7 @@ impl AXfrSecretKey {
     }
 
     #[inline]
-    /// Decrypt a ciphertext.
+    /// Decrypt a ciphertext in the legacy format, which uses a fixed nonce and no associated data.
     pub fn decrypt(&self, share: &AXfrPubKey, ctext: &[u8]) -> Result<Vec<u8>> {
         let dh = share.0.mul(&self.0);
 
@@ -118,6 +300,46 @@ impl AXfrSecretKey {
         };
         Ok(res)
     }
+
+    /// Decrypt a versioned ciphertext produced by `AXfrPubKey::encrypt_with_aad`.
+    pub fn decrypt_with_aad(
+        &self,
+        share: &AXfrPubKey,
+        ctext: &[u8],
+        aad: &[u8],
+    ) -> Result<Vec<u8>> {
+        let header = AXfrMemoHeader::parse(ctext).c(d!(NoahError::DecryptionError))?;
+
+        let dh = share.0.mul(&self.0);
+        if let Some(view_tag) = header.view_tag {
+            if compute_view_tag(&dh) != view_tag {
+                return Err(eg!(NoahError::DecryptionError));
+            }
+        }
+
+        let recipient = SECP256K1G1::get_base().mul(&self.0);
+        let key = derive_memo_key(&dh, &share.0, &recipient, header.suite).c(d!())?;
+
+        // The header is authenticated together with the associated data.
+        let mut header_and_aad = ctext[..header.size()].to_vec();
+        header_and_aad.extend_from_slice(aad);
+
+        header
+            .suite
+            .decrypt(
+                &key,
+                &header.nonce,
+                &ctext[header.size()..],
+                &header_and_aad,
+            )
+            .c(d!())
+    }
+
+    /// Compute the view tag of a memo with the given ephemeral share.
+    /// A mismatch with the view tag in the memo means that the memo is not for this key.
+    pub fn view_tag(&self, share: &AXfrPubKey) -> u8 {
+        compute_view_tag(&share.0.mul(&self.0))
+    }
 }
 
 impl AXfrPubKey {
@@ -139,7 +361,7 @@ impl AXfrPubKey {
         Ok([first, second, third])
     }
 
-    /// Encrypt the message
+    /// Encrypt the message in the legacy format, which uses a fixed nonce and no associated data.
     pub fn encrypt<R: CryptoRng + RngCore>(
         &self,
         prng: &mut R,
@@ -180,6 +402,67 @@ impl AXfrPubKey {
 
         Ok((AXfrPubKey(share), ctext))
     }
+
+    /// Encrypt the message with a key derived by HKDF, a random nonce, and associated data.
+    /// The ciphertext is prefixed with the version, the cipher suite, and the nonce,
+    /// and this header is also authenticated as part of the associated data.
+    pub fn encrypt_with_aad<R: CryptoRng + RngCore>(
+        &self,
+        prng: &mut R,
+        suite: AXfrMemoCipherSuite,
+        msg: &[u8],
+        aad: &[u8],
+    ) -> Result<(Self, Vec<u8>)> {
+        self.encrypt_versioned(prng, suite, false, msg, aad)
+    }
+
+    /// Encrypt the message as `encrypt_with_aad` does, and add a view tag to the header,
+    /// which lets the recipient skip the decryption of memos that are not theirs.
+    pub fn encrypt_with_aad_and_view_tag<R: CryptoRng + RngCore>(
+        &self,
+        prng: &mut R,
+        suite: AXfrMemoCipherSuite,
+        msg: &[u8],
+        aad: &[u8],
+    ) -> Result<(Self, Vec<u8>)> {
+        self.encrypt_versioned(prng, suite, true, msg, aad)
+    }
+
+    fn encrypt_versioned<R: CryptoRng + RngCore>(
+        &self,
+        prng: &mut R,
+        suite: AXfrMemoCipherSuite,
+        with_view_tag: bool,
+        msg: &[u8],
+        aad: &[u8],
+    ) -> Result<(Self, Vec<u8>)> {
+        let share_scalar = SECP256K1Scalar::random(prng);
+        let share = SECP256K1G1::get_base().mul(&share_scalar);
+
+        let dh = self.0.mul(&share_scalar);
+        let key = derive_memo_key(&dh, &share, &self.0, suite).c(d!())?;
+
+        let mut nonce = [0u8; AXFR_MEMO_NONCE_LENGTH];
+        prng.fill_bytes(&mut nonce);
+
+        let header = AXfrMemoHeader {
+            suite,
+            view_tag: if with_view_tag {
+                Some(compute_view_tag(&dh))
+            } else {
+                None
+            },
+            nonce,
+        };
+
+        // The header is authenticated together with the associated data.
+        let mut ctext = header.to_bytes();
+        let mut header_and_aad = ctext.clone();
+        header_and_aad.extend_from_slice(aad);
+        ctext.extend_from_slice(&suite.encrypt(&key, &nonce, msg, &header_and_aad).c(d!())?);
+
+        Ok((AXfrPubKey(share), ctext))
+    }
 }
 
 impl NoahFromToBytes for AXfrKeyPair {
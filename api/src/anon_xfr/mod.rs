@@ -1,5 +1,5 @@
-use crate::anon_xfr::keys::AXfrPubKey;
-use crate::anon_xfr::structs::Commitment;
+use crate::anon_xfr::keys::{AXfrPubKey, AXFR_MEMO_HEADER_LENGTH};
+use crate::anon_xfr::structs::{Commitment, OpenAnonAssetRecordBuilder};
 use crate::{
     anon_xfr::{
         keys::AXfrKeyPair,
@@ -25,6 +25,8 @@ use noah_plonk::{
     },
     poly_commit::kzg_poly_com::KZGCommitmentSchemeBLS,
 };
+#[cfg(feature = "parallel")]
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 
 /// Module for general-purpose anonymous payment.
 pub mod abar_to_abar;
@@ -38,10 +40,14 @@ pub mod address_folding;
 pub mod ar_to_abar;
 /// Module for converting confidential assets to anonymous assets.
 pub mod bar_to_abar;
+/// Module for disclosure receipts of anonymous assets.
+pub mod disclosure;
 /// Module for the spending key and the public key.
 pub mod keys;
 /// Module for shared structures.
 pub mod structs;
+/// Module for tracing the amounts and asset types of anonymous assets.
+pub mod tracing;
 
 /// The asset type for FRA.
 const ASSET_TYPE_FRA: AssetType = AssetType([0; ASSET_TYPE_LENGTH]);
@@ -139,7 +145,7 @@ pub fn parse_memo(
     key_pair: &AXfrKeyPair,
     abar: &AnonAssetRecord,
 ) -> Result<(u64, AssetType, BLSScalar)> {
-    if bytes.len() != 8 + ASSET_TYPE_LENGTH + BLS12_381_SCALAR_LEN {
+    if bytes.len() != MEMO_PLAINTEXT_LEN {
         return Err(eg!(NoahError::ParameterError));
     }
     let amount = u8_le_slice_to_u64(&bytes[0..8]);
@@ -175,10 +181,45 @@ pub fn decrypt_memo(
     key_pair: &AXfrKeyPair,
     abar: &AnonAssetRecord,
 ) -> Result<(u64, AssetType, BLSScalar)> {
-    let plaintext = memo.decrypt(&key_pair.get_secret_key())?;
+    let plaintext = memo.decrypt(&key_pair.get_secret_key(), &abar.commitment)?;
     parse_memo(&plaintext, key_pair, abar)
 }
 
+/// The length of the owner memo plaintext: amount, asset type, and blinding factor.
+const MEMO_PLAINTEXT_LEN: usize = 8 + ASSET_TYPE_LENGTH + BLS12_381_SCALAR_LEN;
+
+/// The length of an owner memo ciphertext with a view tag: the header, the view tag,
+/// the plaintext, and the authentication tag.
+const MEMO_WITH_VIEW_TAG_LEN: usize = AXFR_MEMO_HEADER_LENGTH + 1 + MEMO_PLAINTEXT_LEN + 16;
+
+/// Scan the anonymous asset records with their owner memos and return the ones owned by the key pair.
+/// Memos with a view tag that does not match are skipped without the decryption and the commitment check.
+/// The memos are scanned in parallel if the `parallel` feature is enabled.
+pub fn scan_outputs(
+    key_pair: &AXfrKeyPair,
+    outputs: &[(AnonAssetRecord, AxfrOwnerMemo)],
+) -> Vec<OpenAnonAssetRecord> {
+    let secret_key = key_pair.get_secret_key();
+
+    let scan = |(abar, memo): &(AnonAssetRecord, AxfrOwnerMemo)| {
+        // A legacy memo may look like a memo with a view tag,
+        // but it never has the same length, since it has no header.
+        if memo.ctext.len() == MEMO_WITH_VIEW_TAG_LEN && !memo.match_view_tag(&secret_key) {
+            return None;
+        }
+        OpenAnonAssetRecordBuilder::from_abar(abar, memo.clone(), key_pair)
+            .and_then(|builder| builder.build())
+            .ok()
+    };
+
+    #[cfg(not(feature = "parallel"))]
+    let outputs = outputs.iter();
+    #[cfg(feature = "parallel")]
+    let outputs = outputs.par_iter();
+
+    outputs.filter_map(scan).collect()
+}
+
 /// Compute the nullifier.
 pub fn nullify(
     key_pair: &AXfrKeyPair,
@@ -248,7 +289,10 @@ pub fn commit_in_cs(
     output_var
 }
 
-/// Compute the record's amount||asset type||pub key commitment
+/// Compute the record's amount||asset type||pub key commitment.
+///
+/// The commitment is always computed with Anemoi-Jive rather than a generic `ArithmeticHash`,
+/// since the anonymous transfer circuits consume its trace.
 pub fn commit(
     public_key: &AXfrPubKey,
     blind: BLSScalar,
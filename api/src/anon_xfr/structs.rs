@@ -1,4 +1,4 @@
-use crate::anon_xfr::keys::AXfrSecretKey;
+use crate::anon_xfr::keys::{AXfrMemoCipherSuite, AXfrMemoHeader, AXfrSecretKey};
 use crate::anon_xfr::{
     commit, decrypt_memo,
     keys::{AXfrKeyPair, AXfrPubKey},
@@ -126,6 +126,8 @@ impl OpenAnonAssetRecord {
 /// The builder for an opened anonymous asset record.
 pub struct OpenAnonAssetRecordBuilder {
     pub(crate) oabar: OpenAnonAssetRecord,
+    pub(crate) memo_cipher_suite: AXfrMemoCipherSuite,
+    pub(crate) memo_view_tag: bool,
 }
 
 impl OpenAnonAssetRecordBuilder {
@@ -154,6 +156,18 @@ impl OpenAnonAssetRecordBuilder {
         self
     }
 
+    /// Specify the cipher suite used to encrypt the owner memo
+    pub fn memo_cipher_suite(mut self, suite: AXfrMemoCipherSuite) -> Self {
+        self.memo_cipher_suite = suite;
+        self
+    }
+
+    /// Specify whether the owner memo carries a view tag for fast wallet scanning
+    pub fn memo_view_tag(mut self, with_view_tag: bool) -> Self {
+        self.memo_view_tag = with_view_tag;
+        self
+    }
+
     /// Update mt_leaf_info
     pub fn mt_leaf_info(mut self, mt_leaf_info: MTLeafInfo) -> Self {
         self.oabar.update_mt_leaf_info(mt_leaf_info);
@@ -175,7 +189,31 @@ impl OpenAnonAssetRecordBuilder {
         msg.extend_from_slice(&self.oabar.asset_type.0);
         msg.extend_from_slice(&self.oabar.blind.to_bytes());
 
-        self.oabar.owner_memo = Some(AxfrOwnerMemo::new(prng, &self.oabar.pub_key, &msg)?);
+        let (commitment, _) = commit(
+            &self.oabar.pub_key,
+            self.oabar.blind,
+            self.oabar.amount,
+            self.oabar.asset_type.as_scalar(),
+        )
+        .c(d!())?;
+
+        self.oabar.owner_memo = Some(if self.memo_view_tag {
+            AxfrOwnerMemo::new_with_view_tag(
+                prng,
+                &self.oabar.pub_key,
+                &msg,
+                &commitment,
+                self.memo_cipher_suite,
+            )?
+        } else {
+            AxfrOwnerMemo::new_with_cipher_suite(
+                prng,
+                &self.oabar.pub_key,
+                &msg,
+                &commitment,
+                self.memo_cipher_suite,
+            )?
+        });
         Ok(self)
     }
 
@@ -304,38 +342,131 @@ pub struct PayeeWitness {
     pub public_key: AXfrPubKey,
 }
 
+/// The version of the owner memo format.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AxfrOwnerMemoVersion {
+    /// AES-256-GCM with a key hashed from the shared point, a fixed nonce, and no associated data.
+    Legacy,
+    /// An HKDF-derived key, a random nonce, and the commitment as the associated data.
+    V1(AXfrMemoCipherSuite),
+}
+
 /// Information directed to secret key holder of a BlindAssetRecord
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct AxfrOwnerMemo {
     /// The random point used to generate the shared point.
     pub point: AXfrPubKey,
     /// The ciphertext.
+    /// Versioned memos start with the version byte, the cipher suite byte, the optional view tag,
+    /// and the nonce.
     pub ctext: Vec<u8>,
 }
 
 impl AxfrOwnerMemo {
-    /// Crate an encrypted memo using the public key.
+    /// Crate an encrypted memo using the public key, bound to the commitment of the record.
     pub fn new<R: CryptoRng + RngCore>(
         prng: &mut R,
         pub_key: &AXfrPubKey,
         msg: &[u8],
+        commitment: &Commitment,
+    ) -> Result<Self> {
+        Self::new_with_cipher_suite(
+            prng,
+            pub_key,
+            msg,
+            commitment,
+            AXfrMemoCipherSuite::default(),
+        )
+    }
+
+    /// Crate an encrypted memo using the public key and the specified cipher suite,
+    /// bound to the commitment of the record.
+    pub fn new_with_cipher_suite<R: CryptoRng + RngCore>(
+        prng: &mut R,
+        pub_key: &AXfrPubKey,
+        msg: &[u8],
+        commitment: &Commitment,
+        suite: AXfrMemoCipherSuite,
+    ) -> Result<Self> {
+        let (point, ctext) = pub_key.encrypt_with_aad(prng, suite, msg, &commitment.to_bytes())?;
+        Ok(Self { point, ctext })
+    }
+
+    /// Crate an encrypted memo using the public key and the specified cipher suite,
+    /// bound to the commitment of the record, with a view tag for fast scanning.
+    pub fn new_with_view_tag<R: CryptoRng + RngCore>(
+        prng: &mut R,
+        pub_key: &AXfrPubKey,
+        msg: &[u8],
+        commitment: &Commitment,
+        suite: AXfrMemoCipherSuite,
+    ) -> Result<Self> {
+        let (point, ctext) =
+            pub_key.encrypt_with_aad_and_view_tag(prng, suite, msg, &commitment.to_bytes())?;
+        Ok(Self { point, ctext })
+    }
+
+    /// Crate an encrypted memo in the legacy format using the public key.
+    pub fn new_legacy<R: CryptoRng + RngCore>(
+        prng: &mut R,
+        pub_key: &AXfrPubKey,
+        msg: &[u8],
     ) -> Result<Self> {
         let (point, ctext) = pub_key.encrypt(prng, msg)?;
         Ok(Self { point, ctext })
     }
 
-    /// Decrypt a memo using the viewing key.
-    pub fn decrypt(&self, secret_key: &AXfrSecretKey) -> Result<Vec<u8>> {
+    /// Return the version that the header of the ciphertext claims.
+    /// A legacy ciphertext may look like a versioned one, so `decrypt` falls back to the legacy format.
+    pub fn version(&self) -> AxfrOwnerMemoVersion {
+        match AXfrMemoHeader::parse(&self.ctext) {
+            Ok(header) => AxfrOwnerMemoVersion::V1(header.suite),
+            Err(_) => AxfrOwnerMemoVersion::Legacy,
+        }
+    }
+
+    /// Return the view tag of the memo, if any.
+    pub fn view_tag(&self) -> Option<u8> {
+        AXfrMemoHeader::parse(&self.ctext)
+            .ok()
+            .and_then(|header| header.view_tag)
+    }
+
+    /// Check the view tag of the memo against the viewing key.
+    /// Return false only if the memo has a view tag that does not match,
+    /// in which case the memo is not for this key and the decryption can be skipped.
+    pub fn match_view_tag(&self, secret_key: &AXfrSecretKey) -> bool {
+        match self.view_tag() {
+            Some(view_tag) => secret_key.view_tag(&self.point) == view_tag,
+            None => true,
+        }
+    }
+
+    /// Decrypt a memo using the viewing key and the commitment of the record.
+    /// Both the legacy and the versioned formats are accepted.
+    pub fn decrypt(&self, secret_key: &AXfrSecretKey, commitment: &Commitment) -> Result<Vec<u8>> {
+        if let AxfrOwnerMemoVersion::V1(_) = self.version() {
+            if let Ok(res) =
+                secret_key.decrypt_with_aad(&self.point, &self.ctext, &commitment.to_bytes())
+            {
+                return Ok(res);
+            }
+        }
         secret_key.decrypt(&self.point, &self.ctext)
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::anon_xfr::keys::AXfrKeyPair;
-    use crate::anon_xfr::structs::AXfrPubKey;
+    use crate::anon_xfr::keys::{AXfrKeyPair, AXfrMemoCipherSuite, AXfrMemoHeader};
+    use crate::anon_xfr::scan_outputs;
+    use crate::anon_xfr::structs::{
+        AXfrPubKey, AnonAssetRecord, AxfrOwnerMemo, AxfrOwnerMemoVersion,
+        OpenAnonAssetRecordBuilder,
+    };
+    use crate::xfr::structs::AssetType;
     use ark_std::test_rng;
-    use noah_algebra::prelude::*;
+    use noah_algebra::{bls12_381::BLSScalar, prelude::*};
 
     #[test]
     fn test_axfr_pub_key_serialization() {
@@ -362,4 +493,144 @@ mod test {
         let reformed_key_pair = AXfrKeyPair::noah_from_bytes(bytes.as_slice()).unwrap();
         assert_eq!(keypair, reformed_key_pair);
     }
+
+    #[test]
+    fn test_axfr_owner_memo_versions() {
+        let mut prng = test_rng();
+        let keypair: AXfrKeyPair = AXfrKeyPair::generate(&mut prng);
+        let pub_key = keypair.get_public_key();
+        let secret_key = keypair.get_secret_key();
+        let commitment = BLSScalar::random(&mut prng);
+        let msg = b"owner memo";
+
+        for suite in [
+            AXfrMemoCipherSuite::Aes256Gcm,
+            AXfrMemoCipherSuite::ChaCha20Poly1305,
+        ] {
+            let memo =
+                AxfrOwnerMemo::new_with_cipher_suite(&mut prng, &pub_key, msg, &commitment, suite)
+                    .unwrap();
+            assert_eq!(memo.version(), AxfrOwnerMemoVersion::V1(suite));
+            assert_eq!(
+                memo.decrypt(&secret_key, &commitment).unwrap(),
+                msg.to_vec()
+            );
+
+            // The memo is bound to the commitment.
+            let other_commitment = BLSScalar::random(&mut prng);
+            assert!(memo.decrypt(&secret_key, &other_commitment).is_err());
+
+            // The memo cannot be decrypted by another key.
+            let other_keypair: AXfrKeyPair = AXfrKeyPair::generate(&mut prng);
+            assert!(memo
+                .decrypt(&other_keypair.get_secret_key(), &commitment)
+                .is_err());
+        }
+
+        // Legacy memos can still be decrypted, regardless of the commitment.
+        let memo = AxfrOwnerMemo::new_legacy(&mut prng, &pub_key, msg).unwrap();
+        assert_eq!(
+            memo.decrypt(&secret_key, &commitment).unwrap(),
+            msg.to_vec()
+        );
+    }
+
+    #[test]
+    fn test_axfr_owner_memo_header_is_authenticated() {
+        let mut prng = test_rng();
+        let keypair: AXfrKeyPair = AXfrKeyPair::generate(&mut prng);
+        let secret_key = keypair.get_secret_key();
+        let commitment = BLSScalar::random(&mut prng);
+        let msg = b"owner memo";
+
+        let memo = AxfrOwnerMemo::new_with_view_tag(
+            &mut prng,
+            &keypair.get_public_key(),
+            msg,
+            &commitment,
+            AXfrMemoCipherSuite::ChaCha20Poly1305,
+        )
+        .unwrap();
+        let header_size = AXfrMemoHeader::parse(&memo.ctext).unwrap().size();
+
+        // Changing any byte of the header makes the decryption fail.
+        for i in 0..header_size {
+            let mut tampered = memo.clone();
+            tampered.ctext[i] ^= 1;
+            assert!(secret_key
+                .decrypt_with_aad(&tampered.point, &tampered.ctext, &commitment.to_bytes())
+                .is_err());
+            assert!(tampered.decrypt(&secret_key, &commitment).is_err());
+        }
+    }
+
+    #[test]
+    fn test_axfr_owner_memo_view_tag() {
+        let mut prng = test_rng();
+        let keypair: AXfrKeyPair = AXfrKeyPair::generate(&mut prng);
+        let other_keypair: AXfrKeyPair = AXfrKeyPair::generate(&mut prng);
+        let commitment = BLSScalar::random(&mut prng);
+        let msg = b"owner memo";
+
+        let memo = AxfrOwnerMemo::new_with_view_tag(
+            &mut prng,
+            &keypair.get_public_key(),
+            msg,
+            &commitment,
+            AXfrMemoCipherSuite::ChaCha20Poly1305,
+        )
+        .unwrap();
+        assert_eq!(
+            memo.version(),
+            AxfrOwnerMemoVersion::V1(AXfrMemoCipherSuite::ChaCha20Poly1305)
+        );
+        assert!(memo.view_tag().is_some());
+        assert!(memo.match_view_tag(&keypair.get_secret_key()));
+        assert_eq!(
+            memo.decrypt(&keypair.get_secret_key(), &commitment)
+                .unwrap(),
+            msg.to_vec()
+        );
+
+        let memo =
+            AxfrOwnerMemo::new(&mut prng, &keypair.get_public_key(), msg, &commitment).unwrap();
+        assert!(memo.view_tag().is_none());
+        assert!(memo.match_view_tag(&other_keypair.get_secret_key()));
+    }
+
+    #[test]
+    fn test_scan_outputs() {
+        let mut prng = test_rng();
+        let keypair: AXfrKeyPair = AXfrKeyPair::generate(&mut prng);
+        let other_keypair: AXfrKeyPair = AXfrKeyPair::generate(&mut prng);
+
+        let mut outputs = vec![];
+        for i in 0..8u64 {
+            let owner = if i % 2 == 0 { &keypair } else { &other_keypair };
+            let oabar = OpenAnonAssetRecordBuilder::new()
+                .amount(i)
+                .asset_type(AssetType::from_identical_byte(i as u8))
+                .pub_key(&owner.get_public_key())
+                .memo_view_tag(i < 4)
+                .finalize(&mut prng)
+                .unwrap()
+                .build()
+                .unwrap();
+            outputs.push((
+                AnonAssetRecord::from_oabar(&oabar),
+                oabar.get_owner_memo().unwrap(),
+            ));
+        }
+
+        let owned = scan_outputs(&keypair, &outputs);
+        assert_eq!(owned.len(), 4);
+        for (oabar, i) in owned.iter().zip([0u64, 2, 4, 6]) {
+            assert_eq!(oabar.get_amount(), i);
+            assert_eq!(
+                oabar.get_asset_type(),
+                AssetType::from_identical_byte(i as u8)
+            );
+            assert_eq!(oabar.pub_key_ref(), &keypair.get_public_key());
+        }
+    }
 }
@@ -0,0 +1,477 @@
+use crate::anon_xfr::{
+    abar_to_abar::{AXfrNote, AXfrPreNote},
+    commit, commit_in_cs,
+    structs::{AnonAssetRecord, OpenAnonAssetRecord, PayeeWitness, PayeeWitnessVars},
+    AXfrPlonkPf, TurboPlonkCS,
+};
+use crate::setup::{ProverParams, VerifierParams};
+use crate::xfr::structs::AssetType;
+use merlin::Transcript;
+use noah_algebra::{
+    bls12_381::BLSScalar,
+    jubjub::{JubjubPoint, JubjubScalar},
+    prelude::*,
+};
+use noah_crypto::basic::anemoi_jive::{AnemoiJive, AnemoiJive381, AnemoiVLHTrace};
+use noah_plonk::plonk::{
+    constraint_system::{ecc::Point, TurboCS},
+    prover::prover_with_lagrange,
+    verifier::verifier,
+};
+
+/// The domain separator for anonymous asset tracing, for the Plonk proof.
+const ABAR_TRACING_PLONK_PROOF_TRANSCRIPT: &[u8] = b"ABAR Tracing Plonk Proof";
+/// The number of bits of the ephemeral scalar in the tracer memo.
+const ABAR_TRACING_SCALAR_BITS: usize = 252;
+/// The domain separator of the one-time pad that hides the amount.
+const ABAR_TRACING_AMOUNT_PAD: u64 = 0;
+/// The domain separator of the one-time pad that hides the asset type.
+const ABAR_TRACING_ASSET_TYPE_PAD: u64 = 1;
+
+/// The public key of an anonymous asset tracer.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct AXfrTracerPubKey(pub JubjubPoint);
+
+/// The key pair of an anonymous asset tracer.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AXfrTracerKeyPair {
+    /// The secret key.
+    pub(crate) sec_key: JubjubScalar,
+    /// The public key.
+    pub(crate) pub_key: AXfrTracerPubKey,
+}
+
+impl AXfrTracerKeyPair {
+    /// Generate a new key pair for anonymous asset tracing.
+    pub fn generate<R: CryptoRng + RngCore>(prng: &mut R) -> Self {
+        let sec_key = JubjubScalar::random(prng);
+        let pub_key = AXfrTracerPubKey(JubjubPoint::get_base().mul(&sec_key));
+        AXfrTracerKeyPair { sec_key, pub_key }
+    }
+
+    /// Return the public key.
+    pub fn get_public_key(&self) -> AXfrTracerPubKey {
+        self.pub_key
+    }
+}
+
+/// The memo that locks the amount and the asset type of an anonymous asset record to a tracer.
+///
+/// With `S = r * pk`, the amount and the asset type are masked by one-time pads derived from
+/// `S` using the Anemoi hash, so that the tracer recovers `S = sk * R` and removes the pads.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AXfrTracerMemo {
+    /// The tracer's public key.
+    pub tracer_pub_key: AXfrTracerPubKey,
+    /// The ephemeral point `R = r * G`.
+    pub ephemeral_point: JubjubPoint,
+    /// The amount masked by its one-time pad.
+    pub lock_amount: BLSScalar,
+    /// The asset type scalar masked by its one-time pad.
+    pub lock_asset_type: BLSScalar,
+}
+
+/// The tracer memo of an anonymous asset record, with a proof that it opens to the committed values.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AXfrTracingRecord {
+    /// The tracer memo.
+    pub memo: AXfrTracerMemo,
+    /// The proof that the memo locks the amount and the asset type in the commitment.
+    pub proof: AXfrPlonkPf,
+}
+
+/// The tracing extension of an anonymous transfer note, one record per output.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AXfrTracing {
+    /// The tracing records of the outputs, in the order of the outputs.
+    pub outputs: Vec<AXfrTracingRecord>,
+}
+
+/// Compute the one-time pads of the amount and the asset type from the shared point.
+fn tracing_pads(
+    shared_point: &JubjubPoint,
+) -> (
+    AnemoiVLHTrace<BLSScalar, 2, 12>,
+    AnemoiVLHTrace<BLSScalar, 2, 12>,
+) {
+    let x = shared_point.get_x();
+    let y = shared_point.get_y();
+    let amount_pad_trace = AnemoiJive381::eval_variable_length_hash_with_trace(&[
+        BLSScalar::from(ABAR_TRACING_AMOUNT_PAD),
+        x,
+        y,
+    ]);
+    let asset_type_pad_trace = AnemoiJive381::eval_variable_length_hash_with_trace(&[
+        BLSScalar::from(ABAR_TRACING_ASSET_TYPE_PAD),
+        x,
+        y,
+    ]);
+    (amount_pad_trace, asset_type_pad_trace)
+}
+
+/// Generate the tracing record of an open anonymous asset record.
+pub fn gen_abar_tracing_record<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    params: &ProverParams,
+    oabar: &OpenAnonAssetRecord,
+    tracer_pub_key: &AXfrTracerPubKey,
+) -> Result<AXfrTracingRecord> {
+    let payee_witness = PayeeWitness {
+        amount: oabar.get_amount(),
+        blind: oabar.get_blind(),
+        asset_type: oabar.get_asset_type().as_scalar(),
+        public_key: oabar.pub_key_ref().clone(),
+    };
+    gen_tracing_record(prng, params, &payee_witness, tracer_pub_key).c(d!())
+}
+
+/// Generate the tracing record of an output from its payee witness.
+fn gen_tracing_record<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    params: &ProverParams,
+    payee_witness: &PayeeWitness,
+    tracer_pub_key: &AXfrTracerPubKey,
+) -> Result<AXfrTracingRecord> {
+    let (_, output_trace) = commit(
+        &payee_witness.public_key,
+        payee_witness.blind,
+        payee_witness.amount,
+        payee_witness.asset_type,
+    )
+    .c(d!())?;
+
+    let r = JubjubScalar::random(prng);
+    let shared_point = tracer_pub_key.0.mul(&r);
+    let (amount_pad_trace, asset_type_pad_trace) = tracing_pads(&shared_point);
+
+    let memo = AXfrTracerMemo {
+        tracer_pub_key: *tracer_pub_key,
+        ephemeral_point: JubjubPoint::get_base().mul(&r),
+        lock_amount: BLSScalar::from(payee_witness.amount).add(&amount_pad_trace.output),
+        lock_asset_type: payee_witness.asset_type.add(&asset_type_pad_trace.output),
+    };
+
+    let mut transcript = Transcript::new(ABAR_TRACING_PLONK_PROOF_TRANSCRIPT);
+    let (mut cs, _) = build_abar_tracing_cs(
+        payee_witness,
+        tracer_pub_key,
+        &r,
+        &output_trace,
+        &amount_pad_trace,
+        &asset_type_pad_trace,
+    );
+    let witness = cs.get_and_clear_witness();
+
+    let proof = prover_with_lagrange(
+        prng,
+        &mut transcript,
+        &params.pcs,
+        params.lagrange_pcs.as_ref(),
+        &params.cs,
+        &params.prover_params,
+        &witness,
+    )
+    .c(d!(NoahError::AXfrProofError))?;
+
+    Ok(AXfrTracingRecord { memo, proof })
+}
+
+/// Verify the tracing record of an anonymous asset record for the given tracer.
+pub fn verify_abar_tracing_record(
+    params: &VerifierParams,
+    abar: &AnonAssetRecord,
+    record: &AXfrTracingRecord,
+    tracer_pub_key: &AXfrTracerPubKey,
+) -> Result<()> {
+    if record.memo.tracer_pub_key != *tracer_pub_key {
+        return Err(eg!(NoahError::ParameterError));
+    }
+
+    let ephemeral_point = Point::from(&record.memo.ephemeral_point);
+    let tracer_point = Point::from(&tracer_pub_key.0);
+
+    let mut transcript = Transcript::new(ABAR_TRACING_PLONK_PROOF_TRANSCRIPT);
+    let online_inputs = vec![
+        abar.commitment,
+        *ephemeral_point.get_x(),
+        *ephemeral_point.get_y(),
+        *tracer_point.get_x(),
+        *tracer_point.get_y(),
+        record.memo.lock_amount,
+        record.memo.lock_asset_type,
+    ];
+
+    verifier(
+        &mut transcript,
+        &params.pcs,
+        &params.cs,
+        &params.verifier_params,
+        &online_inputs,
+        &record.proof,
+    )
+    .c(d!(NoahError::AXfrVerificationError))
+}
+
+/// Generate the tracing extension for all the outputs of an anonymous transfer pre-note.
+pub fn gen_anon_xfr_tracing<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    params: &ProverParams,
+    pre_note: &AXfrPreNote,
+    tracer_pub_key: &AXfrTracerPubKey,
+) -> Result<AXfrTracing> {
+    let outputs = pre_note
+        .witness
+        .payees_witnesses
+        .iter()
+        .map(|payee_witness| gen_tracing_record(prng, params, payee_witness, tracer_pub_key))
+        .collect::<Result<Vec<AXfrTracingRecord>>>()
+        .c(d!())?;
+    Ok(AXfrTracing { outputs })
+}
+
+/// Verify that every output of an anonymous transfer note is traced by the given tracer.
+pub fn verify_anon_xfr_tracing(
+    params: &VerifierParams,
+    note: &AXfrNote,
+    tracer_pub_key: &AXfrTracerPubKey,
+) -> Result<()> {
+    let tracing = note
+        .tracing
+        .as_ref()
+        .c(d!(NoahError::AXfrVerificationError))?;
+    if tracing.outputs.len() != note.body.outputs.len() {
+        return Err(eg!(NoahError::AXfrVerificationError));
+    }
+    for (abar, record) in note.body.outputs.iter().zip(tracing.outputs.iter()) {
+        verify_abar_tracing_record(params, abar, record, tracer_pub_key).c(d!())?;
+    }
+    Ok(())
+}
+
+/// Decrypt a tracing record and return the amount and the asset type,
+/// where the asset type is looked up among the candidate asset types.
+pub fn trace_abar(
+    record: &AXfrTracingRecord,
+    tracer_keypair: &AXfrTracerKeyPair,
+    candidate_asset_types: &[AssetType],
+) -> Result<(u64, AssetType)> {
+    if record.memo.tracer_pub_key != tracer_keypair.pub_key {
+        return Err(eg!(NoahError::ParameterError));
+    }
+
+    let shared_point = record.memo.ephemeral_point.mul(&tracer_keypair.sec_key);
+    let (amount_pad_trace, asset_type_pad_trace) = tracing_pads(&shared_point);
+
+    let amount_scalar = record.memo.lock_amount.sub(&amount_pad_trace.output);
+    let amount_bytes = amount_scalar.to_bytes();
+    if amount_bytes[8..].iter().any(|b| *b != 0) {
+        return Err(eg!(NoahError::BogusAssetTracerMemo));
+    }
+    let amount = u64::from_le_bytes(amount_bytes[..8].try_into().unwrap()); // safe unwrap
+
+    let asset_type_scalar = record
+        .memo
+        .lock_asset_type
+        .sub(&asset_type_pad_trace.output);
+    let asset_type = candidate_asset_types
+        .iter()
+        .find(|asset_type| asset_type.as_scalar::<BLSScalar>() == asset_type_scalar)
+        .c(d!(NoahError::BogusAssetTracerMemo))?;
+
+    Ok((amount, *asset_type))
+}
+
+/// Decrypt the tracing extension of an anonymous transfer note and
+/// return the amount and the asset type of each output.
+pub fn trace_anon_assets(
+    note: &AXfrNote,
+    tracer_keypair: &AXfrTracerKeyPair,
+    candidate_asset_types: &[AssetType],
+) -> Result<Vec<(u64, AssetType)>> {
+    let tracing = note.tracing.as_ref().c(d!(NoahError::ParameterError))?;
+    tracing
+        .outputs
+        .iter()
+        .map(|record| trace_abar(record, tracer_keypair, candidate_asset_types))
+        .collect()
+}
+
+/// Construct the constraint system proving that a tracer memo locks the committed amount and asset type.
+pub fn build_abar_tracing_cs(
+    payee_data: &PayeeWitness,
+    tracer_pub_key: &AXfrTracerPubKey,
+    r: &JubjubScalar,
+    output_trace: &AnemoiVLHTrace<BLSScalar, 2, 12>,
+    amount_pad_trace: &AnemoiVLHTrace<BLSScalar, 2, 12>,
+    asset_type_pad_trace: &AnemoiVLHTrace<BLSScalar, 2, 12>,
+) -> (TurboPlonkCS, usize) {
+    let mut cs = TurboCS::new();
+    cs.load_anemoi_jive_parameters::<AnemoiJive381>();
+
+    let amount_var = cs.new_variable(BLSScalar::from(payee_data.amount));
+    let asset_var = cs.new_variable(payee_data.asset_type);
+    let blind = cs.new_variable(payee_data.blind);
+
+    let public_key_scalars = payee_data.public_key.get_public_key_scalars().unwrap();
+    let public_key_scalars_vars = [
+        cs.new_variable(public_key_scalars[0]),
+        cs.new_variable(public_key_scalars[1]),
+        cs.new_variable(public_key_scalars[2]),
+    ];
+
+    let payee = PayeeWitnessVars {
+        amount: amount_var,
+        blind,
+        asset_type: asset_var,
+        public_key_scalars: public_key_scalars_vars.clone(),
+    };
+
+    // commitment
+    let com_abar_out_var = commit_in_cs(
+        &mut cs,
+        payee.blind,
+        payee.amount,
+        payee.asset_type,
+        &public_key_scalars_vars,
+        output_trace,
+    );
+    cs.prepare_pi_variable(com_abar_out_var);
+
+    // the ephemeral point R = r * G
+    let r_var = cs.new_variable(BLSScalar::from_bytes(&r.to_bytes()).unwrap());
+    let ephemeral_var = cs.scalar_mul(JubjubPoint::get_base(), r_var, ABAR_TRACING_SCALAR_BITS);
+    cs.prepare_pi_point_variable(ephemeral_var);
+
+    // the shared point S = r * pk
+    let tracer_var = cs.new_point_variable(Point::from(&tracer_pub_key.0));
+    let shared_var = cs.var_base_scalar_mul(
+        &tracer_var,
+        tracer_pub_key.0,
+        r_var,
+        ABAR_TRACING_SCALAR_BITS,
+    );
+    cs.prepare_pi_point_variable(tracer_var);
+
+    // the one-time pads
+    let zero_var = cs.zero_var();
+    let one_var = cs.one_var();
+    let amount_pad_var = cs.new_variable(amount_pad_trace.output);
+    cs.anemoi_variable_length_hash(
+        amount_pad_trace,
+        &[zero_var, shared_var.get_x(), shared_var.get_y()],
+        amount_pad_var,
+    );
+    let asset_type_pad_var = cs.new_variable(asset_type_pad_trace.output);
+    cs.anemoi_variable_length_hash(
+        asset_type_pad_trace,
+        &[one_var, shared_var.get_x(), shared_var.get_y()],
+        asset_type_pad_var,
+    );
+
+    // the locked amount and asset type
+    let lock_amount_var = cs.add(payee.amount, amount_pad_var);
+    cs.prepare_pi_variable(lock_amount_var);
+    let lock_asset_type_var = cs.add(payee.asset_type, asset_type_pad_var);
+    cs.prepare_pi_variable(lock_asset_type_var);
+
+    // pad the number of constraints to power of two
+    cs.pad();
+
+    let n_constraints = cs.size;
+    (cs, n_constraints)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::anon_xfr::{
+        keys::AXfrKeyPair,
+        structs::{AnonAssetRecord, OpenAnonAssetRecordBuilder},
+        tracing::{
+            gen_abar_tracing_record, trace_abar, verify_abar_tracing_record, AXfrTracerKeyPair,
+        },
+    };
+    use crate::setup::{ProverParams, VerifierParams};
+    use crate::xfr::structs::AssetType;
+    use ark_std::test_rng;
+    use noah_algebra::{bls12_381::BLSScalar, prelude::*};
+
+    #[test]
+    fn test_abar_tracing() {
+        let mut prng = test_rng();
+        let params = ProverParams::abar_tracing_params().unwrap();
+        let verifier_params = VerifierParams::abar_tracing_params().unwrap();
+
+        let keypair = AXfrKeyPair::generate(&mut prng);
+        let tracer_keypair = AXfrTracerKeyPair::generate(&mut prng);
+        let tracer_pub_key = tracer_keypair.get_public_key();
+
+        let asset_type = AssetType::from_identical_byte(3);
+        let other_asset_type = AssetType::from_identical_byte(4);
+        let amount = prng.next_u64();
+
+        let oabar = OpenAnonAssetRecordBuilder::new()
+            .amount(amount)
+            .asset_type(asset_type)
+            .pub_key(&keypair.get_public_key())
+            .finalize(&mut prng)
+            .unwrap()
+            .build()
+            .unwrap();
+        let abar = AnonAssetRecord::from_oabar(&oabar);
+
+        let record = gen_abar_tracing_record(&mut prng, params, &oabar, &tracer_pub_key).unwrap();
+        assert!(
+            verify_abar_tracing_record(&verifier_params, &abar, &record, &tracer_pub_key).is_ok()
+        );
+        assert_eq!(
+            trace_abar(&record, &tracer_keypair, &[other_asset_type, asset_type]).unwrap(),
+            (amount, asset_type)
+        );
+
+        // unknown asset type
+        assert!(trace_abar(&record, &tracer_keypair, &[other_asset_type]).is_err());
+
+        // another tracer
+        let other_tracer_keypair = AXfrTracerKeyPair::generate(&mut prng);
+        let other_tracer_pub_key = other_tracer_keypair.get_public_key();
+        assert!(verify_abar_tracing_record(
+            &verifier_params,
+            &abar,
+            &record,
+            &other_tracer_pub_key
+        )
+        .is_err());
+        assert!(trace_abar(&record, &other_tracer_keypair, &[asset_type]).is_err());
+
+        // another commitment
+        let mut other_abar = abar.clone();
+        other_abar.commitment = BLSScalar::random(&mut prng);
+        assert!(verify_abar_tracing_record(
+            &verifier_params,
+            &other_abar,
+            &record,
+            &tracer_pub_key
+        )
+        .is_err());
+
+        // tampered memos
+        let mut bad_record = record.clone();
+        bad_record.memo.lock_amount = bad_record.memo.lock_amount.add(&BLSScalar::one());
+        assert!(
+            verify_abar_tracing_record(&verifier_params, &abar, &bad_record, &tracer_pub_key)
+                .is_err()
+        );
+        let mut bad_record = record.clone();
+        bad_record.memo.lock_asset_type = bad_record.memo.lock_asset_type.add(&BLSScalar::one());
+        assert!(
+            verify_abar_tracing_record(&verifier_params, &abar, &bad_record, &tracer_pub_key)
+                .is_err()
+        );
+        let mut bad_record = record;
+        bad_record.memo.ephemeral_point = bad_record.memo.ephemeral_point.double();
+        assert!(
+            verify_abar_tracing_record(&verifier_params, &abar, &bad_record, &tracer_pub_key)
+                .is_err()
+        );
+    }
+}
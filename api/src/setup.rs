@@ -8,8 +8,11 @@ use crate::anon_xfr::{
     abar_to_bar::build_abar_to_bar_cs,
     ar_to_abar::build_ar_to_abar_cs,
     bar_to_abar::build_bar_to_abar_cs,
-    commit, nullify,
+    commit,
+    disclosure::build_abar_disclosure_cs,
+    nullify,
     structs::{MTNode, MTPath},
+    tracing::{build_abar_tracing_cs, AXfrTracerKeyPair},
     TurboPlonkCS, FEE_TYPE, TREE_DEPTH,
 };
 use crate::parameters::{
@@ -23,9 +26,12 @@ use bulletproofs::BulletproofGens;
 use noah_algebra::ristretto::RistrettoPoint;
 use noah_algebra::{
     bls12_381::{BLSScalar, BLSG1},
+    jubjub::{JubjubPoint, JubjubScalar},
     prelude::*,
     ristretto::RistrettoScalar,
+    secq256k1::SECQ256K1G1,
 };
+use noah_crypto::basic::anemoi_jive::{AnemoiJive, AnemoiJive381};
 use noah_crypto::delegated_schnorr::{DelegatedSchnorrInspection, DelegatedSchnorrProof};
 use noah_crypto::field_simulation::SimFrParamsRistretto;
 use noah_plonk::{
@@ -33,7 +39,9 @@ use noah_plonk::{
         constraint_system::ConstraintSystem,
         indexer::{indexer_with_lagrange, PlonkPK, PlonkVK},
     },
-    poly_commit::{kzg_poly_com::KZGCommitmentSchemeBLS, pcs::PolyComScheme},
+    poly_commit::{
+        ipa_poly_com::IPAPolyComScheme, kzg_poly_com::KZGCommitmentSchemeBLS, pcs::PolyComScheme,
+    },
 };
 use rand_chacha::ChaChaRng;
 use serde::Deserialize;
@@ -101,8 +109,14 @@ pub struct VerifierParamsSplitSpecific {
 
 /// The range in the Bulletproofs range check.
 pub const BULLET_PROOF_RANGE: usize = 32;
+/// The range in the Bulletproofs range check for amounts with a single 64-bit commitment.
+pub const BULLET_PROOF_RANGE_U64: usize = 64;
 /// The maximal number
 pub const MAX_CONFIDENTIAL_RECORD_NUMBER: usize = 128;
+/// The maximal number of values in a 64-bit range proof, i.e., the confidential outputs and the difference,
+/// such that it needs as many generators as a 32-bit range proof of MAX_CONFIDENTIAL_RECORD_NUMBER values.
+pub const MAX_CONFIDENTIAL_RECORD_NUMBER_U64: usize =
+    MAX_CONFIDENTIAL_RECORD_NUMBER * BULLET_PROOF_RANGE / BULLET_PROOF_RANGE_U64;
 /// The maximal number of inputs and outputs supported by this setup program.
 pub const MAX_ANONYMOUS_RECORD_NUMBER: usize = 6;
 /// The default number of Bulletproofs generators
@@ -432,6 +446,49 @@ impl ProverParams {
         })
     }
 
+    /// Obtain the parameters for disclosure receipts of anonymous assets.
+    pub fn abar_disclosure_params() -> Result<ProverParams> {
+        let bls_zero = BLSScalar::zero();
+
+        // It's okay to choose a fixed seed to build CS.
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let keypair = AXfrKeyPair::generate(&mut prng);
+        let dummy_payee = PayeeWitness {
+            amount: 0,
+            blind: bls_zero,
+            asset_type: bls_zero,
+            public_key: keypair.get_public_key(),
+        };
+
+        let (_, output_commitment_trace) = commit(
+            &dummy_payee.public_key,
+            dummy_payee.blind,
+            dummy_payee.amount,
+            dummy_payee.asset_type,
+        )?;
+
+        let (cs, _) = build_abar_disclosure_cs(dummy_payee, true, true, &output_commitment_trace);
+
+        let pcs = load_srs_params(cs.size())?;
+        let lagrange_pcs = load_lagrange_params(cs.size());
+
+        let prover_params = indexer_with_lagrange(&cs, &pcs, lagrange_pcs.as_ref(), None).unwrap();
+
+        Ok(ProverParams {
+            pcs,
+            lagrange_pcs,
+            cs,
+            prover_params,
+        })
+    }
+
+    /// Obtain the parameters for tracing anonymous assets, which are built once and cached.
+    pub fn abar_tracing_params() -> Result<&'static ProverParams> {
+        ABAR_TRACING_PARAMS
+            .as_ref()
+            .c(d!(NoahError::MissingSRSError))
+    }
+
     /// Obtain the parameters for anonymous to transparent.
     pub fn abar_to_ar_params(tree_depth: usize) -> Result<ProverParams> {
         let bls_zero = BLSScalar::zero();
@@ -500,6 +557,64 @@ impl ProverParams {
     }
 }
 
+lazy_static! {
+    /// The parameters for tracing anonymous assets, built once since every tracing record
+    /// would otherwise run the indexer again.
+    static ref ABAR_TRACING_PARAMS: Option<ProverParams> = build_abar_tracing_params().ok();
+}
+
+/// Build the parameters for tracing anonymous assets.
+fn build_abar_tracing_params() -> Result<ProverParams> {
+    let bls_zero = BLSScalar::zero();
+
+    // It's okay to choose a fixed seed to build CS.
+    let mut prng = ChaChaRng::from_seed([0u8; 32]);
+    let keypair = AXfrKeyPair::generate(&mut prng);
+    let tracer_keypair = AXfrTracerKeyPair::generate(&mut prng);
+    let dummy_payee = PayeeWitness {
+        amount: 0,
+        blind: bls_zero,
+        asset_type: bls_zero,
+        public_key: keypair.get_public_key(),
+    };
+
+    let (_, output_commitment_trace) = commit(
+        &dummy_payee.public_key,
+        dummy_payee.blind,
+        dummy_payee.amount,
+        dummy_payee.asset_type,
+    )?;
+
+    let dummy_r = JubjubScalar::zero();
+    let dummy_shared_point = JubjubPoint::get_identity();
+    let pad_trace = AnemoiJive381::eval_variable_length_hash_with_trace(&[
+        bls_zero,
+        dummy_shared_point.get_x(),
+        dummy_shared_point.get_y(),
+    ]);
+
+    let (cs, _) = build_abar_tracing_cs(
+        &dummy_payee,
+        &tracer_keypair.get_public_key(),
+        &dummy_r,
+        &output_commitment_trace,
+        &pad_trace,
+        &pad_trace,
+    );
+
+    let pcs = load_srs_params(cs.size())?;
+    let lagrange_pcs = load_lagrange_params(cs.size());
+
+    let prover_params = indexer_with_lagrange(&cs, &pcs, lagrange_pcs.as_ref(), None).unwrap();
+
+    Ok(ProverParams {
+        pcs,
+        lagrange_pcs,
+        cs,
+        prover_params,
+    })
+}
+
 fn load_lagrange_params(size: usize) -> Option<KZGCommitmentSchemeBLS> {
     match LAGRANGE_BASES.get(&size) {
         None => None,
@@ -548,6 +663,29 @@ fn load_srs_params(size: usize) -> Result<KZGCommitmentSchemeBLS> {
     })
 }
 
+/// Load the IPA polynomial commitment scheme over the Secq256k1 curve,
+/// which takes its generators from the Bulletproofs(over the Secq256k1 curve) URS.
+/// `max_degree` - max degree of the polynomial, less than `ANON_XFR_BP_GENS_LEN`.
+///
+/// The scalar field of Secq256k1 has two-adicity 1, so it has no FFT domain of size beyond two.
+/// The scheme can commit to and open polynomials directly, but it cannot back Plonk circuits
+/// of a realistic size, whose indexer and prover need an FFT domain of the circuit size.
+/// `IPAPolyComSchemeBN254` can be used with Plonk instead.
+pub fn load_ipa_secq256k1_params(max_degree: usize) -> Result<IPAPolyComScheme<SECQ256K1G1>> {
+    let bp_gens = BulletproofGensOverSecq256k1::load().c(d!())?;
+    let n = (max_degree + 1).next_power_of_two();
+    if n > bp_gens.gens_capacity {
+        return Err(eg!(NoahError::ParameterError));
+    }
+
+    let share = bp_gens.share(0);
+    let generators = share.G(n).map(|g| SECQ256K1G1::from_raw(*g)).collect();
+    let mut h_vec = share.H(2).map(|h| SECQ256K1G1::from_raw(*h));
+    let generator_u = h_vec.next().c(d!(NoahError::ParameterError))?;
+    let generator_h = h_vec.next().c(d!(NoahError::ParameterError))?;
+    IPAPolyComScheme::from_generators(generators, generator_u, generator_h).c(d!())
+}
+
 impl VerifierParams {
     /// Create the verifier parameters for a given number of inputs and a given number of outputs.
     pub fn create(
@@ -651,6 +789,19 @@ impl VerifierParams {
         }
     }
 
+    /// Obtain the parameters for tracing anonymous assets.
+    pub fn abar_tracing_params() -> Result<VerifierParams> {
+        let prover_params = ProverParams::abar_tracing_params().c(d!())?;
+        Ok(VerifierParams {
+            pcs: prover_params.pcs.shrink_to_verifier_only()?,
+            cs: prover_params.cs.shrink_to_verifier_only()?,
+            verifier_params: prover_params
+                .prover_params
+                .get_verifier_params_ref()
+                .clone(),
+        })
+    }
+
     /// Obtain the parameters for anonymous to transparent.
     pub fn abar_to_ar_params() -> Result<VerifierParams> {
         match Self::abar_to_ar_params_prepare() {
@@ -706,12 +857,15 @@ impl From<ProverParams> for VerifierParams {
 
 #[cfg(test)]
 mod test {
-    use super::load_srs_params;
+    use super::{load_ipa_secq256k1_params, load_srs_params};
     use crate::anon_xfr::TREE_DEPTH;
-    use crate::setup::{ProverParams, VerifierParams};
+    use crate::setup::{ProverParams, VerifierParams, ANON_XFR_BP_GENS_LEN};
+    use ark_std::test_rng;
+    use merlin::Transcript;
     use noah_algebra::{
         bls12_381::{BLSScalar, BLSG1},
         prelude::*,
+        secq256k1::SECQ256K1Scalar,
     };
     use noah_plonk::poly_commit::{field_polynomial::FpPolynomial, pcs::PolyComScheme};
 
@@ -759,4 +913,42 @@ mod test {
         }
         assert_eq!(expected_committed_value, commitment.0);
     }
+
+    #[test]
+    fn test_ipa_secq256k1_params() {
+        let mut prng = test_rng();
+        let pcs = load_ipa_secq256k1_params(20).unwrap();
+        assert_eq!(pcs.max_degree(), 31);
+
+        let polys = (0..3)
+            .map(|_| FpPolynomial::<SECQ256K1Scalar>::random(&mut prng, 20))
+            .collect_vec();
+        let cms = polys.iter().map(|p| pcs.commit(p).unwrap()).collect_vec();
+        let point = SECQ256K1Scalar::random(&mut prng);
+        let evals = polys.iter().map(|p| pcs.eval(p, &point)).collect_vec();
+
+        let proof = {
+            let mut transcript = Transcript::new(b"TestIPA");
+            pcs.batch_prove(
+                &mut prng,
+                &mut transcript,
+                None,
+                &polys.iter().collect_vec(),
+                &point,
+                20,
+            )
+            .unwrap()
+        };
+        let mut transcript = Transcript::new(b"TestIPA");
+        pnk!(pcs.batch_verify(
+            &mut transcript,
+            &cms.iter().collect_vec(),
+            20,
+            &point,
+            &evals,
+            &proof,
+        ));
+
+        assert!(load_ipa_secq256k1_params(ANON_XFR_BP_GENS_LEN).is_err());
+    }
 }
@@ -1,12 +1,12 @@
 use crate::anon_creds::{
-    ac_confidential_open_commitment, ACCommitmentKey, ACUserSecretKey, Attr, AttributeCiphertext,
-    ConfidentialAC, Credential,
+    ac_confidential_open_commitment_with_linked, ACCommitmentKey, ACUserSecretKey, Attr,
+    AttributeCiphertext, AttributeEncKey, ConfidentialAC, Credential,
 };
 use crate::xfr::{
     sig::{XfrKeyPair, XfrPublicKey},
     structs::{
-        AssetRecord, AssetRecordTemplate, AssetType, BlindAssetRecord, OpenAssetRecord, OwnerMemo,
-        TracerMemo, TracingPolicies, XfrAmount, XfrAssetType,
+        AssetRecord, AssetRecordTemplate, AssetType, BlindAssetRecord, IdentityRevealPolicy,
+        OpenAssetRecord, OwnerMemo, TracerMemo, TracingPolicies, XfrAmount, XfrAssetType,
     },
 };
 use noah_algebra::{prelude::*, ristretto::RistrettoScalar};
@@ -159,6 +159,30 @@ impl AssetRecord {
         credential_sec_key: &ACUserSecretKey,
         credential: &Credential,
         credential_commitment_key: &ACCommitmentKey,
+    ) -> Result<AssetRecord> {
+        Self::from_open_asset_record_with_linked_tracing(
+            prng,
+            oar,
+            asset_tracing_policies,
+            credential_sec_key,
+            credential,
+            credential_commitment_key,
+            &[],
+        )
+        .c(d!())
+    }
+
+    /// Build a record input from OpenAssetRecord with associated policies for asset *and* identity
+    /// tracing as in `from_open_asset_record_with_tracing`, where the credentials of the further
+    /// issuers required by the identity reveal policies are taken from `linked_credentials`.
+    pub fn from_open_asset_record_with_linked_tracing<R: CryptoRng + RngCore>(
+        prng: &mut R,
+        oar: OpenAssetRecord,
+        asset_tracing_policies: TracingPolicies,
+        credential_sec_key: &ACUserSecretKey,
+        credential: &Credential,
+        credential_commitment_key: &ACCommitmentKey,
+        linked_credentials: &[&Credential],
     ) -> Result<AssetRecord> {
         let mut memos = vec![];
         let mut identity_proofs = vec![];
@@ -194,23 +218,19 @@ impl AssetRecord {
                     if credential.ipk != id_policy.cred_issuer_pub_key {
                         return Err(eg!(NoahError::ParameterError));
                     }
-                    let open = ac_confidential_open_commitment(
+                    let (attrs, open) = open_identity(
                         prng,
+                        &asset_tracing_policy.enc_keys.attrs_enc_key,
+                        id_policy,
                         credential_sec_key,
                         credential,
                         credential_commitment_key,
-                        &asset_tracing_policy.enc_keys.attrs_enc_key,
-                        id_policy.reveal_map.as_slice(),
-                        &[],
+                        linked_credentials,
                     )
                     .c(d!())?;
-                    let attrs_ctext = open.cts;
                     let proof = open.pok;
-                    let attrs = credential
-                        .get_revealed_attributes(id_policy.reveal_map.as_slice())
-                        .c(d!())?;
                     let attrs_and_ctexts: Vec<(Attr, AttributeCiphertext)> =
-                        attrs.into_iter().zip(attrs_ctext).collect();
+                        attrs.into_iter().zip(open.cts).collect();
 
                     (attrs_and_ctexts, Some(proof))
                 }
@@ -257,27 +277,43 @@ impl AssetRecord {
         credential_user_sec_key: &ACUserSecretKey,
         credential: &Credential,
         credential_key: &ACCommitmentKey,
+    ) -> Result<AssetRecord> {
+        Self::from_template_with_linked_identity_tracing(
+            prng,
+            template,
+            credential_user_sec_key,
+            credential,
+            credential_key,
+            &[],
+        )
+        .c(d!())
+    }
+
+    /// Create the asset record using a template, with identity tracing, where the credentials of
+    /// the further issuers required by the identity reveal policies are taken from
+    /// `linked_credentials`.
+    pub fn from_template_with_linked_identity_tracing<R: CryptoRng + RngCore>(
+        prng: &mut R,
+        template: &AssetRecordTemplate,
+        credential_user_sec_key: &ACUserSecretKey,
+        credential: &Credential,
+        credential_key: &ACCommitmentKey,
+        linked_credentials: &[&Credential],
     ) -> Result<AssetRecord> {
         let mut id_proofs_and_attrs = Vec::with_capacity(template.asset_tracing_policies.len());
         for policy in template.asset_tracing_policies.get_policies().iter() {
             let (conf_id, attrs) = if let Some(reveal_policy) = policy.identity_tracing.as_ref() {
-                (
-                    Some(
-                        ac_confidential_open_commitment(
-                            prng,
-                            credential_user_sec_key,
-                            credential,
-                            credential_key,
-                            &policy.enc_keys.attrs_enc_key,
-                            &reveal_policy.reveal_map,
-                            &[],
-                        )
-                        .c(d!())?,
-                    ),
-                    credential
-                        .get_revealed_attributes(reveal_policy.reveal_map.as_slice())
-                        .c(d!())?,
+                let (attrs, conf_id) = open_identity(
+                    prng,
+                    &policy.enc_keys.attrs_enc_key,
+                    reveal_policy,
+                    credential_user_sec_key,
+                    credential,
+                    credential_key,
+                    linked_credentials,
                 )
+                .c(d!())?;
+                (Some(conf_id), attrs)
             } else {
                 (None, vec![])
             };
@@ -541,6 +577,52 @@ pub fn open_blind_asset_record(
     })
 }
 
+/// Open the credential commitment to the attributes revealed by `id_policy`, together with the
+/// credentials of its further issuers, which are looked up in `linked_credentials`.
+/// It returns the revealed attributes in the order of their ciphertexts.
+#[allow(clippy::too_many_arguments)]
+fn open_identity<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    enc_key: &AttributeEncKey,
+    id_policy: &IdentityRevealPolicy,
+    credential_sec_key: &ACUserSecretKey,
+    credential: &Credential,
+    credential_commitment_key: &ACCommitmentKey,
+    linked_credentials: &[&Credential],
+) -> Result<(Vec<Attr>, ConfidentialAC)> {
+    let mut attrs = credential
+        .get_revealed_attributes(id_policy.reveal_map.as_slice())
+        .c(d!())?;
+    let mut linked = vec![];
+    for linked_policy in id_policy.linked_issuers.iter() {
+        let linked_credential = linked_credentials
+            .iter()
+            .find(|c| c.ipk == linked_policy.cred_issuer_pub_key)
+            .c(d!(NoahError::ParameterError))?;
+        attrs.extend(
+            linked_credential
+                .get_revealed_attributes(linked_policy.reveal_map.as_slice())
+                .c(d!())?,
+        );
+        linked.push((*linked_credential, linked_policy.reveal_map.as_slice()));
+    }
+    let open = ac_confidential_open_commitment_with_linked(
+        prng,
+        credential_sec_key,
+        credential,
+        credential_commitment_key,
+        enc_key,
+        id_policy.reveal_map.as_slice(),
+        &[],
+        &id_policy.predicate_map,
+        id_policy.expiry.as_ref(),
+        id_policy.revocation_list.as_ref(),
+        &linked,
+    )
+    .c(d!())?;
+    Ok((attrs, open))
+}
+
 /// Helper function to generate assemble asset record from templates.
 fn build_record_input_from_template<R: CryptoRng + RngCore>(
     prng: &mut R,
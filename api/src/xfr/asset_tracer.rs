@@ -1,6 +1,7 @@
-use crate::anon_creds::{Attr, AttributeCiphertext};
+use crate::anon_creds::{Attr, AttributeCiphertext, AttributeDecShare};
 use crate::xfr::structs::{
-    AssetTracerDecKeys, AssetTracerEncKeys, AssetType, TracerMemo, ASSET_TYPE_LENGTH,
+    AssetTracerDecKeyShares, AssetTracerDecKeys, AssetTracerEncKeys, AssetTracerThresholdParams,
+    AssetType, TracerMemo, ASSET_TYPE_LENGTH,
 };
 use noah_algebra::{
     bls12_381::{BLSScalar, BLSG1},
@@ -9,9 +10,17 @@ use noah_algebra::{
 };
 use noah_crypto::basic::{
     elgamal::{
-        elgamal_encrypt, elgamal_partial_decrypt, ElGamalCiphertext, ElGamalDecKey, ElGamalEncKey,
+        elgamal_decrypt_u32, elgamal_encrypt, elgamal_partial_decrypt, ElGamalCiphertext,
+        ElGamalDecKey, ElGamalDecryptionTable, ElGamalEncKey,
+    },
+    hybrid_encryption::{
+        hybrid_decrypt_with_x25519_secret_key, hybrid_encrypt_x25519,
+        hybrid_partial_decrypt_x25519, hybrid_threshold_decrypt_x25519, XDecShare,
+    },
+    threshold_elgamal::{
+        threshold_elgamal_combine, threshold_elgamal_partial_decrypt, ElGamalDecKeyShare,
+        ElGamalDecShare, ThresholdElGamalParams,
     },
-    hybrid_encryption::{hybrid_decrypt_with_x25519_secret_key, hybrid_encrypt_x25519},
 };
 
 /// The encryption key for the record data.
@@ -20,10 +29,94 @@ pub type RecordDataEncKey = ElGamalEncKey<RistrettoPoint>;
 pub type RecordDataDecKey = ElGamalDecKey<RistrettoScalar>;
 /// The ciphertext of the record data.
 pub type RecordDataCiphertext = ElGamalCiphertext<RistrettoPoint>;
+/// The precomputed table for decrypting the amounts in the record data.
+pub type RecordDataDecryptionTable = ElGamalDecryptionTable<RistrettoPoint>;
+/// A share of the decryption key for the record data.
+pub type RecordDataDecKeyShare = ElGamalDecKeyShare<RistrettoScalar>;
+/// A decryption share of a record data ciphertext.
+pub type RecordDataDecShare = ElGamalDecShare<RistrettoScalar, RistrettoPoint>;
+/// The public parameters of a shared decryption key for the record data.
+pub type RecordDataThresholdParams = ThresholdElGamalParams<RistrettoPoint>;
 type DecryptedAssetMemo = (Option<u64>, Option<AssetType>, Vec<Attr>);
 
 const U32_BYTES: usize = 4;
 
+/// The decryption shares of a tracer memo computed by one of the auditors.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TracerMemoDecShares {
+    /// The decryption shares of the lower and higher 32 bits of the amount.
+    pub lock_amount: Option<(RecordDataDecShare, RecordDataDecShare)>,
+    /// The decryption share of the asset type.
+    pub lock_asset_type: Option<RecordDataDecShare>,
+    /// The decryption shares of the attributes.
+    pub lock_attributes: Vec<AttributeDecShare>,
+    /// The decryption share of the locked information.
+    pub lock_info: XDecShare,
+}
+
+/// The group elements encrypted in the ElGamal ciphertexts of a tracer memo.
+struct OpenedTracerMemo {
+    amount: Option<(RistrettoPoint, RistrettoPoint)>,
+    asset_type: Option<RistrettoPoint>,
+    attributes: Vec<BLSG1>,
+}
+
+impl OpenedTracerMemo {
+    /// Parse the locked information and check it against the ElGamal ciphertexts.
+    fn sanitize(&self, mut plaintext: Vec<u8>) -> Result<DecryptedAssetMemo> {
+        // decrypt and sanitize amount
+        let amount = match self.amount.as_ref() {
+            Some((point_low, point_high)) => {
+                if plaintext.len() < 2 * U32_BYTES {
+                    return Err(eg!(NoahError::BogusAssetTracerMemo));
+                }
+                let amount_low = u8_be_slice_to_u32(&plaintext[0..U32_BYTES]);
+                let amount_high = u8_be_slice_to_u32(&plaintext[U32_BYTES..2 * U32_BYTES]);
+                let base = RistrettoPoint::get_base();
+                if base.mul(&RistrettoScalar::from(amount_low)) != *point_low
+                    || base.mul(&RistrettoScalar::from(amount_high)) != *point_high
+                {
+                    return Err(eg!(NoahError::BogusAssetTracerMemo));
+                }
+                plaintext = plaintext.split_off(2 * U32_BYTES);
+                Some((amount_low as u64) + ((amount_high as u64) << 32))
+            }
+            None => None,
+        };
+
+        // decrypt and sanitize asset type
+        let asset_type = match self.asset_type.as_ref() {
+            Some(point) => {
+                if plaintext.len() < ASSET_TYPE_LENGTH {
+                    return Err(eg!(NoahError::BogusAssetTracerMemo));
+                }
+                let mut asset_type = [0u8; ASSET_TYPE_LENGTH];
+                asset_type.copy_from_slice(&plaintext[0..ASSET_TYPE_LENGTH]);
+                let asset_type = AssetType(asset_type);
+                if RistrettoPoint::get_base().mul(&asset_type.as_scalar()) != *point {
+                    return Err(eg!(NoahError::BogusAssetTracerMemo));
+                }
+                plaintext = plaintext.split_off(ASSET_TYPE_LENGTH);
+                Some(asset_type)
+            }
+            None => None,
+        };
+
+        if plaintext.len() != self.attributes.len() * U32_BYTES {
+            return Err(eg!(NoahError::BogusAssetTracerMemo));
+        }
+        let mut attrs = vec![];
+        for (attr_byte, point) in plaintext.chunks(U32_BYTES).zip(self.attributes.iter()) {
+            let attr = u8_be_slice_to_u32(attr_byte);
+            if BLSG1::get_base().mul(&BLSScalar::from(attr)) != *point {
+                return Err(eg!(NoahError::BogusAssetTracerMemo));
+            }
+            attrs.push(attr);
+        }
+        Ok((amount, asset_type, attrs))
+    }
+}
+
 impl TracerMemo {
     /// Sample a new TracerMemo.
     /// amount_info is (amount_low, amount_high, amount_blind_low, amount_blind_high) tuple
@@ -78,59 +171,137 @@ impl TracerMemo {
     /// Decrypts the asset tracer memo:
     /// Returns NoahError:BogusAssetTracerMemo in case decrypted values are inconsistents
     pub fn decrypt(&self, dec_key: &AssetTracerDecKeys) -> Result<DecryptedAssetMemo> {
-        let mut plaintext =
+        let plaintext =
             hybrid_decrypt_with_x25519_secret_key(&self.lock_info, &dec_key.lock_info_dec_key);
+        let record_data_dec_key = &dec_key.record_data_dec_key;
+        let opened = OpenedTracerMemo {
+            amount: self.lock_amount.as_ref().map(|(ctext_low, ctext_high)| {
+                (
+                    elgamal_partial_decrypt(ctext_low, record_data_dec_key),
+                    elgamal_partial_decrypt(ctext_high, record_data_dec_key),
+                )
+            }),
+            asset_type: self
+                .lock_asset_type
+                .as_ref()
+                .map(|ctext| elgamal_partial_decrypt(ctext, record_data_dec_key)),
+            attributes: self
+                .lock_attributes
+                .iter()
+                .map(|ctext| elgamal_partial_decrypt(ctext, &dec_key.attrs_dec_key))
+                .collect(),
+        };
+        opened.sanitize(plaintext)
+    }
 
-        // decrypt and sanitize amount
-        let amount = if self.lock_amount.is_some() {
-            if plaintext.len() < 2 * U32_BYTES {
-                return Err(eg!(NoahError::BogusAssetTracerMemo));
-            }
-            let amount_low = u8_be_slice_to_u32(&plaintext[0..U32_BYTES]);
-            let amount_high = u8_be_slice_to_u32(&plaintext[U32_BYTES..2 * U32_BYTES]);
-            let amount = (amount_low as u64) + ((amount_high as u64) << 32);
-            self.verify_amount(&dec_key.record_data_dec_key, amount)
+    /// Compute the decryption shares of the memo with the key shares of one of the auditors.
+    /// Each share of an ElGamal ciphertext comes with a proof that it is consistent with the auditor's verification keys.
+    pub fn decryption_shares<R: CryptoRng + RngCore>(
+        &self,
+        prng: &mut R,
+        key_shares: &AssetTracerDecKeyShares,
+    ) -> Result<TracerMemoDecShares> {
+        let record_data_key_share = &key_shares.record_data_dec_key_share;
+        let lock_amount = self.lock_amount.as_ref().map(|(ctext_low, ctext_high)| {
+            (
+                threshold_elgamal_partial_decrypt(prng, ctext_low, record_data_key_share),
+                threshold_elgamal_partial_decrypt(prng, ctext_high, record_data_key_share),
+            )
+        });
+        let lock_asset_type = self
+            .lock_asset_type
+            .as_ref()
+            .map(|ctext| threshold_elgamal_partial_decrypt(prng, ctext, record_data_key_share));
+        let lock_attributes = self
+            .lock_attributes
+            .iter()
+            .map(|ctext| {
+                threshold_elgamal_partial_decrypt(prng, ctext, &key_shares.attrs_dec_key_share)
+            })
+            .collect();
+        let lock_info =
+            hybrid_partial_decrypt_x25519(&self.lock_info, &key_shares.lock_info_dec_key_share)
                 .c(d!(NoahError::BogusAssetTracerMemo))?;
-            plaintext = plaintext.split_off(2 * U32_BYTES);
-            Some(amount)
-        } else {
-            None
-        };
 
-        // decrypt and sanitize asset type
-        let asset_type = if self.lock_asset_type.is_some() {
-            if plaintext.len() < ASSET_TYPE_LENGTH {
-                return Err(eg!(NoahError::BogusAssetTracerMemo));
+        Ok(TracerMemoDecShares {
+            lock_amount,
+            lock_asset_type,
+            lock_attributes,
+            lock_info,
+        })
+    }
+
+    /// Decrypts the asset tracer memo from the decryption shares of the auditors, any `threshold` of which suffice.
+    /// Invalid shares of the ElGamal ciphertexts are skipped. The shares of self.lock_info carry no proof,
+    /// so subsets of `threshold` auditors are tried until the locked information matches the ElGamal ciphertexts.
+    /// Returns NoahError:BogusAssetTracerMemo if there are not enough valid shares or the decrypted values are inconsistent.
+    pub fn threshold_decrypt<R: CryptoRng + RngCore>(
+        &self,
+        prng: &mut R,
+        params: &AssetTracerThresholdParams,
+        dec_shares: &[TracerMemoDecShares],
+    ) -> Result<DecryptedAssetMemo> {
+        let record_data_params = &params.record_data_params;
+        let amount = match self.lock_amount.as_ref() {
+            Some((ctext_low, ctext_high)) => {
+                let (shares_low, shares_high): (Vec<_>, Vec<_>) = dec_shares
+                    .iter()
+                    .filter_map(|dec_share| dec_share.lock_amount.clone())
+                    .unzip();
+                let point_low =
+                    threshold_elgamal_combine(prng, record_data_params, ctext_low, &shares_low)
+                        .c(d!(NoahError::BogusAssetTracerMemo))?;
+                let point_high =
+                    threshold_elgamal_combine(prng, record_data_params, ctext_high, &shares_high)
+                        .c(d!(NoahError::BogusAssetTracerMemo))?;
+                Some((point_low, point_high))
             }
-            let mut asset_type = [0u8; ASSET_TYPE_LENGTH];
-            asset_type.copy_from_slice(&plaintext[0..ASSET_TYPE_LENGTH]);
-            let asset_type = AssetType(asset_type);
+            None => None,
+        };
 
-            self.verify_asset_type(&dec_key.record_data_dec_key, &asset_type)
-                .c(d!(NoahError::BogusAssetTracerMemo))?;
-            plaintext = plaintext.split_off(ASSET_TYPE_LENGTH);
-            Some(asset_type)
-        } else {
-            None
+        let asset_type = match self.lock_asset_type.as_ref() {
+            Some(ctext) => {
+                let shares = dec_shares
+                    .iter()
+                    .filter_map(|dec_share| dec_share.lock_asset_type.clone())
+                    .collect_vec();
+                let point = threshold_elgamal_combine(prng, record_data_params, ctext, &shares)
+                    .c(d!(NoahError::BogusAssetTracerMemo))?;
+                Some(point)
+            }
+            None => None,
         };
 
-        if plaintext.len() < self.lock_attributes.len() * U32_BYTES {
-            return Err(eg!(NoahError::BogusAssetTracerMemo));
-        }
-        let mut attrs = vec![];
-        for attr_byte in plaintext.chunks(U32_BYTES) {
-            attrs.push(u8_be_slice_to_u32(attr_byte));
+        let mut attributes = vec![];
+        for (i, ctext) in self.lock_attributes.iter().enumerate() {
+            let shares = dec_shares
+                .iter()
+                .filter_map(|dec_share| dec_share.lock_attributes.get(i).cloned())
+                .collect_vec();
+            let point = threshold_elgamal_combine(prng, &params.attrs_params, ctext, &shares)
+                .c(d!(NoahError::BogusAssetTracerMemo))?;
+            attributes.push(point);
         }
 
-        if !self
-            .verify_identity_attributes(&dec_key.attrs_dec_key, &attrs)
-            .c(d!(NoahError::BogusAssetTracerMemo))?
+        let opened = OpenedTracerMemo {
+            amount,
+            asset_type,
+            attributes,
+        };
+        for lock_info_shares in dec_shares
             .iter()
-            .all(|&x| x)
+            .map(|dec_share| dec_share.lock_info.clone())
+            .combinations(params.lock_info_params.threshold)
         {
-            return Err(eg!(NoahError::BogusAssetTracerMemo));
+            if let Ok(plaintext) =
+                hybrid_threshold_decrypt_x25519(&self.lock_info, &lock_info_shares)
+            {
+                if let Ok(decrypted) = opened.sanitize(plaintext) {
+                    return Ok(decrypted);
+                }
+            }
         }
-        Ok((amount, asset_type, attrs))
+        Err(eg!(NoahError::BogusAssetTracerMemo))
     }
 
     /// Check if the amount encrypted in self.lock_amount is expected.
@@ -158,6 +329,27 @@ impl TracerMemo {
         }
     }
 
+    /// Decrypt the amount in self.lock_amount with the baby-step giant-step algorithm,
+    /// without relying on self.lock_info.
+    /// If self.lock_amount is None, return Err(NoahError::ParameterError),
+    /// Otherwise, if either half of the amount cannot be found, return Err(NoahError::AssetTracingExtractionError),
+    /// else return the decrypted amount.
+    pub fn extract_amount(
+        &self,
+        dec_key: &ElGamalDecKey<RistrettoScalar>,
+        table: &RecordDataDecryptionTable,
+    ) -> Result<u64> {
+        if let Some((ctext_low, ctext_high)) = self.lock_amount.as_ref() {
+            let low = elgamal_decrypt_u32(ctext_low, dec_key, table)
+                .c(d!(NoahError::AssetTracingExtractionError))?;
+            let high = elgamal_decrypt_u32(ctext_high, dec_key, table)
+                .c(d!(NoahError::AssetTracingExtractionError))?;
+            Ok((low as u64) + ((high as u64) << 32))
+        } else {
+            Err(eg!(NoahError::ParameterError)) // nothing to decrypt
+        }
+    }
+
     /// Check if the asset type encrypted in self.lock_asset_type is expected.
     /// return Err if lock_asset_type is None or the decrypted is not as expected, else returns Ok.
     pub fn verify_asset_type(
@@ -223,10 +415,16 @@ impl TracerMemo {
 
 #[cfg(test)]
 mod tests {
-    use crate::xfr::structs::{AssetTracerKeyPair, AssetType, TracerMemo};
+    use crate::xfr::asset_tracer::RecordDataDecryptionTable;
+    use crate::xfr::structs::{
+        AssetTracerEncKeys, AssetTracerKeyPair, AssetTracerThresholdParams, AssetType, TracerMemo,
+    };
     use ark_std::test_rng;
     use noah_algebra::{bls12_381::BLSScalar, prelude::*, ristretto::RistrettoScalar};
-    use noah_crypto::basic::elgamal::elgamal_encrypt;
+    use noah_crypto::basic::{
+        elgamal::{elgamal_encrypt, ELGAMAL_DEFAULT_BABY_STEP_BITS},
+        hybrid_encryption::NoahHybridCiphertext,
+    };
 
     #[test]
     fn extract_amount_from_tracer_memo() {
@@ -256,6 +454,148 @@ mod tests {
             .is_ok());
     }
 
+    #[test]
+    fn extract_amount_without_lock_info() {
+        let mut prng = test_rng();
+        let tracer_keys = AssetTracerKeyPair::generate(&mut prng);
+        let table = RecordDataDecryptionTable::new(ELGAMAL_DEFAULT_BABY_STEP_BITS).unwrap();
+
+        let memo = TracerMemo::new(&mut prng, &tracer_keys.enc_key, None, None, &[]);
+        msg_eq!(
+            NoahError::ParameterError,
+            memo.extract_amount(&tracer_keys.dec_key.record_data_dec_key, &table)
+                .unwrap_err(),
+        );
+
+        let amount = (123456u64 << 32) + 4_000_000_000;
+        let (low, high) = u64_to_u32_pair(amount);
+        let mut memo = TracerMemo::new(
+            &mut prng,
+            &tracer_keys.enc_key,
+            Some((
+                low,
+                high,
+                &RistrettoScalar::random(&mut prng),
+                &RistrettoScalar::random(&mut prng),
+            )),
+            None,
+            &[],
+        );
+
+        // Corrupt the hybrid ciphertext, so that only the ElGamal ciphertexts are usable.
+        let mut lock_info = memo.lock_info.noah_to_bytes();
+        lock_info.iter_mut().skip(32).for_each(|b| *b ^= 0xff);
+        memo.lock_info = NoahHybridCiphertext::noah_from_bytes(&lock_info).unwrap();
+        assert!(memo.decrypt(&tracer_keys.dec_key).is_err());
+        assert_eq!(
+            memo.extract_amount(&tracer_keys.dec_key.record_data_dec_key, &table)
+                .unwrap(),
+            amount
+        );
+    }
+
+    #[test]
+    fn threshold_decrypt_tracer_memo() {
+        let mut prng = test_rng();
+        let (params, key_shares) = AssetTracerThresholdParams::generate(&mut prng, 2, 3).unwrap();
+        let enc_keys = params.get_enc_keys();
+
+        fn new_memo<R: CryptoRng + RngCore>(
+            prng: &mut R,
+            enc_keys: &AssetTracerEncKeys,
+            amount: u64,
+            asset_type: &AssetType,
+            attrs: &[u32],
+        ) -> TracerMemo {
+            let (low, high) = u64_to_u32_pair(amount);
+            let attrs_info = attrs
+                .iter()
+                .map(|attr| {
+                    let ctext = elgamal_encrypt(
+                        &BLSScalar::from(*attr),
+                        &BLSScalar::random(prng),
+                        &enc_keys.attrs_enc_key,
+                    );
+                    (*attr, ctext)
+                })
+                .collect_vec();
+            TracerMemo::new(
+                prng,
+                &enc_keys,
+                Some((
+                    low,
+                    high,
+                    &RistrettoScalar::random(prng),
+                    &RistrettoScalar::random(prng),
+                )),
+                Some((asset_type, &RistrettoScalar::random(prng))),
+                &attrs_info,
+            )
+        }
+
+        let amount = (3u64 << 32) + 1000;
+        let asset_type = AssetType::from_identical_byte(2u8);
+        let attrs = [7u32, 65535u32];
+        let memo = new_memo(&mut prng, &enc_keys, amount, &asset_type, &attrs);
+
+        let dec_shares = key_shares
+            .iter()
+            .map(|key_share| memo.decryption_shares(&mut prng, key_share).unwrap())
+            .collect_vec();
+        let expected = (Some(amount), Some(asset_type), attrs.to_vec());
+
+        for subset in [[0usize, 1], [2, 0], [1, 2]] {
+            let shares = subset.iter().map(|&i| dec_shares[i].clone()).collect_vec();
+            assert_eq!(
+                memo.threshold_decrypt(&mut prng, &params, &shares).unwrap(),
+                expected
+            );
+        }
+
+        // a single auditor cannot decrypt
+        msg_eq!(
+            NoahError::BogusAssetTracerMemo,
+            memo.threshold_decrypt(&mut prng, &params, &dec_shares[..1])
+                .unwrap_err(),
+        );
+
+        // the shares of a corrupted auditor are skipped
+        let other_memo = new_memo(
+            &mut prng,
+            &enc_keys,
+            1u64,
+            &AssetType::from_identical_byte(3u8),
+            &[8u32, 9u32],
+        );
+        let corrupted_shares = other_memo
+            .decryption_shares(&mut prng, &key_shares[0])
+            .unwrap();
+        let shares = vec![
+            corrupted_shares.clone(),
+            dec_shares[1].clone(),
+            dec_shares[2].clone(),
+        ];
+        assert_eq!(
+            memo.threshold_decrypt(&mut prng, &params, &shares).unwrap(),
+            expected
+        );
+        let mut shares = dec_shares.clone();
+        shares[2].lock_info = corrupted_shares.lock_info.clone();
+        assert_eq!(
+            memo.threshold_decrypt(&mut prng, &params, &shares).unwrap(),
+            expected
+        );
+        msg_eq!(
+            NoahError::BogusAssetTracerMemo,
+            memo.threshold_decrypt(
+                &mut prng,
+                &params,
+                &[corrupted_shares, dec_shares[1].clone()]
+            )
+            .unwrap_err(),
+        );
+    }
+
     #[test]
     fn extract_asset_type_from_tracer_memo() {
         let mut prng = test_rng();
@@ -0,0 +1,378 @@
+use crate::anon_creds::Attr;
+use crate::xfr::{
+    extract_record_data,
+    sig::XfrPublicKey,
+    structs::{AssetTracerKeyPair, AssetType, BlindAssetRecord, XfrNote},
+};
+use noah_algebra::{prelude::*, utils::b64enc};
+use sha2::{Digest, Sha256};
+
+/// The header of the CSV export of audit records.
+pub const XFR_AUDIT_CSV_HEADER: &str =
+    "note_index,note_id,tracer_index,side,record_index,owner,status,amount,asset_type,attributes";
+
+/// Whether a traced record is an input or an output of the note.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum XfrAuditSide {
+    /// An input of the note.
+    Input,
+    /// An output of the note.
+    Output,
+}
+
+/// The outcome of decrypting a tracer memo.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum XfrAuditStatus {
+    /// The memo decrypts to values consistent with the record.
+    Valid,
+    /// The memo fails validation with `NoahError::BogusAssetTracerMemo`.
+    BogusMemo,
+    /// The memo cannot be decrypted against the record for another reason, e.g., it does not lock
+    /// the confidential amount of the record.
+    MalformedMemo,
+    /// The note does not have one list of tracer memos per record, so that its memos cannot be
+    /// matched with the records.
+    MemoCountMismatch,
+}
+
+/// An audit record for one tracer memo of a confidential transfer note.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct XfrAuditRecord {
+    /// The position of the note in the audited stream.
+    pub note_index: usize,
+    /// The identifier of the note, see [`xfr_note_id`].
+    pub note_id: String,
+    /// The position of the tracer key pair that opens the memo, if the memos match the records.
+    pub tracer_index: Option<usize>,
+    /// Whether the record is an input or an output.
+    pub side: XfrAuditSide,
+    /// The position of the record among the inputs or the outputs.
+    pub record_index: usize,
+    /// The owner of the record.
+    pub owner: XfrPublicKey,
+    /// The outcome of decrypting the memo.
+    pub status: XfrAuditStatus,
+    /// The amount, if the memo is valid or the amount is transparent.
+    pub amount: Option<u64>,
+    /// The asset type, if the memo is valid or the asset type is transparent.
+    pub asset_type: Option<AssetType>,
+    /// The revealed identity attributes, empty if the memo is bogus.
+    pub attributes: Vec<Attr>,
+}
+
+impl XfrAuditRecord {
+    /// Return the record as a CSV row following [`XFR_AUDIT_CSV_HEADER`].
+    /// Keys, identifiers, and asset types are URL-safe base64, and attributes are separated by `;`,
+    /// so that no field needs to be quoted.
+    pub fn to_csv_row(&self) -> String {
+        let side = match self.side {
+            XfrAuditSide::Input => "input",
+            XfrAuditSide::Output => "output",
+        };
+        let status = match self.status {
+            XfrAuditStatus::Valid => "valid",
+            XfrAuditStatus::BogusMemo => "bogus_memo",
+            XfrAuditStatus::MalformedMemo => "malformed_memo",
+            XfrAuditStatus::MemoCountMismatch => "memo_count_mismatch",
+        };
+        let tracer_index = self.tracer_index.map(|x| x.to_string()).unwrap_or_default();
+        let amount = self.amount.map(|x| x.to_string()).unwrap_or_default();
+        let asset_type = self.asset_type.map(|x| b64enc(&x.0)).unwrap_or_default();
+        let attributes = self.attributes.iter().map(|x| x.to_string()).join(";");
+        format!(
+            "{},{},{},{},{},{},{},{},{},{}",
+            self.note_index,
+            self.note_id,
+            tracer_index,
+            side,
+            self.record_index,
+            b64enc(&self.owner.to_bytes()),
+            status,
+            amount,
+            asset_type,
+            attributes
+        )
+    }
+}
+
+/// Return the identifier of a confidential transfer note, the URL-safe base64 encoding
+/// of the SHA-256 hash of its serialized body.
+pub fn xfr_note_id(note: &XfrNote) -> Result<String> {
+    let msg = bincode::serialize(&note.body)
+        .map_err(|_| NoahError::SerializationError)
+        .c(d!())?;
+    Ok(b64enc(&Sha256::digest(&msg)))
+}
+
+/// Decrypt all the tracer memos of a note that any of the tracer key pairs can open.
+/// A note whose memos do not match its records yields one `MemoCountMismatch` record per record,
+/// and a memo which cannot be decrypted yields a record with a `BogusMemo` or `MalformedMemo`
+/// status, so that the audit of the other memos goes on.
+pub fn audit_xfr_note(
+    note_index: usize,
+    note: &XfrNote,
+    tracer_keypairs: &[&AssetTracerKeyPair],
+) -> Result<Vec<XfrAuditRecord>> {
+    let body = &note.body;
+    let note_id = xfr_note_id(note).c(d!())?;
+
+    let records = body
+        .inputs
+        .iter()
+        .enumerate()
+        .map(|(i, record)| (XfrAuditSide::Input, i, record))
+        .chain(
+            body.outputs
+                .iter()
+                .enumerate()
+                .map(|(i, record)| (XfrAuditSide::Output, i, record)),
+        );
+    let unopened_record = |side: XfrAuditSide,
+                           record_index: usize,
+                           record: &BlindAssetRecord,
+                           tracer_index: Option<usize>,
+                           status: XfrAuditStatus| {
+        XfrAuditRecord {
+            note_index,
+            note_id: note_id.clone(),
+            tracer_index,
+            side,
+            record_index,
+            owner: record.public_key,
+            status,
+            amount: record.amount.get_amount(),
+            asset_type: record.asset_type.get_asset_type(),
+            attributes: vec![],
+        }
+    };
+
+    if body.inputs.len() + body.outputs.len() != body.asset_tracing_memos.len() {
+        return Ok(records
+            .map(|(side, record_index, record)| {
+                unopened_record(
+                    side,
+                    record_index,
+                    record,
+                    None,
+                    XfrAuditStatus::MemoCountMismatch,
+                )
+            })
+            .collect());
+    }
+
+    let mut result = vec![];
+    for ((side, record_index, record), memos) in records.zip(&body.asset_tracing_memos) {
+        for memo in memos {
+            for (tracer_index, tracer_keypair) in tracer_keypairs.iter().enumerate() {
+                if memo.enc_key != tracer_keypair.enc_key {
+                    continue;
+                }
+                let audit_record = match extract_record_data(record, memo, &tracer_keypair.dec_key)
+                {
+                    Ok((amount, asset_type, attributes, owner)) => XfrAuditRecord {
+                        note_index,
+                        note_id: note_id.clone(),
+                        tracer_index: Some(tracer_index),
+                        side,
+                        record_index,
+                        owner,
+                        status: XfrAuditStatus::Valid,
+                        amount: Some(amount),
+                        asset_type: Some(asset_type),
+                        attributes,
+                    },
+                    Err(e) => {
+                        let status =
+                            if e.msg_has_overloop(eg!(NoahError::BogusAssetTracerMemo).as_ref()) {
+                                XfrAuditStatus::BogusMemo
+                            } else {
+                                XfrAuditStatus::MalformedMemo
+                            };
+                        unopened_record(side, record_index, record, Some(tracer_index), status)
+                    }
+                };
+                result.push(audit_record);
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Audit a stream of confidential transfer notes with the tracer key pairs.
+pub fn audit_xfr_notes<'a, I: IntoIterator<Item = &'a XfrNote>>(
+    notes: I,
+    tracer_keypairs: &[&AssetTracerKeyPair],
+) -> Result<Vec<XfrAuditRecord>> {
+    let mut result = vec![];
+    for (note_index, note) in notes.into_iter().enumerate() {
+        result.extend(audit_xfr_note(note_index, note, tracer_keypairs).c(d!())?);
+    }
+    Ok(result)
+}
+
+/// Export audit records as CSV, starting with [`XFR_AUDIT_CSV_HEADER`].
+pub fn xfr_audit_records_to_csv(records: &[XfrAuditRecord]) -> String {
+    let mut csv = String::from(XFR_AUDIT_CSV_HEADER);
+    csv.push('\n');
+    for record in records {
+        csv.push_str(&record.to_csv_row());
+        csv.push('\n');
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::xfr::{
+        asset_record::AssetRecordType,
+        audit::{
+            audit_xfr_notes, xfr_audit_records_to_csv, xfr_note_id, XfrAuditRecord, XfrAuditSide,
+            XfrAuditStatus, XFR_AUDIT_CSV_HEADER,
+        },
+        structs::{
+            AssetRecordTemplate, AssetTracerKeyPair, AssetType, TracerMemo, TracingPolicies,
+            TracingPolicy,
+        },
+        tests::{create_xfr, gen_key_pair_vec},
+    };
+    use ark_std::test_rng;
+    use noah_algebra::prelude::*;
+
+    #[test]
+    fn test_audit_xfr_notes() {
+        let mut prng = test_rng();
+        let asset_type = AssetType::from_identical_byte(1u8);
+        let record_type = AssetRecordType::ConfidentialAmount_ConfidentialAssetType;
+
+        let tracer1 = AssetTracerKeyPair::generate(&mut prng);
+        let tracer2 = AssetTracerKeyPair::generate(&mut prng);
+        let policies = |tracer: &AssetTracerKeyPair| {
+            TracingPolicies::from_policy(TracingPolicy {
+                enc_keys: tracer.enc_key.clone(),
+                asset_tracing: true,
+                identity_tracing: None,
+            })
+        };
+
+        let in_keys = gen_key_pair_vec(1, &mut prng);
+        let out_keys = gen_key_pair_vec(2, &mut prng);
+        let inputs = [AssetRecordTemplate::with_asset_tracing(
+            30,
+            asset_type,
+            record_type,
+            in_keys[0].pub_key,
+            policies(&tracer1),
+        )];
+        let outputs = [
+            AssetRecordTemplate::with_asset_tracing(
+                10,
+                asset_type,
+                record_type,
+                out_keys[0].pub_key,
+                policies(&tracer1),
+            ),
+            AssetRecordTemplate::with_asset_tracing(
+                20,
+                asset_type,
+                record_type,
+                out_keys[1].pub_key,
+                policies(&tracer2),
+            ),
+        ];
+        let (note, _, _) = create_xfr(&mut prng, &inputs, &outputs, &[&in_keys[0]]);
+
+        // a note whose first output memo does not match the record
+        let mut bogus_note = note.clone();
+        bogus_note.body.asset_tracing_memos[1][0].lock_amount = bogus_note.body.asset_tracing_memos
+            [0][0]
+            .lock_amount
+            .clone();
+
+        // a note with a missing list of memos
+        let mut truncated_note = note.clone();
+        truncated_note.body.asset_tracing_memos.pop();
+
+        let records =
+            audit_xfr_notes([&note, &bogus_note, &truncated_note], &[&tracer1, &tracer2]).unwrap();
+        assert_eq!(records.len(), 9);
+
+        let note_id = xfr_note_id(&note).unwrap();
+        let expected = [
+            (XfrAuditSide::Input, 0, 0, 30, in_keys[0].pub_key),
+            (XfrAuditSide::Output, 0, 0, 10, out_keys[0].pub_key),
+            (XfrAuditSide::Output, 1, 1, 20, out_keys[1].pub_key),
+        ];
+        for (record, (side, record_index, tracer_index, amount, owner)) in
+            records[..3].iter().zip(expected.iter())
+        {
+            assert_eq!(record.note_index, 0);
+            assert_eq!(record.note_id, note_id);
+            assert_eq!(record.side, *side);
+            assert_eq!(record.record_index, *record_index);
+            assert_eq!(record.tracer_index, Some(*tracer_index));
+            assert_eq!(record.owner, *owner);
+            assert_eq!(record.status, XfrAuditStatus::Valid);
+            assert_eq!(record.amount, Some(*amount));
+            assert_eq!(record.asset_type, Some(asset_type));
+        }
+
+        assert_ne!(records[3].note_id, note_id);
+        assert_eq!(records[3].status, XfrAuditStatus::Valid);
+        assert_eq!(records[4].status, XfrAuditStatus::BogusMemo);
+        assert_eq!(records[4].side, XfrAuditSide::Output);
+        assert_eq!(records[4].record_index, 0);
+        assert_eq!(records[4].amount, None);
+        assert_eq!(records[5].status, XfrAuditStatus::Valid);
+        for (record, (side, record_index, _, _, owner)) in records[6..].iter().zip(expected.iter())
+        {
+            assert_eq!(record.note_index, 2);
+            assert_eq!(record.status, XfrAuditStatus::MemoCountMismatch);
+            assert_eq!(record.side, *side);
+            assert_eq!(record.record_index, *record_index);
+            assert_eq!(record.tracer_index, None);
+            assert_eq!(record.owner, *owner);
+        }
+
+        // other errors are recorded as well, e.g., for a memo which does not lock the
+        // confidential amount of its record, and the audit of the stream goes on
+        let mut inconsistent_note = note.clone();
+        inconsistent_note.body.asset_tracing_memos[1][0] =
+            TracerMemo::new(&mut prng, &tracer1.enc_key, None, None, &[]);
+        let inconsistent_records =
+            audit_xfr_notes([&inconsistent_note, &note], &[&tracer1]).unwrap();
+        assert_eq!(inconsistent_records.len(), 4);
+        assert_eq!(inconsistent_records[0].status, XfrAuditStatus::Valid);
+        assert_eq!(
+            inconsistent_records[1].status,
+            XfrAuditStatus::MalformedMemo
+        );
+        assert_eq!(inconsistent_records[1].side, XfrAuditSide::Output);
+        assert_eq!(inconsistent_records[1].record_index, 0);
+        assert_eq!(inconsistent_records[1].amount, None);
+        assert_eq!(inconsistent_records[2].note_index, 1);
+        assert_eq!(inconsistent_records[2].status, XfrAuditStatus::Valid);
+        assert_eq!(inconsistent_records[3].status, XfrAuditStatus::Valid);
+        assert!(xfr_audit_records_to_csv(&inconsistent_records).contains(",malformed_memo,,"));
+
+        // JSON
+        let json = serde_json::to_string(&records).unwrap();
+        let records_de: Vec<XfrAuditRecord> = serde_json::from_str(&json).unwrap();
+        assert_eq!(records, records_de);
+
+        // CSV
+        let csv = xfr_audit_records_to_csv(&records);
+        let lines = csv.lines().collect_vec();
+        assert_eq!(lines.len(), 10);
+        assert_eq!(lines[0], XFR_AUDIT_CSV_HEADER);
+        let n_columns = XFR_AUDIT_CSV_HEADER.split(',').count();
+        for line in lines.iter() {
+            assert_eq!(line.split(',').count(), n_columns);
+        }
+        assert!(lines[1].starts_with(&format!("0,{},0,input,0,", note_id)));
+        assert!(lines[5].contains(",output,0,"));
+        assert!(lines[5].contains(",bogus_memo,,"));
+        assert!(lines[7].starts_with("2,"));
+        assert!(lines[7].contains(",,input,0,"));
+        assert!(lines[7].contains(",memo_count_mismatch,,"));
+    }
+}
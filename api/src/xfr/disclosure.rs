@@ -0,0 +1,261 @@
+use crate::anon_xfr::TWO_POW_32;
+use crate::xfr::structs::{AssetType, BlindAssetRecord, OpenAssetRecord};
+use merlin::Transcript;
+use noah_algebra::{
+    prelude::*,
+    ristretto::{RistrettoPoint, RistrettoScalar},
+};
+use noah_crypto::basic::{
+    matrix_sigma::{sigma_prove, sigma_verify, SigmaProof},
+    pedersen_comm::PedersenCommitmentRistretto,
+};
+
+/// The domain separator for disclosure receipts of confidential asset records.
+const XFR_DISCLOSURE_TRANSCRIPT: &[u8] = b"Xfr Disclosure Receipt";
+
+/// A standalone proof that a blind asset record carries the disclosed amount and/or asset type,
+/// without revealing the blinding factors.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct XfrDisclosureReceipt {
+    /// The disclosed amount, if any.
+    pub amount: Option<u64>,
+    /// The disclosed asset type, if any.
+    pub asset_type: Option<AssetType>,
+    /// The proof of knowledge of the blinding factors of the disclosed commitments.
+    pub proof: SigmaProof<RistrettoScalar, RistrettoPoint>,
+}
+
+/// Collect the commitments to open against the disclosed values, each as `blind * B_blinding`.
+fn disclosed_commitments(
+    pc_gens: &PedersenCommitmentRistretto,
+    record: &BlindAssetRecord,
+    amount: Option<u64>,
+    asset_type: Option<&AssetType>,
+) -> Result<Vec<RistrettoPoint>> {
+    let mut commitments = vec![];
+    if let Some(amount) = amount {
+        if record.amount.is_confidential() {
+            let comm = record.amount.get_amount_commitment(pc_gens).c(d!())?;
+            commitments.push(comm.sub(&pc_gens.B.mul(&RistrettoScalar::from(amount))));
+        } else if record.amount.get_amount() != Some(amount) {
+            return Err(eg!(NoahError::ZKProofVerificationError));
+        }
+    }
+    if let Some(asset_type) = asset_type {
+        if let Some(comm) = record.asset_type.get_commitment() {
+            let comm = comm.decompress().c(d!(NoahError::DecompressElementError))?;
+            commitments.push(comm.sub(&pc_gens.B.mul(&asset_type.as_scalar())));
+        } else if record.asset_type.get_asset_type() != Some(*asset_type) {
+            return Err(eg!(NoahError::ZKProofVerificationError));
+        }
+    }
+    Ok(commitments)
+}
+
+/// Initialize the transcript and the sigma protocol statement `blind_i * B_blinding = D_i`.
+fn init_disclosure(
+    transcript: &mut Transcript,
+    pc_gens: &PedersenCommitmentRistretto,
+    record: &BlindAssetRecord,
+    commitments: &[RistrettoPoint],
+    context: &[u8],
+) -> (Vec<RistrettoPoint>, Vec<Vec<usize>>, Vec<usize>) {
+    transcript.append_message(b"context", context);
+    transcript.append_message(b"public key", &record.public_key.to_bytes());
+
+    let mut elems = vec![RistrettoPoint::get_identity(), pc_gens.B_blinding];
+    elems.extend_from_slice(commitments);
+    let lhs_matrix = (0..commitments.len())
+        .map(|i| {
+            (0..commitments.len())
+                .map(|j| if i == j { 1 } else { 0 })
+                .collect()
+        })
+        .collect();
+    let rhs_vec = (0..commitments.len()).map(|i| i + 2).collect();
+    (elems, lhs_matrix, rhs_vec)
+}
+
+/// Generate a receipt that discloses the amount and/or the asset type of an open asset record
+/// to a third party, bound to the given context (e.g., a challenge from the third party).
+pub fn gen_xfr_disclosure_receipt<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    record: &OpenAssetRecord,
+    disclose_amount: bool,
+    disclose_asset_type: bool,
+    context: &[u8],
+) -> Result<XfrDisclosureReceipt> {
+    if !disclose_amount && !disclose_asset_type {
+        return Err(eg!(NoahError::ParameterError));
+    }
+    let pc_gens = PedersenCommitmentRistretto::default();
+    let bar = &record.blind_asset_record;
+
+    let amount = if disclose_amount {
+        Some(record.amount)
+    } else {
+        None
+    };
+    let asset_type = if disclose_asset_type {
+        Some(record.asset_type)
+    } else {
+        None
+    };
+
+    let mut blinds = vec![];
+    if disclose_amount && bar.amount.is_confidential() {
+        let (blind_lo, blind_hi) = record.amount_blinds;
+        blinds.push(blind_lo.add(&blind_hi.mul(&RistrettoScalar::from(TWO_POW_32))));
+    }
+    if disclose_asset_type && bar.asset_type.is_confidential() {
+        blinds.push(record.type_blind);
+    }
+
+    let commitments = disclosed_commitments(&pc_gens, bar, amount, asset_type.as_ref()).c(d!())?;
+    let mut transcript = Transcript::new(XFR_DISCLOSURE_TRANSCRIPT);
+    let (elems, lhs_matrix, _) =
+        init_disclosure(&mut transcript, &pc_gens, bar, &commitments, context);
+    let proof = sigma_prove(
+        &mut transcript,
+        prng,
+        &elems,
+        &lhs_matrix,
+        &blinds.iter().collect_vec(),
+    );
+
+    Ok(XfrDisclosureReceipt {
+        amount,
+        asset_type,
+        proof,
+    })
+}
+
+/// Verify a disclosure receipt against a blind asset record and the context it is bound to.
+pub fn verify_xfr_disclosure_receipt<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    record: &BlindAssetRecord,
+    receipt: &XfrDisclosureReceipt,
+    context: &[u8],
+) -> Result<()> {
+    if receipt.amount.is_none() && receipt.asset_type.is_none() {
+        return Err(eg!(NoahError::ParameterError));
+    }
+    let pc_gens = PedersenCommitmentRistretto::default();
+
+    let commitments = disclosed_commitments(
+        &pc_gens,
+        record,
+        receipt.amount,
+        receipt.asset_type.as_ref(),
+    )
+    .c(d!())?;
+    let mut transcript = Transcript::new(XFR_DISCLOSURE_TRANSCRIPT);
+    let (elems, lhs_matrix, rhs_vec) =
+        init_disclosure(&mut transcript, &pc_gens, record, &commitments, context);
+    sigma_verify(
+        &mut transcript,
+        prng,
+        &elems,
+        &lhs_matrix,
+        &rhs_vec,
+        &receipt.proof,
+    )
+    .c(d!(NoahError::ZKProofVerificationError))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::xfr::{
+        asset_record::{build_open_asset_record, AssetRecordType},
+        disclosure::{gen_xfr_disclosure_receipt, verify_xfr_disclosure_receipt},
+        sig::XfrKeyPair,
+        structs::{AssetRecordTemplate, AssetType},
+    };
+    use ark_std::test_rng;
+    use noah_crypto::basic::pedersen_comm::PedersenCommitmentRistretto;
+
+    fn do_test_disclosure(record_type: AssetRecordType) {
+        let mut prng = test_rng();
+        let pc_gens = PedersenCommitmentRistretto::default();
+
+        let amount = (1u64 << 40) + 7;
+        let asset_type = AssetType::from_identical_byte(5u8);
+        let keypair = XfrKeyPair::generate(&mut prng);
+        let template = AssetRecordTemplate::with_no_asset_tracing(
+            amount,
+            asset_type,
+            record_type,
+            keypair.pub_key,
+        );
+        let (oar, _, _) = build_open_asset_record(&mut prng, &pc_gens, &template, vec![vec![]]);
+        let bar = &oar.blind_asset_record;
+        let context = b"receipt for invoice #42";
+
+        for (disclose_amount, disclose_asset_type) in [(true, true), (true, false), (false, true)] {
+            let receipt = gen_xfr_disclosure_receipt(
+                &mut prng,
+                &oar,
+                disclose_amount,
+                disclose_asset_type,
+                context,
+            )
+            .unwrap();
+            assert_eq!(receipt.amount.is_some(), disclose_amount);
+            assert_eq!(receipt.asset_type.is_some(), disclose_asset_type);
+            assert!(verify_xfr_disclosure_receipt(&mut prng, bar, &receipt, context).is_ok());
+
+            // transparent values are checked directly, so only proofs about commitments
+            // depend on the context and the record
+            let opens_commitment = (disclose_amount && bar.amount.is_confidential())
+                || (disclose_asset_type && bar.asset_type.is_confidential());
+            assert_eq!(
+                verify_xfr_disclosure_receipt(&mut prng, bar, &receipt, b"another context")
+                    .is_err(),
+                opens_commitment
+            );
+
+            // wrong values
+            if disclose_amount {
+                let mut bad_receipt = receipt.clone();
+                bad_receipt.amount = Some(amount + 1);
+                assert!(
+                    verify_xfr_disclosure_receipt(&mut prng, bar, &bad_receipt, context).is_err()
+                );
+            }
+            if disclose_asset_type {
+                let mut bad_receipt = receipt.clone();
+                bad_receipt.asset_type = Some(AssetType::from_identical_byte(6u8));
+                assert!(
+                    verify_xfr_disclosure_receipt(&mut prng, bar, &bad_receipt, context).is_err()
+                );
+            }
+
+            // another record
+            let other_keypair = XfrKeyPair::generate(&mut prng);
+            let mut other_bar = bar.clone();
+            other_bar.public_key = other_keypair.pub_key;
+            assert_eq!(
+                verify_xfr_disclosure_receipt(&mut prng, &other_bar, &receipt, context).is_err(),
+                opens_commitment
+            );
+        }
+
+        // nothing disclosed
+        assert!(gen_xfr_disclosure_receipt(&mut prng, &oar, false, false, context).is_err());
+    }
+
+    #[test]
+    fn test_disclosure_confidential() {
+        do_test_disclosure(AssetRecordType::ConfidentialAmount_ConfidentialAssetType);
+    }
+
+    #[test]
+    fn test_disclosure_confidential_amount() {
+        do_test_disclosure(AssetRecordType::ConfidentialAmount_NonConfidentialAssetType);
+    }
+
+    #[test]
+    fn test_disclosure_confidential_asset_type() {
+        do_test_disclosure(AssetRecordType::NonConfidentialAmount_ConfidentialAssetType);
+    }
+}
@@ -1,7 +1,7 @@
 use noah_algebra::{
     collections::HashMap,
     prelude::*,
-    ristretto::{CompressedRistretto, RistrettoScalar},
+    ristretto::{CompressedRistretto, RistrettoPoint, RistrettoScalar},
 };
 use noah_crypto::basic::pedersen_comm::{PedersenCommitment, PedersenCommitmentRistretto};
 use serde::ser::Serialize;
@@ -12,8 +12,14 @@ pub mod asset_mixer;
 pub mod asset_record;
 /// Module for asset tracing.
 pub mod asset_tracer;
+/// Module for audit reports of traced transfers.
+pub mod audit;
+/// Module for disclosure receipts.
+pub mod disclosure;
 /// Module for zero-knowledge proofs.
 pub mod proofs;
+/// Module for the registry of tracing policies.
+pub mod registry;
 /// Module for signatures.
 pub mod sig;
 /// Module for shared structures.
@@ -31,7 +37,8 @@ use self::{
     },
     proofs::{
         asset_amount_tracing_proofs, asset_proof, batch_verify_confidential_amount,
-        batch_verify_confidential_asset, batch_verify_tracer_tracing_proof, gen_range_proof,
+        batch_verify_confidential_amount_u64, batch_verify_confidential_asset,
+        batch_verify_tracer_tracing_proof, gen_range_proof, gen_range_proof_u64,
     },
     sig::{XfrKeyPair, XfrMultiSig, XfrPublicKey},
     structs::*,
@@ -72,10 +79,11 @@ impl XfrType {
             if asset_type != record.open_asset_record.asset_type {
                 multi_asset = true;
             }
-            let confidential_amount = matches!(
-                record.open_asset_record.blind_asset_record.amount,
-                XfrAmount::Confidential(_)
-            );
+            let confidential_amount = record
+                .open_asset_record
+                .blind_asset_record
+                .amount
+                .is_confidential();
             let confidential_asset_type = matches!(
                 record.open_asset_record.blind_asset_record.asset_type,
                 XfrAssetType::Confidential(_)
@@ -383,8 +391,17 @@ fn gen_xfr_proofs_single_asset<R: CryptoRng + RngCore>(
 ) -> Result<AssetTypeAndAmountProof> {
     let pc_gens = PedersenCommitmentRistretto::default();
 
+    // Single amount commitments are range-checked with 64-bit proofs.
+    let amount_u64 = inputs
+        .iter()
+        .chain(outputs)
+        .any(|x| matches!(x.blind_asset_record.amount, XfrAmount::ConfidentialU64(_)));
+
     match xfr_type {
         XfrType::NonConfidential_SingleAsset => Ok(AssetTypeAndAmountProof::NoProof),
+        XfrType::ConfidentialAmount_NonConfidentialAssetType_SingleAsset if amount_u64 => Ok(
+            AssetTypeAndAmountProof::ConfAmountU64(gen_range_proof_u64(inputs, outputs).c(d!())?),
+        ),
         XfrType::ConfidentialAmount_NonConfidentialAssetType_SingleAsset => Ok(
             AssetTypeAndAmountProof::ConfAmount(gen_range_proof(inputs, outputs).c(d!())?),
         ),
@@ -393,6 +410,12 @@ fn gen_xfr_proofs_single_asset<R: CryptoRng + RngCore>(
                 asset_proof(prng, &pc_gens, inputs, outputs).c(d!())?,
             )))
         }
+        XfrType::Confidential_SingleAsset if amount_u64 => {
+            Ok(AssetTypeAndAmountProof::ConfAllU64(Box::new((
+                gen_range_proof_u64(inputs, outputs).c(d!())?,
+                asset_proof(prng, &pc_gens, inputs, outputs).c(d!())?,
+            ))))
+        }
         XfrType::Confidential_SingleAsset => Ok(AssetTypeAndAmountProof::ConfAll(Box::new((
             gen_range_proof(inputs, outputs).c(d!())?,
             asset_proof(prng, &pc_gens, inputs, outputs).c(d!())?,
@@ -503,6 +526,7 @@ pub(crate) fn batch_verify_xfr_body_asset_records<R: CryptoRng + RngCore>(
     bodies: &[&XfrBody],
 ) -> Result<()> {
     let mut conf_amount_records = vec![];
+    let mut conf_amount_u64_records = vec![];
     let mut conf_asset_type_records = vec![];
     let mut conf_asset_mix_bodies = vec![];
 
@@ -520,6 +544,18 @@ pub(crate) fn batch_verify_xfr_body_asset_records<R: CryptoRng + RngCore>(
                 verify_plain_asset(body.inputs.as_slice(), body.outputs.as_slice()).c(d!())?;
                 // no batching
             }
+            AssetTypeAndAmountProof::ConfAllU64(x) => {
+                let range_proof = &(*x).0;
+                let asset_proof = &(*x).1;
+                conf_amount_u64_records.push((&body.inputs, &body.outputs, range_proof));
+                conf_asset_type_records.push((&body.inputs, &body.outputs, asset_proof));
+                // save for batching
+            }
+            AssetTypeAndAmountProof::ConfAmountU64(range_proof) => {
+                conf_amount_u64_records.push((&body.inputs, &body.outputs, range_proof)); // save for batching
+                verify_plain_asset(body.inputs.as_slice(), body.outputs.as_slice()).c(d!())?;
+                // no batching
+            }
             AssetTypeAndAmountProof::ConfAsset(asset_proof) => {
                 verify_plain_amounts(body.inputs.as_slice(), body.outputs.as_slice()).c(d!())?; // no batching
                 conf_asset_type_records.push((&body.inputs, &body.outputs, asset_proof));
@@ -542,6 +578,7 @@ pub(crate) fn batch_verify_xfr_body_asset_records<R: CryptoRng + RngCore>(
 
     // 1. Batch-verify confidential amounts.
     batch_verify_confidential_amount(prng, params, conf_amount_records.as_slice()).c(d!())?;
+    batch_verify_confidential_amount_u64(prng, conf_amount_u64_records.as_slice()).c(d!())?;
 
     // 2. Batch-verify confidential asset_types.
     batch_verify_confidential_asset(prng, &conf_asset_type_records).c(d!())?;
@@ -558,6 +595,7 @@ pub struct XfrNotePoliciesRef<'b> {
     pub(crate) inputs_sig_commitments: Vec<Option<&'b ACCommitment>>,
     pub(crate) outputs_tracing_policies: Vec<&'b TracingPolicies>,
     pub(crate) outputs_sig_commitments: Vec<Option<&'b ACCommitment>>,
+    pub(crate) timestamp: Option<u64>,
 }
 
 impl<'b> XfrNotePoliciesRef<'b> {
@@ -574,8 +612,16 @@ impl<'b> XfrNotePoliciesRef<'b> {
             inputs_sig_commitments,
             outputs_tracing_policies,
             outputs_sig_commitments,
+            timestamp: None,
         }
     }
+
+    /// Set the current time of the verifier, in seconds since the Unix epoch, which is required
+    /// to check the expiry of credentials in identity tracing policies.
+    pub fn with_timestamp(mut self, timestamp: u64) -> XfrNotePoliciesRef<'b> {
+        self.timestamp = Some(timestamp);
+        self
+    }
 }
 
 pub(crate) fn if_some_closure(x: &Option<ACCommitment>) -> Option<&ACCommitment> {
@@ -599,6 +645,10 @@ pub struct XfrNotePolicies {
     pub outputs_tracing_policies: Vec<TracingPolicies>,
     /// The attribute commitments for each output.
     pub outputs_sig_commitments: Vec<Option<ACCommitment>>,
+    /// The current time of the verifier, in seconds since the Unix epoch, which is required to
+    /// check the expiry of credentials in identity tracing policies.
+    #[serde(default)]
+    pub timestamp: Option<u64>,
 }
 
 impl XfrNotePolicies {
@@ -615,9 +665,16 @@ impl XfrNotePolicies {
             inputs_sig_commitments,
             outputs_tracing_policies,
             outputs_sig_commitments,
+            timestamp: None,
         }
     }
 
+    /// Set the current time of the verifier, in seconds since the Unix epoch.
+    pub fn with_timestamp(mut self, timestamp: u64) -> XfrNotePolicies {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
     /// Return empty policies for the given numbers of inputs and outputs.
     pub fn empty_policies(num_inputs: usize, num_outputs: usize) -> XfrNotePolicies {
         XfrNotePolicies {
@@ -626,13 +683,14 @@ impl XfrNotePolicies {
             inputs_sig_commitments: vec![None; num_inputs],
             outputs_tracing_policies: vec![Default::default(); num_outputs],
             outputs_sig_commitments: vec![None; num_outputs],
+            timestamp: None,
         }
     }
 
     /// Obtain a reference of the policies.
     pub fn to_ref(&self) -> XfrNotePoliciesRef<'_> {
         if self.valid {
-            XfrNotePoliciesRef::new(
+            let policies = XfrNotePoliciesRef::new(
                 self.inputs_tracing_policies.iter().collect_vec(),
                 self.inputs_sig_commitments
                     .iter()
@@ -643,7 +701,11 @@ impl XfrNotePolicies {
                     .iter()
                     .map(if_some_closure)
                     .collect_vec(),
-            )
+            );
+            match self.timestamp {
+                Some(timestamp) => policies.with_timestamp(timestamp),
+                None => policies,
+            }
         } else {
             XfrNotePoliciesRef::default()
         }
@@ -815,6 +877,10 @@ fn batch_verify_asset_mix<R: CryptoRng + RngCore>(
                         c1.decompress().c(d!(NoahError::DecompressElementError)),
                         c2.decompress().c(d!(NoahError::DecompressElementError)),
                     ),
+                    XfrAmount::ConfidentialU64(c) => (
+                        c.decompress().c(d!(NoahError::DecompressElementError)),
+                        Ok(RistrettoPoint::get_identity()),
+                    ),
                     XfrAmount::NonConfidential(amount) => {
                         let pc_gens = PedersenCommitmentRistretto::default();
                         let (low, high) = u64_to_u32_pair(amount);
@@ -902,41 +968,51 @@ pub(crate) fn extract_tracing_info(
     memos: &[(&BlindAssetRecord, &TracerMemo)],
     dec_key: &AssetTracerDecKeys,
 ) -> Result<Vec<RecordData>> {
-    let mut result = vec![];
-    for (blind_asset_record, memo) in memos {
-        let (amount_option, asset_type_option, attributes) = memo.decrypt(dec_key).c(d!())?; // return BogusAssetTracerMemo in case of error.
-        let amount = match memo.lock_amount {
-            None => blind_asset_record
-                .amount
-                .get_amount()
-                .c(d!(NoahError::InconsistentStructureError))?,
-            Some(_) => match amount_option {
-                None => {
-                    return Err(eg!(NoahError::InconsistentStructureError));
-                }
-                Some(amt) => amt,
-            },
-        };
+    memos
+        .iter()
+        .map(|(blind_asset_record, memo)| {
+            extract_record_data(blind_asset_record, memo, dec_key).c(d!())
+        })
+        .collect()
+}
 
-        let asset_type = match memo.lock_asset_type {
-            None => blind_asset_record
-                .asset_type
-                .get_asset_type()
-                .c(d!(NoahError::InconsistentStructureError))?,
-            Some(_) => match asset_type_option {
-                None => {
-                    return Err(eg!(NoahError::InconsistentStructureError));
-                }
-                Some(asset_type) => asset_type,
-            },
-        };
+/// Decrypt a memo with the decryption keys, completing the transparent fields from the record.
+pub(crate) fn extract_record_data(
+    blind_asset_record: &BlindAssetRecord,
+    memo: &TracerMemo,
+    dec_key: &AssetTracerDecKeys,
+) -> Result<RecordData> {
+    let (amount_option, asset_type_option, attributes) = memo.decrypt(dec_key).c(d!())?; // return BogusAssetTracerMemo in case of error.
+    let amount = match memo.lock_amount {
+        None => blind_asset_record
+            .amount
+            .get_amount()
+            .c(d!(NoahError::InconsistentStructureError))?,
+        Some(_) => match amount_option {
+            None => {
+                return Err(eg!(NoahError::InconsistentStructureError));
+            }
+            Some(amt) => amt,
+        },
+    };
 
-        result.push((
-            amount,
-            asset_type,
-            attributes,
-            blind_asset_record.public_key,
-        ));
-    }
-    Ok(result)
+    let asset_type = match memo.lock_asset_type {
+        None => blind_asset_record
+            .asset_type
+            .get_asset_type()
+            .c(d!(NoahError::InconsistentStructureError))?,
+        Some(_) => match asset_type_option {
+            None => {
+                return Err(eg!(NoahError::InconsistentStructureError));
+            }
+            Some(asset_type) => asset_type,
+        },
+    };
+
+    Ok((
+        amount,
+        asset_type,
+        attributes,
+        blind_asset_record.public_key,
+    ))
 }
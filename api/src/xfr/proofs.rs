@@ -1,15 +1,20 @@
-use crate::anon_creds::{ac_confidential_verify, ACCommitment, ACConfidentialRevealProof};
-use crate::setup::{BulletproofParams, BULLET_PROOF_RANGE, MAX_CONFIDENTIAL_RECORD_NUMBER};
+use crate::anon_creds::{
+    ac_confidential_verify_with_linked, ACCommitment, ACConfidentialRevealProof,
+};
+use crate::setup::{
+    BulletproofParams, BULLET_PROOF_RANGE, BULLET_PROOF_RANGE_U64, MAX_CONFIDENTIAL_RECORD_NUMBER,
+    MAX_CONFIDENTIAL_RECORD_NUMBER_U64,
+};
 use crate::xfr::{
     asset_record::AssetRecordType,
     asset_tracer::RecordDataEncKey,
     structs::{
         AssetRecord, BlindAssetRecord, OpenAssetRecord, TracerMemo, TracingPolicies, XfrAmount,
-        XfrAssetType, XfrBody, XfrRangeProof,
+        XfrAssetType, XfrBody, XfrRangeProof, XfrRangeProofU64,
     },
     XfrNotePoliciesRef,
 };
-use bulletproofs::RangeProof;
+use bulletproofs::{BulletproofGens, RangeProof};
 use linear_map::LinearMap;
 use merlin::Transcript;
 use noah_algebra::{
@@ -30,11 +35,34 @@ use noah_crypto::{
             PedersenElGamalEqProof, PedersenElGamalProofInstance,
         },
     },
-    bulletproofs::range::{batch_verify_ranges, prove_ranges},
+    bulletproofs::range::{
+        aggregated_range_size, batch_verify_ranges, prove_ranges, prove_ranges_padded,
+    },
 };
 
 const POW_2_32: u64 = 0xFFFF_FFFFu64 + 1;
 
+lazy_static! {
+    /// The generators of the 64-bit range proofs, shared by the prover and the verifier.
+    static ref BP_GENS_U64: BulletproofGens =
+        BulletproofGens::new(BULLET_PROOF_RANGE_U64, MAX_CONFIDENTIAL_RECORD_NUMBER_U64);
+}
+
+/// Combine the ciphertexts of the lower and higher 32 bits into a ciphertext of the amount,
+/// which matches a single commitment to the amount.
+fn combine_amount_ctexts(
+    lock_amount: &(
+        ElGamalCiphertext<RistrettoPoint>,
+        ElGamalCiphertext<RistrettoPoint>,
+    ),
+) -> ElGamalCiphertext<RistrettoPoint> {
+    let pow2_32 = RistrettoScalar::from(POW_2_32);
+    ElGamalCiphertext {
+        e1: lock_amount.0.e1.add(&lock_amount.1.e1.mul(&pow2_32)),
+        e2: lock_amount.0.e2.add(&lock_amount.1.e2.mul(&pow2_32)),
+    }
+}
+
 pub(crate) fn asset_amount_tracing_proofs<R: CryptoRng + RngCore>(
     prng: &mut R,
     inputs: &[AssetRecord],
@@ -100,6 +128,22 @@ fn build_same_key_asset_type_amount_tracing_proof<R: CryptoRng + RngCore>(
                     .c(d!(NoahError::DecompressElementError))?,
             );
         }
+        if let XfrAmount::ConfidentialU64(com) = open_record.blind_asset_record.amount {
+            let lock_amount = memo
+                .lock_amount
+                .as_ref()
+                .c(d!(NoahError::InconsistentStructureError))?;
+            let pow2_32 = RistrettoScalar::from(POW_2_32);
+            m.push(RistrettoScalar::from(open_record.amount));
+            r.push(
+                open_record
+                    .amount_blinds
+                    .0
+                    .add(&open_record.amount_blinds.1.mul(&pow2_32)),
+            );
+            ctexts.push(combine_amount_ctexts(lock_amount));
+            commitments.push(com.decompress().c(d!(NoahError::DecompressElementError))?);
+        }
         if let XfrAssetType::Confidential(com) = open_record.blind_asset_record.asset_type {
             let lock_asset_type = memo
                 .lock_asset_type
@@ -269,6 +313,7 @@ pub(crate) fn batch_verify_tracer_tracing_proof<R: CryptoRng + RngCore>(
             &xfr_body.asset_tracing_memos[..inputs_len],
             &xfr_body.proofs.asset_tracing_proof.inputs_identity_proofs,
             &policies.inputs_sig_commitments,
+            policies.timestamp,
         )
         .c(d!())?;
         verify_identity_proofs(
@@ -276,6 +321,7 @@ pub(crate) fn batch_verify_tracer_tracing_proof<R: CryptoRng + RngCore>(
             &xfr_body.asset_tracing_memos[inputs_len..],
             &xfr_body.proofs.asset_tracing_proof.outputs_identity_proofs,
             &policies.outputs_sig_commitments,
+            policies.timestamp,
         )
         .c(d!())?;
     }
@@ -384,6 +430,7 @@ fn verify_identity_proofs(
     memos: &[Vec<TracerMemo>],
     proofs: &[Vec<Option<ACConfidentialRevealProof>>],
     sig_commitments: &[Option<&ACCommitment>],
+    timestamp: Option<u64>,
 ) -> Result<()> {
     // 1. Check structures.
     let n = reveal_policies.len();
@@ -418,7 +465,19 @@ fn verify_identity_proofs(
                 (Some(policy), Some(proof)) => {
                     let sig_com =
                         sig_commitment.c(d!(NoahError::XfrVerifyAssetTracingIdentityError))?;
-                    ac_confidential_verify(
+                    let linked = policy
+                        .linked_issuers
+                        .iter()
+                        .map(|p| (&p.cred_issuer_pub_key, p.reveal_map.as_slice()))
+                        .collect_vec();
+                    // the expiry can only be checked against the time of the verifier
+                    let expiry = match policy.expiry {
+                        Some(expiry) => {
+                            Some(expiry.check_at(timestamp.c(d!(NoahError::ParameterError))?))
+                        }
+                        None => None,
+                    };
+                    ac_confidential_verify_with_linked(
                         &policy.cred_issuer_pub_key,
                         enc_keys,
                         &policy.reveal_map.as_slice(),
@@ -426,6 +485,10 @@ fn verify_identity_proofs(
                         &memo.lock_attributes[..],
                         proof,
                         &[],
+                        &policy.predicate_map,
+                        expiry.as_ref(),
+                        policy.revocation_list.as_ref(),
+                        &linked,
                     )
                     .c(d!(NoahError::XfrVerifyAssetTracingIdentityError))?
                 }
@@ -451,7 +514,12 @@ fn extract_ciphertext_and_commitments(
         if asset_tracer_memo.lock_amount.is_none() && record.amount.is_confidential() {
             return Err(eg!(NoahError::InconsistentStructureError)); // There should be a lock for the amount
         }
-        if let Some(lock_amount) = &asset_tracer_memo.lock_amount {
+        if let (Some(lock_amount), XfrAmount::ConfidentialU64(com)) =
+            (&asset_tracer_memo.lock_amount, &record.amount)
+        {
+            ctexts.push(combine_amount_ctexts(lock_amount));
+            coms.push(com.decompress().c(d!(NoahError::DecompressElementError))?);
+        } else if let Some(lock_amount) = &asset_tracer_memo.lock_amount {
             ctexts.push(lock_amount.0.clone());
             ctexts.push(lock_amount.1.clone());
             let commitments = record
@@ -561,6 +629,68 @@ pub(crate) fn gen_range_proof(
     })
 }
 
+/// Compute a 64-bit range proof for transfers of amounts with single commitments.
+/// The proof guarantees that output amounts and difference between total input,
+/// and total output are in the range [0,2^{64} - 1].
+/// Inputs may use split commitments, but confidential outputs must use single commitments.
+pub(crate) fn gen_range_proof_u64(
+    inputs: &[&OpenAssetRecord],
+    outputs: &[&OpenAssetRecord],
+) -> Result<XfrRangeProofU64> {
+    let num_output = outputs.len();
+    if aggregated_range_size(num_output + 1) > MAX_CONFIDENTIAL_RECORD_NUMBER_U64 {
+        return Err(eg!(NoahError::RangeProofProveError));
+    }
+    if outputs
+        .iter()
+        .any(|x| matches!(x.blind_asset_record.amount, XfrAmount::Confidential(_)))
+    {
+        return Err(eg!(NoahError::RangeProofProveError));
+    }
+
+    // Build values vector (out amounts + amount difference).
+    let in_total = inputs.iter().fold(0u64, |accum, x| accum + x.amount);
+    let mut values: Vec<u64> = outputs.iter().map(|x| x.amount).collect();
+    let out_total = values.iter().sum::<u64>();
+    let xfr_diff = if in_total >= out_total {
+        in_total - out_total
+    } else {
+        return Err(eg!(NoahError::RangeProofProveError));
+    };
+    values.push(xfr_diff);
+
+    // Build blinding vectors (out blindings + blindings difference).
+    let pow2_32 = RistrettoScalar::from(POW_2_32);
+    let (total_blind_input_low, total_blind_input_high) = add_blindings(inputs);
+    let (total_blind_output_low, total_blind_output_high) = add_blindings(outputs);
+    let xfr_blind_diff = total_blind_input_low.sub(&total_blind_output_low).add(
+        &total_blind_input_high
+            .sub(&total_blind_output_high)
+            .mul(&pow2_32),
+    );
+
+    let mut range_proof_blinds: Vec<RistrettoScalar> = outputs
+        .iter()
+        .map(|x| x.amount_blinds.0.add(&x.amount_blinds.1.mul(&pow2_32)))
+        .collect();
+    range_proof_blinds.push(xfr_blind_diff);
+
+    let mut transcript = Transcript::new(b"Noah Range Proof U64");
+    let (range_proof, coms) = prove_ranges_padded(
+        &BP_GENS_U64,
+        &mut transcript,
+        values.as_slice(),
+        range_proof_blinds.as_slice(),
+        BULLET_PROOF_RANGE_U64,
+    )
+    .c(d!(NoahError::RangeProofProveError))?;
+
+    Ok(XfrRangeProofU64 {
+        range_proof,
+        xfr_diff_commitment: coms[num_output],
+    })
+}
+
 fn add_blindings(oar: &[&OpenAssetRecord]) -> (RistrettoScalar, RistrettoScalar) {
     oar.iter().fold(
         (RistrettoScalar::zero(), RistrettoScalar::zero()),
@@ -597,6 +727,90 @@ pub(crate) fn batch_verify_confidential_amount<R: CryptoRng + RngCore>(
     .c(d!(NoahError::XfrVerifyConfidentialAmountError))
 }
 
+pub(crate) fn batch_verify_confidential_amount_u64<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    instances: &[(
+        &Vec<BlindAssetRecord>,
+        &Vec<BlindAssetRecord>,
+        &XfrRangeProofU64,
+    )],
+) -> Result<()> {
+    if instances.is_empty() {
+        return Ok(());
+    }
+
+    let mut transcripts = vec![Transcript::new(b"Noah Range Proof U64"); instances.len()];
+    let proofs: Vec<&RangeProof> = instances.iter().map(|(_, _, pf)| &pf.range_proof).collect();
+    let mut commitments = vec![];
+    for (input, output, proof) in instances {
+        commitments.push(
+            extract_value_commitments_u64(input.as_slice(), output.as_slice(), proof).c(d!())?,
+        );
+    }
+    let value_commitments = commitments.iter().map(|c| c.as_slice()).collect_vec();
+    batch_verify_ranges(
+        prng,
+        &BP_GENS_U64,
+        proofs.as_slice(),
+        &mut transcripts,
+        &value_commitments,
+        BULLET_PROOF_RANGE_U64,
+    )
+    .c(d!(NoahError::XfrVerifyConfidentialAmountError))
+}
+
+fn extract_value_commitments_u64(
+    inputs: &[BlindAssetRecord],
+    outputs: &[BlindAssetRecord],
+    proof: &XfrRangeProofU64,
+) -> Result<Vec<CompressedRistretto>> {
+    let pc_gens = PedersenCommitmentRistretto::default();
+    let upper_power2 = aggregated_range_size(outputs.len() + 1);
+    if upper_power2 > MAX_CONFIDENTIAL_RECORD_NUMBER_U64 {
+        return Err(eg!(NoahError::XfrVerifyConfidentialAmountError));
+    }
+    let mut commitments = Vec::with_capacity(upper_power2);
+
+    // 1. Verify proof commitment to transfer's input - output amounts match proof commitments.
+    let mut total_input_com = RistrettoPoint::get_identity();
+    for input in inputs.iter() {
+        let com = input
+            .amount
+            .get_amount_commitment(&pc_gens)
+            .c(d!(NoahError::XfrVerifyConfidentialAmountError))?;
+        total_input_com = total_input_com.add(&com);
+    }
+    let mut total_output_com = RistrettoPoint::get_identity();
+    for output in outputs.iter() {
+        // The halves of split commitments are not range-checked by this proof.
+        if let XfrAmount::Confidential(_) = output.amount {
+            return Err(eg!(NoahError::XfrVerifyConfidentialAmountError));
+        }
+        let com = output
+            .amount
+            .get_amount_commitment(&pc_gens)
+            .c(d!(NoahError::XfrVerifyConfidentialAmountError))?;
+        total_output_com = total_output_com.add(&com);
+        commitments.push(com.compress());
+    }
+
+    // 2. Derive input - output commitment, compare with proof struct commitment.
+    let derived_xfr_diff_com = total_input_com.sub(&total_output_com);
+    if derived_xfr_diff_com.compress() != proof.xfr_diff_commitment {
+        return Err(eg!(NoahError::XfrVerifyConfidentialAmountError));
+    }
+
+    // 3. Push diff commitment.
+    commitments.push(proof.xfr_diff_commitment);
+
+    // 4. Pad with commitments to 0.
+    for _ in commitments.len()..upper_power2 {
+        commitments.push(CompressedRistretto::identity());
+    }
+
+    Ok(commitments)
+}
+
 fn extract_value_commitments(
     inputs: &[BlindAssetRecord],
     outputs: &[BlindAssetRecord],
@@ -621,6 +835,9 @@ fn extract_value_commitments(
                     .decompress()
                     .c(d!(NoahError::XfrVerifyConfidentialAmountError))?,
             ),
+            XfrAmount::ConfidentialU64(_) => {
+                return Err(eg!(NoahError::XfrVerifyConfidentialAmountError));
+            }
             XfrAmount::NonConfidential(amount) => {
                 let (low, high) = u64_to_u32_pair(amount);
                 let pc_gens = PedersenCommitmentRistretto::default();
@@ -640,6 +857,9 @@ fn extract_value_commitments(
                 com_low.decompress().c(d!(NoahError::ParameterError))?,
                 com_high.decompress().c(d!(NoahError::ParameterError))?,
             ),
+            XfrAmount::ConfidentialU64(_) => {
+                return Err(eg!(NoahError::XfrVerifyConfidentialAmountError));
+            }
             XfrAmount::NonConfidential(amount) => {
                 let (low, high) = u64_to_u32_pair(amount);
                 let pc_gens = PedersenCommitmentRistretto::default();
@@ -774,6 +994,7 @@ mod tests {
             memos.as_slice(),
             proofs.as_slice(),
             sig_commitments.as_slice(),
+            None,
         );
         pnk!(res);
 
@@ -788,6 +1009,7 @@ mod tests {
             memos.as_slice(),
             proofs.as_slice(),
             sig_commitments.as_slice(),
+            None,
         );
 
         msg_eq!(
@@ -810,6 +1032,7 @@ mod tests {
             memos.as_slice(),
             proofs.as_slice(),
             sig_commitments.as_slice(),
+            None,
         );
 
         msg_eq!(
@@ -833,6 +1056,7 @@ mod tests {
             memos.as_slice(),
             proofs.as_slice(),
             sig_commitments.as_slice(),
+            None,
         );
 
         msg_eq!(
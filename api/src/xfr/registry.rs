@@ -0,0 +1,660 @@
+use crate::anon_creds::ACCommitment;
+use crate::setup::BulletproofParams;
+use crate::xfr::{
+    gen_xfr_note,
+    sig::XfrKeyPair,
+    structs::{
+        AssetRecord, AssetType, BlindAssetRecord, TracerMemo, TracingPolicies, XfrBody, XfrNote,
+    },
+    verify_xfr_note, XfrNotePolicies,
+};
+use noah_algebra::prelude::*;
+
+/// An update of the tracing policies of an asset type.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TracingPolicyRegistryEntry {
+    /// The version of the registry after the update.
+    pub version: u64,
+    /// The asset type.
+    pub asset_type: AssetType,
+    /// The new tracing policies, or `None` if the asset type is no longer traced.
+    pub policies: Option<TracingPolicies>,
+}
+
+/// A versioned registry of tracing policies keyed by asset type, kept as a log of updates.
+///
+/// Each update increments the version, and the policies at any past version remain available,
+/// so that a note is verified against the registry as it was when the note was created.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TracingPolicyRegistry {
+    updates: Vec<TracingPolicyRegistryEntry>,
+}
+
+impl TracingPolicyRegistry {
+    /// Create an empty registry, at version 0.
+    pub fn new() -> Self {
+        TracingPolicyRegistry { updates: vec![] }
+    }
+
+    /// Return the current version.
+    pub fn version(&self) -> u64 {
+        self.updates.len() as u64
+    }
+
+    /// Return the log of updates.
+    pub fn updates(&self) -> &[TracingPolicyRegistryEntry] {
+        &self.updates
+    }
+
+    /// Set the tracing policies of an asset type and return the new version.
+    pub fn set_policies(&mut self, asset_type: AssetType, policies: TracingPolicies) -> u64 {
+        self.push_update(asset_type, Some(policies))
+    }
+
+    /// Remove the tracing policies of an asset type and return the new version.
+    pub fn remove_policies(&mut self, asset_type: AssetType) -> u64 {
+        self.push_update(asset_type, None)
+    }
+
+    fn push_update(&mut self, asset_type: AssetType, policies: Option<TracingPolicies>) -> u64 {
+        let version = self.version() + 1;
+        self.updates.push(TracingPolicyRegistryEntry {
+            version,
+            asset_type,
+            policies,
+        });
+        version
+    }
+
+    /// Return the current tracing policies of an asset type.
+    pub fn get_policies(&self, asset_type: &AssetType) -> Option<&TracingPolicies> {
+        self.get_policies_at(asset_type, self.version())
+    }
+
+    /// Return the tracing policies of an asset type at the given version.
+    pub fn get_policies_at(
+        &self,
+        asset_type: &AssetType,
+        version: u64,
+    ) -> Option<&TracingPolicies> {
+        self.updates
+            .iter()
+            .rev()
+            .filter(|entry| entry.version <= version)
+            .find(|entry| entry.asset_type == *asset_type)
+            .and_then(|entry| entry.policies.as_ref())
+            .filter(|policies| !policies.is_empty())
+    }
+
+    /// Return all the non-empty tracing policies at the given version.
+    fn all_policies_at(&self, version: u64) -> Vec<&TracingPolicies> {
+        let mut asset_types: Vec<&AssetType> = vec![];
+        for entry in self.updates.iter().filter(|entry| entry.version <= version) {
+            if !asset_types.contains(&&entry.asset_type) {
+                asset_types.push(&entry.asset_type);
+            }
+        }
+        asset_types
+            .into_iter()
+            .filter_map(|asset_type| self.get_policies_at(asset_type, version))
+            .collect()
+    }
+
+    /// Check that the asset records follow the current tracing policies of their asset types.
+    pub fn check_asset_records(
+        &self,
+        inputs: &[AssetRecord],
+        outputs: &[AssetRecord],
+    ) -> Result<()> {
+        let empty_policies = TracingPolicies::new();
+        for record in inputs.iter().chain(outputs) {
+            let policies = self
+                .get_policies(&record.open_asset_record.asset_type)
+                .unwrap_or(&empty_policies);
+            if *policies != record.tracing_policies {
+                return Err(eg!(NoahError::TracingPolicyMismatchError));
+            }
+        }
+        Ok(())
+    }
+
+    /// Derive the policies of a confidential transfer body from the registry at the given version,
+    /// with the attribute commitments of the inputs and the outputs for identity tracing.
+    ///
+    /// A record with a transparent asset type must carry one tracer memo for each registered policy
+    /// of its asset type. The verifier cannot tell which asset type a record with a confidential
+    /// asset type has, since the memos are not bound to a registered asset type, so such a record
+    /// must carry the memos of the registered policies, which all traced asset types must share.
+    /// Since a confidential asset type without memos cannot be told apart from a traced one, such a
+    /// record is rejected as soon as any asset type is traced.
+    pub fn derive_note_policies(
+        &self,
+        body: &XfrBody,
+        version: u64,
+        inputs_sig_commitments: &[Option<ACCommitment>],
+        outputs_sig_commitments: &[Option<ACCommitment>],
+    ) -> Result<XfrNotePolicies> {
+        if body.inputs.len() + body.outputs.len() != body.asset_tracing_memos.len() {
+            return Err(eg!(NoahError::InconsistentStructureError));
+        }
+        if body.inputs.len() != inputs_sig_commitments.len()
+            || body.outputs.len() != outputs_sig_commitments.len()
+        {
+            return Err(eg!(NoahError::ParameterError));
+        }
+        let (inputs_memos, outputs_memos) = body.asset_tracing_memos.split_at(body.inputs.len());
+
+        let inputs_tracing_policies = body
+            .inputs
+            .iter()
+            .zip(inputs_memos)
+            .map(|(record, memos)| self.derive_record_policies(record, memos, version))
+            .collect::<Result<Vec<TracingPolicies>>>()
+            .c(d!())?;
+        let outputs_tracing_policies = body
+            .outputs
+            .iter()
+            .zip(outputs_memos)
+            .map(|(record, memos)| self.derive_record_policies(record, memos, version))
+            .collect::<Result<Vec<TracingPolicies>>>()
+            .c(d!())?;
+
+        Ok(XfrNotePolicies::new(
+            inputs_tracing_policies,
+            inputs_sig_commitments.to_vec(),
+            outputs_tracing_policies,
+            outputs_sig_commitments.to_vec(),
+        ))
+    }
+
+    fn derive_record_policies(
+        &self,
+        record: &BlindAssetRecord,
+        memos: &[TracerMemo],
+        version: u64,
+    ) -> Result<TracingPolicies> {
+        let memos_match = |policies: &TracingPolicies| {
+            policies.len() == memos.len()
+                && policies
+                    .get_policies()
+                    .iter()
+                    .zip(memos)
+                    .all(|(policy, memo)| policy.enc_keys == memo.enc_key)
+        };
+
+        match record.asset_type.get_asset_type() {
+            Some(asset_type) => match self.get_policies_at(&asset_type, version) {
+                Some(policies) => {
+                    if memos.len() < policies.len() {
+                        return Err(eg!(NoahError::MissingTracerMemoError));
+                    }
+                    if !memos_match(policies) {
+                        return Err(eg!(NoahError::TracingPolicyMismatchError));
+                    }
+                    Ok(policies.clone())
+                }
+                None => {
+                    if !memos.is_empty() {
+                        return Err(eg!(NoahError::TracingPolicyMismatchError));
+                    }
+                    Ok(TracingPolicies::new())
+                }
+            },
+            None => {
+                let all_policies = self.all_policies_at(version);
+                if memos.is_empty() {
+                    if all_policies.is_empty() {
+                        Ok(TracingPolicies::new())
+                    } else {
+                        Err(eg!(NoahError::MissingTracerMemoError))
+                    }
+                } else {
+                    // the memos would otherwise follow the policies of another asset type
+                    let policies = *all_policies
+                        .first()
+                        .c(d!(NoahError::TracingPolicyMismatchError))?;
+                    if all_policies.iter().any(|other| *other != policies) || !memos_match(policies)
+                    {
+                        return Err(eg!(NoahError::TracingPolicyMismatchError));
+                    }
+                    Ok(policies.clone())
+                }
+            }
+        }
+    }
+}
+
+/// Build a confidential transfer note after checking the asset records against the registry.
+pub fn gen_xfr_note_with_registry<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    inputs: &[AssetRecord],
+    outputs: &[AssetRecord],
+    input_key_pairs: &[&XfrKeyPair],
+    registry: &TracingPolicyRegistry,
+) -> Result<XfrNote> {
+    registry.check_asset_records(inputs, outputs).c(d!())?;
+    gen_xfr_note(prng, inputs, outputs, input_key_pairs).c(d!())
+}
+
+/// Verify a confidential transfer note with the policies derived from the registry at the given
+/// version, and with the attribute commitments of the inputs and the outputs for identity tracing.
+pub fn verify_xfr_note_with_registry<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    params: &mut BulletproofParams,
+    xfr_note: &XfrNote,
+    registry: &TracingPolicyRegistry,
+    version: u64,
+    inputs_sig_commitments: &[Option<ACCommitment>],
+    outputs_sig_commitments: &[Option<ACCommitment>],
+) -> Result<()> {
+    let policies = registry
+        .derive_note_policies(
+            &xfr_note.body,
+            version,
+            inputs_sig_commitments,
+            outputs_sig_commitments,
+        )
+        .c(d!())?;
+    verify_xfr_note(prng, params, xfr_note, &policies.to_ref()).c(d!())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::anon_creds::{ac_commit, ac_keygen_issuer, ac_keygen_user, ac_sign, Credential};
+    use crate::setup::BulletproofParams;
+    use crate::xfr::{
+        asset_record::AssetRecordType,
+        registry::{
+            gen_xfr_note_with_registry, verify_xfr_note_with_registry, TracingPolicyRegistry,
+        },
+        sig::XfrKeyPair,
+        structs::{
+            AssetRecord, AssetRecordTemplate, AssetTracerKeyPair, AssetType, IdentityRevealPolicy,
+            TracingPolicies, TracingPolicy, XfrNote,
+        },
+        tests::gen_key_pair_vec,
+    };
+    use ark_std::test_rng;
+    use noah_algebra::prelude::*;
+
+    fn gen_note<R: CryptoRng + RngCore>(
+        prng: &mut R,
+        registry: &TracingPolicyRegistry,
+        record_type: AssetRecordType,
+        asset_type: AssetType,
+        policies: &TracingPolicies,
+        in_keys: &[XfrKeyPair],
+    ) -> Result<XfrNote> {
+        let out_keys = gen_key_pair_vec(1, prng);
+        let template = |pub_key| {
+            if policies.is_empty() {
+                AssetRecordTemplate::with_no_asset_tracing(10, asset_type, record_type, pub_key)
+            } else {
+                AssetRecordTemplate::with_asset_tracing(
+                    10,
+                    asset_type,
+                    record_type,
+                    pub_key,
+                    policies.clone(),
+                )
+            }
+        };
+        let input =
+            AssetRecord::from_template_no_identity_tracing(prng, &template(in_keys[0].pub_key))
+                .unwrap();
+        let output =
+            AssetRecord::from_template_no_identity_tracing(prng, &template(out_keys[0].pub_key))
+                .unwrap();
+        gen_xfr_note_with_registry(prng, &[input], &[output], &[&in_keys[0]], registry)
+    }
+
+    #[test]
+    fn test_tracing_policy_registry() {
+        let mut prng = test_rng();
+        let mut params = BulletproofParams::default();
+        let in_keys = gen_key_pair_vec(1, &mut prng);
+
+        let traced_asset = AssetType::from_identical_byte(1u8);
+        let untraced_asset = AssetType::from_identical_byte(2u8);
+        let tracer = AssetTracerKeyPair::generate(&mut prng);
+        let policies = TracingPolicies::from_policy(TracingPolicy {
+            enc_keys: tracer.enc_key.clone(),
+            asset_tracing: true,
+            identity_tracing: None,
+        });
+        let no_policies = TracingPolicies::new();
+
+        let mut registry = TracingPolicyRegistry::new();
+        assert_eq!(registry.version(), 0);
+        let v1 = registry.set_policies(traced_asset, policies.clone());
+        assert_eq!(v1, 1);
+        assert_eq!(registry.get_policies(&traced_asset), Some(&policies));
+        assert_eq!(registry.get_policies(&untraced_asset), None);
+        assert_eq!(registry.get_policies_at(&traced_asset, 0), None);
+
+        for record_type in [
+            AssetRecordType::NonConfidentialAmount_NonConfidentialAssetType,
+            AssetRecordType::ConfidentialAmount_NonConfidentialAssetType,
+            AssetRecordType::ConfidentialAmount_ConfidentialAssetType,
+        ] {
+            // the traced asset type with its policies
+            let note = gen_note(
+                &mut prng,
+                &registry,
+                record_type,
+                traced_asset,
+                &policies,
+                &in_keys,
+            )
+            .unwrap();
+            pnk!(verify_xfr_note_with_registry(
+                &mut prng,
+                &mut params,
+                &note,
+                &registry,
+                v1,
+                &[None],
+                &[None]
+            ));
+
+            // the traced asset type without its policies
+            msg_eq!(
+                NoahError::TracingPolicyMismatchError,
+                gen_note(
+                    &mut prng,
+                    &registry,
+                    record_type,
+                    traced_asset,
+                    &no_policies,
+                    &in_keys,
+                )
+                .unwrap_err()
+            );
+            let note = gen_note(
+                &mut prng,
+                &TracingPolicyRegistry::new(),
+                record_type,
+                traced_asset,
+                &no_policies,
+                &in_keys,
+            )
+            .unwrap();
+            msg_eq!(
+                NoahError::MissingTracerMemoError,
+                verify_xfr_note_with_registry(
+                    &mut prng,
+                    &mut params,
+                    &note,
+                    &registry,
+                    v1,
+                    &[None],
+                    &[None]
+                )
+                .unwrap_err()
+            );
+        }
+
+        // an untraced transparent asset type
+        let note = gen_note(
+            &mut prng,
+            &registry,
+            AssetRecordType::ConfidentialAmount_NonConfidentialAssetType,
+            untraced_asset,
+            &no_policies,
+            &in_keys,
+        )
+        .unwrap();
+        pnk!(verify_xfr_note_with_registry(
+            &mut prng,
+            &mut params,
+            &note,
+            &registry,
+            v1,
+            &[None],
+            &[None]
+        ));
+
+        // after removing the policies, old notes verify at their version but not at the new one
+        let note = gen_note(
+            &mut prng,
+            &registry,
+            AssetRecordType::ConfidentialAmount_NonConfidentialAssetType,
+            traced_asset,
+            &policies,
+            &in_keys,
+        )
+        .unwrap();
+        let v2 = registry.remove_policies(traced_asset);
+        assert_eq!(v2, 2);
+        assert_eq!(registry.get_policies(&traced_asset), None);
+        assert_eq!(registry.get_policies_at(&traced_asset, v1), Some(&policies));
+        pnk!(verify_xfr_note_with_registry(
+            &mut prng,
+            &mut params,
+            &note,
+            &registry,
+            v1,
+            &[None],
+            &[None]
+        ));
+        msg_eq!(
+            NoahError::TracingPolicyMismatchError,
+            verify_xfr_note_with_registry(
+                &mut prng,
+                &mut params,
+                &note,
+                &registry,
+                v2,
+                &[None],
+                &[None]
+            )
+            .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_tracing_policy_registry_with_several_asset_types() {
+        let mut prng = test_rng();
+        let mut params = BulletproofParams::default();
+        let in_keys = gen_key_pair_vec(1, &mut prng);
+
+        let asset_x = AssetType::from_identical_byte(1u8);
+        let asset_y = AssetType::from_identical_byte(2u8);
+        let new_policies = |tracer: &AssetTracerKeyPair| {
+            TracingPolicies::from_policy(TracingPolicy {
+                enc_keys: tracer.enc_key.clone(),
+                asset_tracing: true,
+                identity_tracing: None,
+            })
+        };
+        let policies_x = new_policies(&AssetTracerKeyPair::generate(&mut prng));
+        let policies_y = new_policies(&AssetTracerKeyPair::generate(&mut prng));
+
+        let mut registry = TracingPolicyRegistry::new();
+        registry.set_policies(asset_x, policies_x.clone());
+        let version = registry.set_policies(asset_y, policies_y.clone());
+
+        // a confidential record of asset type Y which carries the memos of asset type X
+        let mut forged_registry = TracingPolicyRegistry::new();
+        forged_registry.set_policies(asset_y, policies_x.clone());
+        let note = gen_note(
+            &mut prng,
+            &forged_registry,
+            AssetRecordType::ConfidentialAmount_ConfidentialAssetType,
+            asset_y,
+            &policies_x,
+            &in_keys,
+        )
+        .unwrap();
+        msg_eq!(
+            NoahError::TracingPolicyMismatchError,
+            verify_xfr_note_with_registry(
+                &mut prng,
+                &mut params,
+                &note,
+                &registry,
+                version,
+                &[None],
+                &[None]
+            )
+            .unwrap_err()
+        );
+
+        // transparent asset types are still matched with their own policies
+        let note = gen_note(
+            &mut prng,
+            &registry,
+            AssetRecordType::ConfidentialAmount_NonConfidentialAssetType,
+            asset_y,
+            &policies_y,
+            &in_keys,
+        )
+        .unwrap();
+        pnk!(verify_xfr_note_with_registry(
+            &mut prng,
+            &mut params,
+            &note,
+            &registry,
+            version,
+            &[None],
+            &[None]
+        ));
+
+        // confidential asset types are accepted once the traced asset types share their policies
+        let version = registry.set_policies(asset_y, policies_x.clone());
+        let note = gen_note(
+            &mut prng,
+            &registry,
+            AssetRecordType::ConfidentialAmount_ConfidentialAssetType,
+            asset_y,
+            &policies_x,
+            &in_keys,
+        )
+        .unwrap();
+        pnk!(verify_xfr_note_with_registry(
+            &mut prng,
+            &mut params,
+            &note,
+            &registry,
+            version,
+            &[None],
+            &[None]
+        ));
+    }
+
+    #[test]
+    fn test_tracing_policy_registry_with_identity_tracing() {
+        let mut prng = test_rng();
+        let mut params = BulletproofParams::default();
+        let in_keys = gen_key_pair_vec(1, &mut prng);
+        let out_keys = gen_key_pair_vec(1, &mut prng);
+
+        let (issuer_sk, issuer_pk) = ac_keygen_issuer(&mut prng, 2);
+        let (user_sk, user_pk) = ac_keygen_user(&mut prng, &issuer_pk);
+        let attrs = vec![1u32, 2];
+        let credential = Credential {
+            sig: ac_sign(&mut prng, &issuer_sk, &user_pk, &attrs).unwrap(),
+            attrs,
+            ipk: issuer_pk.clone(),
+        };
+        let (sig_commitment, _, key) =
+            ac_commit(&mut prng, &user_sk, &credential, b"address").unwrap();
+        let key = key.unwrap();
+
+        let asset_type = AssetType::from_identical_byte(1u8);
+        let record_type = AssetRecordType::ConfidentialAmount_NonConfidentialAssetType;
+        let tracer = AssetTracerKeyPair::generate(&mut prng);
+        let policies = TracingPolicies::from_policy(TracingPolicy {
+            enc_keys: tracer.enc_key.clone(),
+            asset_tracing: true,
+            identity_tracing: Some(IdentityRevealPolicy {
+                cred_issuer_pub_key: issuer_pk,
+                reveal_map: vec![false, true],
+                revocation_list: None,
+                predicate_map: vec![],
+                expiry: None,
+                linked_issuers: vec![],
+            }),
+        });
+        let mut registry = TracingPolicyRegistry::new();
+        let version = registry.set_policies(asset_type, policies.clone());
+
+        let mut new_record = |pub_key| {
+            let template = AssetRecordTemplate::with_asset_tracing(
+                10,
+                asset_type,
+                record_type,
+                pub_key,
+                policies.clone(),
+            );
+            AssetRecord::from_template_with_identity_tracing(
+                &mut prng,
+                &template,
+                &user_sk,
+                &credential,
+                &key,
+            )
+            .unwrap()
+        };
+        let input = new_record(in_keys[0].pub_key);
+        let output = new_record(out_keys[0].pub_key);
+        let note =
+            gen_xfr_note_with_registry(&mut prng, &[input], &[output], &[&in_keys[0]], &registry)
+                .unwrap();
+
+        pnk!(verify_xfr_note_with_registry(
+            &mut prng,
+            &mut params,
+            &note,
+            &registry,
+            version,
+            &[Some(sig_commitment.clone())],
+            &[Some(sig_commitment.clone())]
+        ));
+
+        // the attribute commitments are required
+        msg_eq!(
+            NoahError::XfrVerifyAssetTracingIdentityError,
+            verify_xfr_note_with_registry(
+                &mut prng,
+                &mut params,
+                &note,
+                &registry,
+                version,
+                &[Some(sig_commitment.clone())],
+                &[None]
+            )
+            .unwrap_err()
+        );
+        msg_eq!(
+            NoahError::ParameterError,
+            verify_xfr_note_with_registry(
+                &mut prng,
+                &mut params,
+                &note,
+                &registry,
+                version,
+                &[],
+                &[]
+            )
+            .unwrap_err()
+        );
+
+        // the attribute commitment of another credential
+        let (other_commitment, _, _) =
+            ac_commit(&mut prng, &user_sk, &credential, b"another address").unwrap();
+        msg_eq!(
+            NoahError::XfrVerifyAssetTracingIdentityError,
+            verify_xfr_note_with_registry(
+                &mut prng,
+                &mut params,
+                &note,
+                &registry,
+                version,
+                &[Some(sig_commitment)],
+                &[Some(other_commitment)]
+            )
+            .unwrap_err()
+        );
+    }
+}
@@ -1,27 +1,35 @@
 use crate::anon_creds::{
-    ACConfidentialRevealProof, ACIssuerPublicKey, AttributeCiphertext, AttributeDecKey,
-    AttributeEncKey,
+    ACAttributePredicate, ACConfidentialRevealProof, ACExpiryPolicy, ACIssuerPublicKey,
+    ACRevocationList, AttributeCiphertext, AttributeDecKey, AttributeDecKeyShare, AttributeEncKey,
+    AttributeThresholdParams,
 };
+use crate::anon_xfr::TWO_POW_32;
 use crate::xfr::{
     asset_mixer::AssetMixProof,
     asset_record::AssetRecordType,
-    asset_tracer::{RecordDataCiphertext, RecordDataDecKey, RecordDataEncKey},
+    asset_tracer::{
+        RecordDataCiphertext, RecordDataDecKey, RecordDataDecKeyShare, RecordDataEncKey,
+        RecordDataThresholdParams,
+    },
     sig::{KeyType, XfrKeyPair, XfrMultiSig, XfrPublicKey},
 };
 use bulletproofs::RangeProof;
 use digest::Digest;
 use noah_algebra::{
     prelude::*,
-    ristretto::{CompressedEdwardsY, CompressedRistretto, RistrettoScalar},
+    ristretto::{CompressedEdwardsY, CompressedRistretto, RistrettoPoint, RistrettoScalar},
     secp256k1::{SECP256K1Scalar, SECP256K1G1},
 };
 use noah_crypto::basic::pedersen_comm::PedersenCommitmentRistretto;
 use noah_crypto::basic::{
     chaum_pedersen::ChaumPedersenProofX,
     elgamal::elgamal_key_gen,
-    hybrid_encryption::{NoahHybridCiphertext, XPublicKey, XSecretKey},
+    hybrid_encryption::{
+        hybrid_x25519_threshold_key_gen, NoahHybridCiphertext, XPublicKey, XSecretKey,
+    },
     pedersen_comm::PedersenCommitment,
     pedersen_elgamal::PedersenElGamalEqProof,
+    threshold_elgamal::threshold_elgamal_key_gen,
 };
 use sha2::Sha512;
 
@@ -106,13 +114,14 @@ impl BlindAssetRecord {
     /// Obtain the record type, which describes the level of confidentiality.
     pub fn get_record_type(&self) -> AssetRecordType {
         AssetRecordType::from_flags(
-            matches!(self.amount, XfrAmount::Confidential(_)),
+            self.amount.is_confidential(),
             matches!(self.asset_type, XfrAssetType::Confidential(_)),
         )
     }
 }
 
-/// Amount in blind asset record: if confidential, provide commitments for lower and hight 32 bits
+/// Amount in blind asset record: if confidential, provide commitments for lower and hight 32 bits,
+/// or a single commitment to the 64-bit amount
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum XfrAmount {
     /// Confidential amount.
@@ -120,6 +129,8 @@ pub enum XfrAmount {
     #[serde(with = "serde_str")]
     /// Transparent amount.
     NonConfidential(u64),
+    /// Confidential amount with a single commitment, whose blind is `blind_lo + 2^32 * blind_hi`.
+    ConfidentialU64(CompressedRistretto),
 }
 
 impl XfrAmount {
@@ -134,7 +145,10 @@ impl XfrAmount {
     /// assert!(!xfr_amount.is_confidential());
     /// ```
     pub fn is_confidential(&self) -> bool {
-        matches!(self, XfrAmount::Confidential(_))
+        matches!(
+            self,
+            XfrAmount::Confidential(_) | XfrAmount::ConfidentialU64(_)
+        )
     }
     /// Return Some(amount) if amount is non-confidential. Otherwise, return None
     /// # Example:
@@ -154,7 +168,7 @@ impl XfrAmount {
     }
 
     /// Return Some((c1,c2)), where (c1,c2) is a commitment to the amount
-    /// if amount is confidential. Otherwise, return None
+    /// if amount is confidential with split commitments. Otherwise, return None
     /// # Example:
     /// ```
     /// use noah::xfr::structs::XfrAmount;
@@ -187,6 +201,55 @@ impl XfrAmount {
             .compress();
         XfrAmount::Confidential((comm_lo, comm_hi))
     }
+
+    /// Return Some(c), where c is the single commitment to the amount
+    /// if amount is confidential with a single commitment. Otherwise, return None
+    pub fn get_commitment(&self) -> Option<CompressedRistretto> {
+        match self {
+            XfrAmount::ConfidentialU64(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    /// Return the commitment to the whole amount, which is `c1 + 2^32 * c2` for split commitments
+    /// and a trivial commitment for transparent amounts.
+    pub fn get_amount_commitment(
+        &self,
+        pc_gens: &PedersenCommitmentRistretto,
+    ) -> Result<RistrettoPoint> {
+        match self {
+            XfrAmount::Confidential((comm_lo, comm_hi)) => {
+                let comm_lo = comm_lo
+                    .decompress()
+                    .c(d!(NoahError::DecompressElementError))?;
+                let comm_hi = comm_hi
+                    .decompress()
+                    .c(d!(NoahError::DecompressElementError))?;
+                Ok(comm_lo.add(&comm_hi.mul(&RistrettoScalar::from(TWO_POW_32))))
+            }
+            XfrAmount::ConfidentialU64(comm) => {
+                comm.decompress().c(d!(NoahError::DecompressElementError))
+            }
+            XfrAmount::NonConfidential(amount) => {
+                Ok(pc_gens.commit(RistrettoScalar::from(*amount), RistrettoScalar::zero()))
+            }
+        }
+    }
+
+    /// Construct a confidential amount with a single commitment with an amount and an amount blind.
+    /// The blinds are the same as in the split commitments, so the owner memo is unchanged.
+    pub fn from_blinds_u64(
+        pc_gens: &PedersenCommitmentRistretto,
+        amount: u64,
+        blind_lo: &RistrettoScalar,
+        blind_hi: &RistrettoScalar,
+    ) -> Self {
+        let blind = blind_lo.add(&blind_hi.mul(&RistrettoScalar::from(TWO_POW_32)));
+        let comm = pc_gens
+            .commit(RistrettoScalar::from(amount), blind)
+            .compress();
+        XfrAmount::ConfidentialU64(comm)
+    }
 }
 
 /// Asset type in BlindAsset record: if confidential, provide commitment.
@@ -312,6 +375,81 @@ impl AssetTracerKeyPair {
     }
 }
 
+/// The shares of the asset tracer decryption keys held by one of the auditors.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AssetTracerDecKeyShares {
+    /// The share of the decryption key for amounts and asset types.
+    pub record_data_dec_key_share: RecordDataDecKeyShare,
+    /// The share of the decryption key for the attributes.
+    pub attrs_dec_key_share: AttributeDecKeyShare,
+    /// The share of the secret scalar of the key for the locked information.
+    pub lock_info_dec_key_share: RecordDataDecKeyShare,
+}
+
+/// The public parameters of an asset tracer whose decryption keys are shared among several auditors.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AssetTracerThresholdParams {
+    /// The parameters of the shared decryption key for amounts and asset types.
+    pub record_data_params: RecordDataThresholdParams,
+    /// The parameters of the shared decryption key for the attributes.
+    pub attrs_params: AttributeThresholdParams,
+    /// The commitments to the sharing of the secret scalar of the key for the locked information.
+    pub lock_info_params: RecordDataThresholdParams,
+    /// The encryption key for the locked information.
+    pub lock_info_enc_key: XPublicKey,
+}
+
+impl AssetTracerThresholdParams {
+    /// Generate asset tracer keys whose decryption keys are split into `num_shares` shares,
+    /// any `threshold` of which can decrypt the tracer memos, including the locked information.
+    pub fn generate<R: CryptoRng + RngCore>(
+        prng: &mut R,
+        threshold: usize,
+        num_shares: usize,
+    ) -> Result<(Self, Vec<AssetTracerDecKeyShares>)> {
+        let (record_data_params, record_data_key_shares) =
+            threshold_elgamal_key_gen(prng, threshold, num_shares).c(d!())?;
+        let (attrs_params, attrs_key_shares) =
+            threshold_elgamal_key_gen(prng, threshold, num_shares).c(d!())?;
+        let (lock_info_enc_key, lock_info_params, lock_info_key_shares) =
+            hybrid_x25519_threshold_key_gen(prng, threshold, num_shares).c(d!())?;
+
+        let key_shares = record_data_key_shares
+            .into_iter()
+            .zip(attrs_key_shares.into_iter())
+            .zip(lock_info_key_shares.into_iter())
+            .map(
+                |((record_data_dec_key_share, attrs_dec_key_share), lock_info_dec_key_share)| {
+                    AssetTracerDecKeyShares {
+                        record_data_dec_key_share,
+                        attrs_dec_key_share,
+                        lock_info_dec_key_share,
+                    }
+                },
+            )
+            .collect();
+
+        Ok((
+            AssetTracerThresholdParams {
+                record_data_params,
+                attrs_params,
+                lock_info_params,
+                lock_info_enc_key,
+            },
+            key_shares,
+        ))
+    }
+
+    /// Return the encryption keys to be used in tracing policies.
+    pub fn get_enc_keys(&self) -> AssetTracerEncKeys {
+        AssetTracerEncKeys {
+            record_data_enc_key: self.record_data_params.get_enc_key(),
+            attrs_enc_key: self.attrs_params.get_enc_key(),
+            lock_info_enc_key: self.lock_info_enc_key.clone(),
+        }
+    }
+}
+
 /// Asset and identity tracing policies for an asset.
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub struct TracingPolicies(pub Vec<TracingPolicy>);
@@ -365,6 +503,28 @@ pub struct IdentityRevealPolicy {
     pub cred_issuer_pub_key: ACIssuerPublicKey,
     /// The attribute revealing map.
     pub reveal_map: Vec<bool>, // i-th is true, if i-th attribute is to be revealed
+    /// The revocation list of the credential issuer, if credentials must be proven unrevoked.
+    #[serde(default)]
+    pub revocation_list: Option<ACRevocationList>,
+    /// The predicates that attributes must be proven to satisfy, empty or one entry per attribute.
+    #[serde(default)]
+    pub predicate_map: Vec<Option<ACAttributePredicate>>,
+    /// The expiry policy of the credentials, whose published bounds are checked against the
+    /// timestamp of the verifier, see `XfrNotePolicies::timestamp`.
+    #[serde(default)]
+    pub expiry: Option<ACExpiryPolicy>,
+    /// The policies for the credentials of further issuers, which must belong to the same user.
+    #[serde(default)]
+    pub linked_issuers: Vec<LinkedRevealPolicy>,
+}
+
+/// A reveal policy for the credential of a further issuer in an identity reveal policy.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct LinkedRevealPolicy {
+    /// The public key of the credential issuer.
+    pub cred_issuer_pub_key: ACIssuerPublicKey,
+    /// The attribute revealing map.
+    pub reveal_map: Vec<bool>,
 }
 
 /// Information directed to an asset tracer.
@@ -607,6 +767,18 @@ impl OpenAssetRecord {
     pub fn get_pub_key(&self) -> &XfrPublicKey {
         &self.blind_asset_record.public_key
     }
+    /// Replace the split amount commitments by a single commitment to the 64-bit amount.
+    /// The blinds, the owner memo and the tracer memos remain valid.
+    pub fn use_single_amount_commitment(&mut self, pc_gens: &PedersenCommitmentRistretto) {
+        if let XfrAmount::Confidential(_) = self.blind_asset_record.amount {
+            self.blind_asset_record.amount = XfrAmount::from_blinds_u64(
+                pc_gens,
+                self.amount,
+                &self.amount_blinds.0,
+                &self.amount_blinds.1,
+            );
+        }
+    }
 }
 
 /// An input or output record and associated information (policies and memos).
@@ -654,6 +826,10 @@ pub enum AssetTypeAndAmountProof {
     ConfAll(Box<(XfrRangeProof, ChaumPedersenProofX)>),
     /// No proof for a transparent transaction.
     NoProof,
+    /// The proof for confidential amounts with single commitments in the single-asset case.
+    ConfAmountU64(XfrRangeProofU64),
+    /// Both proofs for fully confidential single-asset with single amount commitments.
+    ConfAllU64(Box<(XfrRangeProofU64, ChaumPedersenProofX)>),
 }
 
 /// The proofs for a confidential transfer.
@@ -677,6 +853,16 @@ pub struct XfrRangeProof {
     pub xfr_diff_commitment_high: CompressedRistretto,
 }
 
+/// The range proof building block for amounts with a single 64-bit commitment.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct XfrRangeProofU64 {
+    /// The aggregated Bulletproofs range proof.
+    #[serde(with = "noah_obj_serde")]
+    pub range_proof: RangeProof,
+    /// The transfer amount difference commitment.
+    pub xfr_diff_commitment: CompressedRistretto,
+}
+
 /// The asset tracing proofs.
 /// Proof of records' data and identity tracing
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
@@ -699,6 +885,15 @@ impl PartialEq for XfrRangeProof {
 
 impl Eq for XfrRangeProof {}
 
+impl PartialEq for XfrRangeProofU64 {
+    fn eq(&self, other: &XfrRangeProofU64) -> bool {
+        self.range_proof.to_bytes() == other.range_proof.to_bytes()
+            && self.xfr_diff_commitment == other.xfr_diff_commitment
+    }
+}
+
+impl Eq for XfrRangeProofU64 {}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(untagged)]
 enum CompatibleKeyType {
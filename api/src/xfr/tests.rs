@@ -7,8 +7,8 @@ use crate::xfr::{
     sig::XfrKeyPair,
     structs::{
         AssetRecord, AssetRecordTemplate, AssetTracerEncKeys, AssetTracerKeyPair, AssetType,
-        IdentityRevealPolicy, TracerMemo, TracingPolicy, XfrAmount, XfrAssetType, XfrBody, XfrNote,
-        ASSET_TYPE_LENGTH,
+        IdentityRevealPolicy, LinkedRevealPolicy, TracerMemo, TracingPolicy, XfrAmount,
+        XfrAssetType, XfrBody, XfrNote, ASSET_TYPE_LENGTH,
     },
     verify_xfr_body, verify_xfr_note, XfrNotePolicies,
 };
@@ -467,6 +467,167 @@ mod single_asset_no_tracing {
     }
 }
 
+mod single_asset_u64_amounts {
+    use super::*;
+    use crate::setup::MAX_CONFIDENTIAL_RECORD_NUMBER_U64;
+    use crate::xfr::{
+        proofs::gen_range_proof_u64,
+        structs::{AssetTypeAndAmountProof, TracingPolicies},
+        trace_assets,
+    };
+
+    fn do_transfer_tests_u64_amounts(asset_record_type: AssetRecordType) {
+        let mut prng = test_rng();
+        let mut params = BulletproofParams::default();
+        let pc_gens = PedersenCommitmentRistretto::default();
+        let asset_type = AssetType::from_identical_byte(0u8);
+        let tracer_keypair = AssetTracerKeyPair::generate(&mut prng);
+        let tracing_policies = TracingPolicies::from_policy(TracingPolicy {
+            enc_keys: tracer_keypair.enc_key.clone(),
+            asset_tracing: true,
+            identity_tracing: None,
+        });
+
+        let in_amounts = [(3u64 << 32) + 10, 5u64 << 32];
+        let out_amounts = [(7u64 << 32) + 3, (1u64 << 32) + 7];
+        let inkeys = gen_key_pair_vec(2, &mut prng);
+        let inkeys_ref = inkeys.iter().collect_vec();
+        let outkeys = gen_key_pair_vec(2, &mut prng);
+
+        // The first input keeps the split commitments.
+        let mut inputs = in_amounts
+            .iter()
+            .zip(inkeys.iter())
+            .map(|(amount, key_pair)| {
+                let template = AssetRecordTemplate::with_no_asset_tracing(
+                    *amount,
+                    asset_type,
+                    asset_record_type,
+                    key_pair.pub_key,
+                );
+                AssetRecord::from_template_no_identity_tracing(&mut prng, &template).unwrap()
+            })
+            .collect_vec();
+        inputs[1]
+            .open_asset_record
+            .use_single_amount_commitment(&pc_gens);
+
+        // The second output is traced.
+        let mut outputs = out_amounts
+            .iter()
+            .zip(outkeys.iter())
+            .zip([TracingPolicies::new(), tracing_policies.clone()])
+            .map(|((amount, key_pair), policies)| {
+                let template = AssetRecordTemplate::with_asset_tracing(
+                    *amount,
+                    asset_type,
+                    asset_record_type,
+                    key_pair.pub_key,
+                    policies,
+                );
+                AssetRecord::from_template_no_identity_tracing(&mut prng, &template).unwrap()
+            })
+            .collect_vec();
+
+        // Confidential outputs must all use single commitments.
+        outputs[1]
+            .open_asset_record
+            .use_single_amount_commitment(&pc_gens);
+        assert!(gen_xfr_note(&mut prng, &inputs, &outputs, &inkeys_ref).is_err());
+
+        outputs[0]
+            .open_asset_record
+            .use_single_amount_commitment(&pc_gens);
+        let xfr_note = gen_xfr_note(&mut prng, &inputs, &outputs, &inkeys_ref).unwrap();
+        assert!(matches!(
+            xfr_note.body.proofs.asset_type_and_amount_proof,
+            AssetTypeAndAmountProof::ConfAmountU64(_) | AssetTypeAndAmountProof::ConfAllU64(_)
+        ));
+
+        let policies = XfrNotePolicies::new(
+            vec![TracingPolicies::new(); 2],
+            vec![None; 2],
+            vec![TracingPolicies::new(), tracing_policies],
+            vec![None; 2],
+        );
+        pnk!(verify_xfr_note(
+            &mut prng,
+            &mut params,
+            &xfr_note,
+            &policies.to_ref()
+        ));
+
+        let records_data = trace_assets(&xfr_note.body, &tracer_keypair).unwrap();
+        assert_eq!(records_data.len(), 1);
+        assert_eq!(records_data[0].0, out_amounts[1]);
+        assert_eq!(records_data[0].1, asset_type);
+
+        // Inflating an output amount should fail.
+        let mut xfr_note = xfr_note;
+        xfr_note.body.outputs[0].amount = XfrAmount::from_blinds_u64(
+            &pc_gens,
+            out_amounts[0] + 1,
+            &outputs[0].open_asset_record.amount_blinds.0,
+            &outputs[0].open_asset_record.amount_blinds.1,
+        );
+        assert!(
+            batch_verify_xfr_body_asset_records(&mut prng, &mut params, &[&xfr_note.body]).is_err()
+        );
+
+        // Split commitments in the outputs are rejected by the 64-bit proof.
+        xfr_note.body.outputs[0] = outputs[0].open_asset_record.blind_asset_record.clone();
+        xfr_note.body.outputs[0].amount = XfrAmount::from_blinds(
+            &pc_gens,
+            out_amounts[0],
+            &outputs[0].open_asset_record.amount_blinds.0,
+            &outputs[0].open_asset_record.amount_blinds.1,
+        );
+        assert!(
+            batch_verify_xfr_body_asset_records(&mut prng, &mut params, &[&xfr_note.body]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_transfer_u64_amounts_plain_asset() {
+        do_transfer_tests_u64_amounts(AssetRecordType::ConfidentialAmount_NonConfidentialAssetType);
+    }
+
+    #[test]
+    fn test_transfer_u64_amounts_confidential_asset() {
+        do_transfer_tests_u64_amounts(AssetRecordType::ConfidentialAmount_ConfidentialAssetType);
+    }
+
+    #[test]
+    fn test_transfer_u64_amounts_too_many_outputs() {
+        let mut prng = test_rng();
+        let pc_gens = PedersenCommitmentRistretto::default();
+        let key_pair = XfrKeyPair::generate(&mut prng);
+        let new_record = |prng: &mut _, amount: u64| {
+            let template = AssetRecordTemplate::with_no_asset_tracing(
+                amount,
+                AssetType::from_identical_byte(0u8),
+                AssetRecordType::ConfidentialAmount_NonConfidentialAssetType,
+                key_pair.pub_key,
+            );
+            let mut record =
+                AssetRecord::from_template_no_identity_tracing(prng, &template).unwrap();
+            record
+                .open_asset_record
+                .use_single_amount_commitment(&pc_gens);
+            record
+        };
+        let input = new_record(&mut prng, 1000u64);
+        let output = new_record(&mut prng, 1u64);
+
+        // The outputs and the difference must fit in MAX_CONFIDENTIAL_RECORD_NUMBER_U64 values.
+        let outputs = vec![&output.open_asset_record; MAX_CONFIDENTIAL_RECORD_NUMBER_U64];
+        msg_eq!(
+            NoahError::RangeProofProveError,
+            gen_range_proof_u64(&[&input.open_asset_record], &outputs).unwrap_err()
+        );
+    }
+}
+
 mod multi_asset_no_tracing {
     use super::*;
     use crate::xfr::XfrNotePolicies;
@@ -684,6 +845,7 @@ mod keys {
 
 mod identity_tracing {
     use super::*;
+    use crate::anon_creds::{ac_attr_from_date, ACAttributePredicate, ACExpiryPolicy};
     use crate::xfr::{structs::TracingPolicies, XfrNotePoliciesRef};
 
     fn check_identity_tracing_for_asset_type(asset_record_type: AssetRecordType) {
@@ -717,6 +879,10 @@ mod identity_tracing {
         let id_tracing_policy = IdentityRevealPolicy {
             cred_issuer_pub_key: cred_issuer_pk,
             reveal_map: vec![false, true, false, true],
+            revocation_list: None,
+            predicate_map: vec![],
+            expiry: None,
+            linked_issuers: vec![],
         }; // revealing attr2 and attr4
 
         let tracing_policy = TracingPolicies::from_policy(TracingPolicy {
@@ -801,6 +967,635 @@ mod identity_tracing {
         );
     }
 
+    #[test]
+    fn test_identity_tracing_with_revocation() {
+        let mut params = BulletproofParams::default();
+        let mut prng = test_rng();
+        let addr = b"0x7789654"; // receiver address
+
+        let tracer_keys = AssetTracerKeyPair::generate(&mut prng);
+
+        let attrs = vec![1u32, 2, 3, 4]; // the first attribute is the revocation handle
+        let (cred_issuer_sk, cred_issuer_pk) = anon_creds::ac_keygen_issuer(&mut prng, 4);
+        let (receiver_ac_sk, receiver_ac_pk) =
+            anon_creds::ac_keygen_user(&mut prng, &cred_issuer_pk);
+        let ac_signature = anon_creds::ac_sign(
+            &mut prng,
+            &cred_issuer_sk,
+            &receiver_ac_pk,
+            attrs.as_slice(),
+        )
+        .unwrap();
+        let credential = Credential {
+            sig: ac_signature,
+            attrs,
+            ipk: cred_issuer_pk.clone(),
+        };
+        let (sig_commitment, _, key) =
+            ac_commit(&mut prng, &receiver_ac_sk, &credential, addr).unwrap();
+        let key = key.unwrap();
+
+        let mut revocation_list = anon_creds::ac_new_revocation_list(0);
+        pnk!(anon_creds::ac_revoke(&mut revocation_list, 5));
+        pnk!(anon_creds::ac_revoke(&mut revocation_list, 6));
+
+        let tracing_policy = |revocation_list: &anon_creds::ACRevocationList| {
+            TracingPolicies::from_policy(TracingPolicy {
+                enc_keys: tracer_keys.enc_key.clone(),
+                asset_tracing: false,
+                identity_tracing: Some(IdentityRevealPolicy {
+                    cred_issuer_pub_key: cred_issuer_pk.clone(),
+                    reveal_map: vec![false, true, false, true],
+                    revocation_list: Some(revocation_list.clone()),
+                    predicate_map: vec![],
+                    expiry: None,
+                    linked_issuers: vec![],
+                }),
+            })
+        };
+        let policies = tracing_policy(&revocation_list);
+
+        let input_keypair = XfrKeyPair::generate(&mut prng);
+        let asset_record_type = AssetRecordType::ConfidentialAmount_ConfidentialAssetType;
+        let input_asset_record = AssetRecordTemplate::with_no_asset_tracing(
+            10,
+            AssetType::from_identical_byte(0u8),
+            asset_record_type,
+            input_keypair.pub_key,
+        );
+        let input =
+            AssetRecord::from_template_no_identity_tracing(&mut prng, &input_asset_record).unwrap();
+        let output_asset_record = AssetRecordTemplate::with_asset_tracing(
+            10,
+            AssetType::from_identical_byte(0u8),
+            asset_record_type,
+            input_keypair.pub_key,
+            policies.clone(),
+        );
+        let outputs = [AssetRecord::from_template_with_identity_tracing(
+            &mut prng,
+            &output_asset_record,
+            &receiver_ac_sk,
+            &credential,
+            &key,
+        )
+        .unwrap()];
+
+        let xfr_note = gen_xfr_note(&mut prng, &[input], &outputs, &[&input_keypair]).unwrap();
+
+        let null_policies_input = &TracingPolicies::new();
+        let xfr_policies = XfrNotePoliciesRef::new(
+            vec![null_policies_input],
+            vec![None; 1],
+            vec![&policies],
+            vec![Some(&sig_commitment)],
+        );
+        pnk!(verify_xfr_note(
+            &mut prng,
+            &mut params,
+            &xfr_note,
+            &xfr_policies
+        ));
+
+        // an unrelated revocation moves the list to a new epoch, so the hidden revocation handle
+        // has to be proven again against the new list
+        pnk!(anon_creds::ac_revoke(&mut revocation_list, 7));
+        let updated_policies = tracing_policy(&revocation_list);
+        let xfr_policies = XfrNotePoliciesRef::new(
+            vec![null_policies_input],
+            vec![None; 1],
+            vec![&updated_policies],
+            vec![Some(&sig_commitment)],
+        );
+        msg_eq!(
+            NoahError::XfrVerifyAssetTracingIdentityError,
+            verify_xfr_note(&mut prng, &mut params, &xfr_note, &xfr_policies).unwrap_err(),
+        );
+        let input =
+            AssetRecord::from_template_no_identity_tracing(&mut prng, &input_asset_record).unwrap();
+        let output_asset_record = AssetRecordTemplate::with_asset_tracing(
+            10,
+            AssetType::from_identical_byte(0u8),
+            asset_record_type,
+            input_keypair.pub_key,
+            updated_policies.clone(),
+        );
+        let outputs = [AssetRecord::from_template_with_identity_tracing(
+            &mut prng,
+            &output_asset_record,
+            &receiver_ac_sk,
+            &credential,
+            &key,
+        )
+        .unwrap()];
+        let xfr_note = gen_xfr_note(&mut prng, &[input], &outputs, &[&input_keypair]).unwrap();
+        pnk!(verify_xfr_note(
+            &mut prng,
+            &mut params,
+            &xfr_note,
+            &xfr_policies
+        ));
+
+        // once the credential is revoked, the note no longer verifies
+        pnk!(anon_creds::ac_revoke(&mut revocation_list, 1));
+        let revoked_policies = tracing_policy(&revocation_list);
+        let xfr_policies = XfrNotePoliciesRef::new(
+            vec![null_policies_input],
+            vec![None; 1],
+            vec![&revoked_policies],
+            vec![Some(&sig_commitment)],
+        );
+        msg_eq!(
+            NoahError::XfrVerifyAssetTracingIdentityError,
+            verify_xfr_note(&mut prng, &mut params, &xfr_note, &xfr_policies).unwrap_err(),
+        );
+
+        // and new outputs can no longer be created
+        let output_asset_record = AssetRecordTemplate::with_asset_tracing(
+            10,
+            AssetType::from_identical_byte(0u8),
+            asset_record_type,
+            input_keypair.pub_key,
+            revoked_policies.clone(),
+        );
+        msg_eq!(
+            NoahError::CredentialRevokedError,
+            AssetRecord::from_template_with_identity_tracing(
+                &mut prng,
+                &output_asset_record,
+                &receiver_ac_sk,
+                &credential,
+                &key,
+            )
+            .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_identity_tracing_with_linked_issuers() {
+        let mut params = BulletproofParams::default();
+        let mut prng = test_rng();
+        let addr = b"0x7789654"; // receiver address
+
+        let tracer_keys = AssetTracerKeyPair::generate(&mut prng);
+
+        // a KYC issuer and an accredited-investor issuer
+        let (kyc_issuer_sk, kyc_issuer_pk) = anon_creds::ac_keygen_issuer(&mut prng, 4);
+        let (investor_issuer_sk, investor_issuer_pk) = anon_creds::ac_keygen_issuer(&mut prng, 2);
+        let (receiver_ac_sk, receiver_ac_pk) =
+            anon_creds::ac_keygen_user(&mut prng, &kyc_issuer_pk);
+        let receiver_investor_pk =
+            anon_creds::ac_user_pk_for_issuer(&receiver_ac_sk, &investor_issuer_pk);
+
+        let kyc_attrs = vec![1u32, 2, 3, 4];
+        let credential = Credential {
+            sig: anon_creds::ac_sign(&mut prng, &kyc_issuer_sk, &receiver_ac_pk, &kyc_attrs)
+                .unwrap(),
+            attrs: kyc_attrs,
+            ipk: kyc_issuer_pk.clone(),
+        };
+        let investor_attrs = vec![50u32, 60];
+        let investor_credential = Credential {
+            sig: anon_creds::ac_sign(
+                &mut prng,
+                &investor_issuer_sk,
+                &receiver_investor_pk,
+                &investor_attrs,
+            )
+            .unwrap(),
+            attrs: investor_attrs.clone(),
+            ipk: investor_issuer_pk.clone(),
+        };
+        let (sig_commitment, _, key) =
+            ac_commit(&mut prng, &receiver_ac_sk, &credential, addr).unwrap();
+        let key = key.unwrap();
+
+        let tracing_policy = |linked_issuers: Vec<LinkedRevealPolicy>| {
+            TracingPolicies::from_policy(TracingPolicy {
+                enc_keys: tracer_keys.enc_key.clone(),
+                asset_tracing: false,
+                identity_tracing: Some(IdentityRevealPolicy {
+                    cred_issuer_pub_key: kyc_issuer_pk.clone(),
+                    reveal_map: vec![false, true, false, true],
+                    revocation_list: None,
+                    predicate_map: vec![],
+                    expiry: None,
+                    linked_issuers,
+                }),
+            })
+        };
+        let policies = tracing_policy(vec![LinkedRevealPolicy {
+            cred_issuer_pub_key: investor_issuer_pk.clone(),
+            reveal_map: vec![true, false],
+        }]);
+
+        let input_keypair = XfrKeyPair::generate(&mut prng);
+        let asset_record_type = AssetRecordType::ConfidentialAmount_ConfidentialAssetType;
+        let input_asset_record = AssetRecordTemplate::with_no_asset_tracing(
+            10,
+            AssetType::from_identical_byte(0u8),
+            asset_record_type,
+            input_keypair.pub_key,
+        );
+        let output_asset_record = AssetRecordTemplate::with_asset_tracing(
+            10,
+            AssetType::from_identical_byte(0u8),
+            asset_record_type,
+            input_keypair.pub_key,
+            policies.clone(),
+        );
+
+        // the credential of the linked issuer is required
+        msg_eq!(
+            NoahError::ParameterError,
+            AssetRecord::from_template_with_identity_tracing(
+                &mut prng,
+                &output_asset_record,
+                &receiver_ac_sk,
+                &credential,
+                &key,
+            )
+            .unwrap_err()
+        );
+
+        let input =
+            AssetRecord::from_template_no_identity_tracing(&mut prng, &input_asset_record).unwrap();
+        let outputs = [AssetRecord::from_template_with_linked_identity_tracing(
+            &mut prng,
+            &output_asset_record,
+            &receiver_ac_sk,
+            &credential,
+            &key,
+            &[&investor_credential],
+        )
+        .unwrap()];
+        let xfr_note = gen_xfr_note(&mut prng, &[input], &outputs, &[&input_keypair]).unwrap();
+
+        let null_policies_input = &TracingPolicies::new();
+        let xfr_policies = XfrNotePoliciesRef::new(
+            vec![null_policies_input],
+            vec![None; 1],
+            vec![&policies],
+            vec![Some(&sig_commitment)],
+        );
+        pnk!(verify_xfr_note(
+            &mut prng,
+            &mut params,
+            &xfr_note,
+            &xfr_policies
+        ));
+
+        // the tracer learns the attributes of both credentials
+        let memo = &xfr_note.body.asset_tracing_memos[1][0];
+        let (_, _, attrs) = pnk!(memo.decrypt(&tracer_keys.dec_key));
+        assert_eq!(attrs, vec![2, 4, 50]);
+
+        // a policy that does not expect the linked credential rejects the note
+        let single_policies = tracing_policy(vec![]);
+        let xfr_policies = XfrNotePoliciesRef::new(
+            vec![null_policies_input],
+            vec![None; 1],
+            vec![&single_policies],
+            vec![Some(&sig_commitment)],
+        );
+        msg_eq!(
+            NoahError::XfrVerifyAssetTracingIdentityError,
+            verify_xfr_note(&mut prng, &mut params, &xfr_note, &xfr_policies).unwrap_err(),
+        );
+
+        // a credential of the linked issuer held by another user is rejected
+        let (other_ac_sk, _) = anon_creds::ac_keygen_user(&mut prng, &kyc_issuer_pk);
+        let other_investor_pk =
+            anon_creds::ac_user_pk_for_issuer(&other_ac_sk, &investor_issuer_pk);
+        let other_credential = Credential {
+            sig: anon_creds::ac_sign(
+                &mut prng,
+                &investor_issuer_sk,
+                &other_investor_pk,
+                &investor_attrs,
+            )
+            .unwrap(),
+            attrs: investor_attrs,
+            ipk: investor_issuer_pk,
+        };
+        let input =
+            AssetRecord::from_template_no_identity_tracing(&mut prng, &input_asset_record).unwrap();
+        let outputs = [AssetRecord::from_template_with_linked_identity_tracing(
+            &mut prng,
+            &output_asset_record,
+            &receiver_ac_sk,
+            &credential,
+            &key,
+            &[&other_credential],
+        )
+        .unwrap()];
+        let xfr_note = gen_xfr_note(&mut prng, &[input], &outputs, &[&input_keypair]).unwrap();
+        let xfr_policies = XfrNotePoliciesRef::new(
+            vec![null_policies_input],
+            vec![None; 1],
+            vec![&policies],
+            vec![Some(&sig_commitment)],
+        );
+        msg_eq!(
+            NoahError::XfrVerifyAssetTracingIdentityError,
+            verify_xfr_note(&mut prng, &mut params, &xfr_note, &xfr_policies).unwrap_err(),
+        );
+    }
+
+    #[test]
+    fn test_identity_tracing_with_predicates() {
+        let mut params = BulletproofParams::default();
+        let mut prng = test_rng();
+        let addr = b"0x7789654"; // receiver address
+
+        let tracer_keys = AssetTracerKeyPair::generate(&mut prng);
+
+        let attrs = vec![1u32, 25, 276, 4]; // the second attribute is the age, the third the country
+        let (cred_issuer_sk, cred_issuer_pk) = anon_creds::ac_keygen_issuer(&mut prng, 4);
+        let (receiver_ac_sk, receiver_ac_pk) =
+            anon_creds::ac_keygen_user(&mut prng, &cred_issuer_pk);
+        let ac_signature = anon_creds::ac_sign(
+            &mut prng,
+            &cred_issuer_sk,
+            &receiver_ac_pk,
+            attrs.as_slice(),
+        )
+        .unwrap();
+        let credential = Credential {
+            sig: ac_signature,
+            attrs,
+            ipk: cred_issuer_pk.clone(),
+        };
+        let (sig_commitment, _, key) =
+            ac_commit(&mut prng, &receiver_ac_sk, &credential, addr).unwrap();
+        let key = key.unwrap();
+
+        let tracing_policy = |predicate_map: Vec<Option<ACAttributePredicate>>| {
+            TracingPolicies::from_policy(TracingPolicy {
+                enc_keys: tracer_keys.enc_key.clone(),
+                asset_tracing: false,
+                identity_tracing: Some(IdentityRevealPolicy {
+                    cred_issuer_pub_key: cred_issuer_pk.clone(),
+                    reveal_map: vec![true, false, false, false],
+                    revocation_list: None,
+                    predicate_map,
+                    expiry: None,
+                    linked_issuers: vec![],
+                }),
+            })
+        };
+        let policies = tracing_policy(vec![
+            None,
+            Some(ACAttributePredicate::AtLeast(18)),
+            Some(ACAttributePredicate::Membership(vec![250, 276, 380])),
+            None,
+        ]);
+
+        let input_keypair = XfrKeyPair::generate(&mut prng);
+        let asset_record_type = AssetRecordType::ConfidentialAmount_ConfidentialAssetType;
+        let input_asset_record = AssetRecordTemplate::with_no_asset_tracing(
+            10,
+            AssetType::from_identical_byte(0u8),
+            asset_record_type,
+            input_keypair.pub_key,
+        );
+        let input =
+            AssetRecord::from_template_no_identity_tracing(&mut prng, &input_asset_record).unwrap();
+        let output_asset_record = AssetRecordTemplate::with_asset_tracing(
+            10,
+            AssetType::from_identical_byte(0u8),
+            asset_record_type,
+            input_keypair.pub_key,
+            policies.clone(),
+        );
+        let outputs = [AssetRecord::from_template_with_identity_tracing(
+            &mut prng,
+            &output_asset_record,
+            &receiver_ac_sk,
+            &credential,
+            &key,
+        )
+        .unwrap()];
+
+        let xfr_note = gen_xfr_note(&mut prng, &[input], &outputs, &[&input_keypair]).unwrap();
+
+        let null_policies_input = &TracingPolicies::new();
+        let xfr_policies = XfrNotePoliciesRef::new(
+            vec![null_policies_input],
+            vec![None; 1],
+            vec![&policies],
+            vec![Some(&sig_commitment)],
+        );
+        pnk!(verify_xfr_note(
+            &mut prng,
+            &mut params,
+            &xfr_note,
+            &xfr_policies
+        ));
+
+        // the note does not verify under a different policy
+        let stricter_policies = tracing_policy(vec![
+            None,
+            Some(ACAttributePredicate::AtLeast(21)),
+            Some(ACAttributePredicate::Membership(vec![250, 380])),
+            None,
+        ]);
+        let xfr_policies = XfrNotePoliciesRef::new(
+            vec![null_policies_input],
+            vec![None; 1],
+            vec![&stricter_policies],
+            vec![Some(&sig_commitment)],
+        );
+        msg_eq!(
+            NoahError::XfrVerifyAssetTracingIdentityError,
+            verify_xfr_note(&mut prng, &mut params, &xfr_note, &xfr_policies).unwrap_err(),
+        );
+
+        // and no output can be created for a credential that violates the policy
+        let output_asset_record = AssetRecordTemplate::with_asset_tracing(
+            10,
+            AssetType::from_identical_byte(0u8),
+            asset_record_type,
+            input_keypair.pub_key,
+            tracing_policy(vec![
+                None,
+                Some(ACAttributePredicate::AtLeast(26)),
+                None,
+                None,
+            ]),
+        );
+        msg_eq!(
+            NoahError::AttributePredicateError,
+            AssetRecord::from_template_with_identity_tracing(
+                &mut prng,
+                &output_asset_record,
+                &receiver_ac_sk,
+                &credential,
+                &key,
+            )
+            .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_identity_tracing_with_expiry() {
+        let mut params = BulletproofParams::default();
+        let mut prng = test_rng();
+        let addr = b"0x7789654"; // receiver address
+
+        let tracer_keys = AssetTracerKeyPair::generate(&mut prng);
+        let (cred_issuer_sk, cred_issuer_pk) = anon_creds::ac_keygen_issuer(&mut prng, 2);
+        let (receiver_ac_sk, receiver_ac_pk) =
+            anon_creds::ac_keygen_user(&mut prng, &cred_issuer_pk);
+        // the second attribute is the expiry
+        let new_credential = |prng: &mut _, expiry: u32| {
+            let attrs = vec![1u32, expiry];
+            let ac_signature =
+                anon_creds::ac_sign(prng, &cred_issuer_sk, &receiver_ac_pk, attrs.as_slice())
+                    .unwrap();
+            Credential {
+                sig: ac_signature,
+                attrs,
+                ipk: cred_issuer_pk.clone(),
+            }
+        };
+        let valid_credential = new_credential(&mut prng, ac_attr_from_date(2023, 11, 14).unwrap());
+        let expired_credential = new_credential(&mut prng, ac_attr_from_date(2020, 9, 13).unwrap());
+        let bound = u64::from(ac_attr_from_date(2023, 11, 13).unwrap());
+
+        // the bounds are rounded down to weeks
+        let policies = TracingPolicies::from_policy(TracingPolicy {
+            enc_keys: tracer_keys.enc_key.clone(),
+            asset_tracing: false,
+            identity_tracing: Some(IdentityRevealPolicy {
+                cred_issuer_pub_key: cred_issuer_pk.clone(),
+                reveal_map: vec![true, false],
+                revocation_list: None,
+                predicate_map: vec![],
+                expiry: Some(ACExpiryPolicy {
+                    attr_index: 1,
+                    granularity: 7,
+                }),
+                linked_issuers: vec![],
+            }),
+        });
+
+        let input_keypair = XfrKeyPair::generate(&mut prng);
+        let asset_record_type = AssetRecordType::ConfidentialAmount_ConfidentialAssetType;
+        let input_asset_record = AssetRecordTemplate::with_no_asset_tracing(
+            10,
+            AssetType::from_identical_byte(0u8),
+            asset_record_type,
+            input_keypair.pub_key,
+        );
+        let output_asset_record = AssetRecordTemplate::with_asset_tracing(
+            10,
+            AssetType::from_identical_byte(0u8),
+            asset_record_type,
+            input_keypair.pub_key,
+            policies.clone(),
+        );
+        let mut build_note = |credential: &Credential| {
+            let (sig_commitment, _, key) =
+                ac_commit(&mut prng, &receiver_ac_sk, credential, addr).unwrap();
+            let input =
+                AssetRecord::from_template_no_identity_tracing(&mut prng, &input_asset_record)
+                    .unwrap();
+            let output = AssetRecord::from_template_with_identity_tracing(
+                &mut prng,
+                &output_asset_record,
+                &receiver_ac_sk,
+                credential,
+                &key.unwrap(),
+            )
+            .unwrap();
+            let xfr_note = gen_xfr_note(&mut prng, &[input], &[output], &[&input_keypair]).unwrap();
+            (xfr_note, sig_commitment)
+        };
+        let (valid_note, valid_commitment) = build_note(&valid_credential);
+        let (expired_note, expired_commitment) = build_note(&expired_credential);
+
+        let null_policies_input = &TracingPolicies::new();
+        let valid_policies = XfrNotePoliciesRef::new(
+            vec![null_policies_input],
+            vec![None; 1],
+            vec![&policies],
+            vec![Some(&valid_commitment)],
+        );
+        let expired_policies = XfrNotePoliciesRef::new(
+            vec![null_policies_input],
+            vec![None; 1],
+            vec![&policies],
+            vec![Some(&expired_commitment)],
+        );
+        let now = 1_650_000_000; // 2022-04-15
+
+        // only the bound of the expiry is published
+        let proof = valid_note
+            .body
+            .proofs
+            .asset_tracing_proof
+            .outputs_identity_proofs[0][0]
+            .as_ref()
+            .unwrap();
+        assert_eq!(proof.pok.expiry_bound(), Some(bound));
+
+        pnk!(verify_xfr_note(
+            &mut prng,
+            &mut params,
+            &valid_note,
+            &valid_policies.clone().with_timestamp(now),
+        ));
+
+        // the expired credential fails at the same time
+        msg_eq!(
+            NoahError::CredentialExpiredError,
+            verify_xfr_note(
+                &mut prng,
+                &mut params,
+                &expired_note,
+                &expired_policies.clone().with_timestamp(now),
+            )
+            .unwrap_err(),
+        );
+
+        // the unexpired credential fails once the verifier passes the published bound
+        msg_eq!(
+            NoahError::CredentialExpiredError,
+            verify_xfr_note(
+                &mut prng,
+                &mut params,
+                &valid_note,
+                &valid_policies.clone().with_timestamp(1_699_900_000), // 2023-11-13
+            )
+            .unwrap_err(),
+        );
+
+        // the expiry cannot be checked without the time of the verifier
+        msg_eq!(
+            NoahError::ParameterError,
+            verify_xfr_note(&mut prng, &mut params, &valid_note, &valid_policies,).unwrap_err(),
+        );
+
+        // a tampered bound does not verify
+        let mut tampered_note = expired_note.clone();
+        let proof = tampered_note
+            .body
+            .proofs
+            .asset_tracing_proof
+            .outputs_identity_proofs[0][0]
+            .as_mut()
+            .unwrap();
+        proof.pok = proof.pok.clone().with_expiry_bound(bound);
+        assert!(verify_xfr_body(
+            &mut prng,
+            &mut params,
+            &tampered_note.body,
+            &expired_policies.clone().with_timestamp(now),
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_identity_tracing_for_non_conf_assets() {
         check_identity_tracing_for_asset_type(
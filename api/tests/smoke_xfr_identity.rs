@@ -81,6 +81,10 @@ mod smoke_xfr_identity {
         let id_policy_policy = IdentityRevealPolicy {
             cred_issuer_pub_key: cred_issuer_pk.clone(),
             reveal_map: vec![true, true, false, false], // reveal first two attributes
+            revocation_list: None,
+            predicate_map: vec![],
+            expiry: None,
+            linked_issuers: vec![],
         };
         let policy = TracingPolicy {
             enc_keys: tracer_keys.enc_key.clone(),
@@ -259,6 +263,10 @@ mod smoke_xfr_identity {
         let id_policy_policy = IdentityRevealPolicy {
             cred_issuer_pub_key: cred_issuer_pk.clone(),
             reveal_map: vec![false, true, true, true], // reveal last three attributes
+            revocation_list: None,
+            predicate_map: vec![],
+            expiry: None,
+            linked_issuers: vec![],
         };
         let policy = TracingPolicy {
             enc_keys: tracer_keys.enc_key.clone(),
@@ -233,11 +233,19 @@ fn complex_transaction() {
     let id_tracing_policy1 = IdentityRevealPolicy {
         cred_issuer_pub_key: cred_issuer_pk.clone(),
         reveal_map: vec![false, true, false, true],
+        revocation_list: None,
+        predicate_map: vec![],
+        expiry: None,
+        linked_issuers: vec![],
     }; // revealing attr2 and attr4
 
     let id_tracing_policy2 = IdentityRevealPolicy {
         cred_issuer_pub_key: cred_issuer_pk,
         reveal_map: vec![true, true, false, true],
+        revocation_list: None,
+        predicate_map: vec![],
+        expiry: None,
+        linked_issuers: vec![],
     }; // revealing attr1 , attr2 and attr4
 
     let asset_tracing_policy_asset1_input = TracingPolicies::from_policy(TracingPolicy {
@@ -1,7 +1,14 @@
 //! Anonymous credentials enable a credential issuer to issue a credential (with some attributes)
 //! to a user, and the user can later, with anonymity, selectively disclose some attributes.
 
-use crate::{basic::matrix_sigma::SigmaTranscript, confidential_anon_creds::CACTranscript};
+use crate::{
+    basic::matrix_sigma::SigmaTranscript,
+    confidential_anon_creds::CACTranscript,
+    credential_predicates::{
+        predicate_append_proof, predicate_commit, verify_predicate, AttributePredicate,
+        AttributePredicateProof, PredicateProver,
+    },
+};
 use merlin::Transcript;
 use noah_algebra::{prelude::*, traits::Pairing};
 use serde_derive::{Deserialize, Serialize};
@@ -9,7 +16,9 @@ use serde_derive::{Deserialize, Serialize};
 pub(crate) const REVEAL_PROOF_DOMAIN: &[u8] = b"AC Reveal PoK";
 pub(crate) const REVEAL_PROOF_NEW_TRANSCRIPT_INSTANCE: &[u8] = b"AC Reveal PoK Instance";
 pub(crate) const COMMIT_NEW_TRANSCRIPT_INSTANCE: &[u8] = b"AC Commit SoK Instance";
+pub(crate) const PRESENTATION_NEW_TRANSCRIPT_INSTANCE: &[u8] = b"AC Presentation SoK Instance";
 pub(crate) const POK_LABEL: &[u8] = b"Signature Message";
+pub(crate) const NON_REVOCATION_LABEL: &[u8] = b"AC Non-Revocation";
 
 /// Credential issuer public key (`ipk`).
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -134,6 +143,16 @@ pub struct CredentialSigOpenProof<G1, G2, S> {
 /// Proof that revealed attributes verify a credential commitment signature.
 pub type CredentialCommOpenProof<G2, S> = CredentialPoK<G2, S>;
 
+/// Proof of selective disclosure over several credentials, possibly granted by different issuers,
+/// which are all bound to the same user secret key.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CredentialPresentation<G1, G2, S> {
+    /// The credential commitments, one per credential.
+    pub cms: Vec<CredentialComm<G1>>,
+    /// The opening proofs, which share the response for the user secret key.
+    pub proofs_open: Vec<CredentialPoK<G2, S>>,
+}
+
 /// Proof of knowledge for t, sk (UserSecretKey), and hidden attributes that satisfy a
 /// certain relation..
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -142,6 +161,138 @@ pub struct CredentialPoK<G2, S> {
     pub(crate) response_t: S, // c * t + r_t
     pub(crate) response_sk: S, // c * sk + r_sk
     pub(crate) response_attrs: Vec<S>, // {c * a_i + r_{a_i}; a_i in hidden}
+    #[serde(default)]
+    pub(crate) non_revocation: Option<CredentialNonRevocationProof<G2, S>>,
+    #[serde(default)]
+    pub(crate) predicates: Vec<AttributePredicateProof<G2, S>>,
+    #[serde(default)]
+    pub(crate) expiry_bound: Option<u64>,
+}
+
+impl<G2, S> CredentialPoK<G2, S> {
+    /// Return the lower bound on the expiry attribute published with the proof, if any.
+    pub fn expiry_bound(&self) -> Option<u64> {
+        self.expiry_bound
+    }
+
+    /// Publish the lower bound on the expiry attribute chosen by the prover.
+    /// The bound has to be proven by an `AtLeast` predicate on the expiry attribute, which binds
+    /// it to the transcript, so that the verifier can compare it with its own time.
+    pub fn with_expiry_bound(mut self, bound: u64) -> Self {
+        self.expiry_bound = Some(bound);
+        self
+    }
+}
+
+/// The version of the non-revocation proofs, which prove that the revocation handle differs
+/// from every revoked handle, so that their size and verification time are linear in the list.
+/// A later version may replace them with accumulator-based non-membership witnesses.
+pub const NON_REVOCATION_PROOF_VERSION: u64 = 1;
+
+/// The maximal number of revoked handles in a revocation list, which bounds the size of the
+/// non-revocation proofs of version NON_REVOCATION_PROOF_VERSION.
+/// An issuer whose list is full has to reissue the remaining credentials under a fresh list.
+pub const MAX_REVOKED_HANDLES: usize = 256;
+
+/// Revocation list maintained by a credential issuer.
+/// Each credential carries a revocation handle as the attribute at `handle_index`,
+/// and the credential is revoked once its handle is on the list.
+/// Proofs are bound to the whole list, so they have to be recomputed after every revocation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CredentialRevocationList<S> {
+    /// The index of the attribute holding the revocation handle.
+    pub handle_index: usize,
+    /// The epoch of the list, increased on each revocation.
+    pub epoch: u64,
+    /// The revoked handles.
+    pub revoked_handles: Vec<S>,
+}
+
+impl<S: Scalar> CredentialRevocationList<S> {
+    /// Create an empty revocation list for handles stored at attribute `handle_index`.
+    pub fn new(handle_index: usize) -> Self {
+        Self {
+            handle_index,
+            epoch: 0,
+            revoked_handles: vec![],
+        }
+    }
+
+    /// Revoke the credential with revocation handle `handle`.
+    /// Returns Err(NoahError::ParameterError) if the list already holds MAX_REVOKED_HANDLES handles.
+    pub fn revoke(&mut self, handle: &S) -> Result<()> {
+        if !self.is_revoked(handle) {
+            if self.revoked_handles.len() >= MAX_REVOKED_HANDLES {
+                return Err(eg!(NoahError::ParameterError));
+            }
+            self.revoked_handles.push(*handle);
+            self.epoch += 1;
+        }
+        Ok(())
+    }
+
+    /// Check if the revocation handle `handle` has been revoked.
+    pub fn is_revoked(&self, handle: &S) -> bool {
+        self.revoked_handles.contains(handle)
+    }
+
+    pub(crate) fn append_to_transcript(&self, transcript: &mut Transcript) {
+        transcript.append_message(b"New Domain", NON_REVOCATION_LABEL);
+        transcript.append_u64(b"version", NON_REVOCATION_PROOF_VERSION);
+        transcript.append_u64(b"handle index", self.handle_index as u64);
+        transcript.append_u64(b"epoch", self.epoch);
+        for handle in self.revoked_handles.iter() {
+            transcript.append_field_element(b"revoked handle", handle);
+        }
+    }
+}
+
+/// Proof that the revocation handle committed in `comm` differs from one revoked handle.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CredentialNonEqualityProof<G2, S> {
+    pub(crate) blinding: G2,  // k_a * (comm - v * G2) + k_b * Z2
+    pub(crate) response_a: S, // c * (h - v)^{-1} + k_a
+    pub(crate) response_b: S, // c * (-rho * (h - v)^{-1}) + k_b
+}
+
+/// Zero-knowledge proof that the revocation handle of a credential is not on a revocation list.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CredentialNonRevocationProof<G2, S> {
+    pub(crate) comm: G2,        // h * G2 + rho * Z2
+    pub(crate) blinding: G2,    // r_h * G2 + r_rho * Z2, r_h shared with the PoK
+    pub(crate) response_rho: S, // c * rho + r_rho
+    pub(crate) non_equality: Vec<CredentialNonEqualityProof<G2, S>>,
+}
+
+/// The prover state of a non-revocation proof between the commitment and the response.
+pub(crate) struct NonRevocationProver<G2, S> {
+    comm: G2,
+    blinding: G2,
+    rho: S,
+    r_rho: S,
+    // (blinding, a, b, k_a, k_b) for each revoked handle
+    non_equality: Vec<(G2, S, S, S, S)>,
+}
+
+impl<G2: Group<ScalarType = S>, S: Scalar> NonRevocationProver<G2, S> {
+    /// Compute the responses for the challenge `challenge`.
+    pub(crate) fn respond(self, challenge: &S) -> CredentialNonRevocationProof<G2, S> {
+        let non_equality = self
+            .non_equality
+            .into_iter()
+            .map(|(blinding, a, b, k_a, k_b)| CredentialNonEqualityProof {
+                blinding,
+                response_a: challenge.mul(&a).add(&k_a),
+                response_b: challenge.mul(&b).add(&k_b),
+            })
+            .collect_vec();
+        CredentialNonRevocationProof {
+            comm: self.comm,
+            blinding: self.blinding,
+            response_rho: challenge.mul(&self.rho).add(&self.r_rho),
+            non_equality,
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -218,6 +369,15 @@ pub fn user_keygen<R: CryptoRng + RngCore, P: Pairing>(
     (CredentialUserSK(sk), CredentialUserPK(pk))
 }
 
+/// Derive the user public key under the issuer with public key `ipk` from an existing user secret
+/// key, so that credentials granted by several issuers are bound to the same `usk`.
+pub fn user_pk_for_issuer<P: Pairing>(
+    usk: &CredentialUserSK<P::ScalarField>,
+    ipk: &CredentialIssuerPK<P::G1, P::G2>,
+) -> CredentialUserPK<P::G1> {
+    CredentialUserPK(ipk.zz1.mul(&usk.0))
+}
+
 /// The credential issuer can use the issuer secret key `isk` to grant a number of attributes (the
 /// contents of the attributes are described in `\vec{attrs}` to a user, given this user's public
 /// key `upk`.
@@ -314,6 +474,8 @@ pub fn commit<R: CryptoRng + RngCore, P: Pairing>(
         &credential.ipk,
         &rand.t,
         hidden_attrs.as_slice(),
+        None,
+        &[],
     )
     .c(d!())?;
 
@@ -331,6 +493,9 @@ pub fn check_comm<P: Pairing>(
     init_pok_transcript::<P>(&mut transcript, ipk, cm);
     transcript.append_message(POK_LABEL, m);
 
+    if proof_valid.non_revocation.is_some() || !proof_valid.predicates.is_empty() {
+        return Err(eg!(NoahError::IdentityRevealVerifyError));
+    }
     transcript.append_proof_commitment(&proof_valid.blinding);
     let challenge = transcript.get_challenge::<P::ScalarField>();
 
@@ -351,22 +516,74 @@ pub fn open_comm<R: CryptoRng + RngCore, P: Pairing>(
     rand: &CredentialCommRandomizer<P::ScalarField>,
     reveal_map: &[bool],
 ) -> Result<CredentialCommOpenProof<P::G2, P::ScalarField>> {
-    if credential.attrs.len() != reveal_map.len() {
-        return Err(eg!(NoahError::ParameterError));
-    }
+    open_comm_internal::<_, P>(prng, usk, credential, cm, rand, reveal_map, &[], None).c(d!())
+}
 
-    let revealed_attrs = credential
-        .attrs
-        .iter()
-        .zip(reveal_map.iter())
-        .map(|(attr, b)| {
-            if *b {
-                Attribute::Revealed(*attr)
-            } else {
-                Attribute::Hidden(Some(*attr))
-            }
-        })
-        .collect_vec();
+/// Selectively reveal some attributes of the credential previously committed as in `open_comm`,
+/// and additionally prove that the credential is not revoked in `revocation_list`.
+/// If the revocation handle is hidden, the proof includes a zero-knowledge non-revocation proof.
+pub fn open_comm_with_revocation<R: CryptoRng + RngCore, P: Pairing>(
+    prng: &mut R,
+    usk: &CredentialUserSK<P::ScalarField>,
+    credential: &Credential<P::G1, P::G2, P::ScalarField>,
+    cm: &CredentialComm<P::G1>,
+    rand: &CredentialCommRandomizer<P::ScalarField>,
+    reveal_map: &[bool],
+    revocation_list: &CredentialRevocationList<P::ScalarField>,
+) -> Result<CredentialCommOpenProof<P::G2, P::ScalarField>> {
+    open_comm_internal::<_, P>(
+        prng,
+        usk,
+        credential,
+        cm,
+        rand,
+        reveal_map,
+        &[],
+        Some(revocation_list),
+    )
+    .c(d!())
+}
+
+/// Selectively reveal some attributes of the credential previously committed as in `open_comm`,
+/// and additionally prove that each hidden attribute with an entry in `predicate_map` satisfies
+/// the predicate, and, if `revocation_list` is given, that the credential is not revoked.
+/// The `predicate_map` is either empty or has one entry per attribute.
+#[allow(clippy::too_many_arguments)]
+pub fn open_comm_with_predicates<R: CryptoRng + RngCore, P: Pairing>(
+    prng: &mut R,
+    usk: &CredentialUserSK<P::ScalarField>,
+    credential: &Credential<P::G1, P::G2, P::ScalarField>,
+    cm: &CredentialComm<P::G1>,
+    rand: &CredentialCommRandomizer<P::ScalarField>,
+    reveal_map: &[bool],
+    predicate_map: &[Option<AttributePredicate<P::ScalarField>>],
+    revocation_list: Option<&CredentialRevocationList<P::ScalarField>>,
+) -> Result<CredentialCommOpenProof<P::G2, P::ScalarField>> {
+    open_comm_internal::<_, P>(
+        prng,
+        usk,
+        credential,
+        cm,
+        rand,
+        reveal_map,
+        predicate_map,
+        revocation_list,
+    )
+    .c(d!())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn open_comm_internal<R: CryptoRng + RngCore, P: Pairing>(
+    prng: &mut R,
+    usk: &CredentialUserSK<P::ScalarField>,
+    credential: &Credential<P::G1, P::G2, P::ScalarField>,
+    cm: &CredentialComm<P::G1>,
+    rand: &CredentialCommRandomizer<P::ScalarField>,
+    reveal_map: &[bool],
+    predicate_map: &[Option<AttributePredicate<P::ScalarField>>],
+    revocation_list: Option<&CredentialRevocationList<P::ScalarField>>,
+) -> Result<CredentialCommOpenProof<P::G2, P::ScalarField>> {
+    let revealed_attrs = apply_reveal_map(&credential.attrs, reveal_map).c(d!())?;
 
     let mut transcript = Transcript::new(REVEAL_PROOF_NEW_TRANSCRIPT_INSTANCE);
     init_pok_transcript::<P>(&mut transcript, &credential.ipk, &cm); // public parameters
@@ -377,6 +594,8 @@ pub fn open_comm<R: CryptoRng + RngCore, P: Pairing>(
         &credential.ipk,
         &rand.t,
         revealed_attrs.as_slice(),
+        revocation_list,
+        predicate_map,
     )
     .c(d!())?;
 
@@ -392,16 +611,225 @@ pub fn verify_open<P: Pairing>(
     cm: &CredentialComm<P::G1>,
     proof_open: &CredentialCommOpenProof<P::G2, P::ScalarField>,
     attrs: &[Attribute<P::ScalarField>],
+) -> Result<()> {
+    verify_open_internal::<P>(ipk, cm, proof_open, attrs, &[], None).c(d!())
+}
+
+/// Verify a selective opening as in `verify_open`, and additionally check that the credential
+/// is not revoked in `revocation_list`.
+pub fn verify_open_with_revocation<P: Pairing>(
+    ipk: &CredentialIssuerPK<P::G1, P::G2>,
+    cm: &CredentialComm<P::G1>,
+    proof_open: &CredentialCommOpenProof<P::G2, P::ScalarField>,
+    attrs: &[Attribute<P::ScalarField>],
+    revocation_list: &CredentialRevocationList<P::ScalarField>,
+) -> Result<()> {
+    verify_open_internal::<P>(ipk, cm, proof_open, attrs, &[], Some(revocation_list)).c(d!())
+}
+
+/// Verify a selective opening as in `verify_open`, and additionally check that each hidden
+/// attribute with an entry in `predicate_map` satisfies the predicate, and, if `revocation_list`
+/// is given, that the credential is not revoked.
+pub fn verify_open_with_predicates<P: Pairing>(
+    ipk: &CredentialIssuerPK<P::G1, P::G2>,
+    cm: &CredentialComm<P::G1>,
+    proof_open: &CredentialCommOpenProof<P::G2, P::ScalarField>,
+    attrs: &[Attribute<P::ScalarField>],
+    predicate_map: &[Option<AttributePredicate<P::ScalarField>>],
+    revocation_list: Option<&CredentialRevocationList<P::ScalarField>>,
+) -> Result<()> {
+    verify_open_internal::<P>(ipk, cm, proof_open, attrs, predicate_map, revocation_list).c(d!())
+}
+
+fn verify_open_internal<P: Pairing>(
+    ipk: &CredentialIssuerPK<P::G1, P::G2>,
+    cm: &CredentialComm<P::G1>,
+    proof_open: &CredentialCommOpenProof<P::G2, P::ScalarField>,
+    attrs: &[Attribute<P::ScalarField>],
+    predicate_map: &[Option<AttributePredicate<P::ScalarField>>],
+    revocation_list: Option<&CredentialRevocationList<P::ScalarField>>,
 ) -> Result<()> {
     let mut transcript = Transcript::new(REVEAL_PROOF_NEW_TRANSCRIPT_INSTANCE);
     init_pok_transcript::<P>(&mut transcript, ipk, cm);
 
+    let non_revocation = match (revocation_list, &proof_open.non_revocation) {
+        (None, None) => None,
+        (Some(list), proof) => match (attrs.get(list.handle_index), proof) {
+            (Some(Attribute::Revealed(handle)), None) => {
+                if list.is_revoked(handle) {
+                    return Err(eg!(NoahError::CredentialRevokedError));
+                }
+                None
+            }
+            (Some(Attribute::Hidden(_)), Some(proof)) => {
+                non_revocation_append_proof::<P>(&mut transcript, list, proof);
+                let response_handle = proof_open
+                    .response_attrs
+                    .get(hidden_position(attrs, list.handle_index))
+                    .c(d!(NoahError::IdentityRevealVerifyError))?;
+                Some((list, proof, response_handle))
+            }
+            _ => return Err(eg!(NoahError::IdentityRevealVerifyError)),
+        },
+        (None, Some(_)) => return Err(eg!(NoahError::IdentityRevealVerifyError)),
+    };
+
+    if !predicate_map.is_empty() && predicate_map.len() != attrs.len() {
+        return Err(eg!(NoahError::ParameterError));
+    }
+    let predicates = predicate_map
+        .iter()
+        .enumerate()
+        .filter_map(|(i, predicate)| predicate.as_ref().map(|predicate| (i, predicate)))
+        .collect_vec();
+    if predicates.len() != proof_open.predicates.len() {
+        return Err(eg!(NoahError::IdentityRevealVerifyError));
+    }
+    let mut predicate_checks = vec![];
+    for ((i, predicate), proof) in predicates.into_iter().zip(proof_open.predicates.iter()) {
+        if !matches!(attrs[i], Attribute::Hidden(_)) {
+            return Err(eg!(NoahError::ParameterError));
+        }
+        predicate_append_proof::<P>(&mut transcript, i, predicate, proof);
+        let response_attr = proof_open
+            .response_attrs
+            .get(hidden_position(attrs, i))
+            .c(d!(NoahError::IdentityRevealVerifyError))?;
+        predicate_checks.push((predicate, proof, response_attr));
+    }
+
     transcript.append_proof_commitment(&proof_open.blinding);
     let challenge = transcript.get_challenge::<P::ScalarField>();
 
+    if let Some((list, proof, response_handle)) = non_revocation {
+        verify_non_revocation::<P>(ipk, list, proof, response_handle, &challenge).c(d!())?;
+    }
+    for (predicate, proof, response_attr) in predicate_checks {
+        verify_predicate::<P>(ipk, predicate, proof, response_attr, &challenge).c(d!())?;
+    }
+
     verify_pok::<P>(ipk, cm, proof_open, attrs, &challenge)
 }
 
+/// Selectively reveal the attributes of several credentials, possibly granted by different
+/// issuers, in a single proof binding all of them to the user secret key `usk`, where the `i`-th
+/// reveal map applies to the `i`-th credential and `m` is the message bound to the proof.
+pub fn present_credentials<R: CryptoRng + RngCore, P: Pairing>(
+    prng: &mut R,
+    usk: &CredentialUserSK<P::ScalarField>,
+    credentials: &[&Credential<P::G1, P::G2, P::ScalarField>],
+    reveal_maps: &[&[bool]],
+    m: &[u8],
+) -> Result<CredentialPresentation<P::G1, P::G2, P::ScalarField>> {
+    if credentials.is_empty() || credentials.len() != reveal_maps.len() {
+        return Err(eg!(NoahError::ParameterError));
+    }
+
+    let mut transcript = Transcript::new(PRESENTATION_NEW_TRANSCRIPT_INSTANCE);
+    transcript.append_message(POK_LABEL, m);
+
+    // the blinding of the user secret key is shared by all the proofs
+    let beta2 = P::ScalarField::random(prng);
+    let mut cms = vec![];
+    let mut provers = vec![];
+    for (credential, reveal_map) in credentials.iter().zip(reveal_maps.iter()) {
+        let revealed_attrs = apply_reveal_map(&credential.attrs, reveal_map).c(d!())?;
+        let rand = randomizer_gen::<_, P>(prng);
+        let cm = CredentialComm::<P::G1>::new(&credential.sig, &rand);
+        init_pok_transcript::<P>(&mut transcript, &credential.ipk, &cm);
+        let prover = pok_commit::<_, P>(
+            &mut transcript,
+            prng,
+            usk,
+            &credential.ipk,
+            &rand.t,
+            revealed_attrs.as_slice(),
+            None,
+            &[],
+            beta2,
+        )
+        .c(d!())?;
+        cms.push(cm);
+        provers.push(prover);
+    }
+    for prover in provers.iter() {
+        transcript.append_proof_commitment(&prover.blinding);
+    }
+    let challenge = transcript.get_challenge::<P::ScalarField>();
+
+    Ok(CredentialPresentation {
+        cms,
+        proofs_open: provers
+            .into_iter()
+            .map(|prover| prover.respond(&challenge))
+            .collect(),
+    })
+}
+
+/// Verify a presentation of several credentials, where `ipks[i]` is the public key of the issuer
+/// of the `i`-th credential and `attrs[i]` lists its claimed attributes.
+pub fn verify_presentation<P: Pairing>(
+    ipks: &[&CredentialIssuerPK<P::G1, P::G2>],
+    attrs: &[&[Attribute<P::ScalarField>]],
+    presentation: &CredentialPresentation<P::G1, P::G2, P::ScalarField>,
+    m: &[u8],
+) -> Result<()> {
+    let n = ipks.len();
+    if n == 0 || attrs.len() != n {
+        return Err(eg!(NoahError::ParameterError));
+    }
+    if presentation.cms.len() != n || presentation.proofs_open.len() != n {
+        return Err(eg!(NoahError::IdentityRevealVerifyError));
+    }
+    // a common response for the user secret key binds all credentials to the same key
+    let response_sk = &presentation.proofs_open[0].response_sk;
+    for proof in presentation.proofs_open.iter() {
+        if proof.response_sk != *response_sk
+            || proof.non_revocation.is_some()
+            || !proof.predicates.is_empty()
+        {
+            return Err(eg!(NoahError::IdentityRevealVerifyError));
+        }
+    }
+
+    let mut transcript = Transcript::new(PRESENTATION_NEW_TRANSCRIPT_INSTANCE);
+    transcript.append_message(POK_LABEL, m);
+    for (ipk, cm) in ipks.iter().zip(presentation.cms.iter()) {
+        init_pok_transcript::<P>(&mut transcript, ipk, cm);
+    }
+    for proof in presentation.proofs_open.iter() {
+        transcript.append_proof_commitment(&proof.blinding);
+    }
+    let challenge = transcript.get_challenge::<P::ScalarField>();
+
+    for ((ipk, attrs), (cm, proof)) in ipks
+        .iter()
+        .zip(attrs.iter())
+        .zip(presentation.cms.iter().zip(presentation.proofs_open.iter()))
+    {
+        verify_pok::<P>(ipk, cm, proof, attrs, &challenge).c(d!())?;
+    }
+    Ok(())
+}
+
+/// Mark the attributes as revealed or hidden according to `reveal_map`.
+fn apply_reveal_map<A: Copy>(attrs: &[A], reveal_map: &[bool]) -> Result<Vec<Attribute<A>>> {
+    if attrs.len() != reveal_map.len() {
+        return Err(eg!(NoahError::ParameterError));
+    }
+    Ok(attrs
+        .iter()
+        .zip(reveal_map.iter())
+        .map(|(attr, b)| {
+            if *b {
+                Attribute::Revealed(*attr)
+            } else {
+                Attribute::Hidden(Some(*attr))
+            }
+        })
+        .collect_vec())
+}
+
 pub(super) fn init_pok_transcript<P: Pairing>(
     transcript: &mut Transcript,
     ipk: &CredentialIssuerPK<P::G1, P::G2>,
@@ -417,6 +845,15 @@ pub(super) fn init_pok_transcript<P: Pairing>(
     transcript.init_sigma_pairing::<P>(REVEAL_PROOF_DOMAIN, &g1_elems[..], g2_elems.as_slice());
 }
 
+/// Return the position of the attribute `index` among the hidden attributes, which is the index of
+/// its response in a proof of knowledge.
+fn hidden_position<A: Copy>(attrs: &[Attribute<A>], index: usize) -> usize {
+    attrs[..index]
+        .iter()
+        .filter(|attr| matches!(attr, Attribute::Hidden(_)))
+        .count()
+}
+
 /// Internal function for generating a proof of knowledge.
 fn prove_pok<R: CryptoRng + RngCore, P: Pairing>(
     transcript: &mut Transcript,
@@ -425,18 +862,91 @@ fn prove_pok<R: CryptoRng + RngCore, P: Pairing>(
     ipk: &CredentialIssuerPK<P::G1, P::G2>,
     t: &P::ScalarField,
     attrs: &[Attribute<P::ScalarField>],
+    revocation_list: Option<&CredentialRevocationList<P::ScalarField>>,
+    predicate_map: &[Option<AttributePredicate<P::ScalarField>>],
 ) -> Result<CredentialPoK<P::G2, P::ScalarField>> {
-    let beta1 = P::ScalarField::random(prng);
     let beta2 = P::ScalarField::random(prng);
+    let prover = pok_commit::<_, P>(
+        transcript,
+        prng,
+        usk,
+        ipk,
+        t,
+        attrs,
+        revocation_list,
+        predicate_map,
+        beta2,
+    )
+    .c(d!())?;
+    transcript.append_proof_commitment(&prover.blinding);
+    let challenge = transcript.get_challenge::<P::ScalarField>();
+    Ok(prover.respond(&challenge))
+}
+
+/// The prover state of a proof of knowledge between the commitment and the response.
+struct PoKProver<G2, S> {
+    blinding: G2,
+    beta1: S,
+    beta2: S,
+    t: S,
+    sk: S,
+    // (a_i, gamma_i) for each hidden attribute
+    hidden: Vec<(S, S)>,
+    non_revocation: Option<NonRevocationProver<G2, S>>,
+    predicates: Vec<PredicateProver<G2, S>>,
+}
+
+impl<G2: Group<ScalarType = S>, S: Scalar> PoKProver<G2, S> {
+    fn respond(self, challenge: &S) -> CredentialPoK<G2, S> {
+        let response_t = challenge.mul(&self.t).add(&self.beta1); // challenge*t + beta1
+        let response_sk = challenge.mul(&self.sk).add(&self.beta2);
+        let response_attrs = self
+            .hidden
+            .iter()
+            .map(|(attr, gamma)| challenge.mul(attr).add(gamma))
+            .collect();
+        CredentialPoK {
+            blinding: self.blinding,
+            response_t,
+            response_sk,
+            response_attrs,
+            non_revocation: self.non_revocation.map(|prover| prover.respond(challenge)),
+            predicates: self
+                .predicates
+                .into_iter()
+                .map(|prover| prover.respond(challenge))
+                .collect(),
+            expiry_bound: None,
+        }
+    }
+}
+
+/// Internal function for the commitment phase of a proof of knowledge, where `beta2` is the
+/// blinding for the user secret key, which is shared when several credentials are presented.
+#[allow(clippy::too_many_arguments)]
+fn pok_commit<R: CryptoRng + RngCore, P: Pairing>(
+    transcript: &mut Transcript,
+    prng: &mut R,
+    usk: &CredentialUserSK<P::ScalarField>,
+    ipk: &CredentialIssuerPK<P::G1, P::G2>,
+    t: &P::ScalarField,
+    attrs: &[Attribute<P::ScalarField>],
+    revocation_list: Option<&CredentialRevocationList<P::ScalarField>>,
+    predicate_map: &[Option<AttributePredicate<P::ScalarField>>],
+    beta2: P::ScalarField,
+) -> Result<PoKProver<P::G2, P::ScalarField>> {
+    let beta1 = P::ScalarField::random(prng);
     let mut gamma = vec![];
+    let mut hidden = vec![];
     let mut blinding = ipk.gen2.mul(&beta1).add(&ipk.zz2.mul(&beta2));
     for (yy2i, attr) in ipk.yy2.iter().zip(attrs) {
         match attr {
-            Attribute::Hidden(Some(_)) => {
+            Attribute::Hidden(Some(a)) => {
                 let gamma_i = P::ScalarField::random(prng);
                 let elem = yy2i.mul(&gamma_i);
                 blinding = blinding.add(&elem);
                 gamma.push(gamma_i);
+                hidden.push((*a, gamma_i));
             }
             Attribute::Hidden(None) => {
                 return Err(eg!(NoahError::ParameterError));
@@ -444,24 +954,61 @@ fn prove_pok<R: CryptoRng + RngCore, P: Pairing>(
             _ => {}
         }
     }
-    transcript.append_proof_commitment(&blinding);
-    let challenge = transcript.get_challenge::<P::ScalarField>();
-    let response_t = challenge.mul(t).add(&beta1); // challenge*t + beta1
-    let response_sk = challenge.mul(&usk.0).add(&beta2);
-    let mut response_attrs = vec![];
-    let mut gamma_iter = gamma.iter();
-    for attr_enum in attrs {
-        if let Attribute::Hidden(Some(attr)) = attr_enum {
-            let gamma = gamma_iter.next().unwrap(); // safe unwrap()
-            let resp_attr_i = challenge.mul(*attr).add(gamma);
-            response_attrs.push(resp_attr_i);
+    let non_revocation = match revocation_list {
+        Some(list) => match attrs.get(list.handle_index) {
+            Some(Attribute::Hidden(Some(handle))) => {
+                let handle_pos = hidden_position(attrs, list.handle_index);
+                Some(
+                    non_revocation_commit::<_, P>(
+                        transcript,
+                        prng,
+                        ipk,
+                        list,
+                        handle,
+                        gamma.get(handle_pos).c(d!(NoahError::ParameterError))?,
+                    )
+                    .c(d!())?,
+                )
+            }
+            Some(Attribute::Revealed(handle)) => {
+                if list.is_revoked(handle) {
+                    return Err(eg!(NoahError::CredentialRevokedError));
+                }
+                None
+            }
+            _ => return Err(eg!(NoahError::ParameterError)),
+        },
+        None => None,
+    };
+    if !predicate_map.is_empty() && predicate_map.len() != attrs.len() {
+        return Err(eg!(NoahError::ParameterError));
+    }
+    let mut predicates = vec![];
+    for (i, (predicate, attr)) in predicate_map.iter().zip(attrs).enumerate() {
+        if let Some(predicate) = predicate {
+            // predicates are only proven over hidden attributes
+            let attr = match attr {
+                Attribute::Hidden(Some(attr)) => attr,
+                _ => return Err(eg!(NoahError::ParameterError)),
+            };
+            let r_attr = gamma
+                .get(hidden_position(attrs, i))
+                .c(d!(NoahError::ParameterError))?;
+            predicates.push(
+                predicate_commit::<_, P>(transcript, prng, ipk, i, predicate, attr, r_attr)
+                    .c(d!())?,
+            );
         }
     }
-    Ok(CredentialPoK {
+    Ok(PoKProver {
         blinding,
-        response_t,
-        response_sk,
-        response_attrs,
+        beta1,
+        beta2,
+        t: *t,
+        sk: usk.0,
+        hidden,
+        non_revocation,
+        predicates,
     })
 }
 
@@ -519,6 +1066,110 @@ pub(crate) fn verify_pok<P: Pairing>(
     }
 }
 
+/// Internal function for committing to a non-revocation proof of the revocation handle `handle`,
+/// where `r_handle` is the blinding used for the handle in the proof of knowledge.
+pub(crate) fn non_revocation_commit<R: CryptoRng + RngCore, P: Pairing>(
+    transcript: &mut Transcript,
+    prng: &mut R,
+    ipk: &CredentialIssuerPK<P::G1, P::G2>,
+    revocation_list: &CredentialRevocationList<P::ScalarField>,
+    handle: &P::ScalarField,
+    r_handle: &P::ScalarField,
+) -> Result<NonRevocationProver<P::G2, P::ScalarField>> {
+    if revocation_list.is_revoked(handle) {
+        return Err(eg!(NoahError::CredentialRevokedError));
+    }
+    if revocation_list.revoked_handles.len() > MAX_REVOKED_HANDLES {
+        return Err(eg!(NoahError::ParameterError));
+    }
+    revocation_list.append_to_transcript(transcript);
+
+    let rho = P::ScalarField::random(prng);
+    let r_rho = P::ScalarField::random(prng);
+    let comm = ipk.gen2.mul(handle).add(&ipk.zz2.mul(&rho));
+    let blinding = ipk.gen2.mul(r_handle).add(&ipk.zz2.mul(&r_rho));
+    transcript.append_group_element(b"non-revocation comm", &comm);
+    transcript.append_proof_commitment(&blinding);
+
+    // For each revoked v, G2 = a * (comm - v * G2) + b * Z2 with a = (h - v)^{-1} and
+    // b = -rho * a, which has no solution when h = v.
+    let mut non_equality = vec![];
+    for v in revocation_list.revoked_handles.iter() {
+        let d = comm.sub(&ipk.gen2.mul(v));
+        let a = handle.sub(v).inv().c(d!())?;
+        let b = rho.mul(&a).neg();
+        let k_a = P::ScalarField::random(prng);
+        let k_b = P::ScalarField::random(prng);
+        let ne_blinding = d.mul(&k_a).add(&ipk.zz2.mul(&k_b));
+        transcript.append_proof_commitment(&ne_blinding);
+        non_equality.push((ne_blinding, a, b, k_a, k_b));
+    }
+
+    Ok(NonRevocationProver {
+        comm,
+        blinding,
+        rho,
+        r_rho,
+        non_equality,
+    })
+}
+
+/// Internal function for appending a non-revocation proof to the verifier's transcript.
+pub(crate) fn non_revocation_append_proof<P: Pairing>(
+    transcript: &mut Transcript,
+    revocation_list: &CredentialRevocationList<P::ScalarField>,
+    proof: &CredentialNonRevocationProof<P::G2, P::ScalarField>,
+) {
+    revocation_list.append_to_transcript(transcript);
+    transcript.append_group_element(b"non-revocation comm", &proof.comm);
+    transcript.append_proof_commitment(&proof.blinding);
+    for ne in proof.non_equality.iter() {
+        transcript.append_proof_commitment(&ne.blinding);
+    }
+}
+
+/// Internal function for verifying a non-revocation proof, where `response_handle` is the
+/// response for the revocation handle in the proof of knowledge.
+pub(crate) fn verify_non_revocation<P: Pairing>(
+    ipk: &CredentialIssuerPK<P::G1, P::G2>,
+    revocation_list: &CredentialRevocationList<P::ScalarField>,
+    proof: &CredentialNonRevocationProof<P::G2, P::ScalarField>,
+    response_handle: &P::ScalarField,
+    challenge: &P::ScalarField,
+) -> Result<()> {
+    if revocation_list.revoked_handles.len() > MAX_REVOKED_HANDLES
+        || proof.non_equality.len() != revocation_list.revoked_handles.len()
+    {
+        return Err(eg!(NoahError::IdentityRevealVerifyError));
+    }
+
+    // z_h * G2 + z_rho * Z2 = blinding + c * comm
+    let lhs = ipk
+        .gen2
+        .mul(response_handle)
+        .add(&ipk.zz2.mul(&proof.response_rho));
+    let rhs = proof.blinding.add(&proof.comm.mul(challenge));
+    if lhs != rhs {
+        return Err(eg!(NoahError::IdentityRevealVerifyError));
+    }
+
+    // s_a * (comm - v * G2) + s_b * Z2 = blinding_v + c * G2
+    let c_g2 = ipk.gen2.mul(challenge);
+    for (v, ne) in revocation_list
+        .revoked_handles
+        .iter()
+        .zip(proof.non_equality.iter())
+    {
+        let d = proof.comm.sub(&ipk.gen2.mul(v));
+        let lhs = d.mul(&ne.response_a).add(&ipk.zz2.mul(&ne.response_b));
+        let rhs = ne.blinding.add(&c_g2);
+        if lhs != rhs {
+            return Err(eg!(NoahError::IdentityRevealVerifyError));
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 pub(crate) mod credentials_tests {
     use super::*;
@@ -641,4 +1292,264 @@ pub(crate) mod credentials_tests {
         two_attributes();
         ten_attributes();
     }
+
+    fn reveal_with_revocation(reveal_map: &[bool], handle_index: usize) {
+        type P = BLSPairingEngine;
+        type S = <P as Pairing>::ScalarField;
+        let mut prng = test_rng();
+
+        let (isk, ipk) = issuer_keygen::<_, P>(&mut prng, reveal_map.len());
+        let (usk, upk) = user_keygen::<_, P>(&mut prng, &ipk);
+        let attrs = reveal_map
+            .iter()
+            .map(|_| S::random(&mut prng))
+            .collect_vec();
+        let sig = grant_credential::<_, P>(&mut prng, &isk, &upk, attrs.as_slice()).unwrap();
+        let credential = Credential {
+            sig,
+            attrs,
+            ipk: ipk.clone(),
+        };
+
+        let mut revocation_list = CredentialRevocationList::new(handle_index);
+        for _ in 0..3 {
+            pnk!(revocation_list.revoke(&S::random(&mut prng)));
+        }
+        assert_eq!(revocation_list.epoch, 3);
+
+        let rand = randomizer_gen::<_, P>(&mut prng);
+        let cm = CredentialComm::new(&credential.sig, &rand);
+        let proof = open_comm_with_revocation::<_, P>(
+            &mut prng,
+            &usk,
+            &credential,
+            &cm,
+            &rand,
+            reveal_map,
+            &revocation_list,
+        )
+        .unwrap();
+        assert_eq!(proof.non_revocation.is_some(), !reveal_map[handle_index]);
+
+        let revealed_attrs = credential
+            .attrs
+            .iter()
+            .zip(reveal_map.iter())
+            .map(|(a, b)| if *b { Revealed(*a) } else { Hidden(None) })
+            .collect_vec();
+        assert!(verify_open_with_revocation::<P>(
+            &ipk,
+            &cm,
+            &proof,
+            revealed_attrs.as_slice(),
+            &revocation_list
+        )
+        .is_ok());
+
+        // an unrelated revocation moves the list to a new epoch: a hidden handle has to be proven
+        // again against the new list, while a revealed handle is checked by the verifier directly
+        let old_revocation_list = revocation_list.clone();
+        pnk!(revocation_list.revoke(&S::random(&mut prng)));
+        assert_eq!(
+            verify_open_with_revocation::<P>(
+                &ipk,
+                &cm,
+                &proof,
+                revealed_attrs.as_slice(),
+                &revocation_list
+            )
+            .is_ok(),
+            reveal_map[handle_index]
+        );
+        let proof = open_comm_with_revocation::<_, P>(
+            &mut prng,
+            &usk,
+            &credential,
+            &cm,
+            &rand,
+            reveal_map,
+            &revocation_list,
+        )
+        .unwrap();
+        pnk!(verify_open_with_revocation::<P>(
+            &ipk,
+            &cm,
+            &proof,
+            revealed_attrs.as_slice(),
+            &revocation_list
+        ));
+        assert_eq!(
+            verify_open_with_revocation::<P>(
+                &ipk,
+                &cm,
+                &proof,
+                revealed_attrs.as_slice(),
+                &old_revocation_list
+            )
+            .is_ok(),
+            reveal_map[handle_index]
+        );
+
+        // once revoked, the credential can neither be opened nor verified
+        pnk!(revocation_list.revoke(&credential.attrs[handle_index]));
+        msg_eq!(
+            NoahError::CredentialRevokedError,
+            open_comm_with_revocation::<_, P>(
+                &mut prng,
+                &usk,
+                &credential,
+                &cm,
+                &rand,
+                reveal_map,
+                &revocation_list,
+            )
+            .unwrap_err()
+        );
+        assert!(verify_open_with_revocation::<P>(
+            &ipk,
+            &cm,
+            &proof,
+            revealed_attrs.as_slice(),
+            &revocation_list
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_revocation_list_is_bounded() {
+        type S = <BLSPairingEngine as Pairing>::ScalarField;
+        let mut prng = test_rng();
+        let mut revocation_list = CredentialRevocationList::new(0);
+        for _ in 0..MAX_REVOKED_HANDLES {
+            pnk!(revocation_list.revoke(&S::random(&mut prng)));
+        }
+        let handle = revocation_list.revoked_handles[0];
+        pnk!(revocation_list.revoke(&handle));
+        msg_eq!(
+            NoahError::ParameterError,
+            revocation_list.revoke(&S::random(&mut prng)).unwrap_err()
+        );
+        assert_eq!(revocation_list.epoch, MAX_REVOKED_HANDLES as u64);
+    }
+
+    #[test]
+    fn test_revocation() {
+        reveal_with_revocation(&[false], 0);
+        reveal_with_revocation(&[true], 0);
+        reveal_with_revocation(&[true, false, true, false], 1);
+        reveal_with_revocation(&[false, true, false, true], 3);
+        reveal_with_revocation(&[false, true, false, true], 2);
+    }
+
+    #[test]
+    fn test_multi_issuer_presentation() {
+        type P = BLSPairingEngine;
+        type S = <P as Pairing>::ScalarField;
+        let mut prng = test_rng();
+
+        let (isk1, ipk1) = issuer_keygen::<_, P>(&mut prng, 2);
+        let (isk2, ipk2) = issuer_keygen::<_, P>(&mut prng, 3);
+        let (usk, upk1) = user_keygen::<_, P>(&mut prng, &ipk1);
+        let upk2 = user_pk_for_issuer::<P>(&usk, &ipk2);
+
+        let attrs1 = vec![S::from(18u32), S::from(7u32)];
+        let attrs2 = vec![S::from(1u32), S::from(2u32), S::from(3u32)];
+        let credential1 = Credential {
+            sig: grant_credential::<_, P>(&mut prng, &isk1, &upk1, &attrs1).unwrap(),
+            attrs: attrs1.clone(),
+            ipk: ipk1.clone(),
+        };
+        let credential2 = Credential {
+            sig: grant_credential::<_, P>(&mut prng, &isk2, &upk2, &attrs2).unwrap(),
+            attrs: attrs2.clone(),
+            ipk: ipk2.clone(),
+        };
+
+        let map1 = [true, false];
+        let map2 = [false, true, false];
+        let presentation = present_credentials::<_, P>(
+            &mut prng,
+            &usk,
+            &[&credential1, &credential2],
+            &[&map1[..], &map2[..]],
+            b"presentation",
+        )
+        .unwrap();
+
+        let claimed1 = vec![Revealed(attrs1[0]), Hidden(None)];
+        let claimed2 = vec![Hidden(None), Revealed(attrs2[1]), Hidden(None)];
+        assert!(verify_presentation::<P>(
+            &[&ipk1, &ipk2],
+            &[&claimed1[..], &claimed2[..]],
+            &presentation,
+            b"presentation"
+        )
+        .is_ok());
+
+        // wrong message, swapped issuers and wrong revealed attributes
+        assert!(verify_presentation::<P>(
+            &[&ipk1, &ipk2],
+            &[&claimed1[..], &claimed2[..]],
+            &presentation,
+            b"other message"
+        )
+        .is_err());
+        assert!(verify_presentation::<P>(
+            &[&ipk2, &ipk1],
+            &[&claimed2[..], &claimed1[..]],
+            &presentation,
+            b"presentation"
+        )
+        .is_err());
+        let wrong2 = vec![Hidden(None), Revealed(attrs2[0]), Hidden(None)];
+        assert!(verify_presentation::<P>(
+            &[&ipk1, &ipk2],
+            &[&claimed1[..], &wrong2[..]],
+            &presentation,
+            b"presentation"
+        )
+        .is_err());
+
+        // a credential bound to a different user secret key cannot be presented along
+        let (other_usk, other_upk2) = user_keygen::<_, P>(&mut prng, &ipk2);
+        let other_credential2 = Credential {
+            sig: grant_credential::<_, P>(&mut prng, &isk2, &other_upk2, &attrs2).unwrap(),
+            attrs: attrs2.clone(),
+            ipk: ipk2.clone(),
+        };
+        for usk in [&usk, &other_usk].iter() {
+            let presentation = present_credentials::<_, P>(
+                &mut prng,
+                usk,
+                &[&credential1, &other_credential2],
+                &[&map1[..], &map2[..]],
+                b"presentation",
+            )
+            .unwrap();
+            assert!(verify_presentation::<P>(
+                &[&ipk1, &ipk2],
+                &[&claimed1[..], &claimed2[..]],
+                &presentation,
+                b"presentation"
+            )
+            .is_err());
+        }
+
+        // mismatched lengths
+        assert!(present_credentials::<_, P>(
+            &mut prng,
+            &usk,
+            &[&credential1, &credential2],
+            &[&map1[..]],
+            b"presentation",
+        )
+        .is_err());
+        assert!(verify_presentation::<P>(
+            &[&ipk1],
+            &[&claimed1[..]],
+            &presentation,
+            b"presentation"
+        )
+        .is_err());
+    }
 }
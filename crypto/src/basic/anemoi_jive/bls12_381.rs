@@ -1,452 +1,5 @@
-use noah_algebra::bls12_381::BLSScalar;
-use noah_algebra::new_bls12_381;
-use noah_algebra::prelude::*;
-
-/// The MDS matrix
-pub struct MDSMatrix<F: Scalar, const N: usize>(pub [[F; N]; N]);
-
-impl<F: Scalar, const N: usize> Default for MDSMatrix<F, N> {
-    fn default() -> Self {
-        Self([[F::default(); N]; N])
-    }
-}
-
-/// The trait for MDS matrix that can be used in Anemoi-Jive CRH.
-pub trait ApplicableMDSMatrix<F: Scalar, const N: usize> {
-    /// Construct the MDS matrix from the generator.
-    fn from_generator(generator: &F) -> Self;
-
-    /// Perform the permutation in place.
-    fn permute_in_place(&self, x: &mut [F; N], y: &mut [F; N]);
-
-    /// Perform the permutation and return the result.
-    fn permute(&self, x: &[F; N], y: &[F; N]) -> ([F; N], [F; N]) {
-        let mut x: [F; N] = x.clone();
-        let mut y: [F; N] = y.clone();
-        self.permute_in_place(&mut x, &mut y);
-        (x, y)
-    }
-}
-
-impl<F: Scalar> ApplicableMDSMatrix<F, 2> for MDSMatrix<F, 2> {
-    fn from_generator(generator: &F) -> Self {
-        // The matrix is:
-        //     ⌈ 1     g       ⌉
-        //     ⌊ g     g^2 + 1 ⌋
-        Self([
-            [F::one(), *generator],
-            [*generator, generator.square().add(F::one())],
-        ])
-    }
-
-    fn permute_in_place(&self, x: &mut [F; 2], y: &mut [F; 2]) {
-        // Reminder: a different matrix is applied to x and y
-        // The one for y has a simple word permutation.
-
-        let old_x = x.clone();
-        for i in 0..2 {
-            x[i] = F::zero();
-            for j in 0..2 {
-                x[i] += &(self.0[i][j] * old_x[j]);
-            }
-        }
-
-        // y has a simple word permutation.
-        let old_y = [y[1], y[0]];
-        for i in 0..2 {
-            y[i] = F::zero();
-            for j in 0..2 {
-                y[i] += &(self.0[i][j] * old_y[j]);
-            }
-        }
-    }
-}
-
-/// The structure for the trace of the Anemoi-Jive sponge hash function.
-#[derive(Clone)]
-pub struct AnemoiVLHTrace<F: Scalar, const N: usize, const NUM_ROUNDS: usize> {
-    /// The input sequence.
-    pub input: Vec<F>,
-    /// The state before each permutation.
-    pub before_permutation: Vec<([F; N], [F; N])>,
-    /// The intermediate values for each permutation.
-    pub intermediate_values_before_constant_additions:
-        Vec<([[F; N]; NUM_ROUNDS], [[F; N]; NUM_ROUNDS])>,
-    /// The state after each permutation.
-    pub after_permutation: Vec<([F; N], [F; N])>,
-    /// The output.
-    pub output: F,
-}
-
-impl<F: Scalar, const N: usize, const NUM_ROUNDS: usize> Default
-    for AnemoiVLHTrace<F, N, NUM_ROUNDS>
-{
-    fn default() -> Self {
-        Self {
-            input: vec![],
-            before_permutation: vec![],
-            intermediate_values_before_constant_additions: vec![],
-            after_permutation: vec![],
-            output: F::default(),
-        }
-    }
-}
-
-impl<F: Scalar, const N: usize, const NUM_ROUNDS: usize> noah_algebra::fmt::Debug
-    for AnemoiVLHTrace<F, N, NUM_ROUNDS>
-{
-    fn fmt(&self, f: &mut noah_algebra::fmt::Formatter<'_>) -> noah_algebra::fmt::Result {
-        f.write_str("input:\n")?;
-        for (i, elem) in self.input.iter().enumerate() {
-            f.write_fmt(format_args!("\r x[{}] = {:?}\n", i, elem))?;
-        }
-
-        for r in 0..NUM_ROUNDS {
-            f.write_fmt(format_args!("round {}:\n", r))?;
-
-            f.write_str("\r before permutation:")?;
-
-            for (i, elem) in self.before_permutation[r].0.iter().enumerate() {
-                f.write_fmt(format_args!("\r\r x[{}] = {:?}\n", i, elem))?;
-            }
-
-            for (i, elem) in self.before_permutation[r].1.iter().enumerate() {
-                f.write_fmt(format_args!("\r \r y[{}] = {:?}\n", i, elem))?;
-            }
-
-            f.write_str("\r intermediate permutation:")?;
-
-            for (i, elem) in self.intermediate_values_before_constant_additions[r]
-                .0
-                .iter()
-                .enumerate()
-            {
-                f.write_fmt(format_args!("\r\r x[{}] = {:?}\n", i, elem))?;
-            }
-
-            for (i, elem) in self.intermediate_values_before_constant_additions[r]
-                .1
-                .iter()
-                .enumerate()
-            {
-                f.write_fmt(format_args!("\r\r y[{}] = {:?}\n", i, elem))?;
-            }
-
-            f.write_str("\r after permutation:")?;
-
-            for (i, elem) in self.after_permutation[r].0.iter().enumerate() {
-                f.write_fmt(format_args!("\r\r x[{}] = {:?}\n", i, elem))?;
-            }
-
-            for (i, elem) in self.after_permutation[r].1.iter().enumerate() {
-                f.write_fmt(format_args!("\r \r y[{}] = {:?}\n", i, elem))?;
-            }
-        }
-
-        f.write_fmt(format_args!("output = {:?}\n", self.output))
-    }
-}
-
-/// The structure for the trace of the Anemio-Jive CRH.
-#[derive(Clone, PartialEq)]
-pub struct JiveTrace<F: Scalar, const N: usize, const NUM_ROUNDS: usize> {
-    /// The first half of the input.
-    pub input_x: [F; N],
-    /// The second half of the input.
-    pub input_y: [F; N],
-    /// The first half of the intermediate values in the rounds.
-    pub intermediate_x_before_constant_additions: [[F; N]; NUM_ROUNDS],
-    /// The second half of the intermediate values in the rounds.
-    pub intermediate_y_before_constant_additions: [[F; N]; NUM_ROUNDS],
-    /// The first half of the final output (after the linear layer).
-    pub final_x: [F; N],
-    /// The second half of the final output (after the linear layer).
-    pub final_y: [F; N],
-    /// The output of the Jive CRH.
-    pub output: F,
-}
-
-impl<F: Scalar, const N: usize, const NUM_ROUNDS: usize> Default for JiveTrace<F, N, NUM_ROUNDS> {
-    fn default() -> Self {
-        Self {
-            input_x: [F::default(); N],
-            input_y: [F::default(); N],
-            intermediate_x_before_constant_additions: [[F::default(); N]; NUM_ROUNDS],
-            intermediate_y_before_constant_additions: [[F::default(); N]; NUM_ROUNDS],
-            final_x: [F::default(); N],
-            final_y: [F::default(); N],
-            output: F::default(),
-        }
-    }
-}
-
-impl<F: Scalar, const N: usize, const NUM_ROUNDS: usize> noah_algebra::fmt::Debug
-    for JiveTrace<F, N, NUM_ROUNDS>
-{
-    fn fmt(&self, f: &mut noah_algebra::fmt::Formatter<'_>) -> noah_algebra::fmt::Result {
-        f.write_str("input_x:\n")?;
-        for (i, elem) in self.input_x.iter().enumerate() {
-            f.write_fmt(format_args!("\r x[{}] = {:?}\n", i, elem))?;
-        }
-
-        f.write_str("input_y:\n")?;
-        for (i, elem) in self.input_y.iter().enumerate() {
-            f.write_fmt(format_args!("\r y[{}] = {:?}\n", i, elem))?;
-        }
-
-        for r in 0..NUM_ROUNDS {
-            f.write_fmt(format_args!("round {}:\n", r))?;
-
-            for (i, elem) in self.intermediate_x_before_constant_additions[r]
-                .iter()
-                .enumerate()
-            {
-                f.write_fmt(format_args!("\r x[{}] = {:?}\n", i, elem))?;
-            }
-
-            for (i, elem) in self.intermediate_y_before_constant_additions[r]
-                .iter()
-                .enumerate()
-            {
-                f.write_fmt(format_args!("\r y[{}] = {:?}\n", i, elem))?;
-            }
-        }
-
-        f.write_str("final_x:\n")?;
-        for (i, elem) in self.final_x.iter().enumerate() {
-            f.write_fmt(format_args!("\r x[{}] = {:?}\n", i, elem))?;
-        }
-
-        f.write_str("final_y:\n")?;
-        for (i, elem) in self.final_y.iter().enumerate() {
-            f.write_fmt(format_args!("\r y[{}] = {:?}\n", i, elem))?;
-        }
-
-        f.write_fmt(format_args!("output: {:?}\n", self.output))
-    }
-}
-
-/// The trait for the Anemoi-Jive parameters.
-pub trait AnemoiJive<F: Scalar, const N: usize, const NUM_ROUNDS: usize>
-where
-    MDSMatrix<F, N>: ApplicableMDSMatrix<F, N>,
-{
-    /// The S-Box alpha value.
-    const ALPHA: u32;
-
-    /// The generator of the group.
-    const GENERATOR: F;
-
-    /// Delta, which is the inverse of the generator.
-    const GENERATOR_INV: F;
-
-    /// Used in the MDS. The square of the generator plus one.
-    const GENERATOR_SQUARE_PLUS_ONE: F;
-
-    /// The first group of the round keys.
-    const ROUND_KEYS_X: [[F; N]; NUM_ROUNDS];
-
-    /// The second group of the round keys.
-    const ROUND_KEYS_Y: [[F; N]; NUM_ROUNDS];
-
-    /// The first group of the round keys that have been preprocessed with the MDS.
-    const PREPROCESSED_ROUND_KEYS_X: [[F; N]; NUM_ROUNDS];
-
-    /// The second group of the round keys that have been preprocessed with the MDS.
-    const PREPROCESSED_ROUND_KEYS_Y: [[F; N]; NUM_ROUNDS];
-
-    /// The MDS matrix.
-    const MDS_MATRIX: [[F; N]; N];
-
-    /// Return the inverse of alpha over `r - 1`.
-    fn get_alpha_inv() -> Vec<u64>;
-
-    /// Eval the Anemoi sponge.
-    fn eval_variable_length_hash(input: &[F]) -> F {
-        let mut input = input.to_vec();
-
-        let mds = MDSMatrix::<F, N>(Self::MDS_MATRIX);
-        let alpha_inv = Self::get_alpha_inv();
-
-        let sigma = if input.len() % (2 * N - 1) == 0 && !input.is_empty() {
-            F::one()
-        } else {
-            input.push(F::one());
-            if input.len() % (2 * N - 1) != 0 {
-                input.extend_from_slice(
-                    &[F::zero()].repeat(2 * N - 1 - (input.len() % (2 * N - 1))),
-                );
-            }
-
-            F::zero()
-        };
-
-        // after the previous step, the length of input must be multiplies of `2 * N - 1`.
-        assert_eq!(input.len() % (2 * N - 1), 0);
-
-        // initialize the internal state.
-        let mut x = [F::zero(); N];
-        let mut y = [F::zero(); N];
-        for chunk in input.chunks_exact(2 * N - 1) {
-            for i in 0..N {
-                x[i] += &chunk[i];
-            }
-            for i in 0..(N - 1) {
-                y[i] += &chunk[N + i];
-            }
-
-            for r in 0..NUM_ROUNDS {
-                for i in 0..N {
-                    x[i] += &Self::ROUND_KEYS_X[r][i];
-                    y[i] += &Self::ROUND_KEYS_Y[r][i];
-                }
-                mds.permute_in_place(&mut x, &mut y);
-                for i in 0..N {
-                    x[i] -= &(Self::GENERATOR * &(y[i].square()));
-                    y[i] -= &x[i].pow(&alpha_inv);
-                    x[i] += &(Self::GENERATOR * &(y[i].square()) + Self::GENERATOR_INV);
-                }
-            }
-            mds.permute_in_place(&mut x, &mut y);
-        }
-        y[N - 1] += &sigma;
-        // This step can be omitted since we only get one element.
-        // For formality we keep it here.
-
-        x[0]
-    }
-
-    /// Eval the Anemoi sponge and return the trace.
-    fn eval_variable_length_hash_with_trace(input: &[F]) -> AnemoiVLHTrace<F, N, NUM_ROUNDS> {
-        let mut trace = AnemoiVLHTrace::<F, N, NUM_ROUNDS>::default();
-
-        let mut input = input.to_vec();
-        trace.input = input.clone();
-
-        let mds = MDSMatrix::<F, N>(Self::MDS_MATRIX);
-        let alpha_inv = Self::get_alpha_inv();
-
-        let sigma = if input.len() % (2 * N - 1) == 0 && !input.is_empty() {
-            F::one()
-        } else {
-            input.push(F::one());
-            if input.len() % (2 * N - 1) != 0 {
-                input.extend_from_slice(
-                    &[F::zero()].repeat(2 * N - 1 - (input.len() % (2 * N - 1))),
-                );
-            }
-
-            F::zero()
-        };
-
-        // after the previous step, the length of input must be multiplies of `2 * N - 1`.
-        assert_eq!(input.len() % (2 * N - 1), 0);
-
-        // initialize the internal state.
-        let mut x = [F::zero(); N];
-        let mut y = [F::zero(); N];
-        for chunk in input.chunks_exact(2 * N - 1) {
-            for i in 0..N {
-                x[i] += &chunk[i];
-            }
-            for i in 0..(N - 1) {
-                y[i] += &chunk[N + i];
-            }
-
-            trace.before_permutation.push((x.clone(), y.clone()));
-
-            let mut intermediate_values_before_constant_additions =
-                ([[F::zero(); N]; NUM_ROUNDS], [[F::zero(); N]; NUM_ROUNDS]);
-            for r in 0..NUM_ROUNDS {
-                for i in 0..N {
-                    x[i] += &Self::ROUND_KEYS_X[r][i];
-                    y[i] += &Self::ROUND_KEYS_Y[r][i];
-                }
-                mds.permute_in_place(&mut x, &mut y);
-                for i in 0..N {
-                    x[i] -= &(Self::GENERATOR * &(y[i].square()));
-                    y[i] -= &x[i].pow(&alpha_inv);
-                    x[i] += &(Self::GENERATOR * &(y[i].square()) + Self::GENERATOR_INV);
-                }
-
-                intermediate_values_before_constant_additions.0[r] = x.clone();
-                intermediate_values_before_constant_additions.1[r] = y.clone();
-            }
-
-            mds.permute_in_place(&mut x, &mut y);
-
-            trace
-                .intermediate_values_before_constant_additions
-                .push(intermediate_values_before_constant_additions);
-
-            trace.after_permutation.push((x.clone(), y.clone()));
-        }
-        y[N - 1] += &sigma;
-        // This step can be omitted since we only get one element.
-        // For formality we keep it here.
-
-        trace.output = x[0];
-
-        trace
-    }
-
-    /// Eval the Anemoi-Jive hash function and return the result.
-    fn eval_jive(x: &[F; N], y: &[F; N]) -> F {
-        let mds = MDSMatrix::<F, N>(Self::MDS_MATRIX);
-        let alpha_inv = Self::get_alpha_inv();
-        let sum_before_perm: F = x.iter().sum::<F>() + y.iter().sum::<F>();
-        let mut x = x.clone();
-        let mut y = y.clone();
-        for r in 0..NUM_ROUNDS {
-            for i in 0..N {
-                x[i] += &Self::ROUND_KEYS_X[r][i];
-                y[i] += &Self::ROUND_KEYS_Y[r][i];
-            }
-            mds.permute_in_place(&mut x, &mut y);
-            for i in 0..N {
-                x[i] -= &(Self::GENERATOR * &(y[i].square()));
-                y[i] -= &x[i].pow(&alpha_inv);
-                x[i] += &(Self::GENERATOR * &(y[i].square()) + Self::GENERATOR_INV);
-            }
-        }
-        mds.permute_in_place(&mut x, &mut y);
-        let sum_after_perm: F = x.iter().sum::<F>() + y.iter().sum::<F>();
-        sum_before_perm + sum_after_perm
-    }
-
-    /// Eval the Anemoi-Jive hash function and return the trace of execution,
-    /// which is to be used for creating the zero-knowledge proof.
-    fn eval_jive_with_trace(x: &[F; N], y: &[F; N]) -> JiveTrace<F, N, NUM_ROUNDS> {
-        let mds = MDSMatrix::<F, N>(Self::MDS_MATRIX);
-        let alpha_inv = Self::get_alpha_inv();
-        let mut trace = JiveTrace::default();
-        trace.input_x = x.clone();
-        trace.input_y = y.clone();
-        let mut x = x.clone();
-        let mut y = y.clone();
-        let sum_before_perm: F = x.iter().sum::<F>() + y.iter().sum::<F>();
-        for r in 0..NUM_ROUNDS {
-            for i in 0..N {
-                x[i] += &Self::ROUND_KEYS_X[r][i];
-                y[i] += &Self::ROUND_KEYS_Y[r][i];
-            }
-            mds.permute_in_place(&mut x, &mut y);
-            for i in 0..N {
-                x[i] -= &(Self::GENERATOR * &(y[i].square()));
-                y[i] -= &x[i].pow(&alpha_inv);
-                x[i] += &(Self::GENERATOR * &(y[i].square()) + Self::GENERATOR_INV);
-            }
-            trace.intermediate_x_before_constant_additions[r] = x;
-            trace.intermediate_y_before_constant_additions[r] = y;
-        }
-        mds.permute_in_place(&mut x, &mut y);
-        trace.final_x = x;
-        trace.final_y = y;
-        let sum_after_perm: F = x.iter().sum::<F>() + y.iter().sum::<F>();
-        trace.output = sum_before_perm + sum_after_perm;
-        trace
-    }
-}
+use crate::basic::{anemoi_jive::AnemoiJive, arithmetic_hash::ArithmeticHash};
+use noah_algebra::{bls12_381::BLSScalar, new_bls12_381, prelude::*};
 
 /// The salts for the Anemoi-Jive CRH over BLS12-381.
 /// The salts are generated with the following code.
@@ -966,10 +519,27 @@ impl AnemoiJive<BLSScalar, 2usize, 12usize> for AnemoiJive381 {
     }
 }
 
+impl ArithmeticHash<BLSScalar> for AnemoiJive381 {
+    fn hash(input: &[BLSScalar]) -> BLSScalar {
+        Self::eval_variable_length_hash(input)
+    }
+
+    fn compress(input: &[BLSScalar; 3], salt: &BLSScalar) -> BLSScalar {
+        Self::eval_jive(&[input[0], input[1]], &[input[2], *salt])
+    }
+
+    fn salts() -> &'static [BLSScalar] {
+        &ANEMOI_JIVE_381_SALTS
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use noah_algebra::new_bls12_381;
+    use crate::basic::anemoi_jive::{
+        test::{reference_jive, reference_sponge},
+        ApplicableMDSMatrix, MDSMatrix,
+    };
 
     #[test]
     fn test_jive() {
@@ -987,6 +557,31 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_reference() {
+        type F = BLSScalar;
+
+        // The vectors of `test_jive` and `test_anemoi_variable_length_hash` come from the
+        // upstream implementation, so they also pin down the reference evaluation that the
+        // other parameter sets are checked against. The S-box uses alpha = 5.
+        let input_x = [F::from(1u64), F::from(2u64)];
+        let input_y = [F::from(3u64), F::zero()];
+        assert_eq!(
+            reference_jive(&AnemoiJive381::GENERATOR, 5, 12, &input_x, &input_y),
+            new_bls12_381!(
+                "40534080031161498828112599909199108154146698842441932527619782321134903095510"
+            )
+        );
+
+        let input = [F::from(1u64), F::from(2u64), F::from(3u64), F::from(4u64)];
+        assert_eq!(
+            reference_sponge(&AnemoiJive381::GENERATOR, 5, 12, &input),
+            new_bls12_381!(
+                "17913626440896376279858183231538520765146521393387279167163788217724133906091"
+            )
+        );
+    }
+
     #[test]
     fn test_jive_flatten() {
         type F = BLSScalar;
@@ -0,0 +1,690 @@
+use crate::basic::{anemoi_jive::AnemoiJive, arithmetic_hash::ArithmeticHash};
+use noah_algebra::{bn254::BN254Scalar, new_bn254, prelude::*};
+
+/// The salts for the Anemoi-Jive CRH over BN254, which are generated by
+/// `generate_salts` in the documentation of `ANEMOI_JIVE_381_SALTS`
+/// with the generator 5 and alpha 5.
+pub const ANEMOI_JIVE_BN254_SALTS: [BN254Scalar; 64] = [
+    new_bn254!("37"),
+    new_bn254!("3777855693073962033611587937807108737167033657803496141368113046129255982603"),
+    new_bn254!("14490919371381369054680874586716963323926090070511910538885431569344412350452"),
+    new_bn254!("16670097252553418005815728781802959460291411760810299689429023500311632791335"),
+    new_bn254!("16717379854632012655607381652663314246114551507479390385196592239720945852180"),
+    new_bn254!("12071600730865452114023475823590873955430804225078335397160052777026968961071"),
+    new_bn254!("4256004474481609378341833061101749564588432615716830444002527921147763597737"),
+    new_bn254!("5454811177686034905898492547539105787826280069929406838396763676570237850580"),
+    new_bn254!("8630065346525952681213945640391264055464975319520157715000642706175863366382"),
+    new_bn254!("2554211419701916024532024609828046337558052699564647262882447219303301898156"),
+    new_bn254!("5975734742700733153396359360989994275966055028827882459551075207850407851308"),
+    new_bn254!("4654023160227518516005893591666282208989300554123929836166574590199082518508"),
+    new_bn254!("18473613170402255598870381246325461934362309559057065723771284783812688319613"),
+    new_bn254!("16517805415991796677963187756312186901747648378135583475220998955174753071103"),
+    new_bn254!("5500947239035915839035079725858963303489516864003579654309523189401046959982"),
+    new_bn254!("2805662141189531519782511219728172549701794098700903894038565833645809371673"),
+    new_bn254!("20690982030401622419930553572718604282323548095415008965865285616569605608704"),
+    new_bn254!("9712470477347620848833467878997024441077327414003268612663683682881607394351"),
+    new_bn254!("12414073616534302671121569710134040147777330841310023565750379891595743629418"),
+    new_bn254!("8461832263522199092526503423015278491602995143543508453801462744020236987567"),
+    new_bn254!("10745791243326076467928800881719039711879158698056291222094529674206309577955"),
+    new_bn254!("14617181974975523589800467893076894047486446647314814491610148483622103831031"),
+    new_bn254!("7577395954455086684956049232019325749652899597841125962312479202721824617812"),
+    new_bn254!("8149110423042622698047909520241716015480999406917086328049844611938563397439"),
+    new_bn254!("10861486844520951627065628867050257294258464120781773580330233016134344675202"),
+    new_bn254!("4432851336393125067628085925701725636460585346354148687153585414170655808862"),
+    new_bn254!("11195494190156852947180072890574073259787734926918821746380352120198323516408"),
+    new_bn254!("19420779908746903593040459478616262166411537593368837155869400054348236743015"),
+    new_bn254!("14991396972015645006660041903143721685632129583470414984257411663818424977483"),
+    new_bn254!("14168836201010018972854974539714867435039552676569659648973564158978405393858"),
+    new_bn254!("14352638956861626513603408761152503968430740205421555645077410221965661907841"),
+    new_bn254!("2518792532922431785433980072184804896230465164568304408871367783301614939715"),
+    new_bn254!("21455678085824721542727670634637261056721969181246493938206462421535621201226"),
+    new_bn254!("1674640704608146927010770731194013960503295756380603315515964907104420365276"),
+    new_bn254!("6334227354539831046943535700480376512783864369248690653376167263056375104050"),
+    new_bn254!("6218714504489277490537905605251654443683505129387522586020413839031261791885"),
+    new_bn254!("19575779027660211605520694451543831467119759402721657939126561047092441390685"),
+    new_bn254!("18431446141223283833030738837266301979710599602122660629633478108948274444418"),
+    new_bn254!("16385570289114667006072879038290397021774332408341060650257843556015599149300"),
+    new_bn254!("13588791908041385683330387221155636823405362894113635325111076511560863915262"),
+    new_bn254!("20455666466268344468894331464605644640798531431037493678901218161783532715444"),
+    new_bn254!("10861418363703477682010892334039435427663957124378379515056568821687607059985"),
+    new_bn254!("2533132810002375426021345659401941444065360940913043122282841621446525208198"),
+    new_bn254!("12754562389266703661330773241122708438537602068374460959449469798212735816548"),
+    new_bn254!("14905114639075195763853446805464366267746116569317601106460437547977193341691"),
+    new_bn254!("17882626063786511135183659581842576030659207135524083482076705965415738055937"),
+    new_bn254!("292422322237296518473104950465068477868024137555725730787874537617260585359"),
+    new_bn254!("6170733468327063132067569238288447460159635670697937869284023139037692152549"),
+    new_bn254!("6136797132392540775170705740767611675769561215278774066838010119948952777860"),
+    new_bn254!("5738195123555108125870732218482038565320159253413311151333621440609796443947"),
+    new_bn254!("13028540923732753601184629053461888512910667346183863597541892652451320423643"),
+    new_bn254!("3461400185508988111638915123934839361981511659906498686363678069808162748030"),
+    new_bn254!("14451551197591884557585655124678889231281711138814256468598437393112192234837"),
+    new_bn254!("21698735172979564433445758597625456978523064500914114621608616013059485089062"),
+    new_bn254!("20823758027079751687512562798042056556826192541322762117978373280507270820842"),
+    new_bn254!("11367590375644781487414888877641921310304435567985539285684488387353600094303"),
+    new_bn254!("3098536331827013293317104600573400718100773736195441899628902094437767778695"),
+    new_bn254!("20736679058344046064235545310354217218936324920984789262274961911663742432379"),
+    new_bn254!("19285703786498840620698554614562948284688084796106404034848257485794989762391"),
+    new_bn254!("4374025554311164436953653929039749682752816872680020632185773549625619854957"),
+    new_bn254!("4453344013698531017910282766460287295312359699306790767839750544884747513529"),
+    new_bn254!("7668521819988825365281876447168872781610061628989235142433612133300164497673"),
+    new_bn254!("10345662093150473143126913463831103701358169977652336684907332814878226807106"),
+    new_bn254!("5040237000891632757118530440085851629455244755304816341110897331297466039957"),
+];
+
+/// The structure that stores the parameters for the Anemoi-Jive hash function for BN254.
+pub struct AnemoiJive254;
+
+impl AnemoiJive<BN254Scalar, 2usize, 14usize> for AnemoiJive254 {
+    const ALPHA: u32 = 5u32;
+    const GENERATOR: BN254Scalar = new_bn254!("5");
+    const GENERATOR_INV: BN254Scalar =
+        new_bn254!("8755297148735710088898562298102910035419345760166413737479281674630323398247");
+    const GENERATOR_SQUARE_PLUS_ONE: BN254Scalar = new_bn254!("26");
+    const ROUND_KEYS_X: [[BN254Scalar; 2usize]; 14usize] = [
+        [
+            new_bn254!("37"),
+            new_bn254!(
+                "3751828524803055471428227881618625174556947755988347881191159153764975591158"
+            ),
+        ],
+        [
+            new_bn254!(
+                "13352247125433170118601974521234241686699252132838635793584252509352796067497"
+            ),
+            new_bn254!(
+                "21001839722121566863419881512791069124083822968210421491151340238400176843969"
+            ),
+        ],
+        [
+            new_bn254!(
+                "8959866518978803666083663798535154543742217570455117599799616562379347639707"
+            ),
+            new_bn254!(
+                "21722442537234642741320951134727484119993387379465291657407115605240150584902"
+            ),
+        ],
+        [
+            new_bn254!(
+                "3222831896788299315979047232033900743869692917288857580060845801753443388885"
+            ),
+            new_bn254!(
+                "5574110054747610058729632355948568604793546392090976147435879266833412620404"
+            ),
+        ],
+        [
+            new_bn254!(
+                "11437915391085696126542499325791687418764799800375359697173212755436799377493"
+            ),
+            new_bn254!(
+                "19347108854758320361854968987183753113398822331033233961719129079198795045322"
+            ),
+        ],
+        [
+            new_bn254!(
+                "14725846076402186085242174266911981167870784841637418717042290211288365715997"
+            ),
+            new_bn254!(
+                "17733032409684964025894538244134113560864261458948810209753406163729963104066"
+            ),
+        ],
+        [
+            new_bn254!(
+                "3625896738440557179745980526949999799504652863693655156640745358188128872126"
+            ),
+            new_bn254!(
+                "16641102106808059030810525726117803887885616319153331237086309361060282564245"
+            ),
+        ],
+        [
+            new_bn254!(
+                "463291105983501380924034618222275689104775247665779333141206049632645736639"
+            ),
+            new_bn254!(
+                "9245970744804222215259369270991414441925747897718226734085751033703871913242"
+            ),
+        ],
+        [
+            new_bn254!(
+                "17443852951621246980363565040958781632244400021738903729528591709655537559937"
+            ),
+            new_bn254!(
+                "18243401795478654990110719981452738859015913555820749188627866268359980949315"
+            ),
+        ],
+        [
+            new_bn254!(
+                "10761214205488034344706216213805155745482379858424137060372633423069634639664"
+            ),
+            new_bn254!(
+                "18200337361605220875540054729693479452916227111908726624753615870884702413869"
+            ),
+        ],
+        [
+            new_bn254!(
+                "1555059412520168878870894914371762771431462665764010129192912372490340449901"
+            ),
+            new_bn254!(
+                "5239065275003145843160321807696531775964858360555566589197008236687533209496"
+            ),
+        ],
+        [
+            new_bn254!(
+                "7985258549919592662769781896447490440621354347569971700598437766156081995625"
+            ),
+            new_bn254!(
+                "9376351072866485300578251734844671764089160611668390200194570180225759013543"
+            ),
+        ],
+        [
+            new_bn254!(
+                "9570976950823929161626934660575939683401710897903342799921775980893943353035"
+            ),
+            new_bn254!(
+                "6407880900662180043240104510114613236916437723065414158006054747177494383655"
+            ),
+        ],
+        [
+            new_bn254!(
+                "17962366505931708682321542383646032762931774796150042922562707170594807376009"
+            ),
+            new_bn254!(
+                "6245130621382842925623937534683990375669631277871468906941032622563934866013"
+            ),
+        ],
+    ];
+    const ROUND_KEYS_Y: [[BN254Scalar; 2usize]; 14usize] = [
+        [
+            new_bn254!(
+                "8755297148735710088898562298102910035419345760166413737479281674630323398284"
+            ),
+            new_bn254!(
+                "16133435893292874812888083849160666046321318009323051176910097996974633748758"
+            ),
+        ],
+        [
+            new_bn254!(
+                "5240474505904316858775051800099222288270827863409873986701694203345984265770"
+            ),
+            new_bn254!(
+                "16516377322346822856154252461095180562000423191949949242508439100972699801595"
+            ),
+        ],
+        [
+            new_bn254!(
+                "9012679925958717565787111885188464538194947839997341443807348023221726055342"
+            ),
+            new_bn254!(
+                "3513323292129390671339287145562649862242777741759770715956300048086055264273"
+            ),
+        ],
+        [
+            new_bn254!(
+                "21855834035835287540286238525800162342051591799629360593177152465113152235615"
+            ),
+            new_bn254!(
+                "5945179541709432313351711573896685950772105367183734375093638912196647730870"
+            ),
+        ],
+        [
+            new_bn254!(
+                "11227229470941648605622822052481187204980748641142847464327016901091886692935"
+            ),
+            new_bn254!(
+                "874490282529106871250179638055108647411431264552976943414386206857408624500"
+            ),
+        ],
+        [
+            new_bn254!(
+                "8277823808153992786803029269162651355418392229624501612473854822154276610437"
+            ),
+            new_bn254!(
+                "14911320361190879980016686915823914584756893340104182663424627943175208757859"
+            ),
+        ],
+        [
+            new_bn254!(
+                "20904607884889140694334069064199005451741168419308859136555043894134683701950"
+            ),
+            new_bn254!(
+                "15657880601171476575713502187548665287918791967520790431542060879010363657805"
+            ),
+        ],
+        [
+            new_bn254!(
+                "1902748146936068574869616392736208205391158973416079524055965306829204527070"
+            ),
+            new_bn254!(
+                "14311738005510898661766244714944477794557156116636816483240167459479765463026"
+            ),
+        ],
+        [
+            new_bn254!(
+                "14452570815461138929654743535323908350592751448372202277464697056225242868484"
+            ),
+            new_bn254!(
+                "18878429879072656191963192145256996413709289475622337294803628783509021017215"
+            ),
+        ],
+        [
+            new_bn254!(
+                "10548134661912479705005015677785100436776982856523954428067830720054853946467"
+            ),
+            new_bn254!(
+                "21613568037783775488400147863112554980555854603176833550688470336449256480025"
+            ),
+        ],
+        [
+            new_bn254!(
+                "17068729307795998980462158858164249718900656779672000551618940554342475266265"
+            ),
+            new_bn254!(
+                "2490802518193809975066473675670874471230712567215812226164489400543194289596"
+            ),
+        ],
+        [
+            new_bn254!(
+                "16199718037005378969178070485166950928725365516399196926532630556982133691321"
+            ),
+            new_bn254!(
+                "21217120779706380859547833993003263088538196273665904984368420139631145468592"
+            ),
+        ],
+        [
+            new_bn254!(
+                "19148564379197615165212957504107910110246052442686857059768087896511716255278"
+            ),
+            new_bn254!(
+                "19611778548789975299387421023085714500105803761017217976092023831374602045251"
+            ),
+        ],
+        [
+            new_bn254!(
+                "5497141763311860520411283868772341077137612389285480008601414949457218086902"
+            ),
+            new_bn254!(
+                "19294458970356379238521378434506704614768857764591229894917601756581488831876"
+            ),
+        ],
+    ];
+    const PREPROCESSED_ROUND_KEYS_X: [[BN254Scalar; 2usize]; 14usize] = [
+        [
+            new_bn254!(
+                "18759142624015277357141139408093125872784738779941739405955795768824877955827"
+            ),
+            new_bn254!(
+                "9994570157522341368148301941055154184287184054032907536177321251586131387825"
+            ),
+        ],
+        [
+            new_bn254!(
+                "8920231376844628324469353358903211864376544971810571530849932768474637809257"
+            ),
+            new_bn254!(
+                "21826510862666158041273836816792578268869819026431210458004595707621748899020"
+            ),
+        ],
+        [
+            new_bn254!(
+                "8130864845955641261456390745886199700967332465701404168344173655701058086132"
+            ),
+            new_bn254!(
+                "18600281023334298604110093373643932447733320907140243811731575510593824024328"
+            ),
+        ],
+        [
+            new_bn254!(
+                "9205139298687074387380803266519468679289060477327703973542037949344697995288"
+            ),
+            new_bn254!(
+                "7823320804504431551140837198031361824142119977897427327749660640405285605610"
+            ),
+        ],
+        [
+            new_bn254!(
+                "20620488177520197046831721280681352631565453853877392130976041405127540621635"
+            ),
+            new_bn254!(
+                "13008335383162929484781546664304140828484269598340022898108315171957455675412"
+            ),
+        ],
+        [
+            new_bn254!(
+                "15838036637469905325729242506553448617998634534717332391016504283634947253859"
+            ),
+            new_bn254!(
+                "9370244109677389765555127795872256296663976530871334790043110835601465390893"
+            ),
+        ],
+        [
+            new_bn254!(
+                "21166678656963026667059391921767193973287641258212208310977679603762116206500"
+            ),
+            new_bn254!(
+                "13033281032426816254875456608667398311582000608134201073483686446991821118660"
+            ),
+        ],
+        [
+            new_bn254!(
+                "2916659086326062012728069482664797721636785935424844316173552845000388311615"
+            ),
+            new_bn254!(
+                "1941023304595257056653310939058127961561313174426413971255311072130004975700"
+            ),
+        ],
+        [
+            new_bn254!(
+                "21107890441657421041931541967193375573130510199178512297875106305152208324044"
+            ),
+            new_bn254!(
+                "14341639644569384088536401091133241281926642549633138959512376861241980091450"
+            ),
+        ],
+        [
+            new_bn254!(
+                "14209929526157037833420866881243452655870057816303632809347896031189912726541"
+            ),
+            new_bn254!(
+                "1697013505033309153658766154881642378073058591762753296700279280531032064106"
+            ),
+        ],
+        [
+            new_bn254!(
+                "5862142915696622872426098207597146562707390068125808731479749369352198001764"
+            ),
+            new_bn254!(
+                "12661536981646984983044407100424989500953444300768575902897550896872714722699"
+            ),
+        ],
+        [
+            new_bn254!(
+                "11090528170573468721168229080156299083970428605079854014174880294133260072106"
+            ),
+            new_bn254!(
+                "21052506182055278461926585645111617006844574836235591583672563277740442382839"
+            ),
+        ],
+        [
+            new_bn254!(
+                "19722138582295554155581051465891730779435535112814379246253845530205606775693"
+            ),
+            new_bn254!(
+                "17465602324782849932159738858544166779900655685473173014482465651902294279652"
+            ),
+        ],
+        [
+            new_bn254!(
+                "5411533869167372865948418566551434464183202384675318769871461910262864714840"
+            ),
+            new_bn254!(
+                "11414557095380432033119624622183887608037278800832028412600137987302449944596"
+            ),
+        ],
+    ];
+    const PREPROCESSED_ROUND_KEYS_Y: [[BN254Scalar; 2usize]; 14usize] = [
+        [
+            new_bn254!(
+                "16133435893292874812888083849160666046321318009323051176910097996974633748944"
+            ),
+            new_bn254!(
+                "1869505127842983264353358562877139912832478205117532247236954913200258160536"
+            ),
+        ],
+        [
+            new_bn254!(
+                "20830506980029131927783105716334016914806198108583284832318705931126812634828"
+            ),
+            new_bn254!(
+                "21840037918692875608704957400740206508108360804662160773502407112676813457442"
+            ),
+        ],
+        [
+            new_bn254!(
+                "4800237178244428055782035080990422376120788140914409247596631791043068549749"
+            ),
+            new_bn254!(
+                "11125622945341582622450881544883301330250524144153353338092302791861260308470"
+            ),
+        ],
+        [
+            new_bn254!(
+                "5783135361689493903550875476611122218288242363250365622488380304883366430860"
+            ),
+            new_bn254!(
+                "6995025100604206613547804418341223256396074815049120018222645616378367398681"
+            ),
+        ],
+        [
+            new_bn254!(
+                "13234151893558799454871478409946494495218445669435145577653062339165225097941"
+            ),
+            new_bn254!(
+                "11733260323217820213240996866441834415427883787070472321497716037190586695789"
+            ),
+        ],
+        [
+            new_bn254!(
+                "12523953658282293469539021771122621184752125687394622038397493680794974818810"
+            ),
+            new_bn254!(
+                "5232863484047634467758920889003932013533927465349508773366710666401725217636"
+            ),
+        ],
+        [
+            new_bn254!(
+                "10739705666420803936151818782257317103882812061984914395826259416804739689470"
+            ),
+            new_bn254!(
+                "8938407601475334708353945739713765705510135527985328084591728418430956662449"
+            ),
+        ],
+        [
+            new_bn254!(
+                "1937235868351966313867920933368243732964586583301179759821789807049979602759"
+            ),
+            new_bn254!(
+                "11588927488695900144209221059577426870214091889921978323164914342079102540865"
+            ),
+        ],
+        [
+            new_bn254!(
+                "3588312469021249951251286840847437812479589115819211307334297318332001377167"
+            ),
+            new_bn254!(
+                "10505890288728113463664771994303822324442332627052224470437979461309441258702"
+            ),
+        ],
+        [
+            new_bn254!(
+                "8689512731828348346686009016266231898795675684548502659933011376996100725509"
+            ),
+            new_bn254!(
+                "10219212577375670993942249268601709753658632478434399040336479231883740582778"
+            ),
+        ],
+        [
+            new_bn254!(
+                "281477569816703988391644985463022711540538863911677609466375425952336638453"
+            ),
+            new_bn254!(
+                "18476117156879518922420383785479363276603351099230388598950817684104158458530"
+            ),
+        ],
+        [
+            new_bn254!(
+                "14662739477376174816452563437808917377971566253997752242238756178238579942729"
+            ),
+            new_bn254!(
+                "1960443936529152162455264693182437464389739184723820762933594701871799422498"
+            ),
+        ],
+        [
+            new_bn254!(
+                "5913386085581675014220179817338889608594243972371331556441442381054140843556"
+            ),
+            new_bn254!(
+                "4939009063427439791821045100287807976120543503711446154578891428630803481824"
+            ),
+        ],
+        [
+            new_bn254!(
+                "3003682043237131396084986287853859823360190910186561250528268130715962275152"
+            ),
+            new_bn254!(
+                "20515551979497517500836215308041640193938566940218286261242755603037029462662"
+            ),
+        ],
+    ];
+    const MDS_MATRIX: [[BN254Scalar; 2usize]; 2usize] = [
+        [new_bn254!("1"), new_bn254!("5")],
+        [new_bn254!("5"), new_bn254!("26")],
+    ];
+    fn get_alpha_inv() -> Vec<u64> {
+        vec![
+            14981214993055009997u64,
+            6006880321387387405u64,
+            10624953561019755799u64,
+            2789598613442376532u64,
+        ]
+    }
+}
+
+impl ArithmeticHash<BN254Scalar> for AnemoiJive254 {
+    fn hash(input: &[BN254Scalar]) -> BN254Scalar {
+        Self::eval_variable_length_hash(input)
+    }
+
+    fn compress(input: &[BN254Scalar; 3], salt: &BN254Scalar) -> BN254Scalar {
+        Self::eval_jive(&[input[0], input[1]], &[input[2], *salt])
+    }
+
+    fn salts() -> &'static [BN254Scalar] {
+        &ANEMOI_JIVE_BN254_SALTS
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::basic::anemoi_jive::{
+        test::{generate_round_keys, get_n_rounds, reference_jive, reference_sponge},
+        ApplicableMDSMatrix, MDSMatrix,
+    };
+    use ark_std::test_rng;
+
+    #[test]
+    fn test_parameters() {
+        type F = BN254Scalar;
+
+        assert_eq!(
+            AnemoiJive254::GENERATOR.mul(&AnemoiJive254::GENERATOR_INV),
+            F::one()
+        );
+
+        let mds = MDSMatrix::<F, 2>(AnemoiJive254::MDS_MATRIX);
+        for r in 0..14 {
+            let (x, y) = mds.permute(
+                &AnemoiJive254::ROUND_KEYS_X[r],
+                &AnemoiJive254::ROUND_KEYS_Y[r],
+            );
+            assert_eq!(x, AnemoiJive254::PREPROCESSED_ROUND_KEYS_X[r]);
+            assert_eq!(y, AnemoiJive254::PREPROCESSED_ROUND_KEYS_Y[r]);
+        }
+
+        let mut prng = test_rng();
+        let x = F::random(&mut prng);
+        assert_eq!(
+            x.pow(&AnemoiJive254::get_alpha_inv())
+                .pow(&[AnemoiJive254::ALPHA as u64]),
+            x
+        );
+    }
+
+    #[test]
+    fn test_round_keys() {
+        // 128-bit security with two columns.
+        let num_rounds = get_n_rounds(128, 2, AnemoiJive254::ALPHA);
+        assert_eq!(num_rounds, AnemoiJive254::ROUND_KEYS_X.len());
+
+        let (round_keys_x, round_keys_y) =
+            generate_round_keys(&AnemoiJive254::GENERATOR, AnemoiJive254::ALPHA, num_rounds);
+        assert_eq!(round_keys_x, AnemoiJive254::ROUND_KEYS_X.to_vec());
+        assert_eq!(round_keys_y, AnemoiJive254::ROUND_KEYS_Y.to_vec());
+    }
+
+    #[test]
+    fn test_reference() {
+        type F = BN254Scalar;
+
+        let num_rounds = get_n_rounds(128, 2, AnemoiJive254::ALPHA);
+        let mut prng = test_rng();
+        for _ in 0..4 {
+            let input_x = [F::random(&mut prng), F::random(&mut prng)];
+            let input_y = [F::random(&mut prng), F::random(&mut prng)];
+            assert_eq!(
+                AnemoiJive254::eval_jive(&input_x, &input_y),
+                reference_jive(
+                    &AnemoiJive254::GENERATOR,
+                    AnemoiJive254::ALPHA,
+                    num_rounds,
+                    &input_x,
+                    &input_y
+                )
+            );
+        }
+
+        for len in 0..7 {
+            let input = (0..len).map(|_| F::random(&mut prng)).collect::<Vec<F>>();
+            assert_eq!(
+                AnemoiJive254::eval_variable_length_hash(&input),
+                reference_sponge(
+                    &AnemoiJive254::GENERATOR,
+                    AnemoiJive254::ALPHA,
+                    num_rounds,
+                    &input
+                )
+            );
+        }
+    }
+
+    // No published vectors exist for this parameter set. The expected values below come from
+    // the reference evaluation of `test_reference`, which reproduces the upstream BLS12-381
+    // vectors, and were also recomputed with an independent script of the same construction.
+    #[test]
+    fn test_jive() {
+        type F = BN254Scalar;
+
+        let input_x = [F::from(1u64), F::from(2u64)];
+        let input_y = [F::from(3u64), F::zero()];
+
+        let res = AnemoiJive254::eval_jive(&input_x, &input_y);
+        assert_eq!(
+            res,
+            new_bn254!(
+                "19315354256873030487823203884076373987694824762855461096017001558868432247249"
+            )
+        );
+    }
+
+    #[test]
+    fn test_anemoi_variable_length_hash() {
+        type F = BN254Scalar;
+
+        let input = [F::from(1u64), F::from(2u64), F::from(3u64), F::from(4u64)];
+
+        let res = AnemoiJive254::eval_variable_length_hash(&input);
+        assert_eq!(
+            res,
+            new_bn254!(
+                "14132911640816233289648720749201512696123112308175434480483807321253606968575"
+            )
+        );
+
+        let res = AnemoiJive254::eval_variable_length_hash(&[]);
+        assert_eq!(
+            res,
+            new_bn254!(
+                "7090554983020898809033388485747754731319819811564541499009874653671823219386"
+            )
+        );
+    }
+}
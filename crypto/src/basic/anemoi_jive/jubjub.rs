@@ -0,0 +1,693 @@
+use crate::basic::{anemoi_jive::AnemoiJive, arithmetic_hash::ArithmeticHash};
+use noah_algebra::{jubjub::JubjubScalar, new_jubjub, prelude::*};
+
+/// The salts for the Anemoi-Jive CRH over the scalar field of Jubjub, which are generated by
+/// `generate_salts` in the documentation of `ANEMOI_JIVE_381_SALTS`
+/// with the generator 6 and alpha 5.
+pub const ANEMOI_JIVE_JUBJUB_SALTS: [JubjubScalar; 64] = [
+    new_jubjub!("38"),
+    new_jubjub!("291277374985100385304035360869014463735323758788581932994895862862177130171"),
+    new_jubjub!("1031264317429058285297725720355218754662836805843790365423275027940263182342"),
+    new_jubjub!("3519123241922143246489612237032690219268589382276021683277641486842340893829"),
+    new_jubjub!("2032190708518696414669350946482821408889475110314710732065263121649677384056"),
+    new_jubjub!("3853424591277144965093984422982893573520864375997182495458857859743862211856"),
+    new_jubjub!("1633539903184876503728881914233014956035191943315543148307527982919203277835"),
+    new_jubjub!("5969980382006961774768661808386732001796473568557701834741772478347895005998"),
+    new_jubjub!("4209403292815512048236935241997773342233922730402496047437498716069840685909"),
+    new_jubjub!("1720497016079373485288298285851913570742017046130395367602060988457423466013"),
+    new_jubjub!("6286703263519288576501242302803030237226236433904683104109704230871717031221"),
+    new_jubjub!("2129546024648114983561557415280470840531421440265912288484903155696616010385"),
+    new_jubjub!("3109097638498148859299176935861867571833166714147383507827688354264198961755"),
+    new_jubjub!("5446888800366408114468082179237505742484014499151111750741436453468315447845"),
+    new_jubjub!("1770754784074489670960403644549128308914117348999176906276547843775729158160"),
+    new_jubjub!("2058339204157897944789745321342677544819148089099527595981364127430334706328"),
+    new_jubjub!("1112283072720267622496398091956458059423655114831811410700312156088423878188"),
+    new_jubjub!("3249337168492243785469217773034716245276472839682228807364383873931305154312"),
+    new_jubjub!("3991646928135871695066121347881588883615347587067982780560612808419790879739"),
+    new_jubjub!("6415450649682796318929116843153244368500545118804125946832625981629083468416"),
+    new_jubjub!("4515484308652741535405059600528895828009171724408572281370830381260245681182"),
+    new_jubjub!("1120439520754498604220639815515782613733656432240607553896885140374609094481"),
+    new_jubjub!("5846971181027652466638885095042803120891603310305025526572523220793950758146"),
+    new_jubjub!("984817660684100840357858718785105294478936677351875359173544611306078861463"),
+    new_jubjub!("1857033536683884745033313193795583946043009488751290097746713906623651201840"),
+    new_jubjub!("4076583981017221489779148821488847474461253712172048492211914525723667175225"),
+    new_jubjub!("334576874888088185974028707704887815137723716632020921114121225470070546934"),
+    new_jubjub!("3679190995755357320500694861812863075250539403980269187550547520629665922162"),
+    new_jubjub!("2515202562090498139240199795768217228724931276714562431582760430486679398666"),
+    new_jubjub!("3382523860244577696587769950997244056919985762634100841220633857204057184151"),
+    new_jubjub!("3920076299835229813154818550228175461353341691497660129212330927423316311893"),
+    new_jubjub!("5217463953049031884066376865735346431155285004441510200070220069652205671539"),
+    new_jubjub!("3241349137991169967855336712970201516241615881411119546486853712869545468605"),
+    new_jubjub!("6529595627714385182260387851719438831679673981572191449490256851906928310731"),
+    new_jubjub!("1593873547718072889123128352005458652925056232042909317728852467225086502260"),
+    new_jubjub!("1601868014673696288921475684427623755731277651832181446655970656204294709120"),
+    new_jubjub!("2230270518561297508925270316194040046846866923856294291493338613418929725769"),
+    new_jubjub!("4580813067964709799425212403821853412368514246040615312167832613417145742740"),
+    new_jubjub!("4321319851482060009592589184809000721577659076022900361908474174047445936221"),
+    new_jubjub!("117825875362675642751307232836359811004045754867439160051850413783492503806"),
+    new_jubjub!("564437062230288258560191599233026508691645223060723491028251914476050055427"),
+    new_jubjub!("4548952771158695556271281636808535949369903306561092214846882359264616739108"),
+    new_jubjub!("3022577032736846433739429856048026360749195564841027933291598782750604004331"),
+    new_jubjub!("5755140955356659180152406170520554596091138642798043046426044013658349531890"),
+    new_jubjub!("5766817890092484794741817752132550280988946429613470940033578019551817892262"),
+    new_jubjub!("3543720338096099703813077964068072381017694322874010670351548057267022364648"),
+    new_jubjub!("606913157847501211440733058717416691824407228922111269417035157520993977642"),
+    new_jubjub!("5560270282807908205017035118494092603766818696726636816213720206263040128"),
+    new_jubjub!("3199229118171031350512392011129542496466398354165270874969835877616018693976"),
+    new_jubjub!("3655974450630324046725545931868706477204670685818838162934501359301911444310"),
+    new_jubjub!("6114558907224099565012460977173934448689900014245171960014405157817397633250"),
+    new_jubjub!("5631437909567287962592129023579866776273451888589725345972626187195696608023"),
+    new_jubjub!("6496956940738920138588031763006569143844517893328711696438079807201720714265"),
+    new_jubjub!("633051614210660441791730939399711577209550725852690450168036903732107315909"),
+    new_jubjub!("1436499999313293218077686139369970003329408444994999878399371966718288704724"),
+    new_jubjub!("1489504828271923207438963226877264958524589225836428159656418430056255752421"),
+    new_jubjub!("5676888062202218126517414486348327885362348662848639322930173574418735194134"),
+    new_jubjub!("6281400597461893350898468161368961079717288494178258653816109345462768350120"),
+    new_jubjub!("4378459934140529565510548676105680900682438969943124016741966239025126210386"),
+    new_jubjub!("5449716924144944963690319656525299054372591066061928025556513876795214114696"),
+    new_jubjub!("330064820283554607490513904745424329666947794495950310457992934666155528979"),
+    new_jubjub!("1646544625941760772257585114943092485671531741462983067196519993792663943273"),
+    new_jubjub!("4222853778308601265795154499092890103194560827216825984832168949531634018227"),
+    new_jubjub!("4007342797169421230440337763344842089517892160468036288405707918068686606535"),
+];
+
+/// The structure that stores the parameters for the Anemoi-Jive hash function for the scalar field of Jubjub.
+pub struct AnemoiJiveJubjub;
+
+impl AnemoiJive<JubjubScalar, 2usize, 14usize> for AnemoiJiveJubjub {
+    const ALPHA: u32 = 5u32;
+    const GENERATOR: JubjubScalar = new_jubjub!("6");
+    const GENERATOR_INV: JubjubScalar =
+        new_jubjub!("5462070330742311508275806302936038108088267721560264401137799301993486045166");
+    const GENERATOR_SQUARE_PLUS_ONE: JubjubScalar = new_jubjub!("37");
+    const ROUND_KEYS_X: [[JubjubScalar; 2usize]; 14usize] = [
+        [
+            new_jubjub!("38"),
+            new_jubjub!(
+                "5077103235950770377844097421991334891239043083902702827840265730460009142254"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "2533517095545019850545746279467065429090678169954508105887341456663436413503"
+            ),
+            new_jubjub!(
+                "3859272906883181496181130762249757696441619774003130837492771427194753411071"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "1096524037434010271106732431704137274931701190595151338790689150206953842281"
+            ),
+            new_jubjub!(
+                "4549850573405591623342794541368737344790178340837829309220798683848154585160"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "3917946359928798331055819637028677079567256186338731131536320581544141829789"
+            ),
+            new_jubjub!(
+                "381528430663747360791020976487131472678165629000918320082938341402701491341"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "5969225108887428746085494822410339612175937059146351322464826077359911039388"
+            ),
+            new_jubjub!(
+                "370102281635926418871791525027109967281885037421662493450715286851711349954"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "6060224002937010193788818799431331604497659782647124551595372963038127401868"
+            ),
+            new_jubjub!(
+                "5056859214972227127982714974829919105743289666377372057191662916673757326183"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "5150174233558714028652565588602359240995202387741458400934668414385307932015"
+            ),
+            new_jubjub!(
+                "4197834324178389645342114798220530491698960188047806380396503285803758351971"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "1761467937698564281718106990557293414757611267849287168348503800670506985050"
+            ),
+            new_jubjub!(
+                "4525881779050109907981175307882567809495006979801498843663940485949207839426"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "5347298842860603907154521566868959269885668184947025634301744876278524875515"
+            ),
+            new_jubjub!(
+                "2545978408592471234360740225896384658580076721745852713698415185884484353924"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "1450538068151920716002499657981411462796794286755073703753142006040356122518"
+            ),
+            new_jubjub!(
+                "5136014416421404593628270076695996472805741452415547138304642310837838330035"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "5792797810195111261996346139795159554623084441779895037425766667614414070566"
+            ),
+            new_jubjub!(
+                "2595005723697774016728900837081653270895824052233414524890489506494027611065"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "5868376309605361954418087853047904840561015472362238655588805794153667022654"
+            ),
+            new_jubjub!(
+                "78142542505791734213209488126891254220863826152998497680212282599181387082"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "4995518990284305417072636079452943306060911499468137051216611394439108600411"
+            ),
+            new_jubjub!(
+                "253255572519146152214862014440022336366081693244912783108991652531284002755"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "3390815786276457618233491179181603989943809787247952440945228106895815551900"
+            ),
+            new_jubjub!(
+                "2516902759910232560455648130519787743421541314476837073025733076445667110151"
+            ),
+        ],
+    ];
+    const ROUND_KEYS_Y: [[JubjubScalar; 2usize]; 14usize] = [
+        [
+            new_jubjub!(
+                "5462070330742311508275806302936038108088267721560264401137799301993486045204"
+            ),
+            new_jubjub!(
+                "4300380087909355145337371363943693686960924869556775338041623257413357713413"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "5135705697415685603400461796329462655179055588620218888592467627247023333819"
+            ),
+            new_jubjub!(
+                "222668029970120508253313918128475610163611256762649729261455822738202857380"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "1190562653129538248116837573479878628721844477228628762303263062554982658846"
+            ),
+            new_jubjub!(
+                "4959580107208166669501334885684045115919856957437432123162289983548229181917"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "1048673577777793981988075739547234696809768803382635729119482860487962861866"
+            ),
+            new_jubjub!(
+                "4382430963510563890802679845068501236966134841883265589460377170090751557809"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "5215114834234596555497514629766470926361580472341337227872605505070492056893"
+            ),
+            new_jubjub!(
+                "6486167321980915107363214098446053428512985046455091070652771264306521401850"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "3227415252695366692813407906882728339440433647141360883295626498135829556070"
+            ),
+            new_jubjub!(
+                "2539741382837630696155739284820882258025598860837733779320833839123505260577"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "4986695991354750471978713048378558208025322027582402183775169475160489873313"
+            ),
+            new_jubjub!(
+                "4350047000081473157816697460536295876068615157854875553665921733930986073461"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "3923154615088417785924102570062907978390463437748173919655660907267552547005"
+            ),
+            new_jubjub!(
+                "448774977656236671404638526404503060761473213794193704034655817506115927374"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "3435506035705341551247110605735018165490435876250763531750476779131604510094"
+            ),
+            new_jubjub!(
+                "949876519544255947601764467302009971524379743015716001576064476089609768695"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "5955980856909558673648287693344694817183813867613600621849728939967297835617"
+            ),
+            new_jubjub!(
+                "3402663726395315810491525751075600514826375097367882165464787469724642569127"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "4783319658287246614322888653123313775115605175119079865403958140713959185057"
+            ),
+            new_jubjub!(
+                "1901218489896956438203878552949373908727880115538724743297598366945618505748"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "2545262945074902354413289318074286339448603855230215813592315286148652144451"
+            ),
+            new_jubjub!(
+                "3625204492973153013287813719216084900153908804859418327477998324338395543270"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "3640729073643629791244022263741350579989972233983143981751993799455680865961"
+            ),
+            new_jubjub!(
+                "5768640970876291405465650964791241757340599023598362385438650607292085302696"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "3559095838830028077613081981272817411406880215133670601588200214989609710364"
+            ),
+            new_jubjub!(
+                "3000873730570850088983674135150567582224147072328680624097622571891507048807"
+            ),
+        ],
+    ];
+    const PREPROCESSED_ROUND_KEYS_X: [[JubjubScalar; 2usize]; 14usize] = [
+        [
+            new_jubjub!(
+                "4244681828141527027340714277855026428610573439926947841580157733191321836766"
+            ),
+            new_jubjub!(
+                "4327256617236837302164512835028510544078798659975120751859775480039207146054"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "6025701346171787397839628162395874418622633016356341286747892532655407117332"
+            ),
+            new_jubjub!(
+                "686574602569263023633094355485529829941890276907274869787971648774096589869"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "2177689890304464771439629425823578424849086172132858068654044603727148336445"
+            ),
+            new_jubjub!(
+                "4507021121450832632118635969263716434472852841890343158414347981426678095432"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "6207116943911282495801945495951465915636249960344241052033950629960350777835"
+            ),
+            new_jubjub!(
+                "4851808109677573285947856134579698317966059061704778225459846309203889887356"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "1635354401812213449385276409049753686161326017804009001803758636077995884913"
+            ),
+            new_jubjub!(
+                "3627744295618433305252482415802386354543919878373399222907907940927503405233"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "3628957308316503912030270830794617590427791451549770487918554651119755087971"
+            ),
+            new_jubjub!(
+                "612665477308155360440469705504641729486353312186725859241554173823554837213"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "4119242591065956660981384123832559272365278452539027557852251479639125027045"
+            ),
+            new_jubjub!(
+                "2695352283011034371506549287122903207066945839792702602048575514069775497445"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "2698821024436128489881288583759717352903968083169011104870710066797021004810"
+            ),
+            new_jubjub!(
+                "1055354734994559417476004119871134737801051681198613628792123399554784105689"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "959716103743109883526060231677530032248364717805190072396158504408881236462"
+            ),
+            new_jubjub!(
+                "1749790634160356725586134052438319122364343762704675866710007049945588518497"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "6048686979117253038048249864064407380807557937759087408119559221498653085932"
+            ),
+            new_jubjub!(
+                "2101229909780279962331963879942966379415561483736167898829842665476657320433"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "1699378961709433932576848471715341990880264957563430342672626219402029974359"
+            ),
+            new_jubjub!(
+                "6236795097063603802259024103850459486471492531741679299560887660514024203020"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "6337231564640112359697344781809252365886198429280229641670079489748755345146"
+            ),
+            new_jubjub!(
+                "5329109945892596842742440361366176801008448072472789940873893409130797186963"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "6515052425399182330361808166093077324257401658937613749870561309626812616941"
+            ),
+            new_jubjub!(
+                "16663743569597274799905629859011903674964051636691594140204535939060179207"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "5383263551956305361105444835253838991061215142364340316368908240785451704408"
+            ),
+            new_jubjub!(
+                "2044062087194195677433479324426593041259225839301292564412386709197461065604"
+            ),
+        ],
+    ];
+    const PREPROCESSED_ROUND_KEYS_Y: [[JubjubScalar; 2usize]; 14usize] = [
+        [
+            new_jubjub!(
+                "4300380087909355145337371363943693686960924869556775338041623257413357713642"
+            ),
+            new_jubjub!(
+                "5046413270635347140576164232505217311030131875411647303926102196904899310260"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "4818964626901138888932214442012268622414259724994693935354824936651609843498"
+            ),
+            new_jubjub!(
+                "1277071474368649887338910630786845741135007609226796093894621435195766123812"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "5548471629094622348271392763040071158545002554936887415616509196485941880794"
+            ),
+            new_jubjub!(
+                "1708970443243403288090356334104076931462253477488366849175522429509717672615"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "4119988033286553972800166718828663688118826396306762682811915170626345474806"
+            ),
+            new_jubjub!(
+                "6105148586824796388996173361949479636404963383606259981894896397069485948105"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "5004434342934625390693464059428650338152861551141528031061608482768557472213"
+            ),
+            new_jubjub!(
+                "2469298907388479850003461168722144306749143449828919007415460589720920619176"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "2240779708337509423243284035547515105550436946068947234998515340761932834400"
+            ),
+            new_jubjub!(
+                "3563124708938875612411176993121327513331212791810409730556000217923060054072"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "1497800963756106940034137933191416475690940993987702249490142772933009042344"
+            ),
+            new_jubjub!(
+                "864532980109844492321605520480565602759125459763981117985307787974177618979"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "4324249477514421957156351256212213741986490042666285377872543773934881446807"
+            ),
+            new_jubjub!(
+                "3650713892611854289138339853243207511485718630256617061429486901308108211051"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "1899459543103983825291525411142381775349231202903345347982847663702687066662"
+            ),
+            new_jubjub!(
+                "1723294500547696883134327945542817358173980561926201056916844436563360401668"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "6366126883398798802726414093527540769399651973687899489736365297567513311834"
+            ),
+            new_jubjub!(
+                "4825835775957708630420966873370465055346198114507093872075765751019278181427"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "4383198852057340884417340217596273640597826102763934810259910561660640599294"
+            ),
+            new_jubjub!(
+                "4864575183068196681103059704607972699878876728213419601501984861109069764025"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "5787813369641019519905614500615311477433689404496078646301171716445941901578"
+            ),
+            new_jubjub!(
+                "4499721178467150424192138504149926555521133952845101284572549772863387282924"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "1395077825174974913205914293146362318456747364007957150489176754457437481666"
+            ),
+            new_jubjub!(
+                "5456711627802705460548540459096278761024535152158569603321695163808122501758"
+            ),
+        ],
+        [
+            new_jubjub!(
+                "4691995572878697124869263332217734861547664565513752389530746374652615548394"
+            ),
+            new_jubjub!(
+                "5493131688539115587104791720486243661869182544726915813311241813336569983932"
+            ),
+        ],
+    ];
+    const MDS_MATRIX: [[JubjubScalar; 2usize]; 2usize] = [
+        [new_jubjub!("1"), new_jubjub!("6")],
+        [new_jubjub!("6"), new_jubjub!("37")],
+    ];
+    fn get_alpha_inv() -> Vec<u64> {
+        vec![
+            16396996478604955143u64,
+            7194521896225134260u64,
+            7655539046881803520u64,
+            626513764459833701u64,
+        ]
+    }
+}
+
+impl ArithmeticHash<JubjubScalar> for AnemoiJiveJubjub {
+    fn hash(input: &[JubjubScalar]) -> JubjubScalar {
+        Self::eval_variable_length_hash(input)
+    }
+
+    fn compress(input: &[JubjubScalar; 3], salt: &JubjubScalar) -> JubjubScalar {
+        Self::eval_jive(&[input[0], input[1]], &[input[2], *salt])
+    }
+
+    fn salts() -> &'static [JubjubScalar] {
+        &ANEMOI_JIVE_JUBJUB_SALTS
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::basic::anemoi_jive::{
+        test::{generate_round_keys, get_n_rounds, reference_jive, reference_sponge},
+        ApplicableMDSMatrix, MDSMatrix,
+    };
+    use ark_std::test_rng;
+
+    #[test]
+    fn test_parameters() {
+        type F = JubjubScalar;
+
+        assert_eq!(
+            AnemoiJiveJubjub::GENERATOR.mul(&AnemoiJiveJubjub::GENERATOR_INV),
+            F::one()
+        );
+
+        let mds = MDSMatrix::<F, 2>(AnemoiJiveJubjub::MDS_MATRIX);
+        for r in 0..14 {
+            let (x, y) = mds.permute(
+                &AnemoiJiveJubjub::ROUND_KEYS_X[r],
+                &AnemoiJiveJubjub::ROUND_KEYS_Y[r],
+            );
+            assert_eq!(x, AnemoiJiveJubjub::PREPROCESSED_ROUND_KEYS_X[r]);
+            assert_eq!(y, AnemoiJiveJubjub::PREPROCESSED_ROUND_KEYS_Y[r]);
+        }
+
+        let mut prng = test_rng();
+        let x = F::random(&mut prng);
+        assert_eq!(
+            x.pow(&AnemoiJiveJubjub::get_alpha_inv())
+                .pow(&[AnemoiJiveJubjub::ALPHA as u64]),
+            x
+        );
+    }
+
+    #[test]
+    fn test_round_keys() {
+        // 128-bit security with two columns.
+        let num_rounds = get_n_rounds(128, 2, AnemoiJiveJubjub::ALPHA);
+        assert_eq!(num_rounds, AnemoiJiveJubjub::ROUND_KEYS_X.len());
+
+        let (round_keys_x, round_keys_y) = generate_round_keys(
+            &AnemoiJiveJubjub::GENERATOR,
+            AnemoiJiveJubjub::ALPHA,
+            num_rounds,
+        );
+        assert_eq!(round_keys_x, AnemoiJiveJubjub::ROUND_KEYS_X.to_vec());
+        assert_eq!(round_keys_y, AnemoiJiveJubjub::ROUND_KEYS_Y.to_vec());
+    }
+
+    #[test]
+    fn test_reference() {
+        type F = JubjubScalar;
+
+        let num_rounds = get_n_rounds(128, 2, AnemoiJiveJubjub::ALPHA);
+        let mut prng = test_rng();
+        for _ in 0..4 {
+            let input_x = [F::random(&mut prng), F::random(&mut prng)];
+            let input_y = [F::random(&mut prng), F::random(&mut prng)];
+            assert_eq!(
+                AnemoiJiveJubjub::eval_jive(&input_x, &input_y),
+                reference_jive(
+                    &AnemoiJiveJubjub::GENERATOR,
+                    AnemoiJiveJubjub::ALPHA,
+                    num_rounds,
+                    &input_x,
+                    &input_y
+                )
+            );
+        }
+
+        for len in 0..7 {
+            let input = (0..len).map(|_| F::random(&mut prng)).collect::<Vec<F>>();
+            assert_eq!(
+                AnemoiJiveJubjub::eval_variable_length_hash(&input),
+                reference_sponge(
+                    &AnemoiJiveJubjub::GENERATOR,
+                    AnemoiJiveJubjub::ALPHA,
+                    num_rounds,
+                    &input
+                )
+            );
+        }
+    }
+
+    // No published vectors exist for this parameter set. The expected values below come from
+    // the reference evaluation of `test_reference`, which reproduces the upstream BLS12-381
+    // vectors, and were also recomputed with an independent script of the same construction.
+    #[test]
+    fn test_jive() {
+        type F = JubjubScalar;
+
+        let input_x = [F::from(1u64), F::from(2u64)];
+        let input_y = [F::from(3u64), F::zero()];
+
+        let res = AnemoiJiveJubjub::eval_jive(&input_x, &input_y);
+        assert_eq!(
+            res,
+            new_jubjub!(
+                "555412510255932467664817691107775943401870553063702301452030414931661638981"
+            )
+        );
+    }
+
+    #[test]
+    fn test_anemoi_variable_length_hash() {
+        type F = JubjubScalar;
+
+        let input = [F::from(1u64), F::from(2u64), F::from(3u64), F::from(4u64)];
+
+        let res = AnemoiJiveJubjub::eval_variable_length_hash(&input);
+        assert_eq!(
+            res,
+            new_jubjub!(
+                "1356170962355534036324726658091258082273564163684339756394852074593532112635"
+            )
+        );
+
+        let res = AnemoiJiveJubjub::eval_variable_length_hash(&[]);
+        assert_eq!(
+            res,
+            new_jubjub!(
+                "5263108371761553628568384902908806919810152647307636783860838207768544293614"
+            )
+        );
+    }
+}
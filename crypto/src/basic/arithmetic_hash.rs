@@ -0,0 +1,19 @@
+use noah_algebra::prelude::*;
+
+/// The trait for arithmetization-friendly hash functions over a prime field,
+/// which the Merkle trees can be instantiated with.
+///
+/// The record commitments and nullifiers of anonymous transfers are not generic over this
+/// trait and stay on Anemoi-Jive, since their circuits consume the Anemoi-Jive traces.
+pub trait ArithmeticHash<F: Scalar> {
+    /// Hash a sequence of field elements of any length into one field element.
+    fn hash(input: &[F]) -> F;
+
+    /// Compress three field elements into one, where the salt separates the domains,
+    /// e.g., the levels of a ternary Merkle tree.
+    fn compress(input: &[F; 3], salt: &F) -> F;
+
+    /// The salts for `compress` that are specific to this hash function,
+    /// one for each level of a Merkle tree.
+    fn salts() -> &'static [F];
+}
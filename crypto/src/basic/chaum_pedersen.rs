@@ -387,6 +387,84 @@ pub fn chaum_pedersen_batch_verify_multiple_eq<R: CryptoRng + RngCore>(
     }
 }
 
+/// A Chaum-Pedersen proof of equality of discrete logarithms,
+/// i.e., knowledge of `x` such that `h1 = x * g1` and `h2 = x * g2`.
+#[derive(Clone, Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct ChaumPedersenDLEqProof<S, G> {
+    pub(crate) c1: G,
+    pub(crate) c2: G,
+    pub(crate) z: S,
+}
+
+fn init_chaum_pedersen_dleq<G: Group>(
+    transcript: &mut Transcript,
+    g1: &G,
+    h1: &G,
+    g2: &G,
+    h2: &G,
+) -> (Vec<G>, Vec<Vec<usize>>, Vec<usize>) {
+    transcript.append_message(b"new_domain", b"Chaum Pedersen DLEQ");
+    let elems = vec![*g1, *g2, *h1, *h2];
+    let lhs_matrix = vec![vec![0], vec![1]];
+    let rhs_vec = vec![2, 3];
+    (elems, lhs_matrix, rhs_vec)
+}
+
+/// Compute a Chaum-Pedersen proof that `h1 = x * g1` and `h2 = x * g2` for the same `x`.
+pub fn chaum_pedersen_prove_dleq<R: CryptoRng + RngCore, G: Group>(
+    transcript: &mut Transcript,
+    prng: &mut R,
+    x: &G::ScalarType,
+    g1: &G,
+    h1: &G,
+    g2: &G,
+    h2: &G,
+) -> ChaumPedersenDLEqProof<G::ScalarType, G> {
+    let (elems, lhs_matrix, _) = init_chaum_pedersen_dleq(transcript, g1, h1, g2, h2);
+    let proof = sigma_prove(
+        transcript,
+        prng,
+        elems.as_slice(),
+        lhs_matrix.as_slice(),
+        &[x],
+    );
+
+    ChaumPedersenDLEqProof {
+        c1: proof.commitments[0],
+        c2: proof.commitments[1],
+        z: proof.responses[0],
+    }
+}
+
+/// Verify a Chaum-Pedersen proof of equality of discrete logarithms. Return Ok() in case of success,
+/// Err([NoahError::ZKProofVerificationError]) in case of verification failure.
+pub fn chaum_pedersen_verify_dleq<R: CryptoRng + RngCore, G: Group>(
+    transcript: &mut Transcript,
+    prng: &mut R,
+    g1: &G,
+    h1: &G,
+    g2: &G,
+    h2: &G,
+    proof: &ChaumPedersenDLEqProof<G::ScalarType, G>,
+) -> Result<()> {
+    let (elems, lhs_matrix, rhs_vec) = init_chaum_pedersen_dleq(transcript, g1, h1, g2, h2);
+
+    let sigma_proof = SigmaProof {
+        commitments: vec![proof.c1, proof.c2],
+        responses: vec![proof.z],
+    };
+
+    sigma_verify::<_, G>(
+        transcript,
+        prng,
+        elems.as_slice(),
+        lhs_matrix.as_slice(),
+        rhs_vec.as_slice(),
+        &sigma_proof,
+    )
+    .c(d!())
+}
+
 // Helper functions for the proof of multiple commitments equality below
 
 // Obtain a fake compressed commitment to zero, eg The identity
@@ -587,4 +665,45 @@ mod test {
             "Values are the same"
         );
     }
+
+    #[test]
+    fn test_chaum_pedersen_dleq() {
+        let mut prng = test_rng();
+        let x = RistrettoScalar::random(&mut prng);
+        let g1 = RistrettoPoint::get_base();
+        let g2 = RistrettoPoint::random(&mut prng);
+        let h1 = g1.mul(&x);
+        let h2 = g2.mul(&x);
+
+        let mut prover_transcript = Transcript::new(b"test");
+        let proof =
+            chaum_pedersen_prove_dleq(&mut prover_transcript, &mut prng, &x, &g1, &h1, &g2, &h2);
+        let mut verifier_transcript = Transcript::new(b"test");
+        assert!(chaum_pedersen_verify_dleq(
+            &mut verifier_transcript,
+            &mut prng,
+            &g1,
+            &h1,
+            &g2,
+            &h2,
+            &proof
+        )
+        .is_ok());
+
+        let bad_h2 = g2.mul(&x.add(&RistrettoScalar::one()));
+        let mut verifier_transcript = Transcript::new(b"test");
+        msg_eq!(
+            NoahError::ZKProofVerificationError,
+            chaum_pedersen_verify_dleq(
+                &mut verifier_transcript,
+                &mut prng,
+                &g1,
+                &h1,
+                &g2,
+                &bad_h2,
+                &proof
+            )
+            .unwrap_err()
+        );
+    }
 }
@@ -1,8 +1,13 @@
 use noah_algebra::ristretto::RistrettoPoint;
 use noah_algebra::{
+    collections::HashMap,
     hash::{Hash, Hasher},
     prelude::*,
 };
+use std::marker::PhantomData;
+
+/// The default number of bits covered by the baby steps of the decryption table.
+pub const ELGAMAL_DEFAULT_BABY_STEP_BITS: usize = 16;
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 /// The ElGamal encryption key/public key.
@@ -88,12 +93,113 @@ pub fn elgamal_partial_decrypt<G: Group>(
     ctext.e2.sub(&ctext.e1.mul(&sec_key.0))
 }
 
+/// A precomputed table of baby steps for the baby-step giant-step decryption of small messages.
+///
+/// The table maps a prefix of the compressed representation of `j * G` to `j` for `j` in `[0, 2^baby_step_bits)`.
+/// Points sharing a prefix are all kept, and a match is confirmed against the full point.
+/// A message of `n` bits is then found after at most `2^(n - baby_step_bits)` giant steps.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElGamalDecryptionTable<G> {
+    /// The number of bits covered by the baby steps.
+    baby_step_bits: usize,
+    /// The map from the prefix of the compressed `j * G` to every such `j`.
+    baby_steps: HashMap<u64, Vec<u32>>,
+    /// The group of the table.
+    #[serde(skip)]
+    group: PhantomData<G>,
+}
+
+impl<G: Group> ElGamalDecryptionTable<G> {
+    /// Precompute the table with `2^baby_step_bits` baby steps, with `baby_step_bits` in `[1, 32]`.
+    pub fn new(baby_step_bits: usize) -> Result<Self> {
+        if baby_step_bits == 0 || baby_step_bits > 32 {
+            return Err(eg!(NoahError::ParameterError));
+        }
+        let base = G::get_base();
+        let mut baby_steps = HashMap::new();
+        let mut point = G::get_identity();
+        for j in 0..(1u64 << baby_step_bits) {
+            baby_steps
+                .entry(Self::key(&point))
+                .or_insert_with(Vec::new)
+                .push(j as u32);
+            point = point.add(&base);
+        }
+        Ok(Self {
+            baby_step_bits,
+            baby_steps,
+            group: PhantomData,
+        })
+    }
+
+    /// Return the number of bits covered by the baby steps.
+    pub fn baby_step_bits(&self) -> usize {
+        self.baby_step_bits
+    }
+
+    /// Find `m` in `[0, 2^max_bits)` such that `point = m * G`,
+    /// where `max_bits` is at most 64 and at least the number of bits covered by the baby steps.
+    pub fn discrete_log(&self, point: &G, max_bits: usize) -> Result<u64> {
+        if max_bits > 64 || max_bits < self.baby_step_bits {
+            return Err(eg!(NoahError::ParameterError));
+        }
+        let base = G::get_base();
+        let giant_step = base.mul(&G::ScalarType::from(1u64 << self.baby_step_bits));
+        let num_giant_steps = 1u128 << (max_bits - self.baby_step_bits);
+
+        let mut current = *point;
+        for i in 0..num_giant_steps {
+            if let Some(candidates) = self.baby_steps.get(&Self::key(&current)) {
+                // The key is only a prefix of the point, so compare the full point.
+                for j in candidates.iter() {
+                    if base.mul(&G::ScalarType::from(*j)) == current {
+                        return Ok(((i << self.baby_step_bits) + *j as u128) as u64);
+                    }
+                }
+            }
+            current = current.sub(&giant_step);
+        }
+        Err(eg!(NoahError::ElGamalDecryptionError))
+    }
+
+    fn key(point: &G) -> u64 {
+        let bytes = point.to_compressed_bytes();
+        let mut key = [0u8; 8];
+        key.copy_from_slice(&bytes[0..8]);
+        u64::from_le_bytes(key)
+    }
+}
+
+/// Decrypt an ElGamal ciphertext of a 32-bit message with the baby-step giant-step algorithm.
+pub fn elgamal_decrypt_u32<G: Group>(
+    ctext: &ElGamalCiphertext<G>,
+    sec_key: &ElGamalDecKey<G::ScalarType>,
+    table: &ElGamalDecryptionTable<G>,
+) -> Result<u32> {
+    let point = elgamal_partial_decrypt(ctext, sec_key);
+    let m = table.discrete_log(&point, 32).c(d!())?;
+    Ok(m as u32)
+}
+
+/// Decrypt an ElGamal ciphertext of a message of at most `max_bits` bits with the baby-step giant-step algorithm.
+/// The decryption takes up to `2^(max_bits - baby_step_bits)` giant steps, so `max_bits` should be chosen
+/// according to the size of the table.
+pub fn elgamal_decrypt_u64<G: Group>(
+    ctext: &ElGamalCiphertext<G>,
+    sec_key: &ElGamalDecKey<G::ScalarType>,
+    table: &ElGamalDecryptionTable<G>,
+    max_bits: usize,
+) -> Result<u64> {
+    let point = elgamal_partial_decrypt(ctext, sec_key);
+    table.discrete_log(&point, max_bits).c(d!())
+}
+
 #[cfg(test)]
 mod elgamal_test {
     use ark_std::test_rng;
     use noah_algebra::bls12_381::{BLSGt, BLSG1, BLSG2};
     use noah_algebra::prelude::*;
-    use noah_algebra::ristretto::RistrettoPoint;
+    use noah_algebra::ristretto::{RistrettoPoint, RistrettoScalar};
 
     fn verification<G: Group>() {
         let mut prng = test_rng();
@@ -127,6 +233,106 @@ mod elgamal_test {
         pnk!(super::elgamal_verify(&m, &ctext, &secret_key));
     }
 
+    fn bsgs_decryption<G: Group>() {
+        let mut prng = test_rng();
+        let (secret_key, public_key) = super::elgamal_key_gen::<_, G>(&mut prng);
+        let table = super::ElGamalDecryptionTable::<G>::new(12).unwrap();
+
+        for m in [0u32, 1, 4095, 4096, 123456, 1 << 20] {
+            let r = G::ScalarType::random(&mut prng);
+            let ctext = super::elgamal_encrypt(&G::ScalarType::from(m), &r, &public_key);
+            assert_eq!(
+                super::elgamal_decrypt_u64(&ctext, &secret_key, &table, 21).unwrap(),
+                m as u64
+            );
+        }
+
+        let r = G::ScalarType::random(&mut prng);
+        let ctext = super::elgamal_encrypt(&G::ScalarType::from(1u32 << 21), &r, &public_key);
+        msg_eq!(
+            NoahError::ElGamalDecryptionError,
+            super::elgamal_decrypt_u64(&ctext, &secret_key, &table, 21).unwrap_err()
+        );
+    }
+
+    fn bsgs_parameters<G: Group>() {
+        msg_eq!(
+            NoahError::ParameterError,
+            super::ElGamalDecryptionTable::<G>::new(0).unwrap_err()
+        );
+        msg_eq!(
+            NoahError::ParameterError,
+            super::ElGamalDecryptionTable::<G>::new(33).unwrap_err()
+        );
+
+        let table = super::ElGamalDecryptionTable::<G>::new(12).unwrap();
+        let point = G::get_base().mul(&G::ScalarType::from(5u32));
+        msg_eq!(
+            NoahError::ParameterError,
+            table.discrete_log(&point, 11).unwrap_err()
+        );
+        msg_eq!(
+            NoahError::ParameterError,
+            table.discrete_log(&point, 65).unwrap_err()
+        );
+        assert_eq!(table.discrete_log(&point, 12).unwrap(), 5);
+    }
+
+    fn bsgs_prefix_collision<G: Group>() {
+        let mut table = super::ElGamalDecryptionTable::<G>::new(4).unwrap();
+        let base = G::get_base();
+        let point = base.mul(&G::ScalarType::from(3u32));
+
+        // Make another baby step share the prefix of `3 * G`, ahead of it.
+        let key = super::ElGamalDecryptionTable::<G>::key(&point);
+        table.baby_steps.get_mut(&key).unwrap().insert(0, 7);
+        assert_eq!(table.discrete_log(&point, 8).unwrap(), 3);
+
+        let point = base.mul(&G::ScalarType::from(35u32));
+        assert_eq!(table.discrete_log(&point, 8).unwrap(), 35);
+    }
+
+    #[test]
+    fn bsgs_params() {
+        bsgs_parameters::<RistrettoPoint>();
+        bsgs_parameters::<BLSG1>();
+    }
+
+    #[test]
+    fn bsgs_collision() {
+        bsgs_prefix_collision::<RistrettoPoint>();
+        bsgs_prefix_collision::<BLSG1>();
+    }
+
+    #[test]
+    fn bsgs_decrypt() {
+        bsgs_decryption::<RistrettoPoint>();
+        bsgs_decryption::<BLSG1>();
+    }
+
+    #[test]
+    fn bsgs_decrypt_u32() {
+        let mut prng = test_rng();
+        let (secret_key, public_key) = super::elgamal_key_gen::<_, RistrettoPoint>(&mut prng);
+        let table = super::ElGamalDecryptionTable::<RistrettoPoint>::new(
+            super::ELGAMAL_DEFAULT_BABY_STEP_BITS,
+        )
+        .unwrap();
+
+        let bytes = rmp_serde::to_vec(&table).unwrap();
+        let table: super::ElGamalDecryptionTable<RistrettoPoint> =
+            rmp_serde::from_slice(&bytes).unwrap();
+
+        for m in [0u32, 65535, 65536, 0x0123_4567, u32::MAX] {
+            let r = RistrettoScalar::random(&mut prng);
+            let ctext = super::elgamal_encrypt(&RistrettoScalar::from(m), &r, &public_key);
+            assert_eq!(
+                super::elgamal_decrypt_u32(&ctext, &secret_key, &table).unwrap(),
+                m
+            );
+        }
+    }
+
     #[test]
     fn verify() {
         verification::<RistrettoPoint>();
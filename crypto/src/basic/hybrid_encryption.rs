@@ -1,12 +1,22 @@
+use crate::basic::elgamal::ElGamalDecKey;
+use crate::basic::threshold_elgamal::{
+    lagrange_coefficient_at_zero, threshold_elgamal_split, ElGamalDecKeyShare,
+    ThresholdElGamalParams,
+};
 use aes::{
     cipher::{generic_array::GenericArray, KeyIvInit, StreamCipher},
     Aes256,
 };
-use curve25519_dalek::edwards::CompressedEdwardsY;
+use curve25519_dalek::{
+    constants::ED25519_BASEPOINT_POINT,
+    edwards::{CompressedEdwardsY, EdwardsPoint},
+    montgomery::MontgomeryPoint,
+    traits::Identity,
+};
 use ed25519_dalek::{ExpandedSecretKey, PublicKey, SecretKey};
 use noah_algebra::errors::NoahError;
 use noah_algebra::prelude::*;
-use noah_algebra::ristretto::RistrettoScalar;
+use noah_algebra::ristretto::{RistrettoPoint, RistrettoScalar};
 use serde::Serializer;
 use sha2::Digest;
 use wasm_bindgen::prelude::*;
@@ -144,6 +154,21 @@ impl NoahFromToBytes for NoahHybridCiphertext {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+/// A decryption share of a hybrid ciphertext over X25519,
+/// i.e., the ephemeral public key multiplied by a share of the secret scalar.
+pub struct XDecShare {
+    pub(crate) index: u32,
+    pub(crate) point: [u8; 32],
+}
+
+impl XDecShare {
+    /// Return the index of the key share used for the decryption share.
+    pub fn get_index(&self) -> u32 {
+        self.index
+    }
+}
+
 /// Encrypt a message over X25519
 pub fn hybrid_encrypt_x25519<R: CryptoRng + RngCore>(
     prng: &mut R,
@@ -191,10 +216,79 @@ pub fn hybrid_decrypt_with_ed25519_secret_key(
     symmetric_decrypt(&key, &ctext.ciphertext)
 }
 
+/// Generate an X25519 public key whose secret scalar is split into `num_shares` shares,
+/// any `threshold` of which can decrypt without reconstructing the secret scalar.
+/// The parameters allow each party to check its share against the commitments.
+pub fn hybrid_x25519_threshold_key_gen<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    threshold: usize,
+    num_shares: usize,
+) -> Result<(
+    XPublicKey,
+    ThresholdElGamalParams<RistrettoPoint>,
+    Vec<ElGamalDecKeyShare<RistrettoScalar>>,
+)> {
+    let dec_key = ElGamalDecKey(RistrettoScalar::random(prng));
+    let (params, key_shares) =
+        threshold_elgamal_split(prng, &dec_key, threshold, num_shares).c(d!())?;
+    let public_key = (ED25519_BASEPOINT_POINT * dec_key.0 .0).to_montgomery();
+    Ok((
+        XPublicKey {
+            key: x25519_dalek::PublicKey::from(public_key.to_bytes()),
+        },
+        params,
+        key_shares,
+    ))
+}
+
+/// Compute the decryption share of a hybrid ciphertext over X25519 with a share of the secret scalar.
+pub fn hybrid_partial_decrypt_x25519(
+    ctext: &NoahHybridCiphertext,
+    key_share: &ElGamalDecKeyShare<RistrettoScalar>,
+) -> Result<XDecShare> {
+    let ephemeral_key = MontgomeryPoint(*ctext.ephemeral_public_key.key.as_bytes())
+        .to_edwards(0)
+        .c(d!(NoahError::DecompressElementError))?;
+    if !ephemeral_key.is_torsion_free() {
+        return Err(eg!(NoahError::DecompressElementError));
+    }
+    Ok(XDecShare {
+        index: key_share.index,
+        point: (ephemeral_key * key_share.share.0).compress().to_bytes(),
+    })
+}
+
+/// Decrypt a hybrid ciphertext over X25519 from exactly `threshold` decryption shares with distinct indices.
+/// The shares carry no proof, so a wrong share yields a wrong plaintext that the caller must detect.
+pub fn hybrid_threshold_decrypt_x25519(
+    ctext: &NoahHybridCiphertext,
+    dec_shares: &[XDecShare],
+) -> Result<Vec<u8>> {
+    let indices = dec_shares
+        .iter()
+        .map(|dec_share| dec_share.index)
+        .collect_vec();
+    if indices.is_empty() || indices.iter().unique().count() != indices.len() {
+        return Err(eg!(NoahError::ParameterError));
+    }
+
+    let mut shared_point = EdwardsPoint::identity();
+    for dec_share in dec_shares.iter() {
+        let point = CompressedEdwardsY(dec_share.point)
+            .decompress()
+            .c(d!(NoahError::DecompressElementError))?;
+        let coefficient =
+            lagrange_coefficient_at_zero::<RistrettoScalar>(dec_share.index, &indices).c(d!())?;
+        shared_point += point * coefficient.0;
+    }
+    let key = shared_secret_to_symmetric_key(&shared_point.to_montgomery().to_bytes());
+    Ok(symmetric_decrypt(&key, &ctext.ciphertext))
+}
+
 /// Convert the shared secret to a symmetric key
-fn shared_secret_to_symmetric_key(shared_secret: &x25519_dalek::SharedSecret) -> [u8; 32] {
+fn shared_secret_to_symmetric_key(shared_secret: &[u8; 32]) -> [u8; 32] {
     let mut hasher = sha2::Sha256::new();
-    hasher.update(shared_secret.as_bytes());
+    hasher.update(shared_secret);
     let hash = hasher.finalize();
     let mut symmetric_key = [0u8; 32];
     symmetric_key.copy_from_slice(hash.as_slice());
@@ -212,7 +306,7 @@ fn symmetric_key_from_x25519_public_key<R: CryptoRng + RngCore>(
 
     let shared = ephemeral.diffie_hellman(public_key);
 
-    let symmetric_key = shared_secret_to_symmetric_key(&shared);
+    let symmetric_key = shared_secret_to_symmetric_key(shared.as_bytes());
     (symmetric_key, dh_pk)
 }
 
@@ -245,7 +339,7 @@ fn symmetric_key_from_x25519_secret_key(
     ephemeral_public_key: &x25519_dalek::PublicKey,
 ) -> [u8; 32] {
     let shared_key = sec_key.diffie_hellman(ephemeral_public_key);
-    shared_secret_to_symmetric_key(&shared_key)
+    shared_secret_to_symmetric_key(shared_key.as_bytes())
 }
 
 /// Derive a symmetric key from a secret key over Ed25519
@@ -318,4 +412,39 @@ mod test {
         let plaintext = hybrid_decrypt_with_ed25519_secret_key(&cipherbox, &key_pair.secret_key());
         assert_eq!(msg, plaintext.as_slice());
     }
+
+    #[test]
+    fn hybrid_cipher_threshold() {
+        let mut prng = test_rng();
+        let (pub_key, params, key_shares) =
+            hybrid_x25519_threshold_key_gen(&mut prng, 2, 3).unwrap();
+        for key_share in key_shares.iter() {
+            pnk!(params.verify_key_share(key_share));
+        }
+        let msg = b"this is a message for several parties";
+
+        let cipherbox = hybrid_encrypt_x25519(&mut prng, &pub_key, msg);
+        let dec_shares = key_shares
+            .iter()
+            .map(|key_share| hybrid_partial_decrypt_x25519(&cipherbox, key_share).unwrap())
+            .collect_vec();
+
+        for subset in [[0usize, 1], [2, 0], [1, 2]] {
+            let shares = subset.iter().map(|&i| dec_shares[i].clone()).collect_vec();
+            let plaintext = hybrid_threshold_decrypt_x25519(&cipherbox, &shares).unwrap();
+            assert_eq!(msg, plaintext.as_slice());
+        }
+
+        // A single share does not give the plaintext.
+        let plaintext = hybrid_threshold_decrypt_x25519(&cipherbox, &dec_shares[..1]).unwrap();
+        assert_ne!(msg, plaintext.as_slice());
+        msg_eq!(
+            NoahError::ParameterError,
+            hybrid_threshold_decrypt_x25519(
+                &cipherbox,
+                &[dec_shares[0].clone(), dec_shares[0].clone()]
+            )
+            .unwrap_err()
+        );
+    }
 }
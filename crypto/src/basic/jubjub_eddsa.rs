@@ -0,0 +1,239 @@
+use crate::basic::anemoi_jive::{AnemoiJive, AnemoiJive381, AnemoiVLHTrace};
+use noah_algebra::{
+    bls12_381::BLSScalar,
+    jubjub::{JubjubPoint, JubjubScalar},
+    prelude::*,
+};
+use num_bigint::BigUint;
+use serde::{Deserialize, Deserializer};
+use std::fmt;
+
+/// The secret key of EdDSA over Jubjub.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JubjubEdDSASecretKey(pub(crate) JubjubScalar);
+
+impl fmt::Debug for JubjubEdDSASecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("JubjubEdDSASecretKey(<redacted>)")
+    }
+}
+
+/// The public key of EdDSA over Jubjub, `pk = sk * G`, in the prime-order subgroup.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize)]
+pub struct JubjubEdDSAPublicKey(pub JubjubPoint);
+
+impl<'de> Deserialize<'de> for JubjubEdDSAPublicKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        let point = JubjubPoint::deserialize(deserializer)?;
+        Self::from_point(point).map_err(|_| {
+            serde::de::Error::custom("the public key is not in the prime-order subgroup")
+        })
+    }
+}
+
+/// The key pair of EdDSA over Jubjub.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JubjubEdDSAKeyPair {
+    /// The secret key.
+    pub(crate) secret_key: JubjubEdDSASecretKey,
+    /// The public key.
+    pub public_key: JubjubEdDSAPublicKey,
+}
+
+/// An EdDSA signature over Jubjub, `(R, s)` with `s * G = R + c * pk`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JubjubEdDSASignature {
+    /// `R = k * G` for the nonce `k`.
+    pub r: JubjubPoint,
+    /// `s = k + c * sk`.
+    pub s: JubjubScalar,
+}
+
+impl JubjubEdDSAKeyPair {
+    /// Generate a random key pair.
+    pub fn generate<R: CryptoRng + RngCore>(prng: &mut R) -> Self {
+        Self::from_secret_key(JubjubEdDSASecretKey(JubjubScalar::random(prng)))
+    }
+
+    /// Derive the key pair from the secret key.
+    pub fn from_secret_key(secret_key: JubjubEdDSASecretKey) -> Self {
+        let public_key = JubjubEdDSAPublicKey(JubjubPoint::get_base().mul(&secret_key.0));
+        Self {
+            secret_key,
+            public_key,
+        }
+    }
+
+    /// Return the public key.
+    pub fn get_public_key(&self) -> JubjubEdDSAPublicKey {
+        self.public_key
+    }
+
+    /// Sign a message consisting of BLS12-381 scalars.
+    pub fn sign<R: CryptoRng + RngCore>(
+        &self,
+        prng: &mut R,
+        msg: &[BLSScalar],
+    ) -> JubjubEdDSASignature {
+        let k = JubjubScalar::random(prng);
+        let r = JubjubPoint::get_base().mul(&k);
+
+        let c = compute_challenge(&r, &self.public_key, msg);
+        let s = k.add(&c.mul(&self.secret_key.0));
+
+        JubjubEdDSASignature { r, s }
+    }
+}
+
+impl JubjubEdDSAPublicKey {
+    /// Create a public key from a point, which must be in the prime-order subgroup.
+    pub fn from_point(point: JubjubPoint) -> Result<Self> {
+        if !point.is_in_prime_order_subgroup() {
+            return Err(eg!(NoahError::ParameterError));
+        }
+        Ok(Self(point))
+    }
+
+    /// Verify a signature of the message by checking `s * G = R + c * pk`,
+    /// after checking that the public key is in the prime-order subgroup.
+    pub fn verify(&self, msg: &[BLSScalar], signature: &JubjubEdDSASignature) -> Result<()> {
+        if !self.0.is_in_prime_order_subgroup() {
+            return Err(eg!(NoahError::SignatureError));
+        }
+        let c = compute_challenge(&signature.r, self, msg);
+
+        let lhs = JubjubPoint::get_base().mul(&signature.s);
+        let rhs = signature.r.add(&self.0.mul(&c));
+        if lhs == rhs {
+            Ok(())
+        } else {
+            Err(eg!(NoahError::SignatureError))
+        }
+    }
+}
+
+/// Return the trace of the challenge hash `H(R.x, R.y, pk.x, pk.y, msg)`,
+/// which the in-circuit verifier needs.
+pub fn compute_challenge_trace(
+    r: &JubjubPoint,
+    public_key: &JubjubEdDSAPublicKey,
+    msg: &[BLSScalar],
+) -> AnemoiVLHTrace<BLSScalar, 2, 12> {
+    let mut input = vec![
+        r.get_x(),
+        r.get_y(),
+        public_key.0.get_x(),
+        public_key.0.get_y(),
+    ];
+    input.extend_from_slice(msg);
+    AnemoiJive381::eval_variable_length_hash_with_trace(&input)
+}
+
+/// Compute the challenge, which is the hash `H(R.x, R.y, pk.x, pk.y, msg)`
+/// interpreted as an integer and reduced modulo the Jubjub group order.
+fn compute_challenge(
+    r: &JubjubPoint,
+    public_key: &JubjubEdDSAPublicKey,
+    msg: &[BLSScalar],
+) -> JubjubScalar {
+    let mut input = vec![
+        r.get_x(),
+        r.get_y(),
+        public_key.0.get_x(),
+        public_key.0.get_y(),
+    ];
+    input.extend_from_slice(msg);
+    let hash = AnemoiJive381::eval_variable_length_hash(&input);
+    JubjubScalar::from(&<BLSScalar as Into<BigUint>>::into(hash))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ark_std::test_rng;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let mut prng = test_rng();
+        let keypair = JubjubEdDSAKeyPair::generate(&mut prng);
+        let public_key = keypair.get_public_key();
+
+        let msg = vec![BLSScalar::random(&mut prng), BLSScalar::random(&mut prng)];
+        let signature = keypair.sign(&mut prng, &msg);
+        assert!(public_key.verify(&msg, &signature).is_ok());
+
+        // another message
+        let other_msg = vec![msg[0], msg[1].add(&BLSScalar::one())];
+        assert!(public_key.verify(&other_msg, &signature).is_err());
+
+        // another public key
+        let other_public_key = JubjubEdDSAKeyPair::generate(&mut prng).get_public_key();
+        assert!(other_public_key.verify(&msg, &signature).is_err());
+
+        // a tampered signature
+        let tampered = JubjubEdDSASignature {
+            r: signature.r,
+            s: signature.s.add(&JubjubScalar::one()),
+        };
+        assert!(public_key.verify(&msg, &tampered).is_err());
+    }
+
+    #[test]
+    fn test_public_key_subgroup() {
+        let mut prng = test_rng();
+        let keypair = JubjubEdDSAKeyPair::generate(&mut prng);
+        let public_key = keypair.get_public_key();
+        assert!(JubjubEdDSAPublicKey::from_point(public_key.0).is_ok());
+
+        // `(0, -1)` has order two
+        let mut bytes = BLSScalar::zero().to_bytes();
+        bytes.extend_from_slice(&BLSScalar::one().neg().to_bytes());
+        let torsion = JubjubPoint::from_unchecked_bytes(&bytes).unwrap();
+        assert!(!torsion.is_in_prime_order_subgroup());
+        assert_eq!(torsion.double(), JubjubPoint::get_identity());
+
+        let bad_public_key = JubjubEdDSAPublicKey(public_key.0.add(&torsion));
+        msg_eq!(
+            NoahError::ParameterError,
+            JubjubEdDSAPublicKey::from_point(bad_public_key.0).unwrap_err()
+        );
+        let json = serde_json::to_string(&bad_public_key).unwrap();
+        assert!(serde_json::from_str::<JubjubEdDSAPublicKey>(&json).is_err());
+        let json = serde_json::to_string(&public_key).unwrap();
+        assert_eq!(
+            serde_json::from_str::<JubjubEdDSAPublicKey>(&json).unwrap(),
+            public_key
+        );
+
+        let msg = vec![BLSScalar::random(&mut prng)];
+        let signature = keypair.sign(&mut prng, &msg);
+        msg_eq!(
+            NoahError::SignatureError,
+            bad_public_key.verify(&msg, &signature).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_secret_key_debug() {
+        let mut prng = test_rng();
+        let keypair = JubjubEdDSAKeyPair::generate(&mut prng);
+        let secret = format!("{:?}", keypair.secret_key.0);
+        let debug = format!("{:?}", keypair);
+        assert!(!debug.contains(&secret));
+        assert!(debug.contains("<redacted>"));
+    }
+
+    #[test]
+    fn test_challenge_trace() {
+        let mut prng = test_rng();
+        let keypair = JubjubEdDSAKeyPair::generate(&mut prng);
+        let msg = vec![BLSScalar::random(&mut prng)];
+        let signature = keypair.sign(&mut prng, &msg);
+
+        let trace = compute_challenge_trace(&signature.r, &keypair.public_key, &msg);
+        assert_eq!(
+            JubjubScalar::from(&<BLSScalar as Into<BigUint>>::into(trace.output)),
+            compute_challenge(&signature.r, &keypair.public_key, &msg)
+        );
+    }
+}
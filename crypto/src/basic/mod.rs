@@ -1,14 +1,22 @@
 /// The module for the Anemoi-Jive hash functions.
 pub mod anemoi_jive;
+/// The module for the trait of arithmetization-friendly hash functions.
+pub mod arithmetic_hash;
 /// The module for the Chaum-Pedersen protocol.
 pub mod chaum_pedersen;
 /// The module for the ElGamal encryption.
 pub mod elgamal;
 /// The module for hybrid encryption.
 pub mod hybrid_encryption;
+/// The module for EdDSA signatures over Jubjub.
+pub mod jubjub_eddsa;
 /// The module for the matrix Sigma protocol.
 pub mod matrix_sigma;
 /// The module for the Pedersen commitments over the Ristretto group and secq256k1 group.
 pub mod pedersen_comm;
 /// The module for the equality proof between a Pedersen commitment and an ElGamal ciphertext.
 pub mod pedersen_elgamal;
+/// The module for the Poseidon hash function.
+pub mod poseidon;
+/// The module for the threshold ElGamal decryption.
+pub mod threshold_elgamal;
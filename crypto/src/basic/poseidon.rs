@@ -0,0 +1,342 @@
+use crate::basic::arithmetic_hash::ArithmeticHash;
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use noah_algebra::{bls12_381::BLSScalar, prelude::*};
+use num_bigint::BigUint;
+
+/// The width of the Poseidon permutation, which has a rate of two and a capacity of one.
+pub const POSEIDON_WIDTH: usize = 3;
+
+/// The number of full rounds, half of which are before the partial rounds.
+pub const POSEIDON_FULL_ROUNDS: usize = 8;
+
+/// The number of partial rounds.
+pub const POSEIDON_PARTIAL_ROUNDS: usize = 57;
+
+/// The parameters of the Poseidon permutation.
+pub struct PoseidonParams<F: Scalar> {
+    /// The round keys, one for each state element in each round.
+    pub round_keys: Vec<[F; POSEIDON_WIDTH]>,
+    /// The MDS matrix.
+    pub mds: [[F; POSEIDON_WIDTH]; POSEIDON_WIDTH],
+}
+
+/// The number of salts, one for each level of a Merkle tree.
+pub const POSEIDON_NUM_SALTS: usize = 64;
+
+lazy_static! {
+    static ref POSEIDON_381_PARAMS: PoseidonParams<BLSScalar> = generate_params();
+    static ref POSEIDON_381_SALTS: Vec<BLSScalar> = generate_salts();
+}
+
+/// The Grain LFSR that generates the parameters, as in the reference implementation of Poseidon.
+struct GrainLFSR {
+    state: Vec<bool>,
+}
+
+impl GrainLFSR {
+    fn new(field_size: usize, t: usize, full_rounds: usize, partial_rounds: usize) -> Self {
+        let mut state = Vec::with_capacity(80);
+        let mut append = |v: usize, n: usize| {
+            for i in (0..n).rev() {
+                state.push((v >> i) & 1 == 1);
+            }
+        };
+        // a prime field
+        append(1, 2);
+        // the S-box `x^alpha`
+        append(0, 4);
+        append(field_size, 12);
+        append(t, 12);
+        append(full_rounds, 10);
+        append(partial_rounds, 10);
+        state.extend_from_slice(&[true; 30]);
+
+        let mut lfsr = Self { state };
+        for _ in 0..160 {
+            lfsr.update();
+        }
+        lfsr
+    }
+
+    fn update(&mut self) -> bool {
+        let s = &self.state;
+        let new_bit = s[62] ^ s[51] ^ s[38] ^ s[23] ^ s[13] ^ s[0];
+        self.state.remove(0);
+        self.state.push(new_bit);
+        new_bit
+    }
+
+    /// Return the next bit, which is the second bit of the next pair whose first bit is one.
+    fn next_bit(&mut self) -> bool {
+        loop {
+            let first = self.update();
+            let second = self.update();
+            if first {
+                return second;
+            }
+        }
+    }
+
+    /// Return the next `n` bits as an integer, with the most significant bit first.
+    fn next_biguint(&mut self, n: usize) -> BigUint {
+        let mut res = BigUint::zero();
+        for _ in 0..n {
+            res <<= 1;
+            if self.next_bit() {
+                res += 1u32;
+            }
+        }
+        res
+    }
+}
+
+/// Generate the round keys by rejection sampling and the MDS matrix as a Cauchy matrix,
+/// as in the reference implementation of Poseidon.
+fn generate_params() -> PoseidonParams<BLSScalar> {
+    let modulus = BLSScalar::get_field_size_biguint();
+    let field_size = modulus.bits() as usize;
+    let mut lfsr = GrainLFSR::new(
+        field_size,
+        POSEIDON_WIDTH,
+        POSEIDON_FULL_ROUNDS,
+        POSEIDON_PARTIAL_ROUNDS,
+    );
+
+    let mut round_keys = Vec::with_capacity(POSEIDON_FULL_ROUNDS + POSEIDON_PARTIAL_ROUNDS);
+    for _ in 0..(POSEIDON_FULL_ROUNDS + POSEIDON_PARTIAL_ROUNDS) {
+        let mut keys = [BLSScalar::zero(); POSEIDON_WIDTH];
+        for key in keys.iter_mut() {
+            let mut v = lfsr.next_biguint(field_size);
+            while v >= modulus {
+                v = lfsr.next_biguint(field_size);
+            }
+            *key = BLSScalar::from(&v);
+        }
+        round_keys.push(keys);
+    }
+
+    let mds = loop {
+        let mut elements = Vec::with_capacity(2 * POSEIDON_WIDTH);
+        while elements.len() != 2 * POSEIDON_WIDTH || !elements.iter().all_unique() {
+            elements = (0..2 * POSEIDON_WIDTH)
+                .map(|_| BLSScalar::from(&(lfsr.next_biguint(field_size) % &modulus)))
+                .collect::<Vec<BLSScalar>>();
+        }
+        let (xs, ys) = elements.split_at(POSEIDON_WIDTH);
+
+        let mut mds = [[BLSScalar::zero(); POSEIDON_WIDTH]; POSEIDON_WIDTH];
+        let mut invertible = true;
+        for (i, x) in xs.iter().enumerate() {
+            for (j, y) in ys.iter().enumerate() {
+                match x.add(y).inv() {
+                    Ok(v) => mds[i][j] = v,
+                    Err(_) => invertible = false,
+                }
+            }
+        }
+        if invertible {
+            break mds;
+        }
+    };
+
+    PoseidonParams { round_keys, mds }
+}
+
+/// Generate the salts for the compression function by hashing a domain tag together with the level,
+/// so that they are independent of the Anemoi-Jive salts.
+fn generate_salts() -> Vec<BLSScalar> {
+    let tag = BLSScalar::from(&BigUint::from_bytes_le(b"Poseidon381 salts"));
+    (0..POSEIDON_NUM_SALTS)
+        .map(|i| Poseidon381::eval_variable_length_hash(&[tag, BLSScalar::from(i as u64)]))
+        .collect()
+}
+
+/// The Poseidon hash function over BLS12-381 with the S-box `x^5`,
+/// instantiated as `poseidonperm_x5_255_3` in the reference implementation.
+pub struct Poseidon381;
+
+impl Poseidon381 {
+    /// Return the parameters.
+    pub fn get_params() -> &'static PoseidonParams<BLSScalar> {
+        &POSEIDON_381_PARAMS
+    }
+
+    /// Return whether the round is a full round, in which every state element goes through the S-box.
+    pub fn is_full_round(round: usize) -> bool {
+        round < POSEIDON_FULL_ROUNDS / 2
+            || round >= POSEIDON_FULL_ROUNDS / 2 + POSEIDON_PARTIAL_ROUNDS
+    }
+
+    /// Eval the Poseidon permutation.
+    pub fn eval_permutation(input: &[BLSScalar; POSEIDON_WIDTH]) -> [BLSScalar; POSEIDON_WIDTH] {
+        let params = Self::get_params();
+        let mut state = *input;
+
+        for (round, keys) in params.round_keys.iter().enumerate() {
+            for (s, k) in state.iter_mut().zip(keys.iter()) {
+                *s = s.add(k);
+            }
+
+            if Self::is_full_round(round) {
+                for s in state.iter_mut() {
+                    *s = s.pow(&[5u64]);
+                }
+            } else {
+                state[0] = state[0].pow(&[5u64]);
+            }
+
+            let mut new_state = [BLSScalar::zero(); POSEIDON_WIDTH];
+            for (new_s, row) in new_state.iter_mut().zip(params.mds.iter()) {
+                for (m, s) in row.iter().zip(state.iter()) {
+                    *new_s = new_s.add(&m.mul(s));
+                }
+            }
+            state = new_state;
+        }
+        state
+    }
+
+    /// Eval the Poseidon sponge, which initializes the capacity with the input length,
+    /// absorbs the input two elements at a time with zeros padded at the end,
+    /// and outputs the first element of the rate.
+    pub fn eval_variable_length_hash(input: &[BLSScalar]) -> BLSScalar {
+        let mut state = [
+            BLSScalar::from(input.len() as u64),
+            BLSScalar::zero(),
+            BLSScalar::zero(),
+        ];
+
+        let mut absorb = |chunk: &[BLSScalar]| {
+            for (s, v) in state[1..].iter_mut().zip(chunk.iter()) {
+                *s = s.add(v);
+            }
+            state = Self::eval_permutation(&state);
+        };
+        if input.is_empty() {
+            absorb(&[]);
+        }
+        for chunk in input.chunks(POSEIDON_WIDTH - 1) {
+            absorb(chunk);
+        }
+
+        state[1]
+    }
+}
+
+impl ArithmeticHash<BLSScalar> for Poseidon381 {
+    fn hash(input: &[BLSScalar]) -> BLSScalar {
+        Self::eval_variable_length_hash(input)
+    }
+
+    fn compress(input: &[BLSScalar; 3], salt: &BLSScalar) -> BLSScalar {
+        Self::eval_variable_length_hash(&[input[0], input[1], input[2], *salt])
+    }
+
+    fn salts() -> &'static [BLSScalar] {
+        &POSEIDON_381_SALTS
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::basic::anemoi_jive::{AnemoiJive, AnemoiJive381};
+    use noah_algebra::new_bls12_381;
+
+    #[test]
+    fn test_permutation() {
+        // the test vector of `poseidonperm_x5_255_3` in the reference implementation
+        let output = Poseidon381::eval_permutation(&[
+            BLSScalar::zero(),
+            BLSScalar::one(),
+            BLSScalar::from(2u32),
+        ]);
+        assert_eq!(
+            output,
+            [
+                new_bls12_381!(
+                    "18456658763349757341014058622209659766100673761449600566550821987295786346378"
+                ),
+                new_bls12_381!(
+                    "37068251774887509885063625701815026138353041152735229476479055620962268601796"
+                ),
+                new_bls12_381!(
+                    "26763157702141528937904191329664859174584798817251788852101947537759678822298"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_variable_length_hash() {
+        let input = (1..=4u32).map(BLSScalar::from).collect::<Vec<_>>();
+
+        assert_eq!(
+            Poseidon381::eval_variable_length_hash(&[]),
+            new_bls12_381!(
+                "7537180076518580051102512563888216212546078608480875576648073859804775902526"
+            )
+        );
+        assert_eq!(
+            Poseidon381::eval_variable_length_hash(&input[..1]),
+            new_bls12_381!(
+                "49869271545601197545211573054862830882440053307466160302709886879831572270524"
+            )
+        );
+        assert_eq!(
+            Poseidon381::eval_variable_length_hash(&input[..3]),
+            new_bls12_381!(
+                "25429572626228616085148464102860182505402248123997325001366458880238119416105"
+            )
+        );
+        assert_eq!(
+            Poseidon381::eval_variable_length_hash(&input),
+            new_bls12_381!(
+                "39776265280381123705933484543615352611441351314998116776377803618970449346621"
+            )
+        );
+
+        // the input length separates inputs that differ in trailing zeros
+        assert_ne!(
+            Poseidon381::eval_variable_length_hash(&input[..1]),
+            Poseidon381::eval_variable_length_hash(&[input[0], BLSScalar::zero()])
+        );
+    }
+
+    #[test]
+    fn test_arithmetic_hash() {
+        let input = [
+            BLSScalar::from(1u32),
+            BLSScalar::from(2u32),
+            BLSScalar::from(3u32),
+        ];
+        let salt = BLSScalar::from(4u32);
+
+        assert_eq!(
+            <Poseidon381 as ArithmeticHash<BLSScalar>>::hash(&input),
+            Poseidon381::eval_variable_length_hash(&input)
+        );
+        assert_eq!(
+            <Poseidon381 as ArithmeticHash<BLSScalar>>::compress(&input, &salt),
+            Poseidon381::eval_variable_length_hash(&[input[0], input[1], input[2], salt])
+        );
+
+        assert_eq!(
+            <AnemoiJive381 as ArithmeticHash<BLSScalar>>::hash(&input),
+            AnemoiJive381::eval_variable_length_hash(&input)
+        );
+        assert_eq!(
+            <AnemoiJive381 as ArithmeticHash<BLSScalar>>::compress(&input, &salt),
+            AnemoiJive381::eval_jive(&[input[0], input[1]], &[input[2], salt])
+        );
+
+        let poseidon_salts = <Poseidon381 as ArithmeticHash<BLSScalar>>::salts();
+        let anemoi_salts = <AnemoiJive381 as ArithmeticHash<BLSScalar>>::salts();
+        assert_eq!(poseidon_salts.len(), anemoi_salts.len());
+        assert!(poseidon_salts.iter().all_unique());
+        assert!(poseidon_salts
+            .iter()
+            .all(|salt| !anemoi_salts.contains(salt)));
+    }
+}
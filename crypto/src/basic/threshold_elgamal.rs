@@ -0,0 +1,447 @@
+use crate::basic::chaum_pedersen::{
+    chaum_pedersen_prove_dleq, chaum_pedersen_verify_dleq, ChaumPedersenDLEqProof,
+};
+use crate::basic::elgamal::{ElGamalCiphertext, ElGamalDecKey, ElGamalEncKey};
+use merlin::Transcript;
+use noah_algebra::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// A Shamir share of an ElGamal decryption key.
+pub struct ElGamalDecKeyShare<S> {
+    /// The index of the share, i.e., the point where the sharing polynomial is evaluated, starting from 1.
+    pub(crate) index: u32,
+    /// The evaluation of the sharing polynomial.
+    pub(crate) share: S,
+}
+
+impl<S: Scalar> ElGamalDecKeyShare<S> {
+    /// Return the index of the share.
+    pub fn get_index(&self) -> u32 {
+        self.index
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// The public parameters of an ElGamal key shared among several parties.
+pub struct ThresholdElGamalParams<G> {
+    /// The number of shares needed for a decryption.
+    pub threshold: usize,
+    /// The number of shares.
+    pub num_shares: usize,
+    /// The Feldman commitments `a_k * G` to the coefficients of the sharing polynomial,
+    /// where the first one is the encryption key.
+    pub coefficient_commitments: Vec<G>,
+}
+
+impl<G: Group> ThresholdElGamalParams<G> {
+    /// Return the encryption key.
+    pub fn get_enc_key(&self) -> ElGamalEncKey<G> {
+        ElGamalEncKey(self.coefficient_commitments[0])
+    }
+
+    /// Return the verification key `s_i * G` of the share with the given index.
+    pub fn get_verification_key(&self, index: u32) -> G {
+        let x = G::ScalarType::from(index);
+        let mut res = G::get_identity();
+        for commitment in self.coefficient_commitments.iter().rev() {
+            res = res.mul(&x).add(commitment);
+        }
+        res
+    }
+
+    /// Check that a share of the decryption key is consistent with the commitments.
+    pub fn verify_key_share(&self, key_share: &ElGamalDecKeyShare<G::ScalarType>) -> Result<()> {
+        if key_share.index == 0 || key_share.index as usize > self.num_shares {
+            return Err(eg!(NoahError::ParameterError));
+        }
+        if G::get_base().mul(&key_share.share) != self.get_verification_key(key_share.index) {
+            return Err(eg!(NoahError::ElGamalVerificationError));
+        }
+        Ok(())
+    }
+
+    fn check(&self) -> Result<()> {
+        if self.threshold == 0
+            || self.threshold > self.num_shares
+            || self.num_shares > u32::MAX as usize
+            || self.coefficient_commitments.len() != self.threshold
+        {
+            return Err(eg!(NoahError::ParameterError));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// A decryption share of an ElGamal ciphertext, with a proof of correctness.
+pub struct ElGamalDecShare<S, G> {
+    /// The index of the key share.
+    pub(crate) index: u32,
+    /// The partial decryption `s_i * e1`.
+    pub(crate) point: G,
+    /// The proof that `point` and the verification key have the same discrete logarithm.
+    pub(crate) proof: ChaumPedersenDLEqProof<S, G>,
+}
+
+impl<S, G> ElGamalDecShare<S, G> {
+    /// Return the index of the key share used for the decryption share.
+    pub fn get_index(&self) -> u32 {
+        self.index
+    }
+}
+
+/// Split an ElGamal decryption key into `num_shares` shares, any `threshold` of which can decrypt.
+/// The dealer learns the key and should erase it afterwards.
+pub fn threshold_elgamal_split<R: CryptoRng + RngCore, G: Group>(
+    prng: &mut R,
+    dec_key: &ElGamalDecKey<G::ScalarType>,
+    threshold: usize,
+    num_shares: usize,
+) -> Result<(
+    ThresholdElGamalParams<G>,
+    Vec<ElGamalDecKeyShare<G::ScalarType>>,
+)> {
+    if threshold == 0 || threshold > num_shares || num_shares > u32::MAX as usize {
+        return Err(eg!(NoahError::ParameterError));
+    }
+
+    let mut coefficients = vec![dec_key.0];
+    for _ in 1..threshold {
+        coefficients.push(G::ScalarType::random(prng));
+    }
+
+    let base = G::get_base();
+    let coefficient_commitments = coefficients.iter().map(|a| base.mul(a)).collect();
+
+    let key_shares = (1..=num_shares as u32)
+        .map(|index| {
+            let x = G::ScalarType::from(index);
+            let mut share = G::ScalarType::zero();
+            for a in coefficients.iter().rev() {
+                share = share.mul(&x).add(a);
+            }
+            ElGamalDecKeyShare { index, share }
+        })
+        .collect();
+
+    Ok((
+        ThresholdElGamalParams {
+            threshold,
+            num_shares,
+            coefficient_commitments,
+        },
+        key_shares,
+    ))
+}
+
+/// Generate a fresh ElGamal key split into `num_shares` shares, any `threshold` of which can decrypt.
+///
+/// This is also the dealing of one party in the distributed key generation: every party deals a
+/// fresh key, sends the i-th share to the i-th party, and publishes the parameters.
+/// The parties then run `threshold_elgamal_dkg_combine`, so that nobody learns the joint key.
+pub fn threshold_elgamal_key_gen<R: CryptoRng + RngCore, G: Group>(
+    prng: &mut R,
+    threshold: usize,
+    num_shares: usize,
+) -> Result<(
+    ThresholdElGamalParams<G>,
+    Vec<ElGamalDecKeyShare<G::ScalarType>>,
+)> {
+    let dec_key = ElGamalDecKey(G::ScalarType::random(prng));
+    threshold_elgamal_split(prng, &dec_key, threshold, num_shares)
+}
+
+/// Combine the dealings received by one party in the distributed key generation,
+/// i.e., the parameters published by every dealer and the share that the dealer sent to this party.
+/// Return the joint parameters and the share of the joint decryption key of this party.
+pub fn threshold_elgamal_dkg_combine<G: Group>(
+    dealings: &[(ThresholdElGamalParams<G>, ElGamalDecKeyShare<G::ScalarType>)],
+) -> Result<(ThresholdElGamalParams<G>, ElGamalDecKeyShare<G::ScalarType>)> {
+    if dealings.is_empty() {
+        return Err(eg!(NoahError::ParameterError));
+    }
+    let (first_params, first_share) = &dealings[0];
+    first_params.check().c(d!())?;
+
+    let mut coefficient_commitments = vec![G::get_identity(); first_params.threshold];
+    let mut share = G::ScalarType::zero();
+    for (params, key_share) in dealings.iter() {
+        if params.threshold != first_params.threshold
+            || params.num_shares != first_params.num_shares
+            || key_share.index != first_share.index
+        {
+            return Err(eg!(NoahError::ParameterError));
+        }
+        params.check().c(d!())?;
+        params.verify_key_share(key_share).c(d!())?;
+
+        for (acc, commitment) in coefficient_commitments
+            .iter_mut()
+            .zip(params.coefficient_commitments.iter())
+        {
+            *acc = acc.add(commitment);
+        }
+        share = share.add(&key_share.share);
+    }
+
+    Ok((
+        ThresholdElGamalParams {
+            threshold: first_params.threshold,
+            num_shares: first_params.num_shares,
+            coefficient_commitments,
+        },
+        ElGamalDecKeyShare {
+            index: first_share.index,
+            share,
+        },
+    ))
+}
+
+fn init_dec_share_transcript<G: Group>(index: u32, ctext: &ElGamalCiphertext<G>) -> Transcript {
+    let mut transcript = Transcript::new(b"Threshold ElGamal Decryption Share");
+    transcript.append_u64(b"index", index as u64);
+    transcript.append_message(b"e1", &ctext.e1.to_compressed_bytes());
+    transcript.append_message(b"e2", &ctext.e2.to_compressed_bytes());
+    transcript
+}
+
+/// Compute the decryption share `s_i * e1` of a ciphertext,
+/// with a Chaum-Pedersen proof that it is consistent with the verification key `s_i * G`.
+pub fn threshold_elgamal_partial_decrypt<R: CryptoRng + RngCore, G: Group>(
+    prng: &mut R,
+    ctext: &ElGamalCiphertext<G>,
+    key_share: &ElGamalDecKeyShare<G::ScalarType>,
+) -> ElGamalDecShare<G::ScalarType, G> {
+    let base = G::get_base();
+    let verification_key = base.mul(&key_share.share);
+    let point = ctext.e1.mul(&key_share.share);
+
+    let mut transcript = init_dec_share_transcript(key_share.index, ctext);
+    let proof = chaum_pedersen_prove_dleq(
+        &mut transcript,
+        prng,
+        &key_share.share,
+        &base,
+        &verification_key,
+        &ctext.e1,
+        &point,
+    );
+
+    ElGamalDecShare {
+        index: key_share.index,
+        point,
+        proof,
+    }
+}
+
+/// Verify the proof of a decryption share against the verification key derived from the parameters.
+pub fn threshold_elgamal_verify_dec_share<R: CryptoRng + RngCore, G: Group>(
+    prng: &mut R,
+    params: &ThresholdElGamalParams<G>,
+    ctext: &ElGamalCiphertext<G>,
+    dec_share: &ElGamalDecShare<G::ScalarType, G>,
+) -> Result<()> {
+    if dec_share.index == 0 || dec_share.index as usize > params.num_shares {
+        return Err(eg!(NoahError::ParameterError));
+    }
+    let verification_key = params.get_verification_key(dec_share.index);
+
+    let mut transcript = init_dec_share_transcript(dec_share.index, ctext);
+    chaum_pedersen_verify_dleq(
+        &mut transcript,
+        prng,
+        &G::get_base(),
+        &verification_key,
+        &ctext.e1,
+        &dec_share.point,
+        &dec_share.proof,
+    )
+    .c(d!())
+}
+
+/// Return the Lagrange coefficient at zero of `index` for the set `indices`.
+pub(crate) fn lagrange_coefficient_at_zero<S: Scalar>(index: u32, indices: &[u32]) -> Result<S> {
+    let x_i = S::from(index);
+    let mut num = S::one();
+    let mut den = S::one();
+    for &j in indices.iter().filter(|&&j| j != index) {
+        let x_j = S::from(j);
+        num = num.mul(&x_j);
+        den = den.mul(&x_j.sub(&x_i));
+    }
+    Ok(num.mul(&den.inv().c(d!())?))
+}
+
+/// Combine at least `threshold` decryption shares with distinct indices into `m * G`,
+/// the same result as `elgamal_partial_decrypt` with the joint decryption key.
+/// Every share is verified and the invalid ones are skipped, so that any `threshold` valid shares
+/// suffice; Err([NoahError::ParameterError]) is returned if there are not enough of them.
+pub fn threshold_elgamal_combine<R: CryptoRng + RngCore, G: Group>(
+    prng: &mut R,
+    params: &ThresholdElGamalParams<G>,
+    ctext: &ElGamalCiphertext<G>,
+    dec_shares: &[ElGamalDecShare<G::ScalarType, G>],
+) -> Result<G> {
+    params.check().c(d!())?;
+
+    let mut indices: Vec<u32> = vec![];
+    let mut points = vec![];
+    for dec_share in dec_shares.iter() {
+        if indices.contains(&dec_share.index)
+            || threshold_elgamal_verify_dec_share(prng, params, ctext, dec_share).is_err()
+        {
+            continue;
+        }
+        indices.push(dec_share.index);
+        points.push(dec_share.point);
+        if indices.len() == params.threshold {
+            break;
+        }
+    }
+    if indices.len() < params.threshold {
+        return Err(eg!(NoahError::ParameterError));
+    }
+
+    let mut shared_point = G::get_identity();
+    for (index, point) in indices.iter().zip(points.iter()) {
+        let coefficient =
+            lagrange_coefficient_at_zero::<G::ScalarType>(*index, &indices).c(d!())?;
+        shared_point = shared_point.add(&point.mul(&coefficient));
+    }
+    Ok(ctext.e2.sub(&shared_point))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::basic::elgamal::{elgamal_encrypt, elgamal_key_gen, elgamal_partial_decrypt};
+    use ark_std::test_rng;
+    use noah_algebra::bls12_381::BLSG1;
+    use noah_algebra::ristretto::{RistrettoPoint, RistrettoScalar};
+
+    fn threshold_decryption<G: Group>() {
+        let mut prng = test_rng();
+        let (dec_key, enc_key) = elgamal_key_gen::<_, G>(&mut prng);
+        let (params, key_shares) =
+            threshold_elgamal_split::<_, G>(&mut prng, &dec_key, 3, 5).unwrap();
+        assert_eq!(params.get_enc_key(), enc_key);
+        for key_share in key_shares.iter() {
+            pnk!(params.verify_key_share(key_share));
+        }
+
+        let m = G::ScalarType::from(1234u32);
+        let r = G::ScalarType::random(&mut prng);
+        let ctext = elgamal_encrypt(&m, &r, &enc_key);
+        let expected = elgamal_partial_decrypt(&ctext, &dec_key);
+
+        let dec_shares = key_shares
+            .iter()
+            .map(|key_share| threshold_elgamal_partial_decrypt(&mut prng, &ctext, key_share))
+            .collect_vec();
+
+        // Any three shares suffice.
+        for subset in [[0usize, 1, 2], [4, 2, 0], [1, 3, 4]] {
+            let shares = subset.iter().map(|&i| dec_shares[i].clone()).collect_vec();
+            assert_eq!(
+                threshold_elgamal_combine(&mut prng, &params, &ctext, &shares).unwrap(),
+                expected
+            );
+        }
+
+        // Two shares, or three shares with a duplicate, are not enough.
+        msg_eq!(
+            NoahError::ParameterError,
+            threshold_elgamal_combine(&mut prng, &params, &ctext, &dec_shares[0..2]).unwrap_err()
+        );
+        let shares = vec![
+            dec_shares[0].clone(),
+            dec_shares[1].clone(),
+            dec_shares[1].clone(),
+        ];
+        msg_eq!(
+            NoahError::ParameterError,
+            threshold_elgamal_combine(&mut prng, &params, &ctext, &shares).unwrap_err()
+        );
+
+        // A share with a wrong partial decryption is skipped.
+        let mut bad_share = dec_shares[2].clone();
+        bad_share.point = bad_share.point.add(&G::get_base());
+        let shares = vec![
+            dec_shares[0].clone(),
+            bad_share.clone(),
+            dec_shares[1].clone(),
+        ];
+        msg_eq!(
+            NoahError::ParameterError,
+            threshold_elgamal_combine(&mut prng, &params, &ctext, &shares).unwrap_err()
+        );
+        let shares = vec![
+            dec_shares[0].clone(),
+            bad_share,
+            dec_shares[1].clone(),
+            dec_shares[3].clone(),
+        ];
+        assert_eq!(
+            threshold_elgamal_combine(&mut prng, &params, &ctext, &shares).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn threshold_decrypt() {
+        threshold_decryption::<RistrettoPoint>();
+        threshold_decryption::<BLSG1>();
+    }
+
+    #[test]
+    fn distributed_key_generation() {
+        let mut prng = test_rng();
+        let (threshold, num_shares) = (2, 3);
+
+        // Every party deals a fresh key.
+        let dealings = (0..num_shares)
+            .map(|_| {
+                threshold_elgamal_key_gen::<_, RistrettoPoint>(&mut prng, threshold, num_shares)
+                    .unwrap()
+            })
+            .collect_vec();
+
+        // Every party combines the shares sent to it.
+        let mut joint_params = vec![];
+        let mut joint_shares = vec![];
+        for i in 0..num_shares {
+            let received = dealings
+                .iter()
+                .map(|(params, key_shares)| (params.clone(), key_shares[i].clone()))
+                .collect_vec();
+            let (params, key_share) = threshold_elgamal_dkg_combine(&received).unwrap();
+            joint_params.push(params);
+            joint_shares.push(key_share);
+        }
+        assert!(joint_params.iter().all(|params| *params == joint_params[0]));
+        let params = &joint_params[0];
+
+        let m = RistrettoScalar::from(42u32);
+        let r = RistrettoScalar::random(&mut prng);
+        let ctext = elgamal_encrypt(&m, &r, &params.get_enc_key());
+        let shares = joint_shares[1..]
+            .iter()
+            .map(|key_share| threshold_elgamal_partial_decrypt(&mut prng, &ctext, key_share))
+            .collect_vec();
+        assert_eq!(
+            threshold_elgamal_combine(&mut prng, params, &ctext, &shares).unwrap(),
+            RistrettoPoint::get_base().mul(&m)
+        );
+
+        // A share that does not match the commitments of its dealer is rejected.
+        let mut received = dealings
+            .iter()
+            .map(|(params, key_shares)| (params.clone(), key_shares[0].clone()))
+            .collect_vec();
+        received[1].1.share = received[1].1.share.add(&RistrettoScalar::one());
+        msg_eq!(
+            NoahError::ElGamalVerificationError,
+            threshold_elgamal_dkg_combine(&received).unwrap_err()
+        );
+    }
+}
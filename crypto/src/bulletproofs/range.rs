@@ -7,9 +7,28 @@ use merlin::Transcript;
 use noah_algebra::prelude::*;
 use noah_algebra::ristretto::CompressedRistretto;
 use noah_algebra::ristretto::RistrettoScalar as Scalar;
+use noah_algebra::utils::min_greater_equal_power_of_two;
+
+/// The bit widths supported by the Bulletproof range proofs.
+pub const SUPPORTED_RANGE_BITS: [usize; 4] = [8, 16, 32, 64];
+
+/// Check that `log_range_upper_bound` is a supported bit width.
+pub fn check_range_bits(log_range_upper_bound: usize) -> Result<()> {
+    if SUPPORTED_RANGE_BITS.contains(&log_range_upper_bound) {
+        Ok(())
+    } else {
+        Err(eg!(NoahError::ParameterError))
+    }
+}
+
+/// Return the number of values in an aggregated proof for `num_values` values,
+/// i.e., the next power of two, which is the number of parties in the generators.
+pub fn aggregated_range_size(num_values: usize) -> usize {
+    min_greater_equal_power_of_two(num_values as u32) as usize
+}
 
 /// Generate a Bulletproof range proof that values committed using `blindings`
-/// are within [0..2^{`log_range_upper_bound`}-1].
+/// are within [0..2^{`log_range_upper_bound`}-1]. Values out of the range are rejected.
 pub fn prove_ranges(
     bp_gens: &BulletproofGens,
     transcript: &mut Transcript,
@@ -17,6 +36,10 @@ pub fn prove_ranges(
     blindings: &[Scalar],
     log_range_upper_bound: usize,
 ) -> Result<(RangeProof, Vec<CompressedRistretto>)> {
+    check_range_bits(log_range_upper_bound).c(d!(NoahError::RangeProofProveError))?;
+    if log_range_upper_bound < 64 && values.iter().any(|v| v >> log_range_upper_bound != 0) {
+        return Err(eg!(NoahError::RangeProofProveError));
+    }
     let blindings = blindings.iter().map(|s| s.0).collect_vec();
     let pc_gens = PedersenGens::default();
     let (proof, coms) = RangeProof::prove_multiple(
@@ -32,6 +55,33 @@ pub fn prove_ranges(
     Ok((proof, commitments))
 }
 
+/// Generate an aggregated Bulletproof range proof, padding `values` and `blindings` with zeros
+/// to the next power of two. The returned commitments include those of the padding.
+pub fn prove_ranges_padded(
+    bp_gens: &BulletproofGens,
+    transcript: &mut Transcript,
+    values: &[u64],
+    blindings: &[Scalar],
+    log_range_upper_bound: usize,
+) -> Result<(RangeProof, Vec<CompressedRistretto>)> {
+    if values.len() != blindings.len() {
+        return Err(eg!(NoahError::RangeProofProveError));
+    }
+    let size = aggregated_range_size(values.len());
+    let mut values = values.to_vec();
+    values.resize(size, 0u64);
+    let mut blindings = blindings.to_vec();
+    blindings.resize(size, Scalar::zero());
+    prove_ranges(
+        bp_gens,
+        transcript,
+        &values,
+        &blindings,
+        log_range_upper_bound,
+    )
+    .c(d!())
+}
+
 /// Batch-verify a set bulletproof range proofs
 /// State of transcripts should match the state just before each proof was computed
 pub fn batch_verify_ranges<R: CryptoRng + RngCore>(
@@ -42,6 +92,7 @@ pub fn batch_verify_ranges<R: CryptoRng + RngCore>(
     commitments: &[&[CompressedRistretto]],
     log_range_upper_bound: usize,
 ) -> Result<()> {
+    check_range_bits(log_range_upper_bound).c(d!(NoahError::RangeProofVerifyError))?;
     let pc_gens = PedersenGens::default();
     let mut comms = vec![];
     for slice in commitments {
@@ -64,3 +115,81 @@ pub fn batch_verify_ranges<R: CryptoRng + RngCore>(
     )
     .c(d!(NoahError::RangeProofVerifyError))
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::bulletproofs::range::{
+        batch_verify_ranges, prove_ranges, prove_ranges_padded, SUPPORTED_RANGE_BITS,
+    };
+    use ark_std::test_rng;
+    use bulletproofs::BulletproofGens;
+    use merlin::Transcript;
+    use noah_algebra::{prelude::*, ristretto::RistrettoScalar};
+
+    #[test]
+    fn test_range_proof_widths() {
+        let mut prng = test_rng();
+        let bp_gens = BulletproofGens::new(64, 4);
+
+        for bits in SUPPORTED_RANGE_BITS {
+            let max = if bits == 64 {
+                u64::MAX
+            } else {
+                (1u64 << bits) - 1
+            };
+            let values = [max, 0, 1];
+            let blindings = (0..3)
+                .map(|_| RistrettoScalar::random(&mut prng))
+                .collect_vec();
+
+            let mut transcript = Transcript::new(b"Test");
+            let (proof, commitments) =
+                prove_ranges_padded(&bp_gens, &mut transcript, &values, &blindings, bits).unwrap();
+            assert_eq!(commitments.len(), 4);
+
+            let mut transcripts = [Transcript::new(b"Test")];
+            assert!(batch_verify_ranges(
+                &mut prng,
+                &bp_gens,
+                &[&proof],
+                &mut transcripts,
+                &[&commitments],
+                bits,
+            )
+            .is_ok());
+        }
+
+        // out of range
+        let blindings = [RistrettoScalar::random(&mut prng)];
+        let mut transcript = Transcript::new(b"Test");
+        msg_eq!(
+            NoahError::RangeProofProveError,
+            prove_ranges(&bp_gens, &mut transcript, &[1u64 << 32], &blindings, 32).unwrap_err()
+        );
+
+        // a proof for a wider range does not verify at a smaller width
+        let mut transcript = Transcript::new(b"Test");
+        let (proof, commitments) =
+            prove_ranges(&bp_gens, &mut transcript, &[1u64 << 32], &blindings, 64).unwrap();
+        let mut transcripts = [Transcript::new(b"Test")];
+        msg_eq!(
+            NoahError::RangeProofVerifyError,
+            batch_verify_ranges(
+                &mut prng,
+                &bp_gens,
+                &[&proof],
+                &mut transcripts,
+                &[&commitments],
+                32,
+            )
+            .unwrap_err()
+        );
+
+        // unsupported width
+        let mut transcript = Transcript::new(b"Test");
+        msg_eq!(
+            NoahError::ParameterError,
+            prove_ranges(&bp_gens, &mut transcript, &[1u64], &blindings, 12).unwrap_err()
+        );
+    }
+}
@@ -1,13 +1,16 @@
 use crate::anon_creds::{
-    verify_pok, Attribute,
-    Attribute::{Hidden, Revealed},
-    Credential, CredentialComm, CredentialCommRandomizer, CredentialIssuerPK, CredentialPoK,
-    CredentialUserSK, POK_LABEL,
+    non_revocation_append_proof, non_revocation_commit, randomizer_gen, verify_non_revocation,
+    verify_pok, Attribute::Hidden, Credential, CredentialComm, CredentialCommRandomizer,
+    CredentialIssuerPK, CredentialPoK, CredentialRevocationList, CredentialUserSK,
+    NonRevocationProver, POK_LABEL,
 };
 use crate::basic::{
     elgamal::{elgamal_encrypt, ElGamalCiphertext, ElGamalEncKey},
     matrix_sigma::SigmaTranscript,
 };
+use crate::credential_predicates::{
+    predicate_append_proof, predicate_commit, verify_predicate, AttributePredicate, PredicateProver,
+};
 use merlin::Transcript;
 use noah_algebra::{prelude::*, traits::Pairing};
 
@@ -108,6 +111,18 @@ pub struct CACPoK<G1, G2, S> {
     pub cm_ct: Vec<ElGamalCiphertext<G1>>,
     /// The responses for individual attributes.
     pub response_rands: Vec<S>,
+    /// The openings of the linked credentials, which share the user secret key.
+    #[serde(default)]
+    pub linked: Vec<CACLinkedPoK<G1, G2, S>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// The confidential opening of a further credential, linked to the main credential of a `CACPoK`.
+pub struct CACLinkedPoK<G1, G2, S> {
+    /// The commitment to the linked credential.
+    pub cm: CredentialComm<G1>,
+    /// The proof of knowledge, whose response for the user secret key equals the main one.
+    pub pok: CACPoK<G1, G2, S>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -141,45 +156,187 @@ pub fn confidential_open_comm<R: CryptoRng + RngCore, P: Pairing>(
     ek: &ElGamalEncKey<P::G1>,
     m: &[u8],
 ) -> Result<ConfidentialAC<P::G1, P::G2, P::ScalarField>> {
-    // 1. create ciphertext for all revealed attributes
-    let mut cts = vec![];
-    let mut ct_rands = vec![];
-    let mut revealed_attrs = vec![];
-    if credential.attrs.len() != reveal_map.len() {
-        return Err(eg!(NoahError::ParameterError));
-    }
-    for (attr, b) in credential.attrs.iter().zip(reveal_map.iter()) {
-        if *b {
-            let r = P::ScalarField::random(prng);
-            let ct = elgamal_encrypt::<P::G1>(attr, &r, ek);
-            ct_rands.push(r);
-            cts.push(ct);
-            revealed_attrs.push(*attr);
-        }
-    }
-    // 2. Do PoK
-    let mut attributes = vec![];
-    for (attr, b) in credential.attrs.iter().zip(reveal_map.iter()) {
-        if *b {
-            attributes.push(Revealed(attr));
-        } else {
-            attributes.push(Hidden(Some(attr)));
-        }
-    }
+    confidential_open_comm_internal::<_, P>(
+        prng,
+        usk,
+        credential,
+        cm,
+        rand,
+        reveal_map,
+        ek,
+        m,
+        &[],
+        None,
+        &[],
+    )
+    .c(d!())
+}
+
+/// Selectively open some attributes committed in `cm` to ciphertexts as in
+/// `confidential_open_comm`, and additionally prove in zero-knowledge that the revocation handle
+/// of the credential is not in `revocation_list`.
+#[allow(clippy::too_many_arguments)]
+pub fn confidential_open_comm_with_revocation<R: CryptoRng + RngCore, P: Pairing>(
+    prng: &mut R,
+    usk: &CredentialUserSK<P::ScalarField>,
+    credential: &Credential<P::G1, P::G2, P::ScalarField>,
+    cm: &CredentialComm<P::G1>,
+    rand: &CredentialCommRandomizer<P::ScalarField>,
+    reveal_map: &[bool],
+    ek: &ElGamalEncKey<P::G1>,
+    m: &[u8],
+    revocation_list: &CredentialRevocationList<P::ScalarField>,
+) -> Result<ConfidentialAC<P::G1, P::G2, P::ScalarField>> {
+    confidential_open_comm_internal::<_, P>(
+        prng,
+        usk,
+        credential,
+        cm,
+        rand,
+        reveal_map,
+        ek,
+        m,
+        &[],
+        Some(revocation_list),
+        &[],
+    )
+    .c(d!())
+}
+
+/// Selectively open some attributes committed in `cm` to ciphertexts as in
+/// `confidential_open_comm`, and additionally prove in zero-knowledge that each attribute with
+/// an entry in `predicate_map` satisfies the predicate, and, if `revocation_list` is given,
+/// that the credential is not revoked.
+/// The `predicate_map` is either empty or has one entry per attribute.
+#[allow(clippy::too_many_arguments)]
+pub fn confidential_open_comm_with_predicates<R: CryptoRng + RngCore, P: Pairing>(
+    prng: &mut R,
+    usk: &CredentialUserSK<P::ScalarField>,
+    credential: &Credential<P::G1, P::G2, P::ScalarField>,
+    cm: &CredentialComm<P::G1>,
+    rand: &CredentialCommRandomizer<P::ScalarField>,
+    reveal_map: &[bool],
+    ek: &ElGamalEncKey<P::G1>,
+    m: &[u8],
+    predicate_map: &[Option<AttributePredicate<P::ScalarField>>],
+    revocation_list: Option<&CredentialRevocationList<P::ScalarField>>,
+) -> Result<ConfidentialAC<P::G1, P::G2, P::ScalarField>> {
+    confidential_open_comm_internal::<_, P>(
+        prng,
+        usk,
+        credential,
+        cm,
+        rand,
+        reveal_map,
+        ek,
+        m,
+        predicate_map,
+        revocation_list,
+        &[],
+    )
+    .c(d!())
+}
+
+/// Selectively open some attributes committed in `cm` to ciphertexts as in
+/// `confidential_open_comm_with_predicates`, and additionally open some attributes of each of the
+/// `linked` credentials, possibly granted by other issuers, to ciphertexts under the same `ek`,
+/// proving that all credentials belong to `usk`. Each linked credential is given together with its
+/// reveal map, and the ciphertexts of its revealed attributes follow those of `credential`.
+#[allow(clippy::too_many_arguments)]
+pub fn confidential_open_comm_with_linked<R: CryptoRng + RngCore, P: Pairing>(
+    prng: &mut R,
+    usk: &CredentialUserSK<P::ScalarField>,
+    credential: &Credential<P::G1, P::G2, P::ScalarField>,
+    cm: &CredentialComm<P::G1>,
+    rand: &CredentialCommRandomizer<P::ScalarField>,
+    reveal_map: &[bool],
+    ek: &ElGamalEncKey<P::G1>,
+    m: &[u8],
+    predicate_map: &[Option<AttributePredicate<P::ScalarField>>],
+    revocation_list: Option<&CredentialRevocationList<P::ScalarField>>,
+    linked: &[(&Credential<P::G1, P::G2, P::ScalarField>, &[bool])],
+) -> Result<ConfidentialAC<P::G1, P::G2, P::ScalarField>> {
+    confidential_open_comm_internal::<_, P>(
+        prng,
+        usk,
+        credential,
+        cm,
+        rand,
+        reveal_map,
+        ek,
+        m,
+        predicate_map,
+        revocation_list,
+        linked,
+    )
+    .c(d!())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn confidential_open_comm_internal<R: CryptoRng + RngCore, P: Pairing>(
+    prng: &mut R,
+    usk: &CredentialUserSK<P::ScalarField>,
+    credential: &Credential<P::G1, P::G2, P::ScalarField>,
+    cm: &CredentialComm<P::G1>,
+    rand: &CredentialCommRandomizer<P::ScalarField>,
+    reveal_map: &[bool],
+    ek: &ElGamalEncKey<P::G1>,
+    m: &[u8],
+    predicate_map: &[Option<AttributePredicate<P::ScalarField>>],
+    revocation_list: Option<&CredentialRevocationList<P::ScalarField>>,
+    linked: &[(&Credential<P::G1, P::G2, P::ScalarField>, &[bool])],
+) -> Result<ConfidentialAC<P::G1, P::G2, P::ScalarField>> {
     let mut transcript = Transcript::new(CAC_REVEAL_PROOF_NEW_TRANSCRIPT_INSTANCE);
-    let pok = confidential_prove_pok::<_, P>(
+    // the blinding of the user secret key is shared with the linked credentials
+    let r_sk = P::ScalarField::random(prng);
+    let (mut cts, prover) = confidential_pok_commit::<_, P>(
         &mut transcript,
         prng,
         usk,
-        &credential.ipk,
-        &rand,
-        attributes.as_slice(),
-        &cm,
+        credential,
+        cm,
+        rand,
+        reveal_map,
         ek,
-        cts.as_slice(),
-        ct_rands.as_slice(),
-        m,
-    );
+        Some(m),
+        predicate_map,
+        revocation_list,
+        r_sk,
+    )
+    .c(d!())?;
+
+    let mut linked_provers = vec![];
+    for (linked_credential, linked_reveal_map) in linked.iter() {
+        let linked_rand = randomizer_gen::<_, P>(prng);
+        let linked_cm = CredentialComm::new(&linked_credential.sig, &linked_rand);
+        let (linked_cts, linked_prover) = confidential_pok_commit::<_, P>(
+            &mut transcript,
+            prng,
+            usk,
+            linked_credential,
+            &linked_cm,
+            &linked_rand,
+            linked_reveal_map,
+            ek,
+            None,
+            &[],
+            None,
+            r_sk,
+        )
+        .c(d!())?;
+        cts.extend(linked_cts);
+        linked_provers.push((linked_cm, linked_prover));
+    }
+
+    let challenge = transcript.get_challenge::<P::ScalarField>();
+    let mut pok = prover.respond(&challenge);
+    pok.linked = linked_provers
+        .into_iter()
+        .map(|(cm, prover)| CACLinkedPoK {
+            cm,
+            pok: prover.respond(&challenge),
+        })
+        .collect();
 
     Ok(ConfidentialAC { cts, pok })
 }
@@ -195,117 +352,430 @@ pub fn confidential_verify_open<P: Pairing>(
     pok: &CACPoK<P::G1, P::G2, P::ScalarField>,
     m: &[u8],
 ) -> Result<()> {
-    let n = cts.len();
-    let revealed_count = reveal_map
-        .iter()
-        .fold(0, |sum, b| if *b { sum + 1 } else { sum });
+    confidential_verify_open_internal::<P>(ipk, ek, reveal_map, cm, cts, pok, m, &[], None, &[])
+        .c(d!())
+}
+
+/// Verify a confidential selective opening as in `confidential_verify_open`, and additionally
+/// check the proof that the credential is not revoked in `revocation_list`.
+#[allow(clippy::too_many_arguments)]
+pub fn confidential_verify_open_with_revocation<P: Pairing>(
+    ipk: &CredentialIssuerPK<P::G1, P::G2>,
+    ek: &ElGamalEncKey<P::G1>,
+    reveal_map: &[bool],
+    cm: &CredentialComm<P::G1>,
+    cts: &[ElGamalCiphertext<P::G1>],
+    pok: &CACPoK<P::G1, P::G2, P::ScalarField>,
+    m: &[u8],
+    revocation_list: &CredentialRevocationList<P::ScalarField>,
+) -> Result<()> {
+    confidential_verify_open_internal::<P>(
+        ipk,
+        ek,
+        reveal_map,
+        cm,
+        cts,
+        pok,
+        m,
+        &[],
+        Some(revocation_list),
+        &[],
+    )
+    .c(d!())
+}
+
+/// Verify a confidential selective opening as in `confidential_verify_open`, and additionally
+/// check the proofs that each attribute with an entry in `predicate_map` satisfies the predicate,
+/// and, if `revocation_list` is given, that the credential is not revoked.
+#[allow(clippy::too_many_arguments)]
+pub fn confidential_verify_open_with_predicates<P: Pairing>(
+    ipk: &CredentialIssuerPK<P::G1, P::G2>,
+    ek: &ElGamalEncKey<P::G1>,
+    reveal_map: &[bool],
+    cm: &CredentialComm<P::G1>,
+    cts: &[ElGamalCiphertext<P::G1>],
+    pok: &CACPoK<P::G1, P::G2, P::ScalarField>,
+    m: &[u8],
+    predicate_map: &[Option<AttributePredicate<P::ScalarField>>],
+    revocation_list: Option<&CredentialRevocationList<P::ScalarField>>,
+) -> Result<()> {
+    confidential_verify_open_internal::<P>(
+        ipk,
+        ek,
+        reveal_map,
+        cm,
+        cts,
+        pok,
+        m,
+        predicate_map,
+        revocation_list,
+        &[],
+    )
+    .c(d!())
+}
+
+/// Verify a confidential selective opening as in `confidential_verify_open_with_predicates`,
+/// together with the openings of the linked credentials, where each linked credential is given by
+/// the public key of its issuer and its reveal map.
+#[allow(clippy::too_many_arguments)]
+pub fn confidential_verify_open_with_linked<P: Pairing>(
+    ipk: &CredentialIssuerPK<P::G1, P::G2>,
+    ek: &ElGamalEncKey<P::G1>,
+    reveal_map: &[bool],
+    cm: &CredentialComm<P::G1>,
+    cts: &[ElGamalCiphertext<P::G1>],
+    pok: &CACPoK<P::G1, P::G2, P::ScalarField>,
+    m: &[u8],
+    predicate_map: &[Option<AttributePredicate<P::ScalarField>>],
+    revocation_list: Option<&CredentialRevocationList<P::ScalarField>>,
+    linked: &[(&CredentialIssuerPK<P::G1, P::G2>, &[bool])],
+) -> Result<()> {
+    confidential_verify_open_internal::<P>(
+        ipk,
+        ek,
+        reveal_map,
+        cm,
+        cts,
+        pok,
+        m,
+        predicate_map,
+        revocation_list,
+        linked,
+    )
+    .c(d!())
+}
+
+/// Count the attributes to be revealed, after checking that `reveal_map` has one entry per
+/// attribute of `ipk`.
+fn revealed_count<G1: Group, G2: Group>(
+    ipk: &CredentialIssuerPK<G1, G2>,
+    reveal_map: &[bool],
+) -> Result<usize> {
     if reveal_map.len() != ipk.num_attrs() {
         return Err(eg!(NoahError::ParameterError));
     }
-    if n > ipk.num_attrs()
-        || n != pok.cm_ct.len()
-        || n != pok.response_rands.len()
-        || n != revealed_count
+    Ok(reveal_map.iter().filter(|b| **b).count())
+}
+
+/// Check that the ciphertexts and the ciphertext proofs of `pok` match the revealed attributes.
+fn check_ciphertexts_structure<G1, G2, S>(
+    revealed_count: usize,
+    cts: &[ElGamalCiphertext<G1>],
+    pok: &CACPoK<G1, G2, S>,
+) -> Result<()> {
+    if revealed_count != cts.len()
+        || revealed_count != pok.cm_ct.len()
+        || revealed_count != pok.response_rands.len()
     {
         return Err(eg!(NoahError::IdentityRevealVerifyError));
     }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn confidential_verify_open_internal<P: Pairing>(
+    ipk: &CredentialIssuerPK<P::G1, P::G2>,
+    ek: &ElGamalEncKey<P::G1>,
+    reveal_map: &[bool],
+    cm: &CredentialComm<P::G1>,
+    cts: &[ElGamalCiphertext<P::G1>],
+    pok: &CACPoK<P::G1, P::G2, P::ScalarField>,
+    m: &[u8],
+    predicate_map: &[Option<AttributePredicate<P::ScalarField>>],
+    revocation_list: Option<&CredentialRevocationList<P::ScalarField>>,
+    linked: &[(&CredentialIssuerPK<P::G1, P::G2>, &[bool])],
+) -> Result<()> {
+    let n = revealed_count(ipk, reveal_map).c(d!())?;
+    if !predicate_map.is_empty() && predicate_map.len() != ipk.num_attrs() {
+        return Err(eg!(NoahError::ParameterError));
+    }
+    let mut linked_counts = vec![];
+    for (linked_ipk, linked_reveal_map) in linked.iter() {
+        linked_counts.push(revealed_count(linked_ipk, linked_reveal_map).c(d!())?);
+    }
+    if pok.linked.len() != linked.len() || cts.len() != n + linked_counts.iter().sum::<usize>() {
+        return Err(eg!(NoahError::IdentityRevealVerifyError));
+    }
+    let (main_cts, mut rest) = cts.split_at(n);
+    check_ciphertexts_structure(n, main_cts, pok).c(d!())?;
+    let mut linked_cts = vec![];
+    for (count, linked_pok) in linked_counts.iter().zip(pok.linked.iter()) {
+        // the common response binds the linked credentials to the same user secret key
+        if !linked_pok.pok.linked.is_empty()
+            || linked_pok.pok.pok.response_sk != pok.pok.response_sk
+        {
+            return Err(eg!(NoahError::IdentityRevealVerifyError));
+        }
+        let (head, tail) = rest.split_at(*count);
+        check_ciphertexts_structure(*count, head, &linked_pok.pok).c(d!())?;
+        linked_cts.push(head);
+        rest = tail;
+    }
 
     let mut transcript = Transcript::new(CAC_REVEAL_PROOF_NEW_TRANSCRIPT_INSTANCE);
+    transcript.cac_init::<P>(ipk, ek, cm, main_cts);
+    transcript.append_message(POK_LABEL, m);
+    confidential_pok_append_proof::<P>(&mut transcript, pok, predicate_map, revocation_list)
+        .c(d!())?;
+    for ((linked_ipk, _), (linked_pok, linked_cts)) in
+        linked.iter().zip(pok.linked.iter().zip(linked_cts.iter()))
+    {
+        transcript.cac_init::<P>(linked_ipk, ek, &linked_pok.cm, linked_cts);
+        confidential_pok_append_proof::<P>(&mut transcript, &linked_pok.pok, &[], None).c(d!())?;
+    }
+    let challenge = transcript.get_challenge::<P::ScalarField>();
+
+    confidential_verify_pok::<P>(
+        ipk,
+        ek,
+        cm,
+        main_cts,
+        pok,
+        reveal_map,
+        predicate_map,
+        revocation_list,
+        &challenge,
+    )
+    .c(d!())?;
+    for ((linked_ipk, linked_reveal_map), (linked_pok, linked_cts)) in
+        linked.iter().zip(pok.linked.iter().zip(linked_cts.iter()))
+    {
+        confidential_verify_pok::<P>(
+            linked_ipk,
+            ek,
+            &linked_pok.cm,
+            linked_cts,
+            &linked_pok.pok,
+            linked_reveal_map,
+            &[],
+            None,
+            &challenge,
+        )
+        .c(d!())?;
+    }
+    Ok(())
+}
 
-    confidential_verify_pok::<P>(&mut transcript, ipk, ek, cm, cts, pok, reveal_map, m).c(d!())
+/// The prover state of a confidential proof of knowledge between the commitment and the response.
+struct CACProver<G1, G2, S> {
+    blinding: G2,
+    r_t: S,
+    r_sk: S,
+    t: S,
+    sk: S,
+    // (a_i, r_{a_i}) for each attribute
+    attrs: Vec<(S, S)>,
+    // (rand_i, r_{rand_i}) for each ciphertext
+    ct_rands: Vec<(S, S)>,
+    cm_cts: Vec<ElGamalCiphertext<G1>>,
+    non_revocation: Option<NonRevocationProver<G2, S>>,
+    predicates: Vec<PredicateProver<G2, S>>,
+}
+
+impl<G1: Group, G2: Group<ScalarType = S>, S: Scalar> CACProver<G1, G2, S> {
+    fn respond(self, challenge: &S) -> CACPoK<G1, G2, S> {
+        CACPoK {
+            pok: CredentialPoK {
+                blinding: self.blinding,
+                response_t: challenge.mul(&self.t).add(&self.r_t),
+                response_sk: challenge.mul(&self.sk).add(&self.r_sk),
+                response_attrs: self
+                    .attrs
+                    .iter()
+                    .map(|(attr, r_attr)| challenge.mul(attr).add(r_attr))
+                    .collect(),
+                non_revocation: self.non_revocation.map(|prover| prover.respond(challenge)),
+                predicates: self
+                    .predicates
+                    .into_iter()
+                    .map(|prover| prover.respond(challenge))
+                    .collect(),
+                expiry_bound: None,
+            },
+            cm_ct: self.cm_cts,
+            response_rands: self
+                .ct_rands
+                .iter()
+                .map(|(ct_rand, r_rand)| challenge.mul(ct_rand).add(r_rand))
+                .collect(),
+            linked: vec![],
+        }
+    }
 }
 
-pub(crate) fn confidential_prove_pok<R: CryptoRng + RngCore, P: Pairing>(
+/// Encrypt the attributes of `credential` selected by `reveal_map` and compute the commitment
+/// phase of the proof that the ciphertexts are correct, where `r_sk` is the blinding for the user
+/// secret key and `m`, if given, is the message bound to the proof.
+#[allow(clippy::too_many_arguments)]
+fn confidential_pok_commit<R: CryptoRng + RngCore, P: Pairing>(
     transcript: &mut Transcript,
     prng: &mut R,
     usk: &CredentialUserSK<P::ScalarField>,
-    ipk: &CredentialIssuerPK<P::G1, P::G2>,
-    rand: &CredentialCommRandomizer<P::ScalarField>,
-    attrs: &[Attribute<&P::ScalarField>],
+    credential: &Credential<P::G1, P::G2, P::ScalarField>,
     cm: &CredentialComm<P::G1>,
+    rand: &CredentialCommRandomizer<P::ScalarField>,
+    reveal_map: &[bool],
     ek: &ElGamalEncKey<P::G1>,
-    cts: &[ElGamalCiphertext<P::G1>],
-    ct_rands: &[P::ScalarField],
-    m: &[u8],
-) -> CACPoK<P::G1, P::G2, P::ScalarField> {
-    transcript.cac_init::<P>(ipk, ek, cm, cts);
-    transcript.append_message(POK_LABEL, m); // SoK
+    m: Option<&[u8]>,
+    predicate_map: &[Option<AttributePredicate<P::ScalarField>>],
+    revocation_list: Option<&CredentialRevocationList<P::ScalarField>>,
+    r_sk: P::ScalarField,
+) -> Result<(
+    Vec<ElGamalCiphertext<P::G1>>,
+    CACProver<P::G1, P::G2, P::ScalarField>,
+)> {
+    let ipk = &credential.ipk;
+    // 1. create ciphertext for all revealed attributes
+    if credential.attrs.len() != reveal_map.len() || ipk.num_attrs() != reveal_map.len() {
+        return Err(eg!(NoahError::ParameterError));
+    }
+    let mut cts = vec![];
+    let mut ct_rands = vec![];
+    for (attr, b) in credential.attrs.iter().zip(reveal_map.iter()) {
+        if *b {
+            let r = P::ScalarField::random(prng);
+            cts.push(elgamal_encrypt::<P::G1>(attr, &r, ek));
+            ct_rands.push(r);
+        }
+    }
+    // 2. Do PoK
+    transcript.cac_init::<P>(ipk, ek, cm, &cts);
+    if let Some(m) = m {
+        transcript.append_message(POK_LABEL, m); // SoK
+    }
     let r_t = P::ScalarField::random(prng);
-    let r_sk = P::ScalarField::random(prng);
-    let mut r_attrs = vec![];
-    let mut r_rands = vec![];
     let mut blinding = ipk.gen2.mul(&r_t).add(&ipk.zz2.mul(&r_sk));
+    let mut attrs = vec![];
     let mut cm_cts = vec![];
-    for (y2_i, attr) in ipk.yy2.iter().zip(attrs.iter()) {
+    let mut ct_rands_iter = ct_rands.into_iter();
+    let mut ct_rands = vec![];
+    for ((y2_i, attr), b) in ipk
+        .yy2
+        .iter()
+        .zip(credential.attrs.iter())
+        .zip(reveal_map.iter())
+    {
         let r_attr = P::ScalarField::random(prng);
-        let elem = y2_i.mul(&r_attr);
-        blinding = blinding.add(&elem);
-        if let Revealed(_) = attr {
+        blinding = blinding.add(&y2_i.mul(&r_attr));
+        if *b {
             let r_rand = P::ScalarField::random(prng);
             let ct_cm = elgamal_encrypt(&r_attr, &r_rand, ek);
             transcript.append_proof_commitment(&ct_cm.e1);
             transcript.append_proof_commitment(&ct_cm.e2);
             cm_cts.push(ct_cm);
-            r_rands.push(r_rand);
+            let ct_rand = ct_rands_iter.next().c(d!(NoahError::ParameterError))?;
+            ct_rands.push((ct_rand, r_rand));
         };
-        r_attrs.push(r_attr);
+        attrs.push((*attr, r_attr));
     }
-    transcript.append_proof_commitment(&blinding);
-    let challenge = transcript.get_challenge::<P::ScalarField>();
-    let response_t = challenge.mul(rand.t).add(&r_t);
-    let response_sk = challenge.mul(&usk.0).add(&r_sk);
-    let mut response_attrs = vec![];
-    for (attr_enum, r_attr) in attrs.iter().zip(r_attrs.iter()) {
-        match attr_enum {
-            Hidden(Some(attr)) => {
-                let response_attr = challenge.mul(*attr).add(r_attr);
-                response_attrs.push(response_attr);
-            }
-            Revealed(attr) => {
-                let response_attr = challenge.mul(*attr).add(r_attr);
-                response_attrs.push(response_attr);
-            }
-            _ => {}
+    // the revocation handle stays hidden even when revealed to the ciphertexts
+    let non_revocation = match revocation_list {
+        Some(list) => {
+            let (handle, r_handle) = attrs
+                .get(list.handle_index)
+                .c(d!(NoahError::ParameterError))?;
+            Some(
+                non_revocation_commit::<_, P>(transcript, prng, ipk, list, handle, r_handle)
+                    .c(d!())?,
+            )
         }
+        None => None,
+    };
+    if !predicate_map.is_empty() && predicate_map.len() != attrs.len() {
+        return Err(eg!(NoahError::ParameterError));
     }
-    let mut response_rands = vec![];
-    for (ct_rand, r_rand) in ct_rands.iter().zip(r_rands.iter()) {
-        let response_rand = challenge.mul(ct_rand).add(r_rand);
-        response_rands.push(response_rand);
+    let mut predicates = vec![];
+    for (i, (predicate, (attr, r_attr))) in predicate_map.iter().zip(attrs.iter()).enumerate() {
+        if let Some(predicate) = predicate {
+            predicates.push(
+                predicate_commit::<_, P>(transcript, prng, ipk, i, predicate, attr, r_attr)
+                    .c(d!())?,
+            );
+        }
     }
-    CACPoK {
-        pok: CredentialPoK {
+    transcript.append_proof_commitment(&blinding);
+
+    Ok((
+        cts,
+        CACProver {
             blinding,
-            response_t,
-            response_sk,
-            response_attrs,
+            r_t,
+            r_sk,
+            t: rand.t,
+            sk: usk.0,
+            attrs,
+            ct_rands,
+            cm_cts,
+            non_revocation,
+            predicates,
         },
-        cm_ct: cm_cts,
-        response_rands,
+    ))
+}
+
+/// Append the commitments of a confidential proof of knowledge to the transcript, after checking
+/// that its non-revocation and predicate proofs match `revocation_list` and `predicate_map`.
+fn confidential_pok_append_proof<P: Pairing>(
+    transcript: &mut Transcript,
+    pok: &CACPoK<P::G1, P::G2, P::ScalarField>,
+    predicate_map: &[Option<AttributePredicate<P::ScalarField>>],
+    revocation_list: Option<&CredentialRevocationList<P::ScalarField>>,
+) -> Result<()> {
+    for ct in pok.cm_ct.iter() {
+        transcript.append_proof_commitment(&ct.e1);
+        transcript.append_proof_commitment(&ct.e2);
     }
+    match (revocation_list, &pok.pok.non_revocation) {
+        (Some(list), Some(proof)) => non_revocation_append_proof::<P>(transcript, list, proof),
+        (None, None) => {}
+        _ => return Err(eg!(NoahError::IdentityRevealVerifyError)),
+    }
+    let predicates = predicate_map
+        .iter()
+        .enumerate()
+        .filter_map(|(i, predicate)| predicate.as_ref().map(|predicate| (i, predicate)))
+        .collect_vec();
+    if predicates.len() != pok.pok.predicates.len() {
+        return Err(eg!(NoahError::IdentityRevealVerifyError));
+    }
+    for ((i, predicate), proof) in predicates.into_iter().zip(pok.pok.predicates.iter()) {
+        predicate_append_proof::<P>(transcript, i, predicate, proof);
+    }
+    transcript.append_proof_commitment(&pok.pok.blinding);
+    Ok(())
 }
 
 #[allow(clippy::too_many_arguments)]
 fn confidential_verify_pok<P: Pairing>(
-    transcript: &mut Transcript,
     ipk: &CredentialIssuerPK<P::G1, P::G2>,
     ek: &ElGamalEncKey<P::G1>,
     cm: &CredentialComm<P::G1>,
     cts: &[ElGamalCiphertext<P::G1>],
     pok: &CACPoK<P::G1, P::G2, P::ScalarField>,
     reveal_map: &[bool],
-    m: &[u8],
+    predicate_map: &[Option<AttributePredicate<P::ScalarField>>],
+    revocation_list: Option<&CredentialRevocationList<P::ScalarField>>,
+    challenge: &P::ScalarField,
 ) -> Result<()> {
-    transcript.cac_init::<P>(ipk, ek, cm, cts);
-    transcript.append_message(POK_LABEL, m);
-
-    for ct in pok.cm_ct.iter() {
-        transcript.append_proof_commitment(&ct.e1);
-        transcript.append_proof_commitment(&ct.e2);
+    if let (Some(list), Some(proof)) = (revocation_list, &pok.pok.non_revocation) {
+        let response_handle = pok
+            .pok
+            .response_attrs
+            .get(list.handle_index)
+            .c(d!(NoahError::IdentityRevealVerifyError))?;
+        verify_non_revocation::<P>(ipk, list, proof, response_handle, challenge).c(d!())?;
+    }
+    let predicates = predicate_map
+        .iter()
+        .enumerate()
+        .filter_map(|(i, predicate)| predicate.as_ref().map(|predicate| (i, predicate)));
+    for ((i, predicate), proof) in predicates.zip(pok.pok.predicates.iter()) {
+        let response_attr = pok
+            .pok
+            .response_attrs
+            .get(i)
+            .c(d!(NoahError::IdentityRevealVerifyError))?;
+        verify_predicate::<P>(ipk, predicate, proof, response_attr, challenge).c(d!())?;
     }
-    transcript.append_proof_commitment(&pok.pok.blinding);
-
-    let challenge = transcript.get_challenge::<P::ScalarField>();
 
     let mut attr_resps = vec![];
     for (z_attr, b) in pok.pok.response_attrs.iter().zip(reveal_map.iter()) {
@@ -315,7 +785,7 @@ fn confidential_verify_pok<P: Pairing>(
     }
 
     verify_ciphertext::<P>(
-        &challenge,
+        challenge,
         cts,
         pok.cm_ct.as_slice(),
         attr_resps.as_slice(),
@@ -326,7 +796,7 @@ fn confidential_verify_pok<P: Pairing>(
 
     // 3. verify credential proof
     let hidden_attrs = vec![Hidden(None); ipk.num_attrs()];
-    verify_pok::<P>(ipk, cm, &pok.pok, hidden_attrs.as_slice(), &challenge).c(d!())
+    verify_pok::<P>(ipk, cm, &pok.pok, hidden_attrs.as_slice(), challenge).c(d!())
 }
 
 fn verify_ciphertext<P: Pairing>(
@@ -353,10 +823,15 @@ fn verify_ciphertext<P: Pairing>(
 pub(crate) mod test_helper {
     use crate::anon_creds::{
         check_comm, commit_without_randomizer, grant_credential, issuer_keygen, user_keygen,
-        Credential,
+        user_pk_for_issuer, Credential, CredentialIssuerPK, CredentialIssuerSK,
+        CredentialRevocationList, CredentialUserPK,
     };
     use crate::basic::elgamal::elgamal_key_gen;
-    use crate::confidential_anon_creds::{confidential_open_comm, confidential_verify_open};
+    use crate::confidential_anon_creds::{
+        confidential_open_comm, confidential_open_comm_with_linked,
+        confidential_open_comm_with_revocation, confidential_verify_open,
+        confidential_verify_open_with_linked, confidential_verify_open_with_revocation,
+    };
     use ark_std::test_rng;
     use noah_algebra::prelude::*;
     use noah_algebra::traits::Pairing;
@@ -514,11 +989,270 @@ pub(crate) mod test_helper {
             "proof should fail, bad sok message"
         );
     }
+
+    pub(crate) fn test_confidential_ac_revocation<P: Pairing>(reveal_map: &[bool]) {
+        let proof_msg = b"Some message";
+        let num_attr = reveal_map.len();
+        let mut prng = test_rng();
+        let (isk, ipk) = issuer_keygen::<_, P>(&mut prng, num_attr);
+        let (usk, upk) = user_keygen::<_, P>(&mut prng, &ipk);
+        let (_, ek) = elgamal_key_gen::<_, P::G1>(&mut prng);
+
+        let mut attrs = Vec::new();
+        for i in 0..num_attr {
+            attrs.push(byte_slice_to_scalar(format!("attr{}!", i).as_bytes()));
+        }
+        let sig = grant_credential::<_, P>(&mut prng, &isk, &upk, &attrs[..]).unwrap();
+        let credential = Credential {
+            sig,
+            attrs,
+            ipk: ipk.clone(),
+        };
+        let (cm, _, rand) =
+            commit_without_randomizer::<_, P>(&mut prng, &usk, &credential, b"Some address")
+                .unwrap();
+        let rand = rand.unwrap(); // safe unwrap()
+
+        let handle_index = num_attr - 1;
+        let mut revocation_list = CredentialRevocationList::new(handle_index);
+        for i in 0..3 {
+            pnk!(revocation_list.revoke(&byte_slice_to_scalar(format!("revoked{}!", i).as_bytes())));
+        }
+
+        let conf_reveal_proof = confidential_open_comm_with_revocation::<_, P>(
+            &mut prng,
+            &usk,
+            &credential,
+            &cm,
+            &rand,
+            reveal_map,
+            &ek,
+            proof_msg,
+            &revocation_list,
+        )
+        .unwrap();
+        assert!(confidential_verify_open_with_revocation::<P>(
+            &ipk,
+            &ek,
+            reveal_map,
+            &cm,
+            &conf_reveal_proof.cts,
+            &conf_reveal_proof.pok,
+            proof_msg,
+            &revocation_list,
+        )
+        .is_ok());
+
+        // A non-revocation proof is not accepted without the revocation list
+        assert!(confidential_verify_open::<P>(
+            &ipk,
+            &ek,
+            reveal_map,
+            &cm,
+            &conf_reveal_proof.cts,
+            &conf_reveal_proof.pok,
+            proof_msg,
+        )
+        .is_err());
+
+        // A proof without non-revocation is not accepted when a revocation list is given
+        let plain_proof = confidential_open_comm::<_, P>(
+            &mut prng,
+            &usk,
+            &credential,
+            &cm,
+            &rand,
+            reveal_map,
+            &ek,
+            proof_msg,
+        )
+        .unwrap();
+        assert!(confidential_verify_open_with_revocation::<P>(
+            &ipk,
+            &ek,
+            reveal_map,
+            &cm,
+            &plain_proof.cts,
+            &plain_proof.pok,
+            proof_msg,
+            &revocation_list,
+        )
+        .is_err());
+
+        // The proof is bound to the epoch of the revocation list
+        let mut updated_list = revocation_list.clone();
+        pnk!(updated_list.revoke(&byte_slice_to_scalar(b"revoked3!")));
+        let res = confidential_verify_open_with_revocation::<P>(
+            &ipk,
+            &ek,
+            reveal_map,
+            &cm,
+            &conf_reveal_proof.cts,
+            &conf_reveal_proof.pok,
+            proof_msg,
+            &updated_list,
+        );
+        msg_eq!(
+            NoahError::IdentityRevealVerifyError,
+            res.unwrap_err(),
+            "proof should fail, outdated revocation list"
+        );
+
+        // A revoked credential cannot produce a proof
+        pnk!(updated_list.revoke(&credential.attrs[handle_index]));
+        let res = confidential_open_comm_with_revocation::<_, P>(
+            &mut prng,
+            &usk,
+            &credential,
+            &cm,
+            &rand,
+            reveal_map,
+            &ek,
+            proof_msg,
+            &updated_list,
+        );
+        msg_eq!(
+            NoahError::CredentialRevokedError,
+            res.unwrap_err(),
+            "proof should fail, credential revoked"
+        );
+    }
+    pub(crate) fn test_confidential_ac_linked<P: Pairing>(
+        reveal_map: &[bool],
+        linked_map: &[bool],
+    ) {
+        let proof_msg = b"Some message";
+        let mut prng = test_rng();
+        let (isk, ipk) = issuer_keygen::<_, P>(&mut prng, reveal_map.len());
+        let (linked_isk, linked_ipk) = issuer_keygen::<_, P>(&mut prng, linked_map.len());
+        let (usk, upk) = user_keygen::<_, P>(&mut prng, &ipk);
+        let linked_upk = user_pk_for_issuer::<P>(&usk, &linked_ipk);
+        let (_, ek) = elgamal_key_gen::<_, P::G1>(&mut prng);
+
+        fn new_credential<P: Pairing>(
+            prng: &mut (impl CryptoRng + RngCore),
+            isk: &CredentialIssuerSK<P::G1, P::ScalarField>,
+            upk: &CredentialUserPK<P::G1>,
+            ipk: &CredentialIssuerPK<P::G1, P::G2>,
+            tag: &str,
+        ) -> Credential<P::G1, P::G2, P::ScalarField> {
+            let attrs = (0..ipk.num_attrs())
+                .map(|i| byte_slice_to_scalar(format!("{}{}!", tag, i).as_bytes()))
+                .collect_vec();
+            let sig = grant_credential::<_, P>(prng, isk, upk, &attrs[..]).unwrap();
+            Credential {
+                sig,
+                attrs,
+                ipk: ipk.clone(),
+            }
+        }
+        let credential = new_credential::<P>(&mut prng, &isk, &upk, &ipk, "attr");
+        let linked_credential =
+            new_credential::<P>(&mut prng, &linked_isk, &linked_upk, &linked_ipk, "linked");
+        let (cm, _, rand) =
+            commit_without_randomizer::<_, P>(&mut prng, &usk, &credential, b"Some address")
+                .unwrap();
+        let rand = rand.unwrap(); // safe unwrap()
+
+        let proof = confidential_open_comm_with_linked::<_, P>(
+            &mut prng,
+            &usk,
+            &credential,
+            &cm,
+            &rand,
+            reveal_map,
+            &ek,
+            proof_msg,
+            &[],
+            None,
+            &[(&linked_credential, linked_map)],
+        )
+        .unwrap();
+        let revealed = reveal_map.iter().chain(linked_map).filter(|b| **b).count();
+        assert_eq!(proof.cts.len(), revealed);
+        pnk!(confidential_verify_open_with_linked::<P>(
+            &ipk,
+            &ek,
+            reveal_map,
+            &cm,
+            &proof.cts,
+            &proof.pok,
+            proof_msg,
+            &[],
+            None,
+            &[(&linked_ipk, linked_map)],
+        ));
+
+        // The linked credential must be expected by the verifier
+        let res = confidential_verify_open_with_linked::<P>(
+            &ipk,
+            &ek,
+            reveal_map,
+            &cm,
+            &proof.cts,
+            &proof.pok,
+            proof_msg,
+            &[],
+            None,
+            &[],
+        );
+        msg_eq!(NoahError::IdentityRevealVerifyError, res.unwrap_err());
+
+        // The linked credential must be granted by the expected issuer
+        let (_, other_ipk) = issuer_keygen::<_, P>(&mut prng, linked_map.len());
+        let res = confidential_verify_open_with_linked::<P>(
+            &ipk,
+            &ek,
+            reveal_map,
+            &cm,
+            &proof.cts,
+            &proof.pok,
+            proof_msg,
+            &[],
+            None,
+            &[(&other_ipk, linked_map)],
+        );
+        msg_eq!(NoahError::IdentityRevealVerifyError, res.unwrap_err());
+
+        // A credential of another user cannot be linked
+        let (_, other_upk) = user_keygen::<_, P>(&mut prng, &linked_ipk);
+        let other_credential =
+            new_credential::<P>(&mut prng, &linked_isk, &other_upk, &linked_ipk, "other");
+        let proof = confidential_open_comm_with_linked::<_, P>(
+            &mut prng,
+            &usk,
+            &credential,
+            &cm,
+            &rand,
+            reveal_map,
+            &ek,
+            proof_msg,
+            &[],
+            None,
+            &[(&other_credential, linked_map)],
+        )
+        .unwrap();
+        let res = confidential_verify_open_with_linked::<P>(
+            &ipk,
+            &ek,
+            reveal_map,
+            &cm,
+            &proof.cts,
+            &proof.pok,
+            proof_msg,
+            &[],
+            None,
+            &[(&linked_ipk, linked_map)],
+        );
+        msg_eq!(NoahError::IdentityRevealVerifyError, res.unwrap_err());
+    }
 }
 
 #[cfg(test)]
 mod test_bls12_381 {
-    use crate::confidential_anon_creds::test_helper::test_confidential_ac_reveal;
+    use crate::confidential_anon_creds::test_helper::{
+        test_confidential_ac_linked, test_confidential_ac_reveal, test_confidential_ac_revocation,
+    };
     use noah_algebra::bls12_381::BLSPairingEngine;
 
     #[test]
@@ -562,4 +1296,16 @@ mod test_bls12_381 {
             false, true, false, true, false, true, false, true, false, true,
         ]);
     }
+
+    #[test]
+    fn confidential_reveal_with_revocation() {
+        test_confidential_ac_revocation::<BLSPairingEngine>(&[true, false, false]);
+        test_confidential_ac_revocation::<BLSPairingEngine>(&[false, true, true]);
+    }
+
+    #[test]
+    fn confidential_reveal_with_linked_credential() {
+        test_confidential_ac_linked::<BLSPairingEngine>(&[true, false], &[false, true, true]);
+        test_confidential_ac_linked::<BLSPairingEngine>(&[false], &[false]);
+    }
 }
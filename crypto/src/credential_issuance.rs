@@ -0,0 +1,472 @@
+//! Blind issuance of anonymous credentials, which allows a user to obtain a credential on
+//! attributes that the issuer never sees in the clear.
+//!
+//! The user commits to the hidden attributes as `t * g1 + \sum a_i * Y1_i` and proves knowledge
+//! of the opening together with the user secret key. The issuer signs the commitment and the
+//! attributes it sets itself, and the user removes `t` from the signature to obtain a regular
+//! credential signature.
+
+use crate::anon_creds::{
+    Credential, CredentialIssuerPK, CredentialIssuerSK, CredentialSig, CredentialUserPK,
+    CredentialUserSK,
+};
+use crate::basic::matrix_sigma::SigmaTranscript;
+use merlin::Transcript;
+use noah_algebra::{prelude::*, traits::Pairing};
+
+const BLIND_ISSUANCE_DOMAIN: &[u8] = b"AC Blind Issuance";
+const BLIND_ISSUANCE_NEW_TRANSCRIPT_INSTANCE: &[u8] = b"AC Blind Issuance Instance";
+
+/// The issuer key for blind issuance, which extends the issuer public key with the generators of
+/// the attributes in `G1`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CredentialBlindIssuanceKey<G1> {
+    /// The generator `g1` of the signatures.
+    pub gen1: G1,
+    /// The public parameter for each attribute, `y[i] G1`.
+    pub yy1: Vec<G1>,
+}
+
+/// The request of a user for the blind issuance of a credential.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CredentialIssuanceRequest<G1, S> {
+    /// The user public key under the issuer.
+    pub upk: CredentialUserPK<G1>,
+    /// The commitment to the hidden attributes.
+    pub comm: G1,
+    /// Whether each attribute is hidden from the issuer.
+    pub hidden_map: Vec<bool>,
+    /// The proof commitment for the attribute commitment.
+    pub blinding_comm: G1,
+    /// The proof commitment for the user public key.
+    pub blinding_upk: G1,
+    /// The response for the commitment randomness.
+    pub response_t: S,
+    /// The response for the user secret key.
+    pub response_sk: S,
+    /// The responses for the hidden attributes.
+    pub response_attrs: Vec<S>,
+}
+
+/// The secret kept by the user between the request and the unblinding of the signature.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CredentialIssuanceSecret<S> {
+    pub(crate) t: S,
+    pub(crate) attrs: Vec<Option<S>>,
+}
+
+/// The blinded signature returned by the issuer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CredentialBlindSig<G1, S> {
+    /// First element of the signature.
+    pub sigma1: G1,
+    /// Second element of the signature, still blinded by the commitment randomness.
+    pub sigma2: G1,
+    /// The attributes set by the issuer, which are `None` for the hidden attributes.
+    pub attrs: Vec<Option<S>>,
+}
+
+/// Derive the blind issuance key from the issuer secret key.
+pub fn blind_issuance_key<P: Pairing>(
+    isk: &CredentialIssuerSK<P::G1, P::ScalarField>,
+) -> CredentialBlindIssuanceKey<P::G1> {
+    CredentialBlindIssuanceKey {
+        gen1: isk.gen1,
+        yy1: isk.y.iter().map(|y| isk.gen1.mul(y)).collect(),
+    }
+}
+
+/// Check that the blind issuance key is consistent with the issuer public key.
+pub fn verify_blind_issuance_key<P: Pairing>(
+    ipk: &CredentialIssuerPK<P::G1, P::G2>,
+    bik: &CredentialBlindIssuanceKey<P::G1>,
+) -> Result<()> {
+    if bik.yy1.len() != ipk.num_attrs() || bik.gen1 == P::G1::get_identity() {
+        return Err(eg!(NoahError::ParameterError));
+    }
+    // e(g1, z G2) = e(z G1, G2) and e(y[i] G1, G2) = e(g1, y[i] G2)
+    if P::pairing(&bik.gen1, &ipk.zz2) != P::pairing(&ipk.zz1, &ipk.gen2) {
+        return Err(eg!(NoahError::ParameterError));
+    }
+    for (yy1i, yy2i) in bik.yy1.iter().zip(ipk.yy2.iter()) {
+        if P::pairing(yy1i, &ipk.gen2) != P::pairing(&bik.gen1, yy2i) {
+            return Err(eg!(NoahError::ParameterError));
+        }
+    }
+    Ok(())
+}
+
+/// Create a blind issuance request, where `attrs[i]` is `Some(a)` for an attribute `a` hidden from
+/// the issuer and `None` for an attribute set by the issuer. The blind issuance key `bik` should
+/// be checked with `verify_blind_issuance_key` beforehand.
+pub fn blind_issuance_request<R: CryptoRng + RngCore, P: Pairing>(
+    prng: &mut R,
+    ipk: &CredentialIssuerPK<P::G1, P::G2>,
+    bik: &CredentialBlindIssuanceKey<P::G1>,
+    usk: &CredentialUserSK<P::ScalarField>,
+    attrs: &[Option<P::ScalarField>],
+) -> Result<(
+    CredentialIssuanceRequest<P::G1, P::ScalarField>,
+    CredentialIssuanceSecret<P::ScalarField>,
+)> {
+    if attrs.len() != ipk.num_attrs() || bik.yy1.len() != ipk.num_attrs() {
+        return Err(eg!(NoahError::ParameterError));
+    }
+
+    let t = P::ScalarField::random(prng);
+    let beta_t = P::ScalarField::random(prng);
+    let beta_sk = P::ScalarField::random(prng);
+    let upk = CredentialUserPK(ipk.zz1.mul(&usk.0));
+    let mut comm = bik.gen1.mul(&t);
+    let mut blinding_comm = bik.gen1.mul(&beta_t);
+    let mut hidden = vec![];
+    for (attr, yy1i) in attrs.iter().zip(bik.yy1.iter()) {
+        if let Some(attr) = attr {
+            let gamma = P::ScalarField::random(prng);
+            comm = comm.add(&yy1i.mul(attr));
+            blinding_comm = blinding_comm.add(&yy1i.mul(&gamma));
+            hidden.push((*attr, gamma));
+        }
+    }
+    let blinding_upk = ipk.zz1.mul(&beta_sk);
+    let hidden_map = attrs.iter().map(|attr| attr.is_some()).collect_vec();
+
+    let mut transcript = Transcript::new(BLIND_ISSUANCE_NEW_TRANSCRIPT_INSTANCE);
+    init_issuance_transcript::<P>(&mut transcript, ipk, bik, &upk, &comm, &hidden_map);
+    transcript.append_proof_commitment(&blinding_comm);
+    transcript.append_proof_commitment(&blinding_upk);
+    let challenge = transcript.get_challenge::<P::ScalarField>();
+
+    let request = CredentialIssuanceRequest {
+        upk,
+        comm,
+        hidden_map,
+        blinding_comm,
+        blinding_upk,
+        response_t: challenge.mul(&t).add(&beta_t),
+        response_sk: challenge.mul(&usk.0).add(&beta_sk),
+        response_attrs: hidden
+            .iter()
+            .map(|(attr, gamma)| challenge.mul(attr).add(gamma))
+            .collect(),
+    };
+    let secret = CredentialIssuanceSecret {
+        t,
+        attrs: attrs.to_vec(),
+    };
+    Ok((request, secret))
+}
+
+/// Verify a blind issuance request and sign it, where `attrs[i]` is `Some(a)` for an attribute
+/// `a` set by the issuer and `None` for an attribute hidden in the request.
+pub fn blind_grant_credential<R: CryptoRng + RngCore, P: Pairing>(
+    prng: &mut R,
+    isk: &CredentialIssuerSK<P::G1, P::ScalarField>,
+    ipk: &CredentialIssuerPK<P::G1, P::G2>,
+    request: &CredentialIssuanceRequest<P::G1, P::ScalarField>,
+    attrs: &[Option<P::ScalarField>],
+) -> Result<CredentialBlindSig<P::G1, P::ScalarField>> {
+    let n = isk.y.len();
+    if attrs.len() != n {
+        return Err(eg!(NoahError::AnonymousCredentialSignError));
+    }
+    if request.hidden_map.len() != n
+        || attrs
+            .iter()
+            .zip(request.hidden_map.iter())
+            .any(|(attr, hidden)| attr.is_some() == *hidden)
+    {
+        return Err(eg!(NoahError::CredentialIssuanceRequestError));
+    }
+    let bik = blind_issuance_key::<P>(isk);
+    verify_issuance_request::<P>(ipk, &bik, request).c(d!())?;
+
+    let u = P::ScalarField::random(prng);
+    let mut exponent = isk.x;
+    for (attr, yi) in attrs.iter().zip(isk.y.iter()) {
+        if let Some(attr) = attr {
+            exponent = exponent.add(&attr.mul(yi));
+        }
+    }
+    let cc = isk.gen1.mul(&exponent);
+    Ok(CredentialBlindSig {
+        sigma1: isk.gen1.mul(&u),
+        sigma2: request.upk.0.add(&request.comm).add(&cc).mul(&u),
+        attrs: attrs.to_vec(),
+    })
+}
+
+/// Unblind the signature returned by the issuer into a credential, and check that the credential
+/// signature is valid for the user secret key and all the attributes.
+pub fn unblind_credential<P: Pairing>(
+    ipk: &CredentialIssuerPK<P::G1, P::G2>,
+    usk: &CredentialUserSK<P::ScalarField>,
+    secret: &CredentialIssuanceSecret<P::ScalarField>,
+    blind_sig: &CredentialBlindSig<P::G1, P::ScalarField>,
+) -> Result<Credential<P::G1, P::G2, P::ScalarField>> {
+    if secret.attrs.len() != ipk.num_attrs() || blind_sig.attrs.len() != ipk.num_attrs() {
+        return Err(eg!(NoahError::ParameterError));
+    }
+    let mut attrs = vec![];
+    for (hidden, issued) in secret.attrs.iter().zip(blind_sig.attrs.iter()) {
+        match (hidden, issued) {
+            (Some(attr), None) | (None, Some(attr)) => attrs.push(*attr),
+            _ => return Err(eg!(NoahError::ParameterError)),
+        }
+    }
+
+    let sig = CredentialSig {
+        sigma1: blind_sig.sigma1,
+        sigma2: blind_sig.sigma2.sub(&blind_sig.sigma1.mul(&secret.t)),
+    };
+
+    // e(sigma1, X2 + sk Z2 + \sum a_i Y2_i) = e(sigma2, G2)
+    let mut scalars = vec![&usk.0];
+    let mut points = vec![&ipk.zz2];
+    for (attr, yy2i) in attrs.iter().zip(ipk.yy2.iter()) {
+        scalars.push(attr);
+        points.push(yy2i);
+    }
+    let p = ipk
+        .xx2
+        .add(&P::G2::multi_exp(scalars.as_slice(), points.as_slice()));
+    if sig.sigma1 == P::G1::get_identity()
+        || P::pairing(&sig.sigma1, &p) != P::pairing(&sig.sigma2, &ipk.gen2)
+    {
+        return Err(eg!(NoahError::SignatureError));
+    }
+
+    Ok(Credential {
+        sig,
+        attrs,
+        ipk: ipk.clone(),
+    })
+}
+
+fn init_issuance_transcript<P: Pairing>(
+    transcript: &mut Transcript,
+    ipk: &CredentialIssuerPK<P::G1, P::G2>,
+    bik: &CredentialBlindIssuanceKey<P::G1>,
+    upk: &CredentialUserPK<P::G1>,
+    comm: &P::G1,
+    hidden_map: &[bool],
+) {
+    let mut elems = vec![bik.gen1, ipk.zz1, upk.0, *comm];
+    elems.extend_from_slice(&bik.yy1);
+    transcript.init_sigma::<P::G1>(BLIND_ISSUANCE_DOMAIN, &[], elems.as_slice());
+    let hidden_bytes = hidden_map.iter().map(|b| *b as u8).collect_vec();
+    transcript.append_message(b"hidden attributes", hidden_bytes.as_slice());
+}
+
+/// Verify the proof of knowledge in a blind issuance request.
+fn verify_issuance_request<P: Pairing>(
+    ipk: &CredentialIssuerPK<P::G1, P::G2>,
+    bik: &CredentialBlindIssuanceKey<P::G1>,
+    request: &CredentialIssuanceRequest<P::G1, P::ScalarField>,
+) -> Result<()> {
+    let hidden_yy1 = bik
+        .yy1
+        .iter()
+        .zip(request.hidden_map.iter())
+        .filter(|(_, hidden)| **hidden)
+        .map(|(yy1i, _)| yy1i)
+        .collect_vec();
+    if request.hidden_map.len() != bik.yy1.len() || request.response_attrs.len() != hidden_yy1.len()
+    {
+        return Err(eg!(NoahError::CredentialIssuanceRequestError));
+    }
+
+    let mut transcript = Transcript::new(BLIND_ISSUANCE_NEW_TRANSCRIPT_INSTANCE);
+    init_issuance_transcript::<P>(
+        &mut transcript,
+        ipk,
+        bik,
+        &request.upk,
+        &request.comm,
+        &request.hidden_map,
+    );
+    transcript.append_proof_commitment(&request.blinding_comm);
+    transcript.append_proof_commitment(&request.blinding_upk);
+    let challenge = transcript.get_challenge::<P::ScalarField>();
+
+    // r_t g1 + \sum r_i Y1_i = blinding_comm + c comm
+    let mut scalars = vec![&request.response_t];
+    let mut points = vec![&bik.gen1];
+    for (response, yy1i) in request.response_attrs.iter().zip(hidden_yy1) {
+        scalars.push(response);
+        points.push(yy1i);
+    }
+    let lhs = P::G1::multi_exp(scalars.as_slice(), points.as_slice());
+    let rhs = request.blinding_comm.add(&request.comm.mul(&challenge));
+    if lhs != rhs {
+        return Err(eg!(NoahError::CredentialIssuanceRequestError));
+    }
+
+    // r_sk Z1 = blinding_upk + c upk
+    let lhs = ipk.zz1.mul(&request.response_sk);
+    let rhs = request.blinding_upk.add(&request.upk.0.mul(&challenge));
+    if lhs != rhs {
+        return Err(eg!(NoahError::CredentialIssuanceRequestError));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::anon_creds::{issuer_keygen, open_credential, user_keygen, verify_open, Attribute};
+    use ark_std::test_rng;
+    use noah_algebra::bls12_381::BLSPairingEngine;
+
+    type P = BLSPairingEngine;
+    type S = <P as Pairing>::ScalarField;
+
+    #[test]
+    fn test_blind_issuance() {
+        let mut prng = test_rng();
+        let (isk, ipk) = issuer_keygen::<_, P>(&mut prng, 4);
+        let (usk, _) = user_keygen::<_, P>(&mut prng, &ipk);
+        let bik = blind_issuance_key::<P>(&isk);
+        assert!(verify_blind_issuance_key::<P>(&ipk, &bik).is_ok());
+
+        let user_attrs = vec![Some(S::from(11u32)), None, Some(S::from(33u32)), None];
+        let issuer_attrs = vec![None, Some(S::from(22u32)), None, Some(S::from(44u32))];
+        let (request, secret) =
+            blind_issuance_request::<_, P>(&mut prng, &ipk, &bik, &usk, &user_attrs).unwrap();
+
+        // the messages survive serialization
+        let json = serde_json::to_string(&request).unwrap();
+        let request: CredentialIssuanceRequest<_, S> = serde_json::from_str(&json).unwrap();
+        let blind_sig =
+            blind_grant_credential::<_, P>(&mut prng, &isk, &ipk, &request, &issuer_attrs).unwrap();
+        let bytes = rmp_serde::to_vec(&blind_sig).unwrap();
+        let blind_sig: CredentialBlindSig<_, S> = rmp_serde::from_slice(&bytes).unwrap();
+
+        let credential = unblind_credential::<P>(&ipk, &usk, &secret, &blind_sig).unwrap();
+        assert_eq!(
+            credential.attrs,
+            (1..=4u32).map(|i| S::from(i * 11)).collect_vec()
+        );
+
+        // the unblinded credential can be used as any other credential
+        let reveal_map = [false, true, true, false];
+        let proof = open_credential::<_, P>(&mut prng, &usk, &credential, &reveal_map).unwrap();
+        let attrs = credential
+            .attrs
+            .iter()
+            .zip(reveal_map.iter())
+            .map(|(a, b)| {
+                if *b {
+                    Attribute::Revealed(*a)
+                } else {
+                    Attribute::Hidden(None)
+                }
+            })
+            .collect_vec();
+        assert!(verify_open::<P>(&ipk, &proof.cm, &proof.proof_open, &attrs).is_ok());
+
+        // a different secret or user secret key cannot unblind the signature
+        let (other_usk, _) = user_keygen::<_, P>(&mut prng, &ipk);
+        assert!(unblind_credential::<P>(&ipk, &other_usk, &secret, &blind_sig).is_err());
+        let (_, other_secret) =
+            blind_issuance_request::<_, P>(&mut prng, &ipk, &bik, &usk, &user_attrs).unwrap();
+        assert!(unblind_credential::<P>(&ipk, &usk, &other_secret, &blind_sig).is_err());
+    }
+
+    #[test]
+    fn test_blind_issuance_key() {
+        let mut prng = test_rng();
+        let (isk, ipk) = issuer_keygen::<_, P>(&mut prng, 3);
+        let (other_isk, _) = issuer_keygen::<_, P>(&mut prng, 3);
+
+        let mut bik = blind_issuance_key::<P>(&isk);
+        bik.yy1.swap(0, 1);
+        assert!(verify_blind_issuance_key::<P>(&ipk, &bik).is_err());
+        let mut bik = blind_issuance_key::<P>(&isk);
+        bik.yy1.pop();
+        assert!(verify_blind_issuance_key::<P>(&ipk, &bik).is_err());
+        let bik = blind_issuance_key::<P>(&other_isk);
+        assert!(verify_blind_issuance_key::<P>(&ipk, &bik).is_err());
+    }
+
+    #[test]
+    fn test_malformed_requests() {
+        let mut prng = test_rng();
+        let (isk, ipk) = issuer_keygen::<_, P>(&mut prng, 3);
+        let (usk, _) = user_keygen::<_, P>(&mut prng, &ipk);
+        let (_, other_upk) = user_keygen::<_, P>(&mut prng, &ipk);
+        let bik = blind_issuance_key::<P>(&isk);
+
+        let user_attrs = vec![Some(S::from(1u32)), Some(S::from(2u32)), None];
+        let issuer_attrs = vec![None, None, Some(S::from(3u32))];
+        let (request, _) =
+            blind_issuance_request::<_, P>(&mut prng, &ipk, &bik, &usk, &user_attrs).unwrap();
+        assert!(
+            blind_grant_credential::<_, P>(&mut prng, &isk, &ipk, &request, &issuer_attrs).is_ok()
+        );
+
+        // the issuer attributes do not match the hidden attributes
+        assert!(blind_grant_credential::<_, P>(
+            &mut prng,
+            &isk,
+            &ipk,
+            &request,
+            &[Some(S::from(1u32)), None, Some(S::from(3u32))]
+        )
+        .is_err());
+        assert!(blind_grant_credential::<_, P>(
+            &mut prng,
+            &isk,
+            &ipk,
+            &request,
+            &issuer_attrs[..2]
+        )
+        .is_err());
+
+        let tampered = [
+            CredentialIssuanceRequest {
+                upk: other_upk,
+                ..request.clone()
+            },
+            CredentialIssuanceRequest {
+                comm: request.comm.add(&bik.gen1),
+                ..request.clone()
+            },
+            CredentialIssuanceRequest {
+                hidden_map: vec![true, false, false],
+                ..request.clone()
+            },
+            CredentialIssuanceRequest {
+                response_t: request.response_t.add(&S::one()),
+                ..request.clone()
+            },
+            CredentialIssuanceRequest {
+                response_sk: request.response_sk.add(&S::one()),
+                ..request.clone()
+            },
+            CredentialIssuanceRequest {
+                response_attrs: request.response_attrs[..1].to_vec(),
+                ..request.clone()
+            },
+            CredentialIssuanceRequest {
+                response_attrs: vec![request.response_attrs[1], request.response_attrs[0]],
+                ..request.clone()
+            },
+        ];
+        for (i, request) in tampered.iter().enumerate() {
+            let attrs = if i == 2 {
+                vec![None, Some(S::from(2u32)), Some(S::from(3u32))]
+            } else {
+                issuer_attrs.clone()
+            };
+            assert!(
+                blind_grant_credential::<_, P>(&mut prng, &isk, &ipk, request, &attrs).is_err()
+            );
+        }
+
+        // the user attributes must match the issuer public key
+        assert!(
+            blind_issuance_request::<_, P>(&mut prng, &ipk, &bik, &usk, &user_attrs[..2]).is_err()
+        );
+    }
+}
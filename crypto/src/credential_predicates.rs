@@ -0,0 +1,712 @@
+//! Predicate proofs over hidden attributes of anonymous credentials, which allow a user to show
+//! that a hidden attribute lies in a range or in a set of values without disclosing it.
+//!
+//! The attribute is committed as `a * G2 + rho * Z2` in the issuer's group `G2`, and the
+//! commitment is tied to the credential proof of knowledge by sharing the blinding of `a`.
+//! Range predicates are proven by committing to the bits of the differences to the bounds,
+//! and set membership by an OR-proof over the values of the set.
+
+use crate::anon_creds::CredentialIssuerPK;
+use crate::basic::matrix_sigma::SigmaTranscript;
+use merlin::Transcript;
+use noah_algebra::{prelude::*, traits::Pairing};
+use num_bigint::BigUint;
+
+const PREDICATE_LABEL: &[u8] = b"AC Attribute Predicate";
+
+/// The number of bits of the differences to the bounds in range predicates.
+pub const PREDICATE_RANGE_BITS: usize = 32;
+
+/// A predicate over a hidden attribute.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AttributePredicate<AttrType> {
+    /// The attribute is at least the given value.
+    AtLeast(AttrType),
+    /// The attribute is at most the given value.
+    AtMost(AttrType),
+    /// The attribute lies in the inclusive range.
+    InRange(AttrType, AttrType),
+    /// The attribute is one of the given values.
+    Membership(Vec<AttrType>),
+}
+
+impl<AttrType> AttributePredicate<AttrType> {
+    /// Convert the values in the predicate.
+    pub fn map<T, F: Fn(&AttrType) -> T>(&self, f: F) -> AttributePredicate<T> {
+        match self {
+            AttributePredicate::AtLeast(lower) => AttributePredicate::AtLeast(f(lower)),
+            AttributePredicate::AtMost(upper) => AttributePredicate::AtMost(f(upper)),
+            AttributePredicate::InRange(lower, upper) => {
+                AttributePredicate::InRange(f(lower), f(upper))
+            }
+            AttributePredicate::Membership(values) => {
+                AttributePredicate::Membership(values.iter().map(f).collect())
+            }
+        }
+    }
+}
+
+impl<S: Scalar> AttributePredicate<S> {
+    /// Check if the attribute `attr` satisfies the predicate, where the differences to the
+    /// bounds must fit in `PREDICATE_RANGE_BITS` bits.
+    pub fn holds(&self, attr: &S) -> bool {
+        self.bounds(attr)
+            .iter()
+            .all(|(_, diff)| to_range_bits(diff).is_some())
+            && match self {
+                AttributePredicate::Membership(values) => values.contains(attr),
+                _ => true,
+            }
+    }
+
+    /// Return each bound with the difference between the attribute and the bound,
+    /// which is non-negative when the bound is respected.
+    fn bounds(&self, attr: &S) -> Vec<(Bound<S>, S)> {
+        let lower = |l: &S| (Bound::Lower(*l), attr.sub(l));
+        let upper = |u: &S| (Bound::Upper(*u), u.sub(attr));
+        match self {
+            AttributePredicate::AtLeast(l) => vec![lower(l)],
+            AttributePredicate::AtMost(u) => vec![upper(u)],
+            AttributePredicate::InRange(l, u) => vec![lower(l), upper(u)],
+            AttributePredicate::Membership(_) => vec![],
+        }
+    }
+
+    fn check_well_formed(&self) -> Result<()> {
+        match self {
+            AttributePredicate::InRange(l, u) => {
+                let (l, u): (BigUint, BigUint) = ((*l).into(), (*u).into());
+                if l > u {
+                    return Err(eg!(NoahError::ParameterError));
+                }
+            }
+            AttributePredicate::Membership(values) => {
+                if values.is_empty() {
+                    return Err(eg!(NoahError::ParameterError));
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn append_to_transcript(&self, transcript: &mut Transcript, index: usize) {
+        transcript.append_message(b"New Domain", PREDICATE_LABEL);
+        transcript.append_u64(b"attribute index", index as u64);
+        match self {
+            AttributePredicate::AtLeast(l) => {
+                transcript.append_field_element(b"lower bound", l);
+            }
+            AttributePredicate::AtMost(u) => {
+                transcript.append_field_element(b"upper bound", u);
+            }
+            AttributePredicate::InRange(l, u) => {
+                transcript.append_field_element(b"lower bound", l);
+                transcript.append_field_element(b"upper bound", u);
+            }
+            AttributePredicate::Membership(values) => {
+                transcript.append_u64(b"set size", values.len() as u64);
+                for v in values.iter() {
+                    transcript.append_field_element(b"set element", v);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Bound<S> {
+    Lower(S),
+    Upper(S),
+}
+
+impl<S: Scalar> Bound<S> {
+    /// Return `a * G2 + rho * Z2 - l * G2` for a lower bound `l`, and `u * G2 - (a * G2 + rho * Z2)`
+    /// for an upper bound `u`, which commit to the difference.
+    fn diff_comm<G: Group<ScalarType = S>>(&self, comm: &G, gen: &G) -> G {
+        match self {
+            Bound::Lower(l) => comm.sub(&gen.mul(l)),
+            Bound::Upper(u) => gen.mul(u).sub(comm),
+        }
+    }
+
+    /// Return the randomness of `diff_comm` given the randomness `rho` of the attribute commitment.
+    fn diff_rand(&self, rho: &S) -> S {
+        match self {
+            Bound::Lower(_) => *rho,
+            Bound::Upper(_) => rho.neg(),
+        }
+    }
+}
+
+/// Return the `PREDICATE_RANGE_BITS` little-endian bits of `x`, or `None` if `x` is too large.
+fn to_range_bits<S: Scalar>(x: &S) -> Option<Vec<bool>> {
+    let limbs = x.get_little_endian_u64();
+    if limbs.iter().skip(1).any(|limb| *limb != 0) || limbs[0] >> PREDICATE_RANGE_BITS != 0 {
+        return None;
+    }
+    Some(
+        (0..PREDICATE_RANGE_BITS)
+            .map(|i| (limbs[0] >> i) & 1 == 1)
+            .collect(),
+    )
+}
+
+/// Proof that a committed bit is either zero or one.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommittedBitProof<G2, S> {
+    pub(crate) comm: G2,          // b * G2 + r * Z2
+    pub(crate) blinding_zero: G2, // k_0 * Z2
+    pub(crate) blinding_one: G2,  // k_1 * Z2
+    pub(crate) challenge_zero: S, // the challenge for the one branch is c - challenge_zero
+    pub(crate) response_zero: S,
+    pub(crate) response_one: S,
+}
+
+/// Proof that the value committed in a difference commitment fits in `PREDICATE_RANGE_BITS` bits.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommittedRangeProof<G2, S> {
+    pub(crate) bits: Vec<CommittedBitProof<G2, S>>,
+    pub(crate) blinding: G2, // k * Z2
+    pub(crate) response: S,  // c * (rand - sum_i 2^i r_i) + k
+}
+
+/// OR-proof that the committed attribute equals one of the values of a set.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommittedMembershipProof<G2, S> {
+    pub(crate) blindings: Vec<G2>, // k_j * Z2
+    pub(crate) challenges: Vec<S>, // all but the last challenge, which is c - sum_j c_j
+    pub(crate) responses: Vec<S>,
+}
+
+/// Proof that a hidden attribute satisfies an `AttributePredicate`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttributePredicateProof<G2, S> {
+    pub(crate) comm: G2,        // a * G2 + rho * Z2
+    pub(crate) blinding: G2,    // r_a * G2 + r_rho * Z2, r_a shared with the PoK
+    pub(crate) response_rho: S, // c * rho + r_rho
+    pub(crate) ranges: Vec<CommittedRangeProof<G2, S>>,
+    pub(crate) membership: Option<CommittedMembershipProof<G2, S>>,
+}
+
+struct BitWitness<S> {
+    bit: bool,
+    r: S,
+    k: S,
+    simulated_challenge: S,
+    simulated_response: S,
+}
+
+struct RangeWitness<S> {
+    bits: Vec<BitWitness<S>>,
+    rand: S,
+    k: S,
+}
+
+struct MembershipWitness<S> {
+    position: usize,
+    k: S,
+    // the simulated (challenge, response) of every other value
+    simulated: Vec<(S, S)>,
+}
+
+/// The prover state of a predicate proof between the commitment and the response.
+pub(crate) struct PredicateProver<G2, S> {
+    proof: AttributePredicateProof<G2, S>,
+    rho: S,
+    r_rho: S,
+    ranges: Vec<RangeWitness<S>>,
+    membership: Option<MembershipWitness<S>>,
+}
+
+impl<G2: Group<ScalarType = S>, S: Scalar> PredicateProver<G2, S> {
+    /// Compute the responses for the challenge `challenge`.
+    pub(crate) fn respond(self, challenge: &S) -> AttributePredicateProof<G2, S> {
+        let mut proof = self.proof;
+        proof.response_rho = challenge.mul(&self.rho).add(&self.r_rho);
+
+        for (range, witness) in proof.ranges.iter_mut().zip(self.ranges.iter()) {
+            for (bit, w) in range.bits.iter_mut().zip(witness.bits.iter()) {
+                let real_challenge = challenge.sub(&w.simulated_challenge);
+                let real_response = real_challenge.mul(&w.r).add(&w.k);
+                if w.bit {
+                    bit.challenge_zero = w.simulated_challenge;
+                    bit.response_zero = w.simulated_response;
+                    bit.response_one = real_response;
+                } else {
+                    bit.challenge_zero = real_challenge;
+                    bit.response_zero = real_response;
+                    bit.response_one = w.simulated_response;
+                }
+            }
+            range.response = challenge.mul(&witness.rand).add(&witness.k);
+        }
+
+        if let (Some(membership), Some(witness)) = (proof.membership.as_mut(), self.membership) {
+            let mut real_challenge = *challenge;
+            let mut simulated = witness.simulated.iter();
+            for j in 0..membership.blindings.len() {
+                if j == witness.position {
+                    membership.responses.push(S::zero());
+                    membership.challenges.push(S::zero());
+                } else {
+                    let (c_j, s_j) = simulated.next().unwrap(); // safe unwrap()
+                    real_challenge = real_challenge.sub(c_j);
+                    membership.responses.push(*s_j);
+                    membership.challenges.push(*c_j);
+                }
+            }
+            membership.responses[witness.position] = real_challenge.mul(&self.rho).add(&witness.k);
+            membership.challenges[witness.position] = real_challenge;
+            membership.challenges.pop();
+        }
+        proof
+    }
+}
+
+/// Internal function for committing to a proof that the attribute `attr` with index `index`
+/// satisfies `predicate`, where `r_attr` is the blinding used for it in the proof of knowledge.
+pub(crate) fn predicate_commit<R: CryptoRng + RngCore, P: Pairing>(
+    transcript: &mut Transcript,
+    prng: &mut R,
+    ipk: &CredentialIssuerPK<P::G1, P::G2>,
+    index: usize,
+    predicate: &AttributePredicate<P::ScalarField>,
+    attr: &P::ScalarField,
+    r_attr: &P::ScalarField,
+) -> Result<PredicateProver<P::G2, P::ScalarField>> {
+    predicate.check_well_formed().c(d!())?;
+    if !predicate.holds(attr) {
+        return Err(eg!(NoahError::AttributePredicateError));
+    }
+    predicate.append_to_transcript(transcript, index);
+
+    let gen = &ipk.gen2;
+    let h = &ipk.zz2;
+    let rho = P::ScalarField::random(prng);
+    let r_rho = P::ScalarField::random(prng);
+    let comm = gen.mul(attr).add(&h.mul(&rho));
+    let blinding = gen.mul(r_attr).add(&h.mul(&r_rho));
+    transcript.append_group_element(b"predicate comm", &comm);
+    transcript.append_proof_commitment(&blinding);
+
+    let mut ranges = vec![];
+    let mut range_witnesses = vec![];
+    for (bound, diff) in predicate.bounds(attr) {
+        let bits = to_range_bits(&diff).c(d!(NoahError::AttributePredicateError))?;
+        let mut bit_proofs = vec![];
+        let mut bit_witnesses = vec![];
+        let mut rand = bound.diff_rand(&rho);
+        let mut pow = P::ScalarField::one();
+        for bit in bits {
+            let r = P::ScalarField::random(prng);
+            let k = P::ScalarField::random(prng);
+            let simulated_challenge = P::ScalarField::random(prng);
+            let simulated_response = P::ScalarField::random(prng);
+            let mut bit_comm = h.mul(&r);
+            if bit {
+                bit_comm = bit_comm.add(gen);
+            }
+            // the real branch proves knowledge of r in comm - bit * G2 = r * Z2,
+            // the other branch is simulated
+            let real = h.mul(&k);
+            let other_target = if bit { bit_comm } else { bit_comm.sub(gen) };
+            let simulated = h
+                .mul(&simulated_response)
+                .sub(&other_target.mul(&simulated_challenge));
+            let (blinding_zero, blinding_one) = if bit {
+                (simulated, real)
+            } else {
+                (real, simulated)
+            };
+            transcript.append_group_element(b"bit comm", &bit_comm);
+            transcript.append_proof_commitment(&blinding_zero);
+            transcript.append_proof_commitment(&blinding_one);
+
+            rand = rand.sub(&pow.mul(&r));
+            pow = pow.add(&pow);
+            bit_proofs.push(CommittedBitProof {
+                comm: bit_comm,
+                blinding_zero,
+                blinding_one,
+                challenge_zero: P::ScalarField::zero(),
+                response_zero: P::ScalarField::zero(),
+                response_one: P::ScalarField::zero(),
+            });
+            bit_witnesses.push(BitWitness {
+                bit,
+                r,
+                k,
+                simulated_challenge,
+                simulated_response,
+            });
+        }
+        let k = P::ScalarField::random(prng);
+        let range_blinding = h.mul(&k);
+        transcript.append_proof_commitment(&range_blinding);
+        ranges.push(CommittedRangeProof {
+            bits: bit_proofs,
+            blinding: range_blinding,
+            response: P::ScalarField::zero(),
+        });
+        range_witnesses.push(RangeWitness {
+            bits: bit_witnesses,
+            rand,
+            k,
+        });
+    }
+
+    let (membership, membership_witness) = match predicate {
+        AttributePredicate::Membership(values) => {
+            let position = values
+                .iter()
+                .position(|v| v == attr)
+                .c(d!(NoahError::AttributePredicateError))?;
+            let k = P::ScalarField::random(prng);
+            let mut blindings = vec![];
+            let mut simulated = vec![];
+            for (j, v) in values.iter().enumerate() {
+                let b = if j == position {
+                    h.mul(&k)
+                } else {
+                    let c_j = P::ScalarField::random(prng);
+                    let s_j = P::ScalarField::random(prng);
+                    let target = comm.sub(&gen.mul(v));
+                    simulated.push((c_j, s_j));
+                    h.mul(&s_j).sub(&target.mul(&c_j))
+                };
+                transcript.append_proof_commitment(&b);
+                blindings.push(b);
+            }
+            (
+                Some(CommittedMembershipProof {
+                    blindings,
+                    challenges: vec![],
+                    responses: vec![],
+                }),
+                Some(MembershipWitness {
+                    position,
+                    k,
+                    simulated,
+                }),
+            )
+        }
+        _ => (None, None),
+    };
+
+    Ok(PredicateProver {
+        proof: AttributePredicateProof {
+            comm,
+            blinding,
+            response_rho: P::ScalarField::zero(),
+            ranges,
+            membership,
+        },
+        rho,
+        r_rho,
+        ranges: range_witnesses,
+        membership: membership_witness,
+    })
+}
+
+/// Internal function for appending a predicate proof to the verifier's transcript.
+pub(crate) fn predicate_append_proof<P: Pairing>(
+    transcript: &mut Transcript,
+    index: usize,
+    predicate: &AttributePredicate<P::ScalarField>,
+    proof: &AttributePredicateProof<P::G2, P::ScalarField>,
+) {
+    predicate.append_to_transcript(transcript, index);
+    transcript.append_group_element(b"predicate comm", &proof.comm);
+    transcript.append_proof_commitment(&proof.blinding);
+    for range in proof.ranges.iter() {
+        for bit in range.bits.iter() {
+            transcript.append_group_element(b"bit comm", &bit.comm);
+            transcript.append_proof_commitment(&bit.blinding_zero);
+            transcript.append_proof_commitment(&bit.blinding_one);
+        }
+        transcript.append_proof_commitment(&range.blinding);
+    }
+    if let Some(membership) = proof.membership.as_ref() {
+        for b in membership.blindings.iter() {
+            transcript.append_proof_commitment(b);
+        }
+    }
+}
+
+/// Internal function for verifying a predicate proof, where `response_attr` is the response
+/// for the attribute in the proof of knowledge.
+pub(crate) fn verify_predicate<P: Pairing>(
+    ipk: &CredentialIssuerPK<P::G1, P::G2>,
+    predicate: &AttributePredicate<P::ScalarField>,
+    proof: &AttributePredicateProof<P::G2, P::ScalarField>,
+    response_attr: &P::ScalarField,
+    challenge: &P::ScalarField,
+) -> Result<()> {
+    predicate.check_well_formed().c(d!())?;
+    let gen = &ipk.gen2;
+    let h = &ipk.zz2;
+
+    // z_a * G2 + z_rho * Z2 = blinding + c * comm
+    let lhs = gen.mul(response_attr).add(&h.mul(&proof.response_rho));
+    let rhs = proof.blinding.add(&proof.comm.mul(challenge));
+    if lhs != rhs {
+        return Err(eg!(NoahError::IdentityRevealVerifyError));
+    }
+
+    // the bounds do not depend on the attribute, so any value can be used to obtain them
+    let bounds = predicate.bounds(&P::ScalarField::zero());
+    if bounds.len() != proof.ranges.len() {
+        return Err(eg!(NoahError::IdentityRevealVerifyError));
+    }
+    for ((bound, _), range) in bounds.iter().zip(proof.ranges.iter()) {
+        if range.bits.len() != PREDICATE_RANGE_BITS {
+            return Err(eg!(NoahError::IdentityRevealVerifyError));
+        }
+        let mut sum = P::G2::get_identity();
+        let mut pow = P::ScalarField::one();
+        for bit in range.bits.iter() {
+            // s_0 * Z2 = blinding_0 + c_0 * comm, s_1 * Z2 = blinding_1 + c_1 * (comm - G2)
+            let challenge_one = challenge.sub(&bit.challenge_zero);
+            if h.mul(&bit.response_zero)
+                != bit.blinding_zero.add(&bit.comm.mul(&bit.challenge_zero))
+                || h.mul(&bit.response_one)
+                    != bit.blinding_one.add(&bit.comm.sub(gen).mul(&challenge_one))
+            {
+                return Err(eg!(NoahError::IdentityRevealVerifyError));
+            }
+            sum = sum.add(&bit.comm.mul(&pow));
+            pow = pow.add(&pow);
+        }
+        // s * Z2 = blinding + c * (diff_comm - sum_i 2^i * comm_i)
+        let target = bound.diff_comm(&proof.comm, gen).sub(&sum);
+        if h.mul(&range.response) != range.blinding.add(&target.mul(challenge)) {
+            return Err(eg!(NoahError::IdentityRevealVerifyError));
+        }
+    }
+
+    match (predicate, proof.membership.as_ref()) {
+        (AttributePredicate::Membership(values), Some(membership)) => {
+            let n = values.len();
+            if membership.blindings.len() != n
+                || membership.responses.len() != n
+                || membership.challenges.len() + 1 != n
+            {
+                return Err(eg!(NoahError::IdentityRevealVerifyError));
+            }
+            let mut challenges = membership.challenges.clone();
+            let sum = challenges
+                .iter()
+                .fold(P::ScalarField::zero(), |acc, c| acc.add(c));
+            challenges.push(challenge.sub(&sum));
+            for (v, (b, (c_j, s_j))) in values.iter().zip(
+                membership
+                    .blindings
+                    .iter()
+                    .zip(challenges.iter().zip(membership.responses.iter())),
+            ) {
+                // s_j * Z2 = blinding_j + c_j * (comm - v_j * G2)
+                let target = proof.comm.sub(&gen.mul(v));
+                if h.mul(s_j) != b.add(&target.mul(c_j)) {
+                    return Err(eg!(NoahError::IdentityRevealVerifyError));
+                }
+            }
+        }
+        (AttributePredicate::Membership(_), None) | (_, Some(_)) => {
+            return Err(eg!(NoahError::IdentityRevealVerifyError));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AttributePredicate::{self, AtLeast, AtMost, InRange, Membership};
+    use crate::anon_creds::{
+        grant_credential, issuer_keygen, open_comm_with_predicates, randomizer_gen, user_keygen,
+        verify_open_with_predicates, Attribute, Credential, CredentialComm,
+    };
+    use crate::basic::elgamal::elgamal_key_gen;
+    use crate::confidential_anon_creds::{
+        confidential_open_comm_with_predicates, confidential_verify_open_with_predicates,
+    };
+    use ark_std::test_rng;
+    use noah_algebra::{bls12_381::BLSPairingEngine, prelude::*, traits::Pairing};
+
+    type P = BLSPairingEngine;
+    type S = <P as Pairing>::ScalarField;
+
+    fn predicate(p: &AttributePredicate<u32>) -> AttributePredicate<S> {
+        p.map(|v| S::from(*v))
+    }
+
+    fn check_predicates(
+        attrs: &[u32],
+        reveal_map: &[bool],
+        predicate_map: &[Option<AttributePredicate<u32>>],
+    ) -> Result<()> {
+        let mut prng = test_rng();
+        let (isk, ipk) = issuer_keygen::<_, P>(&mut prng, attrs.len());
+        let (usk, upk) = user_keygen::<_, P>(&mut prng, &ipk);
+        let attrs = attrs.iter().map(|a| S::from(*a)).collect_vec();
+        let sig = grant_credential::<_, P>(&mut prng, &isk, &upk, &attrs).unwrap();
+        let credential = Credential {
+            sig,
+            attrs,
+            ipk: ipk.clone(),
+        };
+        let predicate_map = predicate_map
+            .iter()
+            .map(|p| p.as_ref().map(predicate))
+            .collect_vec();
+
+        let rand = randomizer_gen::<_, P>(&mut prng);
+        let cm = CredentialComm::new(&credential.sig, &rand);
+        let proof = open_comm_with_predicates::<_, P>(
+            &mut prng,
+            &usk,
+            &credential,
+            &cm,
+            &rand,
+            reveal_map,
+            &predicate_map,
+            None,
+        )
+        .c(d!())?;
+        let revealed_attrs = credential
+            .attrs
+            .iter()
+            .zip(reveal_map.iter())
+            .map(|(a, b)| {
+                if *b {
+                    Attribute::Revealed(*a)
+                } else {
+                    Attribute::Hidden(None)
+                }
+            })
+            .collect_vec();
+        verify_open_with_predicates::<P>(&ipk, &cm, &proof, &revealed_attrs, &predicate_map, None)
+            .c(d!())?;
+
+        // the proof does not verify against other predicates
+        let other_predicate_map = predicate_map
+            .iter()
+            .map(|p| p.as_ref().map(|_| predicate(&Membership(vec![u32::MAX]))))
+            .collect_vec();
+        assert!(verify_open_with_predicates::<P>(
+            &ipk,
+            &cm,
+            &proof,
+            &revealed_attrs,
+            &other_predicate_map,
+            None
+        )
+        .is_err());
+
+        let (_, ek) = elgamal_key_gen::<_, <P as Pairing>::G1>(&mut prng);
+        let conf_proof = confidential_open_comm_with_predicates::<_, P>(
+            &mut prng,
+            &usk,
+            &credential,
+            &cm,
+            &rand,
+            reveal_map,
+            &ek,
+            b"message",
+            &predicate_map,
+            None,
+        )
+        .c(d!())?;
+        confidential_verify_open_with_predicates::<P>(
+            &ipk,
+            &ek,
+            reveal_map,
+            &cm,
+            &conf_proof.cts,
+            &conf_proof.pok,
+            b"message",
+            &predicate_map,
+            None,
+        )
+        .c(d!())
+    }
+
+    #[test]
+    fn test_range_predicates() {
+        let attrs = [25, 7, 1990];
+        let reveal_map = [false, true, false];
+        for predicate_map in [
+            vec![Some(AtLeast(18)), None, None],
+            vec![Some(AtLeast(25)), None, Some(AtMost(2004))],
+            vec![Some(AtMost(25)), None, Some(InRange(1900, 1990))],
+            vec![Some(InRange(0, u32::MAX)), None, Some(InRange(1990, 1990))],
+        ] {
+            pnk!(check_predicates(&attrs, &reveal_map, &predicate_map));
+        }
+
+        for predicate_map in [
+            vec![Some(AtLeast(26)), None, None],
+            vec![None, None, Some(AtMost(1989))],
+            vec![None, None, Some(InRange(1991, 2004))],
+        ] {
+            msg_eq!(
+                NoahError::AttributePredicateError,
+                check_predicates(&attrs, &reveal_map, &predicate_map).unwrap_err()
+            );
+        }
+    }
+
+    #[test]
+    fn test_membership_predicates() {
+        let attrs = [276, 840];
+        let reveal_map = [false, false];
+        pnk!(check_predicates(
+            &attrs,
+            &reveal_map,
+            &[Some(Membership(vec![250, 276, 380])), None]
+        ));
+        pnk!(check_predicates(
+            &attrs,
+            &reveal_map,
+            &[
+                Some(Membership(vec![276])),
+                Some(Membership(vec![124, 840]))
+            ]
+        ));
+        msg_eq!(
+            NoahError::AttributePredicateError,
+            check_predicates(
+                &attrs,
+                &reveal_map,
+                &[None, Some(Membership(vec![124, 484]))]
+            )
+            .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_malformed_predicates() {
+        let attrs = [25, 7];
+        // predicates over revealed attributes
+        msg_eq!(
+            NoahError::ParameterError,
+            check_predicates(&attrs, &[true, false], &[Some(AtLeast(18)), None]).unwrap_err()
+        );
+        // empty range and empty set
+        msg_eq!(
+            NoahError::ParameterError,
+            check_predicates(&attrs, &[false, false], &[Some(InRange(30, 20)), None]).unwrap_err()
+        );
+        msg_eq!(
+            NoahError::ParameterError,
+            check_predicates(&attrs, &[false, false], &[None, Some(Membership(vec![]))])
+                .unwrap_err()
+        );
+        // predicate map of the wrong length
+        msg_eq!(
+            NoahError::ParameterError,
+            check_predicates(&attrs, &[false, false], &[Some(AtLeast(18))]).unwrap_err()
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use noah_algebra::bls12_381::BLSScalar;
+use noah_algebra::errors::AlgebraError;
 use noah_algebra::prelude::*;
 use noah_algebra::str::FromStr;
 use num_bigint::BigUint;
@@ -132,6 +133,155 @@ impl SimFrParams for SimFrParamsSecq256k1 {
     }
 }
 
+/// The parameters for field simulation for the secp256k1 scalar field.
+#[derive(Clone, Default, Eq, PartialEq, Debug)]
+pub struct SimFrParamsSecp256k1;
+
+impl SimFrParams for SimFrParamsSecp256k1 {
+    const NUM_OF_LIMBS: usize = 6;
+    const BIT_PER_LIMB: usize = 44;
+    const BIT_IN_TOP_LIMB: usize = 36;
+    const NUM_OF_GROUPS: usize = 6;
+
+    fn scalar_field_in_biguint() -> BigUint {
+        BigUint::from_str(
+            "115792089237316195423570985008687907852837564279074904382605163141518161494337",
+        )
+        .unwrap()
+    }
+
+    fn scalar_field_in_limbs() -> Vec<BLSScalar> {
+        [
+            BLSScalar::from_str("15997951426881").unwrap(),
+            BLSScalar::from_str("4990814649637").unwrap(),
+            BLSScalar::from_str("16194960418479").unwrap(),
+            BLSScalar::from_str("17592186044415").unwrap(),
+            BLSScalar::from_str("17592186044415").unwrap(),
+            BLSScalar::from_str("68719476735").unwrap(),
+        ]
+        .to_vec()
+    }
+
+    fn scalar_field_sub_pad_in_limbs() -> Vec<BLSScalar> {
+        [
+            BLSScalar::from_str("31995902853762").unwrap(),
+            BLSScalar::from_str("27573815343690").unwrap(),
+            BLSScalar::from_str("32389920836957").unwrap(),
+            BLSScalar::from_str("35184372088830").unwrap(),
+            BLSScalar::from_str("35184372088830").unwrap(),
+            BLSScalar::from_str("137438953470").unwrap(),
+        ]
+        .to_vec()
+    }
+
+    fn scalar_field_sub_pad_in_biguint() -> BigUint {
+        BigUint::from_str(
+            "231584178474632390847141970017375815705675128558149808765210326283036322988674",
+        )
+        .unwrap()
+    }
+}
+
+/// The parameters for field simulation for the BLS12-381 base field.
+#[derive(Clone, Default, Eq, PartialEq, Debug)]
+pub struct SimFrParamsBLSFq;
+
+impl SimFrParams for SimFrParamsBLSFq {
+    const NUM_OF_LIMBS: usize = 7;
+    const BIT_PER_LIMB: usize = 56;
+    const BIT_IN_TOP_LIMB: usize = 45;
+    const NUM_OF_GROUPS: usize = 7;
+
+    fn scalar_field_in_biguint() -> BigUint {
+        BigUint::from_str(
+            "4002409555221667393417789825735904156556882819939007885332058136124031650490837864442687629129015664037894272559787",
+        )
+        .unwrap()
+    }
+
+    fn scalar_field_in_limbs() -> Vec<BLSScalar> {
+        [
+            BLSScalar::from_str("71776119061195435").unwrap(),
+            BLSScalar::from_str("72056156633169849").unwrap(),
+            BLSScalar::from_str("45307236177944235").unwrap(),
+            BLSScalar::from_str("68544734907805906").unwrap(),
+            BLSScalar::from_str("21300664356785028").unwrap(),
+            BLSScalar::from_str("64908892020651587").unwrap(),
+            BLSScalar::from_str("28591897852287").unwrap(),
+        ]
+        .to_vec()
+    }
+
+    fn scalar_field_sub_pad_in_limbs() -> Vec<BLSScalar> {
+        [
+            BLSScalar::from_str("143552238122390870").unwrap(),
+            BLSScalar::from_str("144112313266339698").unwrap(),
+            BLSScalar::from_str("90614472355888470").unwrap(),
+            BLSScalar::from_str("137089469815611812").unwrap(),
+            BLSScalar::from_str("114658922751497992").unwrap(),
+            BLSScalar::from_str("129817784041303173").unwrap(),
+            BLSScalar::from_str("57183795704574").unwrap(),
+        ]
+        .to_vec()
+    }
+
+    fn scalar_field_sub_pad_in_biguint() -> BigUint {
+        BigUint::from_str(
+            "8004819110443334786835579651471808313113765639878015770664116272248063300981675728885375258258031328075788545119574",
+        )
+        .unwrap()
+    }
+}
+
+/// The parameters for field simulation for the Curve25519 base field.
+#[derive(Clone, Default, Eq, PartialEq, Debug)]
+pub struct SimFrParamsEd25519Fq;
+
+impl SimFrParams for SimFrParamsEd25519Fq {
+    const NUM_OF_LIMBS: usize = 6;
+    const BIT_PER_LIMB: usize = 44;
+    const BIT_IN_TOP_LIMB: usize = 35;
+    const NUM_OF_GROUPS: usize = 6;
+
+    fn scalar_field_in_biguint() -> BigUint {
+        BigUint::from_str(
+            "57896044618658097711785492504343953926634992332820282019728792003956564819949",
+        )
+        .unwrap()
+    }
+
+    fn scalar_field_in_limbs() -> Vec<BLSScalar> {
+        [
+            BLSScalar::from_str("17592186044397").unwrap(),
+            BLSScalar::from_str("17592186044415").unwrap(),
+            BLSScalar::from_str("17592186044415").unwrap(),
+            BLSScalar::from_str("17592186044415").unwrap(),
+            BLSScalar::from_str("17592186044415").unwrap(),
+            BLSScalar::from_str("34359738367").unwrap(),
+        ]
+        .to_vec()
+    }
+
+    fn scalar_field_sub_pad_in_limbs() -> Vec<BLSScalar> {
+        [
+            BLSScalar::from_str("35184372088794").unwrap(),
+            BLSScalar::from_str("35184372088830").unwrap(),
+            BLSScalar::from_str("35184372088830").unwrap(),
+            BLSScalar::from_str("35184372088830").unwrap(),
+            BLSScalar::from_str("35184372088830").unwrap(),
+            BLSScalar::from_str("68719476734").unwrap(),
+        ]
+        .to_vec()
+    }
+
+    fn scalar_field_sub_pad_in_biguint() -> BigUint {
+        BigUint::from_str(
+            "115792089237316195423570985008687907853269984665640564039457584007913129639898",
+        )
+        .unwrap()
+    }
+}
+
 /// A precise indicator of the reducibility in a simulate element.
 #[derive(Eq, PartialEq, Clone)]
 pub enum SimReducibility {
@@ -208,6 +358,26 @@ impl<P: SimFrParams> Sub<&SimFr<P>> for &SimFr<P> {
     }
 }
 
+impl<P: SimFrParams> Add<&SimFr<P>> for &SimFr<P> {
+    type Output = SimFr<P>;
+
+    fn add(self, rhs: &SimFr<P>) -> SimFr<P> {
+        let mut res = SimFr::<P>::default();
+        for i in 0..P::NUM_OF_LIMBS {
+            res.limbs[i] = self.limbs[i].add(&rhs.limbs[i]);
+        }
+        res.val = (&self.val).add(&rhs.val);
+
+        res.num_of_additions_over_normal_form = SimReducibility::Others(
+            BigUint::from(&self.num_of_additions_over_normal_form)
+                + BigUint::from(&rhs.num_of_additions_over_normal_form)
+                + BigUint::one(),
+        );
+
+        res
+    }
+}
+
 impl<P: SimFrParams> Mul<&SimFr<P>> for &SimFr<P> {
     type Output = SimFrMul<P>;
 
@@ -270,6 +440,39 @@ impl<P: SimFrParams> SimFr<P> {
         let (_, rem) = self_biguint.div_rem(&r_biguint);
         rem.is_zero()
     }
+
+    /// Return the simulated field element in the normalized form,
+    /// i.e., with the actual value reduced modulo the scalar field modulus.
+    pub fn reduce(&self) -> SimFr<P> {
+        let self_biguint: BigUint = self.into();
+        let r_biguint = P::scalar_field_in_biguint();
+
+        SimFr::<P>::from(&self_biguint.mod_floor(&r_biguint))
+    }
+
+    /// Return the inverse of the simulated field element in the normalized form.
+    pub fn inv(&self) -> Result<SimFr<P>> {
+        if self.is_zero() {
+            return Err(eg!(AlgebraError::GroupInversionError));
+        }
+        let self_biguint: BigUint = self.into();
+        let r_biguint = P::scalar_field_in_biguint();
+
+        // the scalar field modulus is a prime, so the inverse is `self^{r - 2}`
+        let exp = &r_biguint - BigUint::from(2u32);
+        Ok(SimFr::<P>::from(&self_biguint.modpow(&exp, &r_biguint)))
+    }
+}
+
+impl<P: SimFrParams> From<&SimFr<P>> for SimFrMul<P> {
+    fn from(src: &SimFr<P>) -> Self {
+        let mut res = SimFrMul::<P>::default();
+        res.limbs[..P::NUM_OF_LIMBS].copy_from_slice(&src.limbs);
+        res.val = src.val.clone();
+        res.prod_of_num_of_additions =
+            BigUint::from(&src.num_of_additions_over_normal_form).add(&BigUint::one());
+        res
+    }
 }
 
 /// `SimFrMul` is the intermediate representation for
@@ -545,6 +748,49 @@ mod test_ristretto {
         }
     }
 
+    #[test]
+    fn test_add() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsRistretto::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+            let sum_sim_fr = &a_sim_fr + &b_sim_fr;
+
+            let sum_recovered: BigUint = (&sum_sim_fr).into();
+            assert_eq!(&a + &b, sum_recovered);
+
+            let sum_reduced = sum_sim_fr.reduce();
+            assert_eq!(
+                (&a + &b) % &r_biguint,
+                <&SimFrTest as Into<BigUint>>::into(&sum_reduced)
+            );
+        }
+    }
+
+    #[test]
+    fn test_inv() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsRistretto::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::one(), &r_biguint);
+            let a_sim_fr = SimFrTest::from(&a);
+            let a_inv_sim_fr = a_sim_fr.inv().unwrap();
+
+            let a_inv: BigUint = (&a_inv_sim_fr).into();
+            assert!(a_inv < r_biguint);
+            assert_eq!((&a * &a_inv) % &r_biguint, BigUint::one());
+        }
+
+        assert!(SimFrTest::from(&BigUint::zero()).inv().is_err());
+        assert!(SimFrTest::from(&r_biguint).inv().is_err());
+    }
+
     #[test]
     fn test_enforce_zero_trivial() {
         let zero_fr = SimFrTest::from(&BigUint::zero());
@@ -669,6 +915,49 @@ mod test_secq256k1 {
         }
     }
 
+    #[test]
+    fn test_add() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsSecq256k1::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+            let sum_sim_fr = &a_sim_fr + &b_sim_fr;
+
+            let sum_recovered: BigUint = (&sum_sim_fr).into();
+            assert_eq!(&a + &b, sum_recovered);
+
+            let sum_reduced = sum_sim_fr.reduce();
+            assert_eq!(
+                (&a + &b) % &r_biguint,
+                <&SimFrTest as Into<BigUint>>::into(&sum_reduced)
+            );
+        }
+    }
+
+    #[test]
+    fn test_inv() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsSecq256k1::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::one(), &r_biguint);
+            let a_sim_fr = SimFrTest::from(&a);
+            let a_inv_sim_fr = a_sim_fr.inv().unwrap();
+
+            let a_inv: BigUint = (&a_inv_sim_fr).into();
+            assert!(a_inv < r_biguint);
+            assert_eq!((&a * &a_inv) % &r_biguint, BigUint::one());
+        }
+
+        assert!(SimFrTest::from(&BigUint::zero()).inv().is_err());
+        assert!(SimFrTest::from(&r_biguint).inv().is_err());
+    }
+
     #[test]
     fn test_enforce_zero_trivial() {
         let zero_fr = SimFrTest::from(&BigUint::zero());
@@ -726,3 +1015,375 @@ mod test_secq256k1 {
         zero_supposed_manipulated.enforce_zero();
     }
 }
+
+#[cfg(test)]
+mod test_secp256k1 {
+    use crate::field_simulation::{SimFr, SimFrParams, SimFrParamsSecp256k1};
+    use ark_std::test_rng;
+    use noah_algebra::prelude::*;
+    use num_bigint::{BigUint, RandBigInt};
+    use num_integer::Integer;
+
+    type SimFrTest = SimFr<SimFrParamsSecp256k1>;
+
+    #[test]
+    fn test_sim_fr_biguint_conversion() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsSecp256k1::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+            let a_sim_fr = SimFrTest::from(&a);
+            let a_recovered: BigUint = (&a_sim_fr).into();
+
+            assert_eq!(a, a_recovered);
+        }
+    }
+
+    #[test]
+    fn test_sub() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsSecp256k1::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+            let sum_sim_fr = &a_sim_fr - &b_sim_fr;
+
+            let (_, sum) = a.add(&r_biguint).sub(&b).div_rem(&r_biguint);
+            let (_, sum_recovered) =
+                <&SimFrTest as Into<BigUint>>::into(&sum_sim_fr).div_rem(&r_biguint);
+
+            assert_eq!(sum, sum_recovered);
+        }
+    }
+
+    #[test]
+    fn test_mul() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsSecp256k1::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+
+            let prod_sim_fr_mul = a_sim_fr.mul(&b_sim_fr);
+            let prod_sim_fr_mul_recovered: BigUint = (&prod_sim_fr_mul).into();
+
+            let prod = &a * &b;
+
+            assert_eq!(prod, prod_sim_fr_mul_recovered);
+        }
+    }
+
+    #[test]
+    fn test_enforce_zero_trivial() {
+        let zero_fr = SimFrTest::from(&BigUint::zero());
+        let zero_fr_mul = (&zero_fr) * (&zero_fr);
+
+        zero_fr_mul.enforce_zero();
+    }
+
+    #[test]
+    fn test_enforce_zero() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsSecp256k1::scalar_field_in_biguint();
+
+        for _ in 0..1000 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+
+            let a_fr = SimFrTest::from(&a);
+            let b_fr = SimFrTest::from(&b);
+
+            let ab_fr_mul = &a_fr * &b_fr;
+            let ab_fr = &a * &b;
+            assert_eq!(ab_fr, (&ab_fr_mul).into());
+
+            let ab_fr_reduced = &ab_fr % &r_biguint;
+            let ab_reduced = SimFrTest::from(&ab_fr_reduced);
+
+            let zero_supposed = &ab_fr_mul - &ab_reduced;
+            let zero_supposed_biguint: BigUint = (&zero_supposed).into();
+            assert_eq!(BigUint::zero(), &zero_supposed_biguint % &r_biguint);
+            zero_supposed.enforce_zero();
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_enforce_zero_panic() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsSecp256k1::scalar_field_in_biguint();
+
+        let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+        let b = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+
+        let a_fr = SimFrTest::from(&a);
+        let b_fr = SimFrTest::from(&b);
+
+        let ab_fr_mul = &a_fr * &b_fr;
+        let ab_fr = &a * &b;
+        assert_eq!(ab_fr, (&ab_fr_mul).into());
+
+        let ab_fr_reduced_manipulated = &ab_fr % &r_biguint + &BigUint::from(10u64);
+        let ab_reduced_manipulated = SimFrTest::from(&ab_fr_reduced_manipulated);
+
+        let zero_supposed_manipulated = &ab_fr_mul - &ab_reduced_manipulated;
+        zero_supposed_manipulated.enforce_zero();
+    }
+}
+
+#[cfg(test)]
+mod test_bls_fq {
+    use crate::field_simulation::{SimFr, SimFrParams, SimFrParamsBLSFq};
+    use ark_std::test_rng;
+    use noah_algebra::prelude::*;
+    use num_bigint::{BigUint, RandBigInt};
+    use num_integer::Integer;
+
+    type SimFrTest = SimFr<SimFrParamsBLSFq>;
+
+    #[test]
+    fn test_sim_fr_biguint_conversion() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsBLSFq::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+            let a_sim_fr = SimFrTest::from(&a);
+            let a_recovered: BigUint = (&a_sim_fr).into();
+
+            assert_eq!(a, a_recovered);
+        }
+    }
+
+    #[test]
+    fn test_sub() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsBLSFq::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+            let sum_sim_fr = &a_sim_fr - &b_sim_fr;
+
+            let (_, sum) = a.add(&r_biguint).sub(&b).div_rem(&r_biguint);
+            let (_, sum_recovered) =
+                <&SimFrTest as Into<BigUint>>::into(&sum_sim_fr).div_rem(&r_biguint);
+
+            assert_eq!(sum, sum_recovered);
+        }
+    }
+
+    #[test]
+    fn test_mul() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsBLSFq::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+
+            let prod_sim_fr_mul = a_sim_fr.mul(&b_sim_fr);
+            let prod_sim_fr_mul_recovered: BigUint = (&prod_sim_fr_mul).into();
+
+            let prod = &a * &b;
+
+            assert_eq!(prod, prod_sim_fr_mul_recovered);
+        }
+    }
+
+    #[test]
+    fn test_enforce_zero_trivial() {
+        let zero_fr = SimFrTest::from(&BigUint::zero());
+        let zero_fr_mul = (&zero_fr) * (&zero_fr);
+
+        zero_fr_mul.enforce_zero();
+    }
+
+    #[test]
+    fn test_enforce_zero() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsBLSFq::scalar_field_in_biguint();
+
+        for _ in 0..1000 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+
+            let a_fr = SimFrTest::from(&a);
+            let b_fr = SimFrTest::from(&b);
+
+            let ab_fr_mul = &a_fr * &b_fr;
+            let ab_fr = &a * &b;
+            assert_eq!(ab_fr, (&ab_fr_mul).into());
+
+            let ab_fr_reduced = &ab_fr % &r_biguint;
+            let ab_reduced = SimFrTest::from(&ab_fr_reduced);
+
+            let zero_supposed = &ab_fr_mul - &ab_reduced;
+            let zero_supposed_biguint: BigUint = (&zero_supposed).into();
+            assert_eq!(BigUint::zero(), &zero_supposed_biguint % &r_biguint);
+            zero_supposed.enforce_zero();
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_enforce_zero_panic() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsBLSFq::scalar_field_in_biguint();
+
+        let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+        let b = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+
+        let a_fr = SimFrTest::from(&a);
+        let b_fr = SimFrTest::from(&b);
+
+        let ab_fr_mul = &a_fr * &b_fr;
+        let ab_fr = &a * &b;
+        assert_eq!(ab_fr, (&ab_fr_mul).into());
+
+        let ab_fr_reduced_manipulated = &ab_fr % &r_biguint + &BigUint::from(10u64);
+        let ab_reduced_manipulated = SimFrTest::from(&ab_fr_reduced_manipulated);
+
+        let zero_supposed_manipulated = &ab_fr_mul - &ab_reduced_manipulated;
+        zero_supposed_manipulated.enforce_zero();
+    }
+}
+
+#[cfg(test)]
+mod test_ed25519_fq {
+    use crate::field_simulation::{SimFr, SimFrParams, SimFrParamsEd25519Fq};
+    use ark_std::test_rng;
+    use noah_algebra::prelude::*;
+    use num_bigint::{BigUint, RandBigInt};
+    use num_integer::Integer;
+
+    type SimFrTest = SimFr<SimFrParamsEd25519Fq>;
+
+    #[test]
+    fn test_sim_fr_biguint_conversion() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsEd25519Fq::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+            let a_sim_fr = SimFrTest::from(&a);
+            let a_recovered: BigUint = (&a_sim_fr).into();
+
+            assert_eq!(a, a_recovered);
+        }
+    }
+
+    #[test]
+    fn test_sub() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsEd25519Fq::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+            let sum_sim_fr = &a_sim_fr - &b_sim_fr;
+
+            let (_, sum) = a.add(&r_biguint).sub(&b).div_rem(&r_biguint);
+            let (_, sum_recovered) =
+                <&SimFrTest as Into<BigUint>>::into(&sum_sim_fr).div_rem(&r_biguint);
+
+            assert_eq!(sum, sum_recovered);
+        }
+    }
+
+    #[test]
+    fn test_mul() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsEd25519Fq::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+
+            let prod_sim_fr_mul = a_sim_fr.mul(&b_sim_fr);
+            let prod_sim_fr_mul_recovered: BigUint = (&prod_sim_fr_mul).into();
+
+            let prod = &a * &b;
+
+            assert_eq!(prod, prod_sim_fr_mul_recovered);
+        }
+    }
+
+    #[test]
+    fn test_enforce_zero_trivial() {
+        let zero_fr = SimFrTest::from(&BigUint::zero());
+        let zero_fr_mul = (&zero_fr) * (&zero_fr);
+
+        zero_fr_mul.enforce_zero();
+    }
+
+    #[test]
+    fn test_enforce_zero() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsEd25519Fq::scalar_field_in_biguint();
+
+        for _ in 0..1000 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+
+            let a_fr = SimFrTest::from(&a);
+            let b_fr = SimFrTest::from(&b);
+
+            let ab_fr_mul = &a_fr * &b_fr;
+            let ab_fr = &a * &b;
+            assert_eq!(ab_fr, (&ab_fr_mul).into());
+
+            let ab_fr_reduced = &ab_fr % &r_biguint;
+            let ab_reduced = SimFrTest::from(&ab_fr_reduced);
+
+            let zero_supposed = &ab_fr_mul - &ab_reduced;
+            let zero_supposed_biguint: BigUint = (&zero_supposed).into();
+            assert_eq!(BigUint::zero(), &zero_supposed_biguint % &r_biguint);
+            zero_supposed.enforce_zero();
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_enforce_zero_panic() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsEd25519Fq::scalar_field_in_biguint();
+
+        let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+        let b = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+
+        let a_fr = SimFrTest::from(&a);
+        let b_fr = SimFrTest::from(&b);
+
+        let ab_fr_mul = &a_fr * &b_fr;
+        let ab_fr = &a * &b;
+        assert_eq!(ab_fr, (&ab_fr_mul).into());
+
+        let ab_fr_reduced_manipulated = &ab_fr % &r_biguint + &BigUint::from(10u64);
+        let ab_reduced_manipulated = SimFrTest::from(&ab_fr_reduced_manipulated);
+
+        let zero_supposed_manipulated = &ab_fr_mul - &ab_reduced_manipulated;
+        zero_supposed_manipulated.enforce_zero();
+    }
+}
@@ -31,6 +31,10 @@ pub mod basic;
 pub mod bulletproofs;
 /// The module for confidential anonymous credentials.
 pub mod confidential_anon_creds;
+/// The module for blind issuance of anonymous credentials.
+pub mod credential_issuance;
+/// The module for predicate proofs over hidden credential attributes.
+pub mod credential_predicates;
 /// The module for the delegated Schnorr protocol.
 pub mod delegated_schnorr;
 /// The module for field simulation.
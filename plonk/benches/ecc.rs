@@ -0,0 +1,84 @@
+use ark_std::test_rng;
+use criterion::{criterion_group, criterion_main, Criterion};
+use noah_algebra::{
+    bls12_381::BLSScalar,
+    jubjub::{JubjubPoint, JubjubScalar},
+    prelude::*,
+};
+use noah_plonk::plonk::constraint_system::{
+    ecc::{FixedBaseTable, Point},
+    TurboCS,
+};
+
+const SCALAR_BITS: usize = 252;
+
+fn new_scalar_cs(scalars: &[JubjubScalar]) -> (TurboCS<BLSScalar>, Vec<usize>) {
+    let mut cs = TurboCS::new();
+    let scalar_vars = scalars
+        .iter()
+        .map(|s| cs.new_variable(BLSScalar::from_bytes(&s.to_bytes()).unwrap()))
+        .collect();
+    (cs, scalar_vars)
+}
+
+fn bench_scalar_mul(c: &mut Criterion) {
+    let mut prng = test_rng();
+    let g = JubjubPoint::get_base();
+    let h = g.mul(&JubjubScalar::random(&mut prng));
+    let scalars = [
+        JubjubScalar::random(&mut prng),
+        JubjubScalar::random(&mut prng),
+    ];
+    let g_table = FixedBaseTable::new(g, SCALAR_BITS);
+    let h_table = FixedBaseTable::new(h, SCALAR_BITS);
+
+    let fixed_base = || {
+        let (mut cs, scalar_vars) = new_scalar_cs(&scalars[..1]);
+        cs.scalar_mul(g, scalar_vars[0], SCALAR_BITS);
+        cs
+    };
+    let fixed_base_with_table = || {
+        let (mut cs, scalar_vars) = new_scalar_cs(&scalars[..1]);
+        cs.fixed_base_scalar_mul(&g_table, scalar_vars[0]);
+        cs
+    };
+    let var_base = || {
+        let (mut cs, scalar_vars) = new_scalar_cs(&scalars[..1]);
+        let base_var = cs.new_point_variable(Point::from(&g));
+        cs.var_base_scalar_mul(&base_var, g, scalar_vars[0], SCALAR_BITS);
+        cs
+    };
+    let pedersen = || {
+        let (mut cs, scalar_vars) = new_scalar_cs(&scalars);
+        cs.fixed_base_multi_scalar_mul(&[&g_table, &h_table], &scalar_vars);
+        cs
+    };
+
+    // the table only saves the native precomputation, the circuits are the same
+    assert_eq!(fixed_base().size, fixed_base_with_table().size);
+    println!(
+        "constraints: fixed-base {}, fixed-base with table {}, variable-base {}, Pedersen commitment {}",
+        fixed_base().size,
+        fixed_base_with_table().size,
+        var_base().size,
+        pedersen().size
+    );
+
+    let mut group = c.benchmark_group("bench_ecc");
+    group.bench_function("fixed-base scalar mul", |b| {
+        b.iter(fixed_base);
+    });
+    group.bench_function("fixed-base scalar mul with table", |b| {
+        b.iter(fixed_base_with_table);
+    });
+    group.bench_function("variable-base scalar mul", |b| {
+        b.iter(var_base);
+    });
+    group.bench_function("Pedersen commitment", |b| {
+        b.iter(pedersen);
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_scalar_mul);
+criterion_main!(benches);
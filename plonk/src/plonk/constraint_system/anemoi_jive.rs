@@ -420,7 +420,7 @@ mod kzg_test {
     use crate::plonk::indexer::indexer;
     use crate::plonk::prover::prover;
     use crate::plonk::verifier::verifier;
-    use crate::poly_commit::kzg_poly_com::KZGCommitmentScheme;
+    use crate::poly_commit::kzg_poly_com::KZGCommitmentSchemeBLS;
     use crate::poly_commit::pcs::PolyComScheme;
     use ark_std::test_rng;
     use merlin::Transcript;
@@ -431,7 +431,7 @@ mod kzg_test {
     #[test]
     fn test_turbo_plonk_kzg_anemoi_jive() {
         let mut prng = test_rng();
-        let pcs = KZGCommitmentScheme::new(260, &mut prng);
+        let pcs = KZGCommitmentSchemeBLS::new(260, &mut prng);
         test_turbo_plonk_anemoi_variable_length_hash(&pcs, &mut prng);
         test_turbo_plonk_jive_crh(&pcs, &mut prng);
     }
@@ -96,6 +96,37 @@ fn compute_base_multiples(base: JubjubPoint, n: usize) -> Vec<Vec<JubjubPoint>>
     bases
 }
 
+/// The precomputed multiples of a fixed base point `[G]` for the windowed scalar multiplication,
+/// which, for the `i`-th two-bit window, are `4^i * [G]`, `2 * 4^i * [G]`, and `3 * 4^i * [G]`.
+///
+/// The table can be computed once and reused for every scalar multiplication with the same base,
+/// which saves the native computation of the multiples but not constraints: the circuit is the
+/// same as that of `scalar_mul`.
+/// Two-bit windows are used because the lookup of a two-bit window takes one gate per coordinate,
+/// while a three-bit lookup needs seven non-constant monomials of the bits, more than one gate
+/// covers, so that wider windows do not reduce the number of constraints.
+#[derive(Clone, Debug)]
+pub struct FixedBaseTable {
+    bases: Vec<Vec<JubjubPoint>>,
+}
+
+impl FixedBaseTable {
+    /// Precompute the multiples of `base` for `n_bits`-bit scalars.
+    /// `n_bits` should be a positive even number.
+    pub fn new(base: JubjubPoint, n_bits: usize) -> Self {
+        assert_eq!(n_bits & 1, 0, "n_bits is odd");
+        assert!(n_bits > 0, "n_bits is not positive");
+        Self {
+            bases: compute_base_multiples(base, n_bits >> 1),
+        }
+    }
+
+    /// Return the number of bits of the scalars supported by the table.
+    pub fn num_bits(&self) -> usize {
+        self.bases[0].len() << 1
+    }
+}
+
 impl TurboCS<BLSScalar> {
     /// Create variables for a point.
     pub fn new_point_variable(&mut self, point: Point) -> PointVar {
@@ -265,9 +296,61 @@ impl TurboCS<BLSScalar> {
         assert_eq!(n_bits & 1, 0, "n_bits is odd");
         assert!(n_bits > 0, "n_bits is not positive");
 
-        let b_scalar_var = self.range_check(scalar_var, n_bits);
-        let bases = compute_base_multiples(base, n_bits >> 1);
-        self.scalar_mul_with_bases(&bases[0], &bases[1], &bases[2], &b_scalar_var)
+        let table = FixedBaseTable::new(base, n_bits);
+        self.fixed_base_scalar_mul(&table, scalar_var)
+    }
+
+    /// Fixed-base scalar multiplication with a precomputed table:
+    /// Given the table of a base point `[G]` and a secret scalar `s`
+    /// of `table.num_bits()` bits, returns `s * [G]`.
+    /// The constraints are those of `scalar_mul`, only the table is reused.
+    pub fn fixed_base_scalar_mul(
+        &mut self,
+        table: &FixedBaseTable,
+        scalar_var: VarIndex,
+    ) -> PointVar {
+        let b_scalar_var = self.range_check(scalar_var, table.num_bits());
+        self.scalar_mul_with_bases(
+            &table.bases[0],
+            &table.bases[1],
+            &table.bases[2],
+            &b_scalar_var,
+        )
+    }
+
+    /// Fixed-base multi-scalar multiplication with precomputed tables:
+    /// Given the tables of base points `[G_0], ..., [G_{k-1}]` and secret scalars
+    /// `s_0, ..., s_{k-1}`, returns `s_0 * [G_0] + ... + s_{k-1} * [G_{k-1}]`,
+    /// e.g., a Pedersen commitment `v * [G] + r * [H]`.
+    ///
+    /// The windows of all the scalars are accumulated in one chain of additions, which takes as many
+    /// constraints as separate scalar multiplications followed by the additions of their results.
+    pub fn fixed_base_multi_scalar_mul(
+        &mut self,
+        tables: &[&FixedBaseTable],
+        scalar_vars: &[VarIndex],
+    ) -> PointVar {
+        assert_eq!(tables.len(), scalar_vars.len(), "wrong number of scalars");
+        assert!(!tables.is_empty(), "no base points");
+
+        let mut acc_var_ext: Option<ExtendedPointVar> = None;
+        for (table, scalar_var) in tables.iter().zip(scalar_vars.iter()) {
+            let b_scalar_var = self.range_check(*scalar_var, table.num_bits());
+            for i in 0..table.bases[0].len() {
+                let tmp_var_ext = self.ecc_select(
+                    &table.bases[0][i],
+                    &table.bases[1][i],
+                    &table.bases[2][i],
+                    b_scalar_var[2 * i],
+                    b_scalar_var[2 * i + 1],
+                );
+                acc_var_ext = Some(match acc_var_ext {
+                    None => tmp_var_ext,
+                    Some(acc) => self.ecc_add(&acc.0, &tmp_var_ext.0, &acc.1, &tmp_var_ext.1),
+                });
+            }
+        }
+        acc_var_ext.unwrap().0
     }
 
     /// Fixed-base scalar multiplication with precomputed bases.
@@ -318,11 +401,65 @@ impl TurboCS<BLSScalar> {
         }
         p_var_ext.0
     }
+
+    /// Variable-base scalar multiplication:
+    /// Given a point variable `[P]` with value `base` and an `n_bits`-bit secret scalar `s`,
+    /// returns `s * [P]`, computed by double-and-add from the most significant bit.
+    pub fn var_base_scalar_mul(
+        &mut self,
+        base_var: &PointVar,
+        base: JubjubPoint,
+        scalar_var: VarIndex,
+        n_bits: usize,
+    ) -> PointVar {
+        let b_scalar_var = self.range_check(scalar_var, n_bits);
+        self.var_base_scalar_mul_with_bits(base_var, base, &b_scalar_var)
+    }
+
+    /// Variable-base scalar multiplication with the little-endian bits of the secret scalar,
+    /// which must have been constrained to be boolean.
+    pub fn var_base_scalar_mul_with_bits(
+        &mut self,
+        base_var: &PointVar,
+        base: JubjubPoint,
+        b_scalar_var: &[VarIndex],
+    ) -> PointVar {
+        assert!(
+            base_var.0 < self.num_vars,
+            "base.x variable index out of bound"
+        );
+        assert!(
+            base_var.1 < self.num_vars,
+            "base.y variable index out of bound"
+        );
+
+        let mut p_var_ext = ExtendedPointVar(
+            PointVar(self.zero_var(), self.one_var()),
+            JubjubPoint::get_identity(),
+        );
+        for bit_var in b_scalar_var.iter().rev().copied() {
+            let doubled = self.ecc_add(&p_var_ext.0, &p_var_ext.0, &p_var_ext.1, &p_var_ext.1);
+            let added = self.ecc_add(&doubled.0, base_var, &doubled.1, &base);
+            let x = self.select(doubled.0 .0, added.0 .0, bit_var);
+            let y = self.select(doubled.0 .1, added.0 .1, bit_var);
+            let point = if self.witness[bit_var] == BLSScalar::one() {
+                added.1
+            } else {
+                doubled.1
+            };
+            p_var_ext = ExtendedPointVar(PointVar(x, y), point);
+        }
+        p_var_ext.0
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::plonk::constraint_system::{ecc::Point, TurboCS};
+    use crate::plonk::constraint_system::{
+        ecc::{FixedBaseTable, Point},
+        TurboCS,
+    };
+    use ark_std::test_rng;
     use noah_algebra::{
         bls12_381::BLSScalar,
         jubjub::{JubjubPoint, JubjubScalar},
@@ -392,6 +529,86 @@ mod test {
         assert!(cs.verify_witness(&witness[..], &[]).is_err());
     }
 
+    #[test]
+    fn test_var_base_scalar_mul() {
+        let mut cs = TurboCS::new();
+        let mut prng = test_rng();
+
+        let jubjub_scalar = JubjubScalar::random(&mut prng);
+        let scalar = BLSScalar::from_bytes(&jubjub_scalar.to_bytes()).unwrap();
+        let base_ext = JubjubPoint::get_base().mul(&JubjubScalar::random(&mut prng));
+        let p_out_ext = base_ext.mul(&jubjub_scalar);
+        let p_out_plus_ext = p_out_ext.add(&base_ext);
+
+        // build circuit
+        let base_var = cs.new_point_variable(Point::from(&base_ext));
+        let scalar_var = cs.new_variable(scalar);
+        let p_out_var = cs.var_base_scalar_mul(&base_var, base_ext, scalar_var, 252);
+        let mut witness = cs.get_and_clear_witness();
+        let p_out_point = Point::from(&p_out_ext);
+        assert_eq!(witness[p_out_var.0], p_out_point.0);
+        assert_eq!(witness[p_out_var.1], p_out_point.1);
+        pnk!(cs.verify_witness(&witness[..], &[]));
+
+        // wrong witness: point = base * (jubjub_scalar + 1)
+        let p_out_plus_point = Point::from(&p_out_plus_ext);
+        witness[p_out_var.0] = p_out_plus_point.0;
+        witness[p_out_var.1] = p_out_plus_point.1;
+        assert!(cs.verify_witness(&witness[..], &[]).is_err());
+
+        // the zero scalar gives the identity
+        let mut cs = TurboCS::new();
+        let base_var = cs.new_point_variable(Point::from(&base_ext));
+        let scalar_var = cs.new_variable(BLSScalar::zero());
+        let p_out_var = cs.var_base_scalar_mul(&base_var, base_ext, scalar_var, 64);
+        let witness = cs.get_and_clear_witness();
+        assert_eq!(witness[p_out_var.0], BLSScalar::zero());
+        assert_eq!(witness[p_out_var.1], BLSScalar::one());
+        pnk!(cs.verify_witness(&witness[..], &[]));
+    }
+
+    #[test]
+    fn test_fixed_base_multi_scalar_mul() {
+        let mut cs = TurboCS::new();
+        let mut prng = test_rng();
+
+        let g = JubjubPoint::get_base();
+        let h = g.mul(&JubjubScalar::random(&mut prng));
+        let g_table = FixedBaseTable::new(g, 64);
+        let h_table = FixedBaseTable::new(h, 252);
+
+        let value = JubjubScalar::from(prng.next_u64());
+        let blind = JubjubScalar::random(&mut prng);
+        let comm = g.mul(&value).add(&h.mul(&blind));
+
+        // build circuit
+        let value_var = cs.new_variable(BLSScalar::from_bytes(&value.to_bytes()).unwrap());
+        let blind_var = cs.new_variable(BLSScalar::from_bytes(&blind.to_bytes()).unwrap());
+        let comm_var =
+            cs.fixed_base_multi_scalar_mul(&[&g_table, &h_table], &[value_var, blind_var]);
+        let mut witness = cs.get_and_clear_witness();
+        let comm_point = Point::from(&comm);
+        assert_eq!(witness[comm_var.0], comm_point.0);
+        assert_eq!(witness[comm_var.1], comm_point.1);
+        pnk!(cs.verify_witness(&witness[..], &[]));
+
+        // wrong witness: comm + G
+        let wrong_point = Point::from(&comm.add(&g));
+        witness[comm_var.0] = wrong_point.0;
+        witness[comm_var.1] = wrong_point.1;
+        assert!(cs.verify_witness(&witness[..], &[]).is_err());
+
+        // the same result as the single scalar multiplications
+        let mut cs = TurboCS::new();
+        let value_var = cs.new_variable(BLSScalar::from_bytes(&value.to_bytes()).unwrap());
+        let p_out_var = cs.fixed_base_scalar_mul(&g_table, value_var);
+        let witness = cs.get_and_clear_witness();
+        let p_out_point = Point::from(&g.mul(&value));
+        assert_eq!(witness[p_out_var.0], p_out_point.0);
+        assert_eq!(witness[p_out_var.1], p_out_point.1);
+        pnk!(cs.verify_witness(&witness[..], &[]));
+    }
+
     #[test]
     fn test_scalar_mul_with_zero_scalar() {
         let mut cs = TurboCS::new();
@@ -468,3 +468,246 @@ mod test_secq256k1 {
         zero_supposed_manipulated.enforce_zero(&mut cs);
     }
 }
+
+#[cfg(test)]
+mod test_secp256k1 {
+    use crate::plonk::constraint_system::{field_simulation::SimFrVar, turbo::TurboCS};
+    use ark_std::test_rng;
+    use noah_algebra::{bls12_381::BLSScalar, prelude::*};
+    use noah_crypto::field_simulation::{SimFr, SimFrParams, SimFrParamsSecp256k1};
+    use num_bigint::{BigUint, RandBigInt};
+
+    type SimFrTest = SimFr<SimFrParamsSecp256k1>;
+    type SimFrVarTest = SimFrVar<SimFrParamsSecp256k1>;
+
+    #[test]
+    fn test_enforce_zero_trivial() {
+        let mut cs = TurboCS::<BLSScalar>::new();
+
+        let zero_fr = SimFrTest::from(&BigUint::zero());
+        let (zero_fr_val, _) = SimFrVarTest::alloc_witness(&mut cs, &zero_fr);
+        let zero_fr_mul_val = zero_fr_val.mul(&mut cs, &zero_fr_val);
+
+        zero_fr_mul_val.enforce_zero(&mut cs);
+    }
+
+    #[test]
+    fn test_enforce_zero() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsSecp256k1::scalar_field_in_biguint();
+
+        for _ in 0..1000 {
+            let mut cs = TurboCS::<BLSScalar>::new();
+
+            let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+
+            let a_fr = SimFrTest::from(&a);
+            let b_fr = SimFrTest::from(&b);
+
+            let (a_fr_val, _) = SimFrVarTest::alloc_witness(&mut cs, &a_fr);
+            let (b_fr_val, _) = SimFrVarTest::alloc_witness(&mut cs, &b_fr);
+
+            let ab_fr_mul_val = a_fr_val.mul(&mut cs, &b_fr_val);
+
+            let ab_fr = &a * &b;
+            let ab_fr_reduced = &ab_fr % &r_biguint;
+            let ab_reduced = SimFrTest::from(&ab_fr_reduced);
+            let (ab_reduced_val, _) = SimFrVarTest::alloc_witness(&mut cs, &ab_reduced);
+
+            let zero_supposed = ab_fr_mul_val.sub(&mut cs, &ab_reduced_val);
+            zero_supposed.enforce_zero(&mut cs);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_enforce_zero_panic() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsSecp256k1::scalar_field_in_biguint();
+
+        let mut cs = TurboCS::<BLSScalar>::new();
+
+        let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+        let b = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+
+        let a_fr = SimFrTest::from(&a);
+        let b_fr = SimFrTest::from(&b);
+
+        let (a_fr_val, _) = SimFrVarTest::alloc_witness(&mut cs, &a_fr);
+        let (b_fr_val, _) = SimFrVarTest::alloc_witness(&mut cs, &b_fr);
+
+        let ab_fr_mul_val = a_fr_val.mul(&mut cs, &b_fr_val);
+
+        let ab_fr = &a * &b;
+        let ab_fr_reduced_manipulated = &ab_fr % &r_biguint + &BigUint::from(10u64);
+        let ab_reduced_manipulated = SimFrTest::from(&ab_fr_reduced_manipulated);
+        let (ab_reduced_manipulated_val, _) =
+            SimFrVarTest::alloc_witness(&mut cs, &ab_reduced_manipulated);
+
+        let zero_supposed_manipulated = ab_fr_mul_val.sub(&mut cs, &ab_reduced_manipulated_val);
+        zero_supposed_manipulated.enforce_zero(&mut cs);
+    }
+}
+
+#[cfg(test)]
+mod test_bls_fq {
+    use crate::plonk::constraint_system::{field_simulation::SimFrVar, turbo::TurboCS};
+    use ark_std::test_rng;
+    use noah_algebra::{bls12_381::BLSScalar, prelude::*};
+    use noah_crypto::field_simulation::{SimFr, SimFrParams, SimFrParamsBLSFq};
+    use num_bigint::{BigUint, RandBigInt};
+
+    type SimFrTest = SimFr<SimFrParamsBLSFq>;
+    type SimFrVarTest = SimFrVar<SimFrParamsBLSFq>;
+
+    #[test]
+    fn test_enforce_zero_trivial() {
+        let mut cs = TurboCS::<BLSScalar>::new();
+
+        let zero_fr = SimFrTest::from(&BigUint::zero());
+        let (zero_fr_val, _) = SimFrVarTest::alloc_witness(&mut cs, &zero_fr);
+        let zero_fr_mul_val = zero_fr_val.mul(&mut cs, &zero_fr_val);
+
+        zero_fr_mul_val.enforce_zero(&mut cs);
+    }
+
+    #[test]
+    fn test_enforce_zero() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsBLSFq::scalar_field_in_biguint();
+
+        for _ in 0..1000 {
+            let mut cs = TurboCS::<BLSScalar>::new();
+
+            let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+
+            let a_fr = SimFrTest::from(&a);
+            let b_fr = SimFrTest::from(&b);
+
+            let (a_fr_val, _) = SimFrVarTest::alloc_witness(&mut cs, &a_fr);
+            let (b_fr_val, _) = SimFrVarTest::alloc_witness(&mut cs, &b_fr);
+
+            let ab_fr_mul_val = a_fr_val.mul(&mut cs, &b_fr_val);
+
+            let ab_fr = &a * &b;
+            let ab_fr_reduced = &ab_fr % &r_biguint;
+            let ab_reduced = SimFrTest::from(&ab_fr_reduced);
+            let (ab_reduced_val, _) = SimFrVarTest::alloc_witness(&mut cs, &ab_reduced);
+
+            let zero_supposed = ab_fr_mul_val.sub(&mut cs, &ab_reduced_val);
+            zero_supposed.enforce_zero(&mut cs);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_enforce_zero_panic() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsBLSFq::scalar_field_in_biguint();
+
+        let mut cs = TurboCS::<BLSScalar>::new();
+
+        let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+        let b = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+
+        let a_fr = SimFrTest::from(&a);
+        let b_fr = SimFrTest::from(&b);
+
+        let (a_fr_val, _) = SimFrVarTest::alloc_witness(&mut cs, &a_fr);
+        let (b_fr_val, _) = SimFrVarTest::alloc_witness(&mut cs, &b_fr);
+
+        let ab_fr_mul_val = a_fr_val.mul(&mut cs, &b_fr_val);
+
+        let ab_fr = &a * &b;
+        let ab_fr_reduced_manipulated = &ab_fr % &r_biguint + &BigUint::from(10u64);
+        let ab_reduced_manipulated = SimFrTest::from(&ab_fr_reduced_manipulated);
+        let (ab_reduced_manipulated_val, _) =
+            SimFrVarTest::alloc_witness(&mut cs, &ab_reduced_manipulated);
+
+        let zero_supposed_manipulated = ab_fr_mul_val.sub(&mut cs, &ab_reduced_manipulated_val);
+        zero_supposed_manipulated.enforce_zero(&mut cs);
+    }
+}
+
+#[cfg(test)]
+mod test_ed25519_fq {
+    use crate::plonk::constraint_system::{field_simulation::SimFrVar, turbo::TurboCS};
+    use ark_std::test_rng;
+    use noah_algebra::{bls12_381::BLSScalar, prelude::*};
+    use noah_crypto::field_simulation::{SimFr, SimFrParams, SimFrParamsEd25519Fq};
+    use num_bigint::{BigUint, RandBigInt};
+
+    type SimFrTest = SimFr<SimFrParamsEd25519Fq>;
+    type SimFrVarTest = SimFrVar<SimFrParamsEd25519Fq>;
+
+    #[test]
+    fn test_enforce_zero_trivial() {
+        let mut cs = TurboCS::<BLSScalar>::new();
+
+        let zero_fr = SimFrTest::from(&BigUint::zero());
+        let (zero_fr_val, _) = SimFrVarTest::alloc_witness(&mut cs, &zero_fr);
+        let zero_fr_mul_val = zero_fr_val.mul(&mut cs, &zero_fr_val);
+
+        zero_fr_mul_val.enforce_zero(&mut cs);
+    }
+
+    #[test]
+    fn test_enforce_zero() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsEd25519Fq::scalar_field_in_biguint();
+
+        for _ in 0..1000 {
+            let mut cs = TurboCS::<BLSScalar>::new();
+
+            let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+
+            let a_fr = SimFrTest::from(&a);
+            let b_fr = SimFrTest::from(&b);
+
+            let (a_fr_val, _) = SimFrVarTest::alloc_witness(&mut cs, &a_fr);
+            let (b_fr_val, _) = SimFrVarTest::alloc_witness(&mut cs, &b_fr);
+
+            let ab_fr_mul_val = a_fr_val.mul(&mut cs, &b_fr_val);
+
+            let ab_fr = &a * &b;
+            let ab_fr_reduced = &ab_fr % &r_biguint;
+            let ab_reduced = SimFrTest::from(&ab_fr_reduced);
+            let (ab_reduced_val, _) = SimFrVarTest::alloc_witness(&mut cs, &ab_reduced);
+
+            let zero_supposed = ab_fr_mul_val.sub(&mut cs, &ab_reduced_val);
+            zero_supposed.enforce_zero(&mut cs);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_enforce_zero_panic() {
+        let mut prng = test_rng();
+        let r_biguint = SimFrParamsEd25519Fq::scalar_field_in_biguint();
+
+        let mut cs = TurboCS::<BLSScalar>::new();
+
+        let a = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+        let b = prng.gen_biguint_range(&BigUint::zero(), &r_biguint);
+
+        let a_fr = SimFrTest::from(&a);
+        let b_fr = SimFrTest::from(&b);
+
+        let (a_fr_val, _) = SimFrVarTest::alloc_witness(&mut cs, &a_fr);
+        let (b_fr_val, _) = SimFrVarTest::alloc_witness(&mut cs, &b_fr);
+
+        let ab_fr_mul_val = a_fr_val.mul(&mut cs, &b_fr_val);
+
+        let ab_fr = &a * &b;
+        let ab_fr_reduced_manipulated = &ab_fr % &r_biguint + &BigUint::from(10u64);
+        let ab_reduced_manipulated = SimFrTest::from(&ab_fr_reduced_manipulated);
+        let (ab_reduced_manipulated_val, _) =
+            SimFrVarTest::alloc_witness(&mut cs, &ab_reduced_manipulated);
+
+        let zero_supposed_manipulated = ab_fr_mul_val.sub(&mut cs, &ab_reduced_manipulated_val);
+        zero_supposed_manipulated.enforce_zero(&mut cs);
+    }
+}
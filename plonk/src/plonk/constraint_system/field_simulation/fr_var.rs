@@ -4,7 +4,8 @@ use noah_algebra::{
     cmp::{max, min},
     prelude::*,
 };
-use noah_crypto::field_simulation::{SimFr, SimFrParams, SimReducibility};
+use noah_crypto::field_simulation::{SimFr, SimFrMul, SimFrParams, SimReducibility};
+use num_bigint::BigUint;
 
 /// `SimFrVar` is the variable for `SimFr` in
 /// `TurboConstraintSystem<BLSScalar>`
@@ -25,6 +26,38 @@ impl<P: SimFrParams> SimFrVar<P> {
         }
     }
 
+    /// the Add operation.
+    pub fn add(&self, cs: &mut TurboCS<BLSScalar>, other: &SimFrVar<P>) -> SimFrVar<P> {
+        let mut res = SimFrVar::<P>::new(cs);
+        res.val = &self.val + &other.val;
+
+        let zero = BLSScalar::zero();
+        let one = BLSScalar::one();
+
+        let zero_var = cs.zero_var();
+
+        // The following gate represents
+        // res.var[i] := self.var[i] + other.var[i]
+        for i in 0..P::NUM_OF_LIMBS {
+            res.var[i] = cs.new_variable(res.val.limbs[i]);
+
+            cs.push_add_selectors(one, zero, one, zero);
+            cs.push_mul_selectors(zero, zero);
+            cs.push_constant_selector(zero);
+            cs.push_ecc_selector(zero);
+            cs.push_out_selector(one);
+
+            cs.wiring[0].push(self.var[i]);
+            cs.wiring[1].push(zero_var);
+            cs.wiring[2].push(other.var[i]);
+            cs.wiring[3].push(zero_var);
+            cs.wiring[4].push(res.var[i]);
+            cs.finish_new_gate();
+        }
+
+        res
+    }
+
     /// the Sub operation.
     pub fn sub(&self, cs: &mut TurboCS<BLSScalar>, other: &SimFrVar<P>) -> SimFrVar<P> {
         let mut res = SimFrVar::<P>::new(cs);
@@ -181,6 +214,143 @@ impl<P: SimFrParams> SimFrVar<P> {
         }
         (res, bits)
     }
+
+    /// the Neg operation.
+    pub fn neg(&self, cs: &mut TurboCS<BLSScalar>) -> SimFrVar<P> {
+        SimFrVar::<P>::new(cs).sub(cs, self)
+    }
+
+    /// the Inverse operation, which allocates the inverse as a witness
+    /// and enforces that its product with `self` is one.
+    pub fn inv(&self, cs: &mut TurboCS<BLSScalar>) -> Result<SimFrVar<P>> {
+        let inv_val = self.val.inv().c(d!())?;
+        let (inv, _) = Self::alloc_witness(cs, &inv_val);
+
+        let one = Self::alloc_constant(cs, &SimFr::<P>::from(&BigUint::one()));
+        let prod = self.mul(cs, &inv);
+        prod.sub(cs, &one).enforce_zero(cs);
+
+        Ok(inv)
+    }
+
+    /// Enforce that `self` and `other` represent the same field element.
+    pub fn enforce_equal(&self, cs: &mut TurboCS<BLSScalar>, other: &SimFrVar<P>) {
+        self.to_mul_var(cs).sub(cs, other).enforce_zero(cs);
+    }
+
+    /// Reduce to the normalized form, i.e., to limbs of a value smaller than the modulus,
+    /// and return the reduced element together with its little-endian bits.
+    pub fn reduce(&self, cs: &mut TurboCS<BLSScalar>) -> (SimFrVar<P>, Vec<VarIndex>) {
+        let (mut res, bits) = Self::alloc_witness(cs, &self.val.reduce());
+        res.enforce_less_than_modulus(cs);
+        self.enforce_equal(cs, &res);
+
+        res.val.num_of_additions_over_normal_form = SimReducibility::StrictlyNotReducible;
+        (res, bits)
+    }
+
+    /// Return the little-endian bits of the normalized form.
+    pub fn to_bits(&self, cs: &mut TurboCS<BLSScalar>) -> Vec<VarIndex> {
+        let (_, bits) = self.reduce(cs);
+        bits
+    }
+
+    /// Return the limbs of the normalized form.
+    pub fn to_limbs(&self, cs: &mut TurboCS<BLSScalar>) -> Vec<VarIndex> {
+        let (res, _) = self.reduce(cs);
+        res.var
+    }
+
+    /// Build a `SimFr` variable from native limbs, with range check gates on each limb,
+    /// and return it together with its little-endian bits.
+    pub fn from_limbs(cs: &mut TurboCS<BLSScalar>, limbs: &[VarIndex]) -> (Self, Vec<VarIndex>) {
+        assert_eq!(limbs.len(), P::NUM_OF_LIMBS);
+
+        let step = BigUint::from(1u32).shl(P::BIT_PER_LIMB);
+        let mut val = BigUint::zero();
+        for limb in limbs.iter().rev() {
+            val.mul_assign(&step);
+            val.add_assign(&cs.witness[*limb].into());
+        }
+
+        let mut res = Self::new(cs);
+        res.val = SimFr::<P>::from(&val);
+        res.val.num_of_additions_over_normal_form = SimReducibility::AtMostReducibleByOne;
+
+        let mut bits = Vec::new();
+        for i in 0..P::NUM_OF_LIMBS {
+            res.var[i] = limbs[i];
+
+            if i == P::NUM_OF_LIMBS - 1 {
+                bits.extend_from_slice(&cs.range_check(res.var[i], P::BIT_IN_TOP_LIMB));
+            } else {
+                bits.extend_from_slice(&cs.range_check(res.var[i], P::BIT_PER_LIMB));
+            }
+        }
+        (res, bits)
+    }
+
+    /// View `self` as a `SimFrMul` variable, with the higher limbs being zero.
+    fn to_mul_var(&self, cs: &mut TurboCS<BLSScalar>) -> SimFrMulVar<P> {
+        let mut res = SimFrMulVar::<P>::new(cs);
+        res.val = SimFrMul::<P>::from(&self.val);
+        res.var[..P::NUM_OF_LIMBS].copy_from_slice(&self.var);
+        res
+    }
+
+    /// Enforce that the value of a range-checked `self` is smaller than the modulus,
+    /// by showing that `self + diff = r - 1` for a range-checked `diff`.
+    /// A larger value leaves the constraints unsatisfied.
+    pub fn enforce_less_than_modulus(&self, cs: &mut TurboCS<BLSScalar>) {
+        let bound = P::scalar_field_in_biguint().sub(&BigUint::one());
+        let self_biguint: BigUint = (&self.val).into();
+        let diff_val = if self_biguint <= bound {
+            (&bound).sub(&self_biguint)
+        } else {
+            BigUint::zero()
+        };
+        let (diff, _) = Self::alloc_witness(cs, &SimFr::<P>::from(&diff_val));
+        let bound_limbs = SimFr::<P>::from(&bound).limbs;
+
+        let zero = BLSScalar::zero();
+        let one = BLSScalar::one();
+        let step = BLSScalar::from(&BigUint::from(1u32).shl(P::BIT_PER_LIMB));
+
+        let zero_var = cs.zero_var();
+
+        // The following gate represents
+        // self.var[i] + diff.var[i] + carry_in - carry * 2^{BIT_PER_LIMB} = bound_limbs[i]
+        // where the carry of the top limb is zero.
+        let mut carry_in = zero_var;
+        for i in 0..P::NUM_OF_LIMBS {
+            let sum: BigUint = self.val.limbs[i]
+                .add(&diff.val.limbs[i])
+                .add(&cs.witness[carry_in])
+                .into();
+            let carry = if i == P::NUM_OF_LIMBS - 1 {
+                zero_var
+            } else {
+                let carry = cs.new_variable(BLSScalar::from(&sum.shr(P::BIT_PER_LIMB)));
+                cs.insert_boolean_gate(carry);
+                carry
+            };
+
+            cs.push_add_selectors(one, one, one, step.neg());
+            cs.push_mul_selectors(zero, zero);
+            cs.push_constant_selector(bound_limbs[i].neg());
+            cs.push_ecc_selector(zero);
+            cs.push_out_selector(zero);
+
+            cs.wiring[0].push(self.var[i]);
+            cs.wiring[1].push(diff.var[i]);
+            cs.wiring[2].push(carry_in);
+            cs.wiring[3].push(carry);
+            cs.wiring[4].push(zero_var);
+            cs.finish_new_gate();
+
+            carry_in = carry;
+        }
+    }
 }
 
 #[cfg(test)]
@@ -191,7 +361,9 @@ mod test_ristretto {
     };
     use ark_std::test_rng;
     use noah_algebra::{bls12_381::BLSScalar, ops::Shl, prelude::*};
-    use noah_crypto::field_simulation::{SimFr, SimFrParams, SimFrParamsRistretto};
+    use noah_crypto::field_simulation::{
+        SimFr, SimFrParams, SimFrParamsRistretto, SimReducibility,
+    };
     use num_bigint::{BigUint, RandBigInt};
 
     type SimFrTest = SimFr<SimFrParamsRistretto>;
@@ -300,6 +472,270 @@ mod test_ristretto {
         }
     }
 
+    #[test]
+    fn test_add() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsRistretto::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let (b_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &b_sim_fr);
+
+                let c_sim_fr_var = a_sim_fr_var.add(&mut cs, &b_sim_fr_var);
+                assert_eq!(
+                    <&SimFrTest as Into<BigUint>>::into(&c_sim_fr_var.val),
+                    &a + &b
+                );
+                test_sim_fr_equality(cs, &c_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    fn test_neg() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsRistretto::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let a_sim_fr = SimFrTest::from(&a);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let c_sim_fr_var = a_sim_fr_var.neg(&mut cs);
+                assert_eq!(
+                    c_sim_fr_var.val.reduce().val,
+                    (&p_biguint - &a) % &p_biguint
+                );
+                test_sim_fr_equality(cs, &c_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inv() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsRistretto::scalar_field_in_biguint();
+
+        for _ in 0..10 {
+            let a = prng.gen_biguint_range(&BigUint::one(), &p_biguint);
+            let a_sim_fr = SimFrTest::from(&a);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let c_sim_fr_var = a_sim_fr_var.inv(&mut cs).unwrap();
+                assert_eq!(c_sim_fr_var.val.val, a_sim_fr.inv().unwrap().val);
+                test_sim_fr_equality(cs, &c_sim_fr_var);
+            }
+        }
+
+        let mut cs = TurboCS::<BLSScalar>::new();
+        let (zero_sim_fr_var, _) =
+            SimFrVarTest::alloc_witness(&mut cs, &SimFrTest::from(&BigUint::zero()));
+        assert!(zero_sim_fr_var.inv(&mut cs).is_err());
+    }
+
+    #[test]
+    fn test_reduce() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsRistretto::scalar_field_in_biguint();
+
+        for _ in 0..10 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let (b_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &b_sim_fr);
+                let c_sim_fr_var = a_sim_fr_var.add(&mut cs, &b_sim_fr_var);
+
+                let (d_sim_fr_var, _) = c_sim_fr_var.reduce(&mut cs);
+                assert_eq!(d_sim_fr_var.val.val, (&a + &b) % &p_biguint);
+                assert_eq!(
+                    d_sim_fr_var.val.limbs,
+                    SimFrTest::from(&((&a + &b) % &p_biguint)).limbs
+                );
+                test_sim_fr_equality(cs, &d_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inv_forged() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsRistretto::scalar_field_in_biguint();
+
+        let a = prng.gen_biguint_range(&BigUint::one(), &p_biguint);
+        let b = (&a + &BigUint::one()) % &p_biguint;
+
+        // the inverse witness is computed for `b` while the variables hold `a`
+        let mut cs = TurboCS::<BLSScalar>::new();
+        let (mut a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &SimFrTest::from(&a));
+        a_sim_fr_var.val = SimFrTest::from(&b);
+        a_sim_fr_var.val.num_of_additions_over_normal_form = SimReducibility::AtMostReducibleByOne;
+        a_sim_fr_var.inv(&mut cs).unwrap();
+
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness[..], &[]).is_err());
+    }
+
+    #[test]
+    fn test_reduce_non_canonical() {
+        let p_biguint = SimFrParamsRistretto::scalar_field_in_biguint();
+        let a = BigUint::from(1u32);
+        let b = BigUint::from(2u32);
+
+        for (res, is_ok) in [(BigUint::from(3u32), true), (&p_biguint + 3u32, false)] {
+            let mut cs = TurboCS::<BLSScalar>::new();
+            let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &SimFrTest::from(&a));
+            let (b_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &SimFrTest::from(&b));
+            let c_sim_fr_var = a_sim_fr_var.add(&mut cs, &b_sim_fr_var);
+
+            // the constraints of `reduce` with `res` as the reduced element
+            let (d_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &SimFrTest::from(&res));
+            d_sim_fr_var.enforce_less_than_modulus(&mut cs);
+            c_sim_fr_var.enforce_equal(&mut cs, &d_sim_fr_var);
+
+            let witness = cs.get_and_clear_witness();
+            assert_eq!(cs.verify_witness(&witness[..], &[]).is_ok(), is_ok);
+        }
+    }
+
+    #[test]
+    fn test_enforce_equal() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsRistretto::scalar_field_in_biguint();
+
+        for _ in 0..10 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+            let c_sim_fr = SimFrTest::from(&((&a + &b) % &p_biguint));
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let (b_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &b_sim_fr);
+                let (c_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &c_sim_fr);
+
+                let sum_sim_fr_var = a_sim_fr_var.add(&mut cs, &b_sim_fr_var);
+                sum_sim_fr_var.enforce_equal(&mut cs, &c_sim_fr_var);
+                test_sim_fr_equality(cs, &c_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_enforce_equal_panic() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsRistretto::scalar_field_in_biguint();
+
+        let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+        let b = (&a + &BigUint::one()) % &p_biguint;
+
+        let mut cs = TurboCS::<BLSScalar>::new();
+        let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &SimFrTest::from(&a));
+        let (b_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &SimFrTest::from(&b));
+        a_sim_fr_var.enforce_equal(&mut cs, &b_sim_fr_var);
+    }
+
+    #[test]
+    fn test_to_bits() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsRistretto::scalar_field_in_biguint();
+
+        for _ in 0..10 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let (b_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &b_sim_fr);
+                let c_sim_fr_var = a_sim_fr_var.sub(&mut cs, &b_sim_fr_var);
+
+                let bits = c_sim_fr_var.to_bits(&mut cs);
+                assert_eq!(
+                    bits.len(),
+                    SimFrParamsRistretto::BIT_PER_LIMB * (SimFrParamsRistretto::NUM_OF_LIMBS - 1)
+                        + SimFrParamsRistretto::BIT_IN_TOP_LIMB
+                );
+
+                let mut c = BigUint::zero();
+                for bit in bits.iter().rev() {
+                    c = c.shl(1u32) + <BLSScalar as Into<BigUint>>::into(cs.witness[*bit]);
+                }
+                assert_eq!(c, (&a + &p_biguint - &b) % &p_biguint);
+
+                let witness = cs.get_and_clear_witness();
+                assert!(cs.verify_witness(&witness[..], &[]).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn test_limbs_conversion() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsRistretto::scalar_field_in_biguint();
+
+        for _ in 0..10 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let a_sim_fr = SimFrTest::from(&a);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let limbs = a_sim_fr
+                    .limbs
+                    .iter()
+                    .map(|limb| cs.new_variable(*limb))
+                    .collect::<Vec<_>>();
+                let (a_sim_fr_var, _) = SimFrVarTest::from_limbs(&mut cs, &limbs);
+                assert_eq!(a_sim_fr_var.val.val, a);
+
+                let b_sim_fr_var = a_sim_fr_var.add(&mut cs, &a_sim_fr_var);
+                let b_limbs = b_sim_fr_var.to_limbs(&mut cs);
+                let b_limbs_val = b_limbs
+                    .iter()
+                    .map(|limb| cs.witness[*limb])
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    b_limbs_val,
+                    SimFrTest::from(&((&a + &a) % &p_biguint)).limbs
+                );
+
+                let witness = cs.get_and_clear_witness();
+                assert!(cs.verify_witness(&witness[..], &[]).is_ok());
+            }
+        }
+    }
+
     #[test]
     fn test_bounded_allocated_witness() {
         let mut prng = test_rng();
@@ -452,6 +888,834 @@ mod test_secq256k1 {
         }
     }
 
+    #[test]
+    fn test_add() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsSecq256k1::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let (b_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &b_sim_fr);
+
+                let c_sim_fr_var = a_sim_fr_var.add(&mut cs, &b_sim_fr_var);
+                assert_eq!(
+                    <&SimFrTest as Into<BigUint>>::into(&c_sim_fr_var.val),
+                    &a + &b
+                );
+                test_sim_fr_equality(cs, &c_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    fn test_neg() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsSecq256k1::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let a_sim_fr = SimFrTest::from(&a);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let c_sim_fr_var = a_sim_fr_var.neg(&mut cs);
+                assert_eq!(
+                    c_sim_fr_var.val.reduce().val,
+                    (&p_biguint - &a) % &p_biguint
+                );
+                test_sim_fr_equality(cs, &c_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inv() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsSecq256k1::scalar_field_in_biguint();
+
+        for _ in 0..10 {
+            let a = prng.gen_biguint_range(&BigUint::one(), &p_biguint);
+            let a_sim_fr = SimFrTest::from(&a);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let c_sim_fr_var = a_sim_fr_var.inv(&mut cs).unwrap();
+                assert_eq!(c_sim_fr_var.val.val, a_sim_fr.inv().unwrap().val);
+                test_sim_fr_equality(cs, &c_sim_fr_var);
+            }
+        }
+
+        let mut cs = TurboCS::<BLSScalar>::new();
+        let (zero_sim_fr_var, _) =
+            SimFrVarTest::alloc_witness(&mut cs, &SimFrTest::from(&BigUint::zero()));
+        assert!(zero_sim_fr_var.inv(&mut cs).is_err());
+    }
+
+    #[test]
+    fn test_reduce() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsSecq256k1::scalar_field_in_biguint();
+
+        for _ in 0..10 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let (b_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &b_sim_fr);
+                let c_sim_fr_var = a_sim_fr_var.add(&mut cs, &b_sim_fr_var);
+
+                let (d_sim_fr_var, _) = c_sim_fr_var.reduce(&mut cs);
+                assert_eq!(d_sim_fr_var.val.val, (&a + &b) % &p_biguint);
+                assert_eq!(
+                    d_sim_fr_var.val.limbs,
+                    SimFrTest::from(&((&a + &b) % &p_biguint)).limbs
+                );
+                test_sim_fr_equality(cs, &d_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    fn test_enforce_equal() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsSecq256k1::scalar_field_in_biguint();
+
+        for _ in 0..10 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+            let c_sim_fr = SimFrTest::from(&((&a + &b) % &p_biguint));
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let (b_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &b_sim_fr);
+                let (c_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &c_sim_fr);
+
+                let sum_sim_fr_var = a_sim_fr_var.add(&mut cs, &b_sim_fr_var);
+                sum_sim_fr_var.enforce_equal(&mut cs, &c_sim_fr_var);
+                test_sim_fr_equality(cs, &c_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_enforce_equal_panic() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsSecq256k1::scalar_field_in_biguint();
+
+        let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+        let b = (&a + &BigUint::one()) % &p_biguint;
+
+        let mut cs = TurboCS::<BLSScalar>::new();
+        let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &SimFrTest::from(&a));
+        let (b_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &SimFrTest::from(&b));
+        a_sim_fr_var.enforce_equal(&mut cs, &b_sim_fr_var);
+    }
+
+    #[test]
+    fn test_to_bits() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsSecq256k1::scalar_field_in_biguint();
+
+        for _ in 0..10 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let (b_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &b_sim_fr);
+                let c_sim_fr_var = a_sim_fr_var.sub(&mut cs, &b_sim_fr_var);
+
+                let bits = c_sim_fr_var.to_bits(&mut cs);
+                assert_eq!(
+                    bits.len(),
+                    SimFrParamsSecq256k1::BIT_PER_LIMB * (SimFrParamsSecq256k1::NUM_OF_LIMBS - 1)
+                        + SimFrParamsSecq256k1::BIT_IN_TOP_LIMB
+                );
+
+                let mut c = BigUint::zero();
+                for bit in bits.iter().rev() {
+                    c = c.shl(1u32) + <BLSScalar as Into<BigUint>>::into(cs.witness[*bit]);
+                }
+                assert_eq!(c, (&a + &p_biguint - &b) % &p_biguint);
+
+                let witness = cs.get_and_clear_witness();
+                assert!(cs.verify_witness(&witness[..], &[]).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn test_limbs_conversion() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsSecq256k1::scalar_field_in_biguint();
+
+        for _ in 0..10 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let a_sim_fr = SimFrTest::from(&a);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let limbs = a_sim_fr
+                    .limbs
+                    .iter()
+                    .map(|limb| cs.new_variable(*limb))
+                    .collect::<Vec<_>>();
+                let (a_sim_fr_var, _) = SimFrVarTest::from_limbs(&mut cs, &limbs);
+                assert_eq!(a_sim_fr_var.val.val, a);
+
+                let b_sim_fr_var = a_sim_fr_var.add(&mut cs, &a_sim_fr_var);
+                let b_limbs = b_sim_fr_var.to_limbs(&mut cs);
+                let b_limbs_val = b_limbs
+                    .iter()
+                    .map(|limb| cs.witness[*limb])
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    b_limbs_val,
+                    SimFrTest::from(&((&a + &a) % &p_biguint)).limbs
+                );
+
+                let witness = cs.get_and_clear_witness();
+                assert!(cs.verify_witness(&witness[..], &[]).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn test_bounded_allocated_witness() {
+        let mut prng = test_rng();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint(240);
+            let a_sim_fr = SimFrTest::from(&a);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) =
+                    SimFrVarTest::alloc_witness_bounded_total_bits(&mut cs, &a_sim_fr, 240);
+                test_sim_fr_equality(cs, &a_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bounded_allocated_witness_bad() {
+        let a = BigUint::from(1u32).shl(240);
+        let a_sim_fr = SimFrTest::from(&a);
+
+        {
+            let mut cs = TurboCS::<BLSScalar>::new();
+
+            let (a_sim_fr_var, _) =
+                SimFrVarTest::alloc_witness_bounded_total_bits(&mut cs, &a_sim_fr, 240);
+
+            test_sim_fr_equality(cs, &a_sim_fr_var);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_secp256k1 {
+    use crate::plonk::constraint_system::{
+        field_simulation::{SimFrMulVar, SimFrVar},
+        TurboCS,
+    };
+    use ark_std::test_rng;
+    use noah_algebra::{bls12_381::BLSScalar, ops::Shl, prelude::*};
+    use noah_crypto::field_simulation::{
+        SimFr, SimFrParams, SimFrParamsSecp256k1, SimReducibility,
+    };
+    use num_bigint::{BigUint, RandBigInt};
+
+    type SimFrTest = SimFr<SimFrParamsSecp256k1>;
+    type SimFrVarTest = SimFrVar<SimFrParamsSecp256k1>;
+    type SimFrMulVarTest = SimFrMulVar<SimFrParamsSecp256k1>;
+
+    fn test_sim_fr_equality(cs: TurboCS<BLSScalar>, val: &SimFrVarTest) {
+        let mut cs = cs;
+        for i in 0..SimFrParamsSecp256k1::NUM_OF_LIMBS {
+            cs.insert_constant_gate(val.var[i], val.val.limbs[i]);
+        }
+
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness[..], &[]).is_ok());
+    }
+
+    fn test_sim_fr_mul_equality(cs: TurboCS<BLSScalar>, val: &SimFrMulVarTest) {
+        let mut cs = cs;
+        for i in 0..SimFrParamsSecp256k1::NUM_OF_LIMBS_MUL {
+            cs.insert_constant_gate(val.var[i], val.val.limbs[i]);
+        }
+
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness[..], &[]).is_ok());
+    }
+
+    #[test]
+    fn test_alloc_constant() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsSecp256k1::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let a_sim_fr = SimFrTest::from(&a);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+                let a_sim_fr_var = SimFrVarTest::alloc_constant(&mut cs, &a_sim_fr);
+                test_sim_fr_equality(cs, &a_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    fn test_alloc_witness() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsSecp256k1::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let a_sim_fr = SimFrTest::from(&a);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                test_sim_fr_equality(cs, &a_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sub() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsSecp256k1::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let (b_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &b_sim_fr);
+
+                let c_sim_fr_var = a_sim_fr_var.sub(&mut cs, &b_sim_fr_var);
+                test_sim_fr_equality(cs, &c_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsSecp256k1::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let (b_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &b_sim_fr);
+
+                let c_sim_fr_mul_var = a_sim_fr_var.mul(&mut cs, &b_sim_fr_var);
+                test_sim_fr_mul_equality(cs, &c_sim_fr_mul_var);
+            }
+        }
+    }
+
+    #[test]
+    fn test_add() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsSecp256k1::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let (b_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &b_sim_fr);
+
+                let c_sim_fr_var = a_sim_fr_var.add(&mut cs, &b_sim_fr_var);
+                assert_eq!(
+                    <&SimFrTest as Into<BigUint>>::into(&c_sim_fr_var.val),
+                    &a + &b
+                );
+                test_sim_fr_equality(cs, &c_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    fn test_neg() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsSecp256k1::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let a_sim_fr = SimFrTest::from(&a);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let c_sim_fr_var = a_sim_fr_var.neg(&mut cs);
+                assert_eq!(
+                    c_sim_fr_var.val.reduce().val,
+                    (&p_biguint - &a) % &p_biguint
+                );
+                test_sim_fr_equality(cs, &c_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inv() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsSecp256k1::scalar_field_in_biguint();
+
+        for _ in 0..10 {
+            let a = prng.gen_biguint_range(&BigUint::one(), &p_biguint);
+            let a_sim_fr = SimFrTest::from(&a);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let c_sim_fr_var = a_sim_fr_var.inv(&mut cs).unwrap();
+                assert_eq!(c_sim_fr_var.val.val, a_sim_fr.inv().unwrap().val);
+                test_sim_fr_equality(cs, &c_sim_fr_var);
+            }
+        }
+
+        let mut cs = TurboCS::<BLSScalar>::new();
+        let (zero_sim_fr_var, _) =
+            SimFrVarTest::alloc_witness(&mut cs, &SimFrTest::from(&BigUint::zero()));
+        assert!(zero_sim_fr_var.inv(&mut cs).is_err());
+    }
+
+    #[test]
+    fn test_reduce() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsSecp256k1::scalar_field_in_biguint();
+
+        for _ in 0..10 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let (b_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &b_sim_fr);
+                let c_sim_fr_var = a_sim_fr_var.add(&mut cs, &b_sim_fr_var);
+
+                let (d_sim_fr_var, _) = c_sim_fr_var.reduce(&mut cs);
+                assert_eq!(d_sim_fr_var.val.val, (&a + &b) % &p_biguint);
+                assert_eq!(
+                    d_sim_fr_var.val.limbs,
+                    SimFrTest::from(&((&a + &b) % &p_biguint)).limbs
+                );
+                test_sim_fr_equality(cs, &d_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inv_forged() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsSecp256k1::scalar_field_in_biguint();
+
+        let a = prng.gen_biguint_range(&BigUint::one(), &p_biguint);
+        let b = (&a + &BigUint::one()) % &p_biguint;
+
+        // the inverse witness is computed for `b` while the variables hold `a`
+        let mut cs = TurboCS::<BLSScalar>::new();
+        let (mut a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &SimFrTest::from(&a));
+        a_sim_fr_var.val = SimFrTest::from(&b);
+        a_sim_fr_var.val.num_of_additions_over_normal_form = SimReducibility::AtMostReducibleByOne;
+        a_sim_fr_var.inv(&mut cs).unwrap();
+
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness[..], &[]).is_err());
+    }
+
+    #[test]
+    fn test_reduce_non_canonical() {
+        let p_biguint = SimFrParamsSecp256k1::scalar_field_in_biguint();
+        let a = BigUint::from(1u32);
+        let b = BigUint::from(2u32);
+
+        for (res, is_ok) in [(BigUint::from(3u32), true), (&p_biguint + 3u32, false)] {
+            let mut cs = TurboCS::<BLSScalar>::new();
+            let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &SimFrTest::from(&a));
+            let (b_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &SimFrTest::from(&b));
+            let c_sim_fr_var = a_sim_fr_var.add(&mut cs, &b_sim_fr_var);
+
+            // the constraints of `reduce` with `res` as the reduced element
+            let (d_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &SimFrTest::from(&res));
+            d_sim_fr_var.enforce_less_than_modulus(&mut cs);
+            c_sim_fr_var.enforce_equal(&mut cs, &d_sim_fr_var);
+
+            let witness = cs.get_and_clear_witness();
+            assert_eq!(cs.verify_witness(&witness[..], &[]).is_ok(), is_ok);
+        }
+    }
+
+    #[test]
+    fn test_bounded_allocated_witness() {
+        let mut prng = test_rng();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint(240);
+            let a_sim_fr = SimFrTest::from(&a);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) =
+                    SimFrVarTest::alloc_witness_bounded_total_bits(&mut cs, &a_sim_fr, 240);
+                test_sim_fr_equality(cs, &a_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bounded_allocated_witness_bad() {
+        let a = BigUint::from(1u32).shl(240);
+        let a_sim_fr = SimFrTest::from(&a);
+
+        {
+            let mut cs = TurboCS::<BLSScalar>::new();
+
+            let (a_sim_fr_var, _) =
+                SimFrVarTest::alloc_witness_bounded_total_bits(&mut cs, &a_sim_fr, 240);
+
+            test_sim_fr_equality(cs, &a_sim_fr_var);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_bls_fq {
+    use crate::plonk::constraint_system::{
+        field_simulation::{SimFrMulVar, SimFrVar},
+        TurboCS,
+    };
+    use ark_std::test_rng;
+    use noah_algebra::{bls12_381::BLSScalar, ops::Shl, prelude::*};
+    use noah_crypto::field_simulation::{SimFr, SimFrParams, SimFrParamsBLSFq};
+    use num_bigint::{BigUint, RandBigInt};
+
+    type SimFrTest = SimFr<SimFrParamsBLSFq>;
+    type SimFrVarTest = SimFrVar<SimFrParamsBLSFq>;
+    type SimFrMulVarTest = SimFrMulVar<SimFrParamsBLSFq>;
+
+    fn test_sim_fr_equality(cs: TurboCS<BLSScalar>, val: &SimFrVarTest) {
+        let mut cs = cs;
+        for i in 0..SimFrParamsBLSFq::NUM_OF_LIMBS {
+            cs.insert_constant_gate(val.var[i], val.val.limbs[i]);
+        }
+
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness[..], &[]).is_ok());
+    }
+
+    fn test_sim_fr_mul_equality(cs: TurboCS<BLSScalar>, val: &SimFrMulVarTest) {
+        let mut cs = cs;
+        for i in 0..SimFrParamsBLSFq::NUM_OF_LIMBS_MUL {
+            cs.insert_constant_gate(val.var[i], val.val.limbs[i]);
+        }
+
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness[..], &[]).is_ok());
+    }
+
+    #[test]
+    fn test_alloc_constant() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsBLSFq::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let a_sim_fr = SimFrTest::from(&a);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+                let a_sim_fr_var = SimFrVarTest::alloc_constant(&mut cs, &a_sim_fr);
+                test_sim_fr_equality(cs, &a_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    fn test_alloc_witness() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsBLSFq::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let a_sim_fr = SimFrTest::from(&a);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                test_sim_fr_equality(cs, &a_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sub() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsBLSFq::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let (b_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &b_sim_fr);
+
+                let c_sim_fr_var = a_sim_fr_var.sub(&mut cs, &b_sim_fr_var);
+                test_sim_fr_equality(cs, &c_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsBLSFq::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let (b_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &b_sim_fr);
+
+                let c_sim_fr_mul_var = a_sim_fr_var.mul(&mut cs, &b_sim_fr_var);
+                test_sim_fr_mul_equality(cs, &c_sim_fr_mul_var);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bounded_allocated_witness() {
+        let mut prng = test_rng();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint(240);
+            let a_sim_fr = SimFrTest::from(&a);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) =
+                    SimFrVarTest::alloc_witness_bounded_total_bits(&mut cs, &a_sim_fr, 240);
+                test_sim_fr_equality(cs, &a_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bounded_allocated_witness_bad() {
+        let a = BigUint::from(1u32).shl(240);
+        let a_sim_fr = SimFrTest::from(&a);
+
+        {
+            let mut cs = TurboCS::<BLSScalar>::new();
+
+            let (a_sim_fr_var, _) =
+                SimFrVarTest::alloc_witness_bounded_total_bits(&mut cs, &a_sim_fr, 240);
+
+            test_sim_fr_equality(cs, &a_sim_fr_var);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_ed25519_fq {
+    use crate::plonk::constraint_system::{
+        field_simulation::{SimFrMulVar, SimFrVar},
+        TurboCS,
+    };
+    use ark_std::test_rng;
+    use noah_algebra::{bls12_381::BLSScalar, ops::Shl, prelude::*};
+    use noah_crypto::field_simulation::{SimFr, SimFrParams, SimFrParamsEd25519Fq};
+    use num_bigint::{BigUint, RandBigInt};
+
+    type SimFrTest = SimFr<SimFrParamsEd25519Fq>;
+    type SimFrVarTest = SimFrVar<SimFrParamsEd25519Fq>;
+    type SimFrMulVarTest = SimFrMulVar<SimFrParamsEd25519Fq>;
+
+    fn test_sim_fr_equality(cs: TurboCS<BLSScalar>, val: &SimFrVarTest) {
+        let mut cs = cs;
+        for i in 0..SimFrParamsEd25519Fq::NUM_OF_LIMBS {
+            cs.insert_constant_gate(val.var[i], val.val.limbs[i]);
+        }
+
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness[..], &[]).is_ok());
+    }
+
+    fn test_sim_fr_mul_equality(cs: TurboCS<BLSScalar>, val: &SimFrMulVarTest) {
+        let mut cs = cs;
+        for i in 0..SimFrParamsEd25519Fq::NUM_OF_LIMBS_MUL {
+            cs.insert_constant_gate(val.var[i], val.val.limbs[i]);
+        }
+
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness[..], &[]).is_ok());
+    }
+
+    #[test]
+    fn test_alloc_constant() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsEd25519Fq::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let a_sim_fr = SimFrTest::from(&a);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+                let a_sim_fr_var = SimFrVarTest::alloc_constant(&mut cs, &a_sim_fr);
+                test_sim_fr_equality(cs, &a_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    fn test_alloc_witness() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsEd25519Fq::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let a_sim_fr = SimFrTest::from(&a);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                test_sim_fr_equality(cs, &a_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sub() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsEd25519Fq::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let (b_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &b_sim_fr);
+
+                let c_sim_fr_var = a_sim_fr_var.sub(&mut cs, &b_sim_fr_var);
+                test_sim_fr_equality(cs, &c_sim_fr_var);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul() {
+        let mut prng = test_rng();
+        let p_biguint = SimFrParamsEd25519Fq::scalar_field_in_biguint();
+
+        for _ in 0..100 {
+            let a = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+            let b = prng.gen_biguint_range(&BigUint::zero(), &p_biguint);
+
+            let a_sim_fr = SimFrTest::from(&a);
+            let b_sim_fr = SimFrTest::from(&b);
+
+            {
+                let mut cs = TurboCS::<BLSScalar>::new();
+
+                let (a_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &a_sim_fr);
+                let (b_sim_fr_var, _) = SimFrVarTest::alloc_witness(&mut cs, &b_sim_fr);
+
+                let c_sim_fr_mul_var = a_sim_fr_var.mul(&mut cs, &b_sim_fr_var);
+                test_sim_fr_mul_equality(cs, &c_sim_fr_mul_var);
+            }
+        }
+    }
+
     #[test]
     fn test_bounded_allocated_witness() {
         let mut prng = test_rng();
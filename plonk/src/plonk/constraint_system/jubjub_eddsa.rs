@@ -0,0 +1,186 @@
+use crate::plonk::constraint_system::{
+    ecc::{Point, PointVar},
+    TurboCS, VarIndex,
+};
+use noah_algebra::{
+    bls12_381::BLSScalar,
+    jubjub::{JubjubPoint, JubjubScalar},
+    prelude::*,
+};
+use noah_crypto::basic::jubjub_eddsa::{
+    compute_challenge_trace, JubjubEdDSAPublicKey, JubjubEdDSASignature,
+};
+use num_bigint::BigUint;
+
+/// The number of bits of a BLS12-381 scalar, which the challenge is decomposed into.
+const BLS_SCALAR_BITS: usize = 255;
+
+/// The number of bits of a Jubjub scalar, which the response is decomposed into.
+const JUBJUB_SCALAR_BITS: usize = 252;
+
+impl TurboCS<BLSScalar> {
+    /// Enforce that `signature` is a valid EdDSA signature over Jubjub of the message
+    /// `msg_vars` under the public key `public_key_var`, i.e., `s * G = R + c * pk`
+    /// where `c = H(R.x, R.y, pk.x, pk.y, msg)`.
+    ///
+    /// The public key is not checked to be on the curve and in the prime-order subgroup,
+    /// which is left to the caller, e.g., by making it a public input of a key that passed
+    /// `JubjubEdDSAPublicKey::from_point`, as the native verifier rejects other keys.
+    /// The Anemoi-Jive parameters must have been loaded.
+    pub fn jubjub_eddsa_verify(
+        &mut self,
+        public_key_var: &PointVar,
+        public_key: &JubjubEdDSAPublicKey,
+        msg_vars: &[VarIndex],
+        signature: &JubjubEdDSASignature,
+    ) {
+        let msg = msg_vars
+            .iter()
+            .map(|var| self.witness[*var])
+            .collect::<Vec<BLSScalar>>();
+
+        // 1. compute the challenge.
+        let r_var = self.new_point_variable(Point::from(&signature.r));
+        let trace = compute_challenge_trace(&signature.r, public_key, &msg);
+        let c_var = self.new_variable(trace.output);
+
+        let mut input_vars = vec![
+            r_var.get_x(),
+            r_var.get_y(),
+            public_key_var.get_x(),
+            public_key_var.get_y(),
+        ];
+        input_vars.extend_from_slice(msg_vars);
+        self.anemoi_variable_length_hash(&trace, &input_vars, c_var);
+
+        // 2. decompose the challenge uniquely, so that it is the same as the one outside.
+        let c_bits = self.range_check(c_var, BLS_SCALAR_BITS);
+        self.enforce_bits_less_than_modulus(&c_bits);
+        let c = JubjubScalar::from(&<BLSScalar as Into<BigUint>>::into(trace.output));
+
+        // 3. compute `s * G - c * pk`.
+        let s_var = self.new_variable(BLSScalar::from_bytes(&signature.s.to_bytes()).unwrap());
+        let s_g_var = self.scalar_mul(JubjubPoint::get_base(), s_var, JUBJUB_SCALAR_BITS);
+        let c_pk_var = self.var_base_scalar_mul_with_bits(public_key_var, public_key.0, &c_bits);
+
+        let zero_var = self.zero_var();
+        let neg_c_pk_x_var = self.sub(zero_var, c_pk_var.get_x());
+        let neg_c_pk_var = PointVar::new(neg_c_pk_x_var, c_pk_var.get_y());
+
+        let res_var = self.ecc_add(
+            &s_g_var,
+            &neg_c_pk_var,
+            &JubjubPoint::get_base().mul(&signature.s),
+            &public_key.0.mul(&c).neg(),
+        );
+
+        // 4. compare with `R`.
+        self.equal(res_var.get_var().get_x(), r_var.get_x());
+        self.equal(res_var.get_var().get_y(), r_var.get_y());
+    }
+
+    /// Enforce that the little-endian bits represent an integer smaller than
+    /// the BLS12-381 scalar field modulus, which makes the bit decomposition unique.
+    fn enforce_bits_less_than_modulus(&mut self, bits: &[VarIndex]) {
+        let bound = BigUint::from_bytes_le(&BLSScalar::get_field_size_le_bytes()) - 1u32;
+        assert_eq!(bits.len() as u64, bound.bits());
+
+        // `prefix_eq_var` remains one while the higher bits equal those of the bound,
+        // and a one bit where the bound has a zero bit is only allowed after that.
+        let zero_var = self.zero_var();
+        let mut prefix_eq_var = self.one_var();
+        for (i, bit_var) in bits.iter().enumerate().rev() {
+            if bound.bit(i as u64) {
+                prefix_eq_var = self.mul(prefix_eq_var, *bit_var);
+            } else {
+                self.insert_mul_gate(prefix_eq_var, *bit_var, zero_var);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::plonk::constraint_system::{ecc::Point, TurboCS};
+    use ark_std::test_rng;
+    use noah_algebra::{bls12_381::BLSScalar, jubjub::JubjubScalar, prelude::*};
+    use noah_crypto::basic::{
+        anemoi_jive::AnemoiJive381,
+        jubjub_eddsa::{JubjubEdDSAKeyPair, JubjubEdDSAPublicKey, JubjubEdDSASignature},
+    };
+    use num_bigint::BigUint;
+
+    fn build_cs(
+        public_key: &JubjubEdDSAPublicKey,
+        msg: &[BLSScalar],
+        signature: &JubjubEdDSASignature,
+    ) -> TurboCS<BLSScalar> {
+        let mut cs = TurboCS::new();
+        cs.load_anemoi_jive_parameters::<AnemoiJive381>();
+
+        let public_key_var = cs.new_point_variable(Point::from(&public_key.0));
+        let msg_vars = msg.iter().map(|v| cs.new_variable(*v)).collect::<Vec<_>>();
+        cs.jubjub_eddsa_verify(&public_key_var, public_key, &msg_vars, signature);
+        cs
+    }
+
+    #[test]
+    fn test_jubjub_eddsa_verify() {
+        let mut prng = test_rng();
+        let keypair = JubjubEdDSAKeyPair::generate(&mut prng);
+        let public_key = keypair.get_public_key();
+        let msg = vec![BLSScalar::random(&mut prng), BLSScalar::random(&mut prng)];
+        let signature = keypair.sign(&mut prng, &msg);
+
+        let mut cs = build_cs(&public_key, &msg, &signature);
+        let witness = cs.get_and_clear_witness();
+        pnk!(cs.verify_witness(&witness, &[]));
+
+        // another message
+        let other_msg = vec![msg[0], msg[1].add(&BLSScalar::one())];
+        let mut cs = build_cs(&public_key, &other_msg, &signature);
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness, &[]).is_err());
+
+        // another public key
+        let other_public_key = JubjubEdDSAKeyPair::generate(&mut prng).get_public_key();
+        let mut cs = build_cs(&other_public_key, &msg, &signature);
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness, &[]).is_err());
+
+        // a tampered signature
+        let tampered = JubjubEdDSASignature {
+            r: signature.r,
+            s: signature.s.add(&JubjubScalar::one()),
+        };
+        let mut cs = build_cs(&public_key, &msg, &tampered);
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness, &[]).is_err());
+    }
+
+    #[test]
+    fn test_enforce_bits_less_than_modulus() {
+        let modulus = BigUint::from_bytes_le(&BLSScalar::get_field_size_le_bytes());
+
+        let check = |value: &BigUint| {
+            let mut cs = TurboCS::<BLSScalar>::new();
+            let bits = (0..255)
+                .map(|i| {
+                    let bit = cs.new_variable(BLSScalar::from(value.bit(i) as u32));
+                    cs.insert_boolean_gate(bit);
+                    bit
+                })
+                .collect::<Vec<_>>();
+            cs.enforce_bits_less_than_modulus(&bits);
+            let witness = cs.get_and_clear_witness();
+            cs.verify_witness(&witness, &[]).is_ok()
+        };
+
+        assert!(check(&BigUint::zero()));
+        assert!(check(&(&modulus - 1u32)));
+        assert!(!check(&modulus));
+        // the bits of `r + 1` also sum up to one modulo `r`
+        assert!(!check(&(&modulus + 1u32)));
+        assert!(!check(&((BigUint::one() << 255u32) - 1u32)));
+    }
+}
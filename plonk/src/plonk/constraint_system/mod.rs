@@ -9,9 +9,18 @@ pub mod turbo;
 /// Module for ECC.
 pub mod ecc;
 
+/// Module for EdDSA signature verification over Jubjub.
+pub mod jubjub_eddsa;
+
+/// Module for secp256k1 point arithmetic and ECDSA verification with field simulation.
+pub mod secp256k1;
+
 /// Module for the Anemoi-Jive hash function.
 pub mod anemoi_jive;
 
+/// Module for the Poseidon hash function.
+pub mod poseidon;
+
 /// Default used constraint system.
 #[doc(hidden)]
 pub use turbo::TurboCS;
@@ -0,0 +1,182 @@
+use crate::plonk::constraint_system::{TurboCS, VarIndex};
+use noah_algebra::{bls12_381::BLSScalar, prelude::*};
+use noah_crypto::basic::poseidon::{Poseidon381, POSEIDON_WIDTH};
+
+impl TurboCS<BLSScalar> {
+    /// Create an output variable `q1 * w1 + q2 * w2 + q3 * w3 + qc` and the gate for it.
+    fn poseidon_linear_combine(
+        &mut self,
+        vars: &[VarIndex; POSEIDON_WIDTH],
+        coefs: &[BLSScalar; POSEIDON_WIDTH],
+        constant: BLSScalar,
+    ) -> VarIndex {
+        let zero = BLSScalar::zero();
+        let zero_var = self.zero_var();
+
+        let mut value = constant;
+        for (var, coef) in vars.iter().zip(coefs.iter()) {
+            value.add_assign(&self.witness[*var].mul(coef));
+        }
+        let out_var = self.new_variable(value);
+
+        self.push_add_selectors(coefs[0], coefs[1], coefs[2], zero);
+        self.push_mul_selectors(zero, zero);
+        self.push_constant_selector(constant);
+        self.push_ecc_selector(zero);
+        self.push_out_selector(BLSScalar::one());
+
+        self.wiring[0].push(vars[0]);
+        self.wiring[1].push(vars[1]);
+        self.wiring[2].push(vars[2]);
+        self.wiring[3].push(zero_var);
+        self.wiring[4].push(out_var);
+        self.finish_new_gate();
+
+        out_var
+    }
+
+    /// Create constraints for the S-box `x^5`.
+    fn poseidon_sbox(&mut self, var: VarIndex) -> VarIndex {
+        let square_var = self.mul(var, var);
+        let quad_var = self.mul(square_var, square_var);
+        self.mul(quad_var, var)
+    }
+
+    /// Create constraints for the Poseidon permutation.
+    ///
+    /// The round keys of each round, except the first, are added together with the MDS matrix
+    /// of the previous round, so that a round takes three gates for the linear layer.
+    pub fn poseidon_permutation(
+        &mut self,
+        input_var: &[VarIndex; POSEIDON_WIDTH],
+    ) -> [VarIndex; POSEIDON_WIDTH] {
+        let params = Poseidon381::get_params();
+        let zero = BLSScalar::zero();
+        let one = BLSScalar::one();
+
+        let mut state_var = [self.zero_var(); POSEIDON_WIDTH];
+        for (state, (input, key)) in state_var
+            .iter_mut()
+            .zip(input_var.iter().zip(params.round_keys[0].iter()))
+        {
+            let vars = [*input, *input, *input];
+            *state = self.poseidon_linear_combine(&vars, &[one, zero, zero], *key);
+        }
+
+        let num_rounds = params.round_keys.len();
+        for round in 0..num_rounds {
+            if Poseidon381::is_full_round(round) {
+                for state in state_var.iter_mut() {
+                    *state = self.poseidon_sbox(*state);
+                }
+            } else {
+                state_var[0] = self.poseidon_sbox(state_var[0]);
+            }
+
+            let next_round_keys = if round + 1 < num_rounds {
+                params.round_keys[round + 1]
+            } else {
+                [zero; POSEIDON_WIDTH]
+            };
+
+            let mut new_state_var = state_var;
+            for (new_state, (row, key)) in new_state_var
+                .iter_mut()
+                .zip(params.mds.iter().zip(next_round_keys.iter()))
+            {
+                *new_state = self.poseidon_linear_combine(&state_var, row, *key);
+            }
+            state_var = new_state_var;
+        }
+
+        state_var
+    }
+
+    /// Create constraints for the Poseidon variable-length hash and return the output variable,
+    /// which follows the sponge in `Poseidon381::eval_variable_length_hash`.
+    pub fn poseidon_variable_length_hash(&mut self, input_var: &[VarIndex]) -> VarIndex {
+        let len_var = self.new_variable(BLSScalar::from(input_var.len() as u64));
+        self.insert_constant_gate(len_var, BLSScalar::from(input_var.len() as u64));
+
+        let zero_var = self.zero_var();
+        let mut state_var = [len_var, zero_var, zero_var];
+
+        let mut absorb = |cs: &mut Self, chunk: &[VarIndex]| {
+            for (state, var) in state_var[1..].iter_mut().zip(chunk.iter()) {
+                *state = cs.add(*state, *var);
+            }
+            state_var = cs.poseidon_permutation(&state_var);
+        };
+        if input_var.is_empty() {
+            absorb(self, &[]);
+        }
+        for chunk in input_var.chunks(POSEIDON_WIDTH - 1) {
+            absorb(self, chunk);
+        }
+
+        state_var[1]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::plonk::constraint_system::TurboCS;
+    use ark_std::test_rng;
+    use noah_algebra::{bls12_381::BLSScalar, prelude::*};
+    use noah_crypto::basic::poseidon::Poseidon381;
+
+    #[test]
+    fn test_poseidon_permutation() {
+        let mut prng = test_rng();
+        let input = [
+            BLSScalar::random(&mut prng),
+            BLSScalar::random(&mut prng),
+            BLSScalar::random(&mut prng),
+        ];
+
+        let mut cs = TurboCS::new();
+        let input_var = [
+            cs.new_variable(input[0]),
+            cs.new_variable(input[1]),
+            cs.new_variable(input[2]),
+        ];
+        let output_var = cs.poseidon_permutation(&input_var);
+
+        let output = Poseidon381::eval_permutation(&input);
+        for (var, value) in output_var.iter().zip(output.iter()) {
+            assert_eq!(cs.witness[*var], *value);
+        }
+
+        let witness = cs.get_and_clear_witness();
+        pnk!(cs.verify_witness(&witness, &[]));
+    }
+
+    #[test]
+    fn test_poseidon_variable_length_hash() {
+        let mut prng = test_rng();
+
+        for len in 0..5 {
+            let input = (0..len)
+                .map(|_| BLSScalar::random(&mut prng))
+                .collect::<Vec<_>>();
+
+            let mut cs = TurboCS::new();
+            let input_var = input
+                .iter()
+                .map(|v| cs.new_variable(*v))
+                .collect::<Vec<_>>();
+            let output_var = cs.poseidon_variable_length_hash(&input_var);
+            assert_eq!(
+                cs.witness[output_var],
+                Poseidon381::eval_variable_length_hash(&input)
+            );
+
+            let mut witness = cs.get_and_clear_witness();
+            pnk!(cs.verify_witness(&witness, &[]));
+
+            // a wrong output
+            witness[output_var].add_assign(&BLSScalar::one());
+            assert!(cs.verify_witness(&witness, &[]).is_err());
+        }
+    }
+}
@@ -0,0 +1,484 @@
+use crate::plonk::constraint_system::{field_simulation::SimFrVar, TurboCS, VarIndex};
+use noah_algebra::{
+    bls12_381::BLSScalar,
+    prelude::*,
+    secp256k1::{SECP256K1Scalar, SECP256K1G1},
+    secq256k1::SECQ256K1Scalar,
+};
+use noah_crypto::field_simulation::{
+    SimFr, SimFrParams, SimFrParamsSecp256k1, SimFrParamsSecq256k1, SimReducibility,
+};
+use num_bigint::BigUint;
+use rand_chacha::ChaChaRng;
+
+/// The simulated base field of secp256k1, which is the scalar field of secq256k1.
+type SimFqVar = SimFrVar<SimFrParamsSecq256k1>;
+
+/// The simulated scalar field of secp256k1.
+type SimFrSecp256k1Var = SimFrVar<SimFrParamsSecp256k1>;
+
+/// Convert a secp256k1 base field element into a simulated field element.
+fn to_sim_fq(x: &SECQ256K1Scalar) -> SimFr<SimFrParamsSecq256k1> {
+    SimFr::<SimFrParamsSecq256k1>::from(&<SECQ256K1Scalar as Into<BigUint>>::into(*x))
+}
+
+/// Convert a simulated field element into a secp256k1 base field element.
+fn from_sim_fq(x: &SimFr<SimFrParamsSecq256k1>) -> SECQ256K1Scalar {
+    SECQ256K1Scalar::from(&x.reduce().val)
+}
+
+/// The offset point that scalar multiplications start from, so that the incomplete
+/// addition formulas never meet the point at infinity.
+fn scalar_mul_offset() -> SECP256K1G1 {
+    SECP256K1G1::random(&mut ChaChaRng::from_seed([0u8; 32]))
+}
+
+/// A secp256k1 point in affine coordinates, with coordinates simulated in TurboCS.
+#[derive(Clone)]
+pub struct SECP256K1PointVar {
+    /// The x-coordinate.
+    pub x: SimFqVar,
+    /// The y-coordinate.
+    pub y: SimFqVar,
+}
+
+impl TurboCS<BLSScalar> {
+    /// Create variables for a constant secp256k1 point, which must not be the point at infinity.
+    pub fn new_secp256k1_point_constant(&mut self, point: &SECP256K1G1) -> SECP256K1PointVar {
+        SECP256K1PointVar {
+            x: SimFqVar::alloc_constant(self, &to_sim_fq(&point.get_x())),
+            y: SimFqVar::alloc_constant(self, &to_sim_fq(&point.get_y())),
+        }
+    }
+
+    /// Create variables for a secp256k1 point, which must not be the point at infinity,
+    /// and enforce that the point is on the curve `y^2 = x^3 + 7`.
+    pub fn new_secp256k1_point_variable(&mut self, point: &SECP256K1G1) -> SECP256K1PointVar {
+        let x_val = point.get_x();
+        let y_val = point.get_y();
+
+        let (x, _) = SimFqVar::alloc_witness(self, &to_sim_fq(&x_val));
+        let (y, _) = SimFqVar::alloc_witness(self, &to_sim_fq(&y_val));
+
+        let (x_sq, _) = SimFqVar::alloc_witness(self, &to_sim_fq(&x_val.mul(&x_val)));
+        x.mul(self, &x).sub(self, &x_sq).enforce_zero(self);
+
+        let (y_sq, _) = SimFqVar::alloc_witness(self, &to_sim_fq(&y_val.mul(&y_val)));
+        y.mul(self, &y).sub(self, &y_sq).enforce_zero(self);
+
+        let seven = SimFqVar::alloc_constant(self, &to_sim_fq(&SECQ256K1Scalar::from(7u32)));
+        let y_sq_minus_seven = y_sq.sub(self, &seven);
+        x_sq.mul(self, &x)
+            .sub(self, &y_sq_minus_seven)
+            .enforce_zero(self);
+
+        SECP256K1PointVar { x, y }
+    }
+
+    /// Add two secp256k1 points with distinct x-coordinates.
+    ///
+    /// The slope `lambda` is a witness, and the constraints are
+    /// `lambda * (x2 - x1) = y2 - y1`, `lambda^2 = x3 + x1 + x2`, and `lambda * (x1 - x3) = y3 + y1`,
+    /// in addition to `x2 - x1` being invertible.
+    pub fn secp256k1_add(
+        &mut self,
+        p1_var: &SECP256K1PointVar,
+        p2_var: &SECP256K1PointVar,
+    ) -> Result<SECP256K1PointVar> {
+        let x1_val = from_sim_fq(&p1_var.x.val);
+        let y1_val = from_sim_fq(&p1_var.y.val);
+        let x2_val = from_sim_fq(&p2_var.x.val);
+        let y2_val = from_sim_fq(&p2_var.y.val);
+
+        let dx = p2_var.x.sub(self, &p1_var.x);
+        let dy = p2_var.y.sub(self, &p1_var.y);
+        dx.inv(self).c(d!())?;
+
+        let lambda_val = y2_val.sub(&y1_val).mul(&x2_val.sub(&x1_val).inv().c(d!())?);
+        let (lambda, _) = SimFqVar::alloc_witness(self, &to_sim_fq(&lambda_val));
+        lambda.mul(self, &dx).sub(self, &dy).enforce_zero(self);
+
+        let x3_val = lambda_val.mul(&lambda_val).sub(&x1_val).sub(&x2_val);
+        let (x3, _) = SimFqVar::alloc_witness(self, &to_sim_fq(&x3_val));
+        let x1_plus_x2 = p1_var.x.add(self, &p2_var.x);
+        lambda
+            .mul(self, &lambda)
+            .sub(self, &x3)
+            .sub(self, &x1_plus_x2)
+            .enforce_zero(self);
+
+        let y3_val = lambda_val.mul(&x1_val.sub(&x3_val)).sub(&y1_val);
+        let (y3, _) = SimFqVar::alloc_witness(self, &to_sim_fq(&y3_val));
+        let x1_minus_x3 = p1_var.x.sub(self, &x3);
+        let y3_plus_y1 = y3.add(self, &p1_var.y);
+        lambda
+            .mul(self, &x1_minus_x3)
+            .sub(self, &y3_plus_y1)
+            .enforce_zero(self);
+
+        Ok(SECP256K1PointVar { x: x3, y: y3 })
+    }
+
+    /// Double a secp256k1 point.
+    ///
+    /// The slope `lambda` is a witness, and the constraints are
+    /// `lambda * 2y = 3x^2`, `lambda^2 = x3 + 2x`, and `lambda * (x - x3) = y3 + y`.
+    /// Since secp256k1 has no point of order two, `y` is never zero for points on the curve.
+    pub fn secp256k1_double(&mut self, p_var: &SECP256K1PointVar) -> Result<SECP256K1PointVar> {
+        let x_val = from_sim_fq(&p_var.x.val);
+        let y_val = from_sim_fq(&p_var.y.val);
+
+        let three_x_sq_val = x_val.mul(&x_val).mul(&SECQ256K1Scalar::from(3u32));
+        let (three_x_sq, _) = SimFqVar::alloc_witness(self, &to_sim_fq(&three_x_sq_val));
+        let two_x = p_var.x.add(self, &p_var.x);
+        let three_x = two_x.add(self, &p_var.x);
+        p_var
+            .x
+            .mul(self, &three_x)
+            .sub(self, &three_x_sq)
+            .enforce_zero(self);
+
+        let lambda_val = three_x_sq_val.mul(&y_val.add(&y_val).inv().c(d!())?);
+        let (lambda, _) = SimFqVar::alloc_witness(self, &to_sim_fq(&lambda_val));
+        let two_y = p_var.y.add(self, &p_var.y);
+        lambda
+            .mul(self, &two_y)
+            .sub(self, &three_x_sq)
+            .enforce_zero(self);
+
+        let x3_val = lambda_val.mul(&lambda_val).sub(&x_val).sub(&x_val);
+        let (x3, _) = SimFqVar::alloc_witness(self, &to_sim_fq(&x3_val));
+        lambda
+            .mul(self, &lambda)
+            .sub(self, &x3)
+            .sub(self, &two_x)
+            .enforce_zero(self);
+
+        let y3_val = lambda_val.mul(&x_val.sub(&x3_val)).sub(&y_val);
+        let (y3, _) = SimFqVar::alloc_witness(self, &to_sim_fq(&y3_val));
+        let x_minus_x3 = p_var.x.sub(self, &x3);
+        let y3_plus_y = y3.add(self, &p_var.y);
+        lambda
+            .mul(self, &x_minus_x3)
+            .sub(self, &y3_plus_y)
+            .enforce_zero(self);
+
+        Ok(SECP256K1PointVar { x: x3, y: y3 })
+    }
+
+    /// Select `p0_var` if `bit` is zero, and `p1_var` if `bit` is one.
+    pub fn secp256k1_select(
+        &mut self,
+        p0_var: &SECP256K1PointVar,
+        p1_var: &SECP256K1PointVar,
+        bit: VarIndex,
+    ) -> SECP256K1PointVar {
+        SECP256K1PointVar {
+            x: self.select_sim_fq(&p0_var.x, &p1_var.x, bit),
+            y: self.select_sim_fq(&p0_var.y, &p1_var.y, bit),
+        }
+    }
+
+    fn select_sim_fq(&mut self, a0: &SimFqVar, a1: &SimFqVar, bit: VarIndex) -> SimFqVar {
+        for a in [a0, a1] {
+            assert!(
+                a.val.num_of_additions_over_normal_form == SimReducibility::StrictlyNotReducible
+                    || a.val.num_of_additions_over_normal_form
+                        == SimReducibility::AtMostReducibleByOne
+            );
+        }
+
+        let mut res = if self.witness[bit] == BLSScalar::zero() {
+            a0.clone()
+        } else {
+            a1.clone()
+        };
+        res.val.num_of_additions_over_normal_form = SimReducibility::AtMostReducibleByOne;
+        for i in 0..SimFrParamsSecq256k1::NUM_OF_LIMBS {
+            res.var[i] = self.select(a0.var[i], a1.var[i], bit);
+        }
+        res
+    }
+
+    /// Compute `s * [base]` for a constant base point, given the little-endian bits of `s`,
+    /// by adding the precomputed `2^i * [base]` for each bit.
+    pub fn secp256k1_fixed_base_scalar_mul(
+        &mut self,
+        base: &SECP256K1G1,
+        scalar_bits: &[VarIndex],
+    ) -> Result<SECP256K1PointVar> {
+        let offset = scalar_mul_offset();
+
+        let mut acc = self.new_secp256k1_point_constant(&offset);
+        let mut point = *base;
+        for bit in scalar_bits.iter() {
+            let point_var = self.new_secp256k1_point_constant(&point);
+            let sum = self.secp256k1_add(&acc, &point_var).c(d!())?;
+            acc = self.secp256k1_select(&acc, &sum, *bit);
+            point = point.double();
+        }
+
+        let neg_offset = self.new_secp256k1_point_constant(&offset.neg());
+        self.secp256k1_add(&acc, &neg_offset).c(d!())
+    }
+
+    /// Compute `s * [base]` for a variable base point, given the little-endian bits of `s`,
+    /// by doubling and adding from the most significant bit.
+    pub fn secp256k1_scalar_mul(
+        &mut self,
+        base_var: &SECP256K1PointVar,
+        scalar_bits: &[VarIndex],
+    ) -> Result<SECP256K1PointVar> {
+        let offset = scalar_mul_offset();
+
+        let mut acc = self.new_secp256k1_point_constant(&offset);
+        let mut acc_offset = offset;
+        for bit in scalar_bits.iter().rev() {
+            acc = self.secp256k1_double(&acc).c(d!())?;
+            let sum = self.secp256k1_add(&acc, base_var).c(d!())?;
+            acc = self.secp256k1_select(&acc, &sum, *bit);
+            acc_offset = acc_offset.double();
+        }
+
+        let neg_offset = self.new_secp256k1_point_constant(&acc_offset.neg());
+        self.secp256k1_add(&acc, &neg_offset).c(d!())
+    }
+
+    /// Enforce that `(r, s)` is a valid secp256k1 ECDSA signature of the message hash `z`
+    /// under the public key `public_key_var`, i.e., the x-coordinate of
+    /// `(z / s) * [G] + (r / s) * [public_key]` equals `r`.
+    ///
+    /// The x-coordinate is compared with `r` as an integer, so signatures whose x-coordinate
+    /// is at least the group order, which happens with probability about 2^{-128},
+    /// are rejected.
+    pub fn secp256k1_ecdsa_verify(
+        &mut self,
+        public_key_var: &SECP256K1PointVar,
+        z_var: &SimFrSecp256k1Var,
+        r_var: &SimFrSecp256k1Var,
+        s_var: &SimFrSecp256k1Var,
+    ) -> Result<()> {
+        r_var.inv(self).c(d!())?;
+        let w = s_var.inv(self).c(d!())?;
+
+        let u1_val = (&z_var.val * &w.val).val % SimFrParamsSecp256k1::scalar_field_in_biguint();
+        let (u1, _) = SimFrSecp256k1Var::alloc_witness(self, &SimFr::from(&u1_val));
+        z_var.mul(self, &w).sub(self, &u1).enforce_zero(self);
+
+        let u2_val = (&r_var.val * &w.val).val % SimFrParamsSecp256k1::scalar_field_in_biguint();
+        let (u2, _) = SimFrSecp256k1Var::alloc_witness(self, &SimFr::from(&u2_val));
+        r_var.mul(self, &w).sub(self, &u2).enforce_zero(self);
+
+        let u1_bits = u1.to_bits(self);
+        let u2_bits = u2.to_bits(self);
+
+        let p1 = self
+            .secp256k1_fixed_base_scalar_mul(&SECP256K1G1::get_base(), &u1_bits)
+            .c(d!())?;
+        let p2 = self
+            .secp256k1_scalar_mul(public_key_var, &u2_bits)
+            .c(d!())?;
+        let point = self.secp256k1_add(&p1, &p2).c(d!())?;
+
+        // Both simulated fields use the same limb layout, so the normalized forms
+        // are equal as integers if and only if their limbs are equal.
+        assert_eq!(
+            SimFrParamsSecq256k1::NUM_OF_LIMBS,
+            SimFrParamsSecp256k1::NUM_OF_LIMBS
+        );
+        assert_eq!(
+            SimFrParamsSecq256k1::BIT_PER_LIMB,
+            SimFrParamsSecp256k1::BIT_PER_LIMB
+        );
+        let x_limbs = point.x.to_limbs(self);
+        let r_limbs = r_var.to_limbs(self);
+        for (x_limb, r_limb) in x_limbs.iter().zip(r_limbs.iter()) {
+            self.equal(*x_limb, *r_limb);
+        }
+
+        Ok(())
+    }
+
+    /// Enforce that `public_key_var` is `s * [G]` for the secp256k1 generator `G`,
+    /// given the little-endian bits of the secret key `s`.
+    pub fn secp256k1_public_key_derivation(
+        &mut self,
+        public_key_var: &SECP256K1PointVar,
+        secret_key_bits: &[VarIndex],
+    ) -> Result<()> {
+        let point = self
+            .secp256k1_fixed_base_scalar_mul(&SECP256K1G1::get_base(), secret_key_bits)
+            .c(d!())?;
+        point.x.enforce_equal(self, &public_key_var.x);
+        point.y.enforce_equal(self, &public_key_var.y);
+        Ok(())
+    }
+}
+
+/// Convert a secp256k1 scalar into a simulated field element.
+pub fn secp256k1_scalar_to_sim_fr(s: &SECP256K1Scalar) -> SimFr<SimFrParamsSecp256k1> {
+    SimFr::<SimFrParamsSecp256k1>::from(&<SECP256K1Scalar as Into<BigUint>>::into(*s))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::plonk::constraint_system::{
+        field_simulation::SimFrVar,
+        secp256k1::{secp256k1_scalar_to_sim_fr, to_sim_fq},
+        TurboCS,
+    };
+    use ark_std::test_rng;
+    use noah_algebra::{
+        bls12_381::BLSScalar,
+        prelude::*,
+        secp256k1::{SECP256K1Scalar, SECP256K1G1},
+    };
+    use noah_crypto::field_simulation::SimFrParamsSecp256k1;
+    use num_bigint::BigUint;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    fn check_point(
+        cs: &TurboCS<BLSScalar>,
+        point_var: &super::SECP256K1PointVar,
+        point: &SECP256K1G1,
+    ) {
+        assert_eq!(point_var.x.val.reduce().val, to_sim_fq(&point.get_x()).val);
+        assert_eq!(point_var.y.val.reduce().val, to_sim_fq(&point.get_y()).val);
+        let witness = cs.witness.clone();
+        assert!(cs.verify_witness(&witness, &[]).is_ok());
+    }
+
+    #[test]
+    fn test_secp256k1_add_and_double() {
+        let mut prng = test_rng();
+        let p1 = SECP256K1G1::random(&mut prng);
+        let p2 = SECP256K1G1::random(&mut prng);
+
+        let mut cs = TurboCS::<BLSScalar>::new();
+        let p1_var = cs.new_secp256k1_point_variable(&p1);
+        let p2_var = cs.new_secp256k1_point_variable(&p2);
+
+        let sum_var = cs.secp256k1_add(&p1_var, &p2_var).unwrap();
+        check_point(&cs, &sum_var, &p1.add(&p2));
+
+        let double_var = cs.secp256k1_double(&p1_var).unwrap();
+        check_point(&cs, &double_var, &p1.double());
+
+        // adding a point to itself is not supported by the addition formula
+        assert!(cs.secp256k1_add(&p1_var, &p1_var).is_err());
+    }
+
+    #[test]
+    fn test_secp256k1_point_not_on_curve() {
+        let mut prng = test_rng();
+        let p = SECP256K1G1::random(&mut prng);
+
+        let mut cs = TurboCS::<BLSScalar>::new();
+        let p_var = cs.new_secp256k1_point_variable(&p);
+        let mut witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness, &[]).is_ok());
+
+        // move the point off the curve
+        witness[p_var.y.var[0]] = witness[p_var.y.var[0]].add(&BLSScalar::one());
+        assert!(cs.verify_witness(&witness, &[]).is_err());
+    }
+
+    #[test]
+    fn test_secp256k1_scalar_mul() {
+        let mut prng = test_rng();
+        let base = SECP256K1G1::random(&mut prng);
+        let s = SECP256K1Scalar::from(prng.next_u32());
+
+        let mut cs = TurboCS::<BLSScalar>::new();
+        let s_var = cs.new_variable(BLSScalar::from(&<SECP256K1Scalar as Into<BigUint>>::into(
+            s,
+        )));
+        let s_bits = cs.range_check(s_var, 32);
+
+        let base_var = cs.new_secp256k1_point_variable(&base);
+        let res_var = cs.secp256k1_scalar_mul(&base_var, &s_bits).unwrap();
+        check_point(&cs, &res_var, &base.mul(&s));
+
+        let res_var = cs.secp256k1_fixed_base_scalar_mul(&base, &s_bits).unwrap();
+        check_point(&cs, &res_var, &base.mul(&s));
+    }
+
+    #[test]
+    fn test_secp256k1_public_key_derivation() {
+        let mut prng = test_rng();
+        let sk = SECP256K1Scalar::random(&mut prng);
+        let pk = SECP256K1G1::get_base().mul(&sk);
+
+        let mut cs = TurboCS::<BLSScalar>::new();
+        let (_, sk_bits) = SimFrVar::<SimFrParamsSecp256k1>::alloc_witness(
+            &mut cs,
+            &secp256k1_scalar_to_sim_fr(&sk),
+        );
+        let pk_var = cs.new_secp256k1_point_variable(&pk);
+        cs.secp256k1_public_key_derivation(&pk_var, &sk_bits)
+            .unwrap();
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness, &[]).is_ok());
+
+        // another public key
+        let other_pk = SECP256K1G1::random(&mut prng);
+        let mut cs = TurboCS::<BLSScalar>::new();
+        let (_, sk_bits) = SimFrVar::<SimFrParamsSecp256k1>::alloc_witness(
+            &mut cs,
+            &secp256k1_scalar_to_sim_fr(&sk),
+        );
+        let pk_var = cs.new_secp256k1_point_variable(&other_pk);
+        let res = catch_unwind(AssertUnwindSafe(move || {
+            cs.secp256k1_public_key_derivation(&pk_var, &sk_bits)
+                .unwrap();
+            let witness = cs.get_and_clear_witness();
+            cs.verify_witness(&witness, &[]).is_ok()
+        }));
+        assert!(!res.unwrap_or(false));
+    }
+
+    #[test]
+    fn test_secp256k1_ecdsa_verify() {
+        let mut prng = test_rng();
+        let sk = SECP256K1Scalar::random(&mut prng);
+        let pk = SECP256K1G1::get_base().mul(&sk);
+        let z = SECP256K1Scalar::random(&mut prng);
+
+        let (r, s) = loop {
+            let k = SECP256K1Scalar::random(&mut prng);
+            let point = SECP256K1G1::get_base().mul(&k);
+            let x: BigUint = point.get_x().into();
+            let r = SECP256K1Scalar::from(&x);
+            let s = k.inv().unwrap().mul(&z.add(&r.mul(&sk)));
+            // signatures whose x-coordinate is at least the group order are not supported
+            if x == r.into() {
+                break (r, s);
+            }
+        };
+
+        let verify = |pk: &SECP256K1G1, z: &SECP256K1Scalar| {
+            let mut cs = TurboCS::<BLSScalar>::new();
+            let pk_var = cs.new_secp256k1_point_variable(pk);
+            let alloc = |cs: &mut TurboCS<BLSScalar>, v: &SECP256K1Scalar| {
+                let (var, _) = SimFrVar::<SimFrParamsSecp256k1>::alloc_witness(
+                    cs,
+                    &secp256k1_scalar_to_sim_fr(v),
+                );
+                var
+            };
+            let z_var = alloc(&mut cs, z);
+            let r_var = alloc(&mut cs, &r);
+            let s_var = alloc(&mut cs, &s);
+            cs.secp256k1_ecdsa_verify(&pk_var, &z_var, &r_var, &s_var)
+                .unwrap();
+            let witness = cs.get_and_clear_witness();
+            cs.verify_witness(&witness, &[]).is_ok()
+        };
+
+        assert!(verify(&pk, &z));
+
+        // another message
+        let other_z = z.add(&SECP256K1Scalar::one());
+        assert!(!catch_unwind(AssertUnwindSafe(|| verify(&pk, &other_z))).unwrap_or(false));
+    }
+}
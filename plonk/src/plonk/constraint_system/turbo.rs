@@ -980,10 +980,19 @@ mod test {
         prover::prover,
         verifier::verifier,
     };
-    use crate::poly_commit::{kzg_poly_com::KZGCommitmentScheme, pcs::PolyComScheme};
+    use crate::poly_commit::{
+        fri_poly_com::FRIPolyComScheme,
+        ipa_poly_com::{IPAPolyComScheme, IPAPolyComSchemeBN254},
+        kzg_poly_com::{KZGCommitmentSchemeBLS, KZGCommitmentSchemeBN254},
+        pcs::PolyComScheme,
+    };
     use ark_std::test_rng;
     use merlin::Transcript;
-    use noah_algebra::{bls12_381::BLSScalar, prelude::*};
+    use noah_algebra::{
+        bls12_381::BLSScalar,
+        prelude::*,
+        secp256k1::{SECP256K1Scalar, SECP256K1G1},
+    };
 
     type F = BLSScalar;
 
@@ -1341,7 +1350,60 @@ mod test {
     #[test]
     fn test_turbo_plonk_kzg() {
         let mut prng = test_rng();
-        let pcs = KZGCommitmentScheme::new(20, &mut prng);
+        let pcs = KZGCommitmentSchemeBLS::new(20, &mut prng);
+        test_turbo_plonk_with_constant_and_online_values(&pcs, &mut prng);
+        test_turbo_plonk_arithmetic_gates(&pcs, &mut prng);
+    }
+
+    #[test]
+    fn test_turbo_plonk_kzg_bn254() {
+        let mut prng = test_rng();
+        let pcs = KZGCommitmentSchemeBN254::new(20, &mut prng);
+        test_turbo_plonk_with_constant_and_online_values(&pcs, &mut prng);
+        test_turbo_plonk_arithmetic_gates(&pcs, &mut prng);
+    }
+
+    #[test]
+    fn test_turbo_plonk_ipa_bn254() {
+        let mut prng = test_rng();
+        let pcs = IPAPolyComSchemeBN254::new(20);
+        test_turbo_plonk_with_constant_and_online_values(&pcs, &mut prng);
+        test_turbo_plonk_arithmetic_gates(&pcs, &mut prng);
+    }
+
+    #[test]
+    fn test_turbo_plonk_ipa_secp256k1() {
+        let mut prng = test_rng();
+        let pcs = IPAPolyComScheme::<SECP256K1G1>::new(20);
+
+        // The FFT domains over the secp256k1 scalar field have at most 2^6 elements,
+        // so the circuit is kept within four constraints.
+        // circuit (x_0 + x_1) * x_2 = y, where y is an online variable
+        // witness (1 + 2) * 3 = 9
+        let num: Vec<SECP256K1Scalar> = (0..10).map(|x| SECP256K1Scalar::from(x as u32)).collect();
+        let mut cs = TurboCS::<SECP256K1Scalar>::new();
+        let x_0 = cs.new_variable(num[1]);
+        let x_1 = cs.new_variable(num[2]);
+        let x_2 = cs.new_variable(num[3]);
+        let sum = cs.add(x_0, x_1);
+        let y = cs.mul(sum, x_2);
+        cs.prepare_pi_variable(y);
+        cs.pad();
+
+        let mut online_vars = [num[9]];
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness, &online_vars).is_ok());
+        check_turbo_plonk_proof(&pcs, &mut prng, &cs, &witness, &online_vars);
+
+        online_vars[0] = num[8];
+        assert!(cs.verify_witness(&witness, &online_vars).is_err());
+    }
+
+    // The FRI scheme is not hiding, so these proofs are sound but not zero-knowledge.
+    #[test]
+    fn test_turbo_plonk_fri() {
+        let mut prng = test_rng();
+        let pcs = FRIPolyComScheme::<BLSScalar>::new(20, 4, 16);
         test_turbo_plonk_with_constant_and_online_values(&pcs, &mut prng);
         test_turbo_plonk_arithmetic_gates(&pcs, &mut prng);
     }
@@ -393,27 +393,22 @@ pub(super) fn t_poly<PCS: PolyComScheme, CS: ConstraintSystem<Field = PCS::Field
     ))
 }
 
-/// Compute r polynomial or commitment.
-#[cfg(not(feature = "parallel"))]
-fn r_poly_or_comm<F: Scalar, PCSType: HomomorphicPolyComElem<Scalar = F>>(
+/// Compute the coefficients of the r polynomial in the polynomials it is made up from,
+/// in the order: the selectors, qb, q_{prk1}, q_{prk2}, perm_{n_wires_per_gate}, z and
+/// the `n_split_t_polys` pieces of t.
+fn r_coefs<F: Scalar>(
     w: &[F],
-    q_polys_or_comms: &[PCSType],
-    qb_poly_or_comm: &PCSType,
-    q_prk1_poly_or_comm: &PCSType,
-    q_prk2_poly_or_comm: &PCSType,
     k: &[F],
-    last_s_poly_or_comm: &PCSType,
-    z_poly_or_comm: &PCSType,
     w_polys_eval_zeta: &[&F],
     s_polys_eval_zeta: &[&F],
     q_prk3_eval_zeta: &F,
     z_eval_zeta_omega: &F,
     challenges: &PlonkChallenges<F>,
-    t_polys_or_comms: &[PCSType],
+    n_split_t_polys: usize,
     first_lagrange_eval_zeta: &F,
     z_h_eval_zeta: &F,
     n_t_polys: usize,
-) -> PCSType {
+) -> Vec<F> {
     let (beta, gamma) = challenges.get_beta_gamma().unwrap();
     let alpha = challenges.get_alpha().unwrap();
     let zeta = challenges.get_zeta().unwrap();
@@ -426,18 +421,19 @@ fn r_poly_or_comm<F: Scalar, PCSType: HomomorphicPolyComElem<Scalar = F>>(
     let alpha_pow_7 = alpha_pow_6.mul(alpha);
 
     // 1. sum_{i=1..n_selectors} wi * qi(X)
-    let mut l = q_polys_or_comms[0].mul(&w[0]);
-    for i in 1..q_polys_or_comms.len() {
-        l.add_assign(&q_polys_or_comms[i].mul(&w[i]));
-    }
+    let mut coefs = w.to_vec();
 
-    // 2. z(X) [ alpha * prod_{j=1..n_wires_per_gate} (fj(zeta) + beta * kj * zeta + gamma)
-    //              + alpha^2 * L1(zeta)]
-    let z_scalar =
-        compute_z_scalar_in_r(w_polys_eval_zeta, k, challenges, first_lagrange_eval_zeta);
-    l.add_assign(&z_poly_or_comm.mul(&z_scalar));
+    // 2. + qb(X) * (w[1] (w[1] - 1) * alpha^3 + w[2] (w[2] - 1) * alpha^4 + w[3] (w[3] - 1) * alpha^5)
+    let w1_part = w[1].mul(&(w[1] - &F::one())).mul(&alpha_pow_3);
+    let w2_part = w[2].mul(&(w[2] - &F::one())).mul(&alpha_pow_4);
+    let w3_part = w[3].mul(&(w[3] - &F::one())).mul(&alpha_pow_5);
+    coefs.push(w1_part.add(w2_part).add(w3_part));
 
-    // 3. - perm_{n_wires_per_gate}(X) [alpha * z(zeta * omega) * beta
+    // 3. + q_{prk3}(eval zeta) * (q_{prk1}(X) * alpha^6 + q_{prk2}(X) * alpha ^ 7)
+    coefs.push(q_prk3_eval_zeta.mul(alpha_pow_6));
+    coefs.push(q_prk3_eval_zeta.mul(alpha_pow_7));
+
+    // 4. - perm_{n_wires_per_gate}(X) [alpha * z(zeta * omega) * beta
     //    * prod_{j=1..n_wires_per_gate-1}(fj(zeta) + beta * perm_j(zeta) + gamma)]
     let mut s_last_poly_scalar = alpha.mul(&z_eval_zeta_omega.mul(beta));
     for i in 0..w_polys_eval_zeta.len() - 1 {
@@ -446,151 +442,30 @@ fn r_poly_or_comm<F: Scalar, PCSType: HomomorphicPolyComElem<Scalar = F>>(
             .add(gamma);
         s_last_poly_scalar.mul_assign(&tmp);
     }
-    l.sub_assign(&last_s_poly_or_comm.mul(&s_last_poly_scalar));
-
-    // 4. + qb(X) * (w[1] (w[1] - 1) * alpha^3 + w[2] (w[2] - 1) * alpha^4 + w[3] (w[3] - 1) * alpha^5)
-    let w1_part = w[1].mul(&(w[1] - &F::one())).mul(&alpha_pow_3);
-    let w2_part = w[2].mul(&(w[2] - &F::one())).mul(&alpha_pow_4);
-    let w3_part = w[3].mul(&(w[3] - &F::one())).mul(&alpha_pow_5);
-    l.add_assign(&qb_poly_or_comm.mul(&w1_part.add(w2_part).add(w3_part)));
-
-    // 5. + q_{prk3}(eval zeta) * (q_{prk1}(X) * alpha^6 + q_{prk2}(X) * alpha ^ 7)
-    l.add_assign(&q_prk1_poly_or_comm.mul(&q_prk3_eval_zeta.mul(alpha_pow_6)));
-    l.add_assign(&q_prk2_poly_or_comm.mul(&q_prk3_eval_zeta.mul(alpha_pow_7)));
-
-    let factor = zeta.pow(&[n_t_polys as u64]);
-    let mut exponent = z_h_eval_zeta.mul(factor);
-    let mut t_poly_combined = t_polys_or_comms[0].clone().mul(&z_h_eval_zeta);
-    for t_poly in t_polys_or_comms.iter().skip(1) {
-        t_poly_combined.add_assign(&t_poly.mul(&exponent));
-        exponent.mul_assign(&factor);
-    }
-    l.sub_assign(&t_poly_combined);
-    l
-}
-
-/// Compute r polynomial or commitment.
-#[cfg(feature = "parallel")]
-fn r_poly_or_comm<F: Scalar, PCSType: HomomorphicPolyComElem<Scalar = F>>(
-    w: &[F],
-    q_polys_or_comms: &[PCSType],
-    qb_poly_or_comm: &PCSType,
-    q_prk1_poly_or_comm: &PCSType,
-    q_prk2_poly_or_comm: &PCSType,
-    k: &[F],
-    last_s_poly_or_comm: &PCSType,
-    z_poly_or_comm: &PCSType,
-    w_polys_eval_zeta: &[&F],
-    s_polys_eval_zeta: &[&F],
-    q_prk3_eval_zeta: &F,
-    z_eval_zeta_omega: &F,
-    challenges: &PlonkChallenges<F>,
-    t_polys_or_comms: &[PCSType],
-    first_lagrange_eval_zeta: &F,
-    z_h_eval_zeta: &F,
-    n_t_polys: usize,
-) -> PCSType {
-    let (beta, gamma) = challenges.get_beta_gamma().unwrap();
-    let zeta = challenges.get_zeta().unwrap();
-    let alpha = challenges.get_alpha().unwrap();
-    let alpha_neg = alpha.neg();
-    let beta_zeta = beta.mul(zeta);
-    let one = F::one();
-    let zero = F::zero();
-    let z_h_eval_zeta_neg = z_h_eval_zeta.neg();
+    coefs.push(s_last_poly_scalar.neg());
 
-    let alpha_pow_2 = alpha.mul(alpha);
-    let alpha_pow_3 = alpha_pow_2.mul(alpha);
-    let alpha_pow_4 = alpha_pow_3.mul(alpha);
-    let alpha_pow_5 = alpha_pow_4.mul(alpha);
-    let alpha_pow_6 = alpha_pow_5.mul(alpha);
-    let alpha_pow = vec![&zero, &alpha_pow_3, &alpha_pow_4, &alpha_pow_5];
-
-    let mut polys_or_comms = q_polys_or_comms.iter().collect::<Vec<&PCSType>>();
-    let mut challenges = w.iter().collect::<Vec<&F>>();
-
-    // res.0 = prod_{j=1..n_wires_per_gate-1} (wj(zeta) + beta * kj * zeta + gamma)
-    // res.1 = prod_{j=1..n_wires_per_gate-1} (wj(zeta) + beta * perm_j(zeta) + gamma)
-    // res.2 = prod_{j=2..n_wires_per_gate-1} (wj(zeta) * (wj(zeta)-1) * alpha ^ j)
-    let mut res = w_polys_eval_zeta
-        .par_iter()
-        .take(w_polys_eval_zeta.len() - 1)
-        .zip(k)
-        .zip(s_polys_eval_zeta)
-        .zip(alpha_pow)
-        .map(|(((wj, kj), sj), alpha_pow)| {
-            let term1 = wj.add(kj.mul(&beta_zeta)).add(gamma);
-            let term2 = wj.add(beta.mul(*sj)).add(gamma);
-            let term3 = wj.mul(alpha_pow).mul(wj.sub(&one));
-
-            (term1, term2, term3)
-        })
-        .reduce(
-            || (one, one, zero),
-            |x, y| ((x.0.mul(&y.0)), (x.1.mul(&y.1)), (x.2.add(&y.2))),
-        );
-
-    // res.0 * (w_{n_wires_per_gate}(zeta) + beta * k_{n_wires_per_gate} * zeta + gamma)
-    //  = prod_{j=1..n_wires_per_gate} (wj(zeta) + beta * kj * zeta + gamma)
-    res.0.mul_assign(
-        &w_polys_eval_zeta[w_polys_eval_zeta.len() - 1]
-            .add(k[k.len() - 1].mul(&beta_zeta))
-            .add(gamma),
-    );
+    // 5. + z(X) [ alpha * prod_{j=1..n_wires_per_gate} (fj(zeta) + beta * kj * zeta + gamma)
+    //              + alpha^2 * L1(zeta)]
+    coefs.push(compute_z_scalar_in_r(
+        w_polys_eval_zeta,
+        k,
+        challenges,
+        first_lagrange_eval_zeta,
+    ));
 
-    // (res.0 + (L1(zeta) * alpha)) * alpha * z(x)
-    //  = res.0 * alpha * z(x) + L1(zeta) * alpha ^ 2 * z(x)
-    res.0.add_assign(&first_lagrange_eval_zeta.mul(alpha));
-    res.0.mul_assign(alpha);
-    polys_or_comms.push(&z_poly_or_comm);
-    challenges.push(&res.0);
-
-    // res.1 * z(zeta * omega) * beta * perm_{n_wires_per_gate}(X)
-    polys_or_comms.push(last_s_poly_or_comm);
-    res.1
-        .mul_assign(&z_eval_zeta_omega.mul(beta).mul(&alpha_neg));
-    challenges.push(&res.1);
-
-    // res.2 * qb(X)
-    polys_or_comms.push(&qb_poly_or_comm);
-    challenges.push(&res.2);
-
-    // q_{prk1}(X) * q_{prk3}(eval zeta) * alpha ^ 6
-    polys_or_comms.push(&q_prk1_poly_or_comm);
-    let q_prk3_pow_6 = q_prk3_eval_zeta.mul(alpha_pow_6);
-    challenges.push(&q_prk3_pow_6);
-
-    // q_{prk2}(X) * q_{prk3}(eval zeta) * alpha ^ 7
-    polys_or_comms.push(&q_prk2_poly_or_comm);
-    let q_prk3_pow_7 = q_prk3_pow_6.mul(alpha);
-    challenges.push(&q_prk3_pow_7);
-
-    // - z_h(zeta) * t_0(x) - \sum_{j=1..t_polys_or_comms.len()-1} (t_j(x) * (zeta) ^ (n_t_polys * j) * z_h(zeta))
-    let mut exponents = Vec::new();
-    exponents.push(z_h_eval_zeta_neg);
+    // 6. - z_h(zeta) * t_0(X) - \sum_{j=1..n_split_t_polys-1} (t_j(X) * (zeta) ^ (n_t_polys * j) * z_h(zeta))
     let factor = zeta.pow(&[n_t_polys as u64]);
-    let mut exponent = factor.mul(&z_h_eval_zeta_neg);
-    for _ in 0..t_polys_or_comms.len() - 1 {
-        exponents.push(exponent);
+    let mut exponent = z_h_eval_zeta.neg();
+    for _ in 0..n_split_t_polys {
+        coefs.push(exponent);
         exponent.mul_assign(&factor);
     }
-    for (t_poly_or_comm, exp) in t_polys_or_comms.iter().zip(&exponents) {
-        polys_or_comms.push(t_poly_or_comm);
-        challenges.push(exp);
-    }
-
-    // sum_{j=0..polys_or_comms.len()} (polys_or_comms[j] * challenges[j])
-    polys_or_comms
-        .par_iter()
-        .zip(challenges)
-        .map(|(polys_or_comm, challenge)| polys_or_comm.mul(challenge))
-        .reduce(|| PCSType::default(), |x, y| x.add(&y))
+    coefs
 }
 
 /// compute the scalar factor of z(X) in the r poly.
 /// prod(fi(\zeta) + \beta * k_i * \zeta + \gamma) * \alpha
 ///       + (\zeta^n - 1) / (\zeta-1) * \alpha^2
-#[cfg(not(feature = "parallel"))]
 fn compute_z_scalar_in_r<F: Scalar>(
     w_polys_eval_zeta: &[&F],
     k: &[F],
@@ -616,40 +491,44 @@ fn compute_z_scalar_in_r<F: Scalar>(
     z_scalar
 }
 
-/// Compute the r polynomial.
-pub(super) fn r_poly<PCS: PolyComScheme, CS: ConstraintSystem<Field = PCS::Field>>(
-    prover_params: &PlonkPK<PCS>,
-    z: &FpPolynomial<PCS::Field>,
+/// Compute the r polynomial as a linear combination of the committed polynomials,
+/// returning the polynomials and their coefficients.
+pub(super) fn r_poly_terms<'a, PCS: PolyComScheme, CS: ConstraintSystem<Field = PCS::Field>>(
+    prover_params: &'a PlonkPK<PCS>,
+    z: &'a FpPolynomial<PCS::Field>,
     w_polys_eval_zeta: &[&PCS::Field],
     s_polys_eval_zeta: &[&PCS::Field],
     q_prk3_eval_zeta: &PCS::Field,
     z_eval_zeta_omega: &PCS::Field,
     challenges: &PlonkChallenges<PCS::Field>,
-    t_polys: &[FpPolynomial<PCS::Field>],
+    t_polys: &'a [FpPolynomial<PCS::Field>],
     first_lagrange_eval_zeta: &PCS::Field,
     z_h_eval_zeta: &PCS::Field,
     n_t_polys: usize,
-) -> FpPolynomial<PCS::Field> {
+) -> (Vec<&'a FpPolynomial<PCS::Field>>, Vec<PCS::Field>) {
     let w = CS::eval_selector_multipliers(w_polys_eval_zeta).unwrap(); // safe unwrap
-    r_poly_or_comm::<PCS::Field, FpPolynomial<PCS::Field>>(
+    let mut polys: Vec<&FpPolynomial<PCS::Field>> = prover_params.q_polys.iter().collect();
+    polys.push(&prover_params.qb_poly);
+    polys.push(&prover_params.q_prk_polys[0]);
+    polys.push(&prover_params.q_prk_polys[1]);
+    polys.push(&prover_params.s_polys[CS::n_wires_per_gate() - 1]);
+    polys.push(z);
+    polys.extend(t_polys.iter());
+
+    let coefs = r_coefs(
         &w,
-        &prover_params.q_polys,
-        &prover_params.qb_poly,
-        &prover_params.q_prk_polys[0],
-        &prover_params.q_prk_polys[1],
         &prover_params.verifier_params.k,
-        &prover_params.s_polys[CS::n_wires_per_gate() - 1],
-        z,
         w_polys_eval_zeta,
         s_polys_eval_zeta,
         q_prk3_eval_zeta,
         z_eval_zeta_omega,
         challenges,
-        t_polys,
+        t_polys.len(),
         first_lagrange_eval_zeta,
         z_h_eval_zeta,
         n_t_polys,
-    )
+    );
+    (polys, coefs)
 }
 
 /// Commit the r commitment.
@@ -667,25 +546,55 @@ pub(super) fn r_commitment<PCS: PolyComScheme, CS: ConstraintSystem<Field = PCS:
     n_t_polys: usize,
 ) -> PCS::Commitment {
     let w = CS::eval_selector_multipliers(w_polys_eval_zeta).unwrap(); // safe unwrap
-    r_poly_or_comm::<PCS::Field, PCS::Commitment>(
+    let mut cms: Vec<&PCS::Commitment> = verifier_params.cm_q_vec.iter().collect();
+    cms.push(&verifier_params.cm_qb);
+    cms.push(&verifier_params.cm_prk_vec[0]);
+    cms.push(&verifier_params.cm_prk_vec[1]);
+    cms.push(&verifier_params.cm_s_vec[CS::n_wires_per_gate() - 1]);
+    cms.push(cm_z);
+    cms.extend(t_polys.iter());
+
+    let coefs = r_coefs(
         &w,
-        &verifier_params.cm_q_vec,
-        &verifier_params.cm_qb,
-        &verifier_params.cm_prk_vec[0],
-        &verifier_params.cm_prk_vec[1],
         &verifier_params.k,
-        &verifier_params.cm_s_vec[CS::n_wires_per_gate() - 1],
-        cm_z,
         w_polys_eval_zeta,
         s_polys_eval_zeta,
         q_prk3_eval_zeta,
         z_eval_zeta_omega,
         challenges,
-        t_polys,
+        t_polys.len(),
         first_lagrange_eval_zeta,
         z_h_eval_zeta,
         n_t_polys,
-    )
+    );
+
+    linear_combination(&cms, &coefs)
+}
+
+/// Compute sum_{j} (elems[j] * coefs[j]).
+#[cfg(feature = "parallel")]
+fn linear_combination<F: Scalar, C: HomomorphicPolyComElem<Scalar = F>>(
+    elems: &[&C],
+    coefs: &[F],
+) -> C {
+    elems
+        .par_iter()
+        .zip(coefs.par_iter())
+        .map(|(elem, coef)| elem.mul(coef))
+        .reduce(|| C::get_identity(), |x, y| x.add(&y))
+}
+
+/// Compute sum_{j} (elems[j] * coefs[j]).
+#[cfg(not(feature = "parallel"))]
+fn linear_combination<F: Scalar, C: HomomorphicPolyComElem<Scalar = F>>(
+    elems: &[&C],
+    coefs: &[F],
+) -> C {
+    let mut res = C::get_identity();
+    for (elem, coef) in elems.iter().zip(coefs.iter()) {
+        res.add_assign(&elem.mul(coef));
+    }
+    res
 }
 
 /// Compute sum_{i=1}^\ell w_i L_j(X), where j is the constraint
@@ -937,7 +846,7 @@ mod test {
         helpers::{z_poly, PlonkChallenges},
         indexer::indexer,
     };
-    use crate::poly_commit::kzg_poly_com::{KZGCommitmentScheme, KZGCommitmentSchemeBLS};
+    use crate::poly_commit::kzg_poly_com::KZGCommitmentSchemeBLS;
     use ark_std::test_rng;
     use noah_algebra::{bls12_381::BLSScalar, prelude::*};
 
@@ -966,7 +875,7 @@ mod test {
         cs.pad();
 
         let mut prng = test_rng();
-        let pcs = KZGCommitmentScheme::new(20, &mut prng);
+        let pcs = KZGCommitmentSchemeBLS::new(20, &mut prng);
         let params = indexer(&cs, &pcs).unwrap();
 
         let mut challenges = PlonkChallenges::<F>::new();
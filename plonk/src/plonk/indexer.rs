@@ -8,8 +8,10 @@ use noah_algebra::{prelude::*, traits::Domain};
 use rand_chacha::ChaChaRng;
 
 /// The data structure of a Plonk proof.
+/// The evaluation proofs `E` default to the commitment type `C`,
+/// as they were before `PolyComScheme::EvalProof` was introduced.
 #[derive(Debug, Deserialize, Eq, PartialEq, Serialize, Clone)]
-pub struct PlonkProof<C, F> {
+pub struct PlonkProof<C, F, E = C> {
     /// The witness polynomial commitments.
     pub cm_w_vec: Vec<C>,
     /// The split quotient polynomial commitments
@@ -28,15 +30,18 @@ pub struct PlonkProof<C, F> {
     pub z_eval_zeta_omega: F,
     /// The openings of permutation polynomials at \zeta.
     pub s_polys_eval_zeta: Vec<F>,
-    /// The commitment for the first witness polynomial, for \zeta.
-    pub opening_witness_zeta: C,
-    /// The commitment for the second witness polynomial, for \zeta\omega.
-    pub opening_witness_zeta_omega: C,
+    /// The evaluation proof for the first batch of polynomials, for \zeta.
+    pub opening_witness_zeta: E,
+    /// The evaluation proof for the second batch of polynomials, for \zeta\omega.
+    pub opening_witness_zeta_omega: E,
 }
 
 /// The type of the Plonk proof with a specific polynomial commitment scheme.
-pub type PlonkPf<PCS> =
-    PlonkProof<<PCS as PolyComScheme>::Commitment, <PCS as PolyComScheme>::Field>;
+pub type PlonkPf<PCS> = PlonkProof<
+    <PCS as PolyComScheme>::Commitment,
+    <PCS as PolyComScheme>::Field,
+    <PCS as PolyComScheme>::EvalProof,
+>;
 
 /// Plonk prover parameters.
 #[derive(Debug, Serialize, Deserialize)]
@@ -2,8 +2,8 @@ use crate::plonk::{
     constraint_system::ConstraintSystem,
     errors::PlonkError,
     helpers::{
-        first_lagrange_poly, hide_polynomial, pi_poly, r_poly, split_t_and_commit, t_poly, z_poly,
-        PlonkChallenges,
+        first_lagrange_poly, hide_polynomial, pi_poly, r_poly_terms, split_t_and_commit, t_poly,
+        z_poly, PlonkChallenges,
     },
     indexer::{get_domain_and_root, PlonkPK, PlonkPf, PlonkProof},
     transcript::{
@@ -22,6 +22,8 @@ use noah_algebra::prelude::*;
 /// Proof verifier must use a transcript with same state as prover and match the public parameters,
 /// It returns [PlonkError] if an error occurs in computing proof commitments, meaning parameters of the polynomial
 /// commitment scheme `pcs` do not match the constraint system parameters.
+/// The proof is zero-knowledge only if the openings of `pcs` are hiding, which is not the case for
+/// [`FRIPolyComScheme`](crate::poly_commit::fri_poly_com::FRIPolyComScheme).
 /// # Example
 /// ```
 /// use noah_plonk::plonk::{
@@ -30,13 +32,13 @@ use noah_algebra::prelude::*;
 ///     prover::prover,
 ///     indexer::indexer
 /// };
-/// use noah_plonk::poly_commit::kzg_poly_com::KZGCommitmentScheme;
+/// use noah_plonk::poly_commit::kzg_poly_com::KZGCommitmentSchemeBLS;
 /// use merlin::Transcript;
 /// use rand_chacha::ChaChaRng;
 /// use noah_algebra::{prelude::*, bls12_381::BLSScalar};
 ///
 /// let mut prng = ChaChaRng::from_seed([0u8; 32]);
-/// let pcs = KZGCommitmentScheme::new(20, &mut prng);
+/// let pcs = KZGCommitmentSchemeBLS::new(20, &mut prng);
 /// let mut cs = TurboCS::new();
 ///
 /// // circuit (x_0 + x_1);
@@ -266,7 +268,7 @@ pub fn prover_with_lagrange<
 
     let (z_h_eval_zeta, first_lagrange_eval_zeta) =
         first_lagrange_poly::<PCS>(&challenges, cs.size() as u64);
-    let r_poly = r_poly::<PCS, CS>(
+    let (r_polys, r_coefs) = r_poly_terms::<PCS, CS>(
         prover_params,
         &z_poly,
         &w_polys_eval_zeta_as_ref[..],
@@ -291,15 +293,17 @@ pub fn prover_with_lagrange<
         .collect();
     polys_to_open.push(&prover_params.q_prk_polys[2]);
     polys_to_open.push(&prover_params.q_prk_polys[3]);
-    polys_to_open.push(&r_poly);
 
     let zeta = challenges.get_zeta().unwrap();
 
     let opening_witness_zeta = pcs
-        .batch_prove(
+        .batch_prove_with_linear_combination(
+            prng,
             transcript,
             lagrange_pcs,
             &polys_to_open[..],
+            &r_polys[..],
+            &r_coefs[..],
             &zeta,
             n_constraints + 2,
         )
@@ -310,6 +314,7 @@ pub fn prover_with_lagrange<
 
     let opening_witness_zeta_omega = pcs
         .batch_prove(
+            prng,
             transcript,
             lagrange_pcs,
             &polys_to_open[..],
@@ -0,0 +1,943 @@
+use crate::poly_commit::{
+    errors::PolyComSchemeError,
+    field_polynomial::FpPolynomial,
+    pcs::{HomomorphicPolyComElem, PolyComScheme, ToBytes},
+    transcript::PolyComTranscript,
+};
+use merlin::Transcript;
+use noah_algebra::{prelude::*, traits::Domain};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::{cmp::Ordering, fmt::Debug, marker::PhantomData};
+
+/// The digest of a node in the Merkle tree.
+pub type MerkleDigest = [u8; 32];
+
+/// A commitment in the FRI scheme.
+///
+/// Merkle roots cannot be added or scaled, so the commitment is kept as a formal linear
+/// combination of the Merkle roots of the evaluations of committed polynomials,
+/// plus a public polynomial such as the blinding factors over the vanishing part.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct FRICommitment<F> {
+    /// The Merkle roots of the committed polynomials in increasing order, with their coefficients.
+    pub terms: Vec<(MerkleDigest, F)>,
+    /// The coefficients of the public polynomial.
+    pub public_coefs: Vec<F>,
+}
+
+impl<F> Default for FRICommitment<F> {
+    fn default() -> Self {
+        Self {
+            terms: vec![],
+            public_coefs: vec![],
+        }
+    }
+}
+
+impl<F: Scalar> ToBytes for FRICommitment<F> {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = (self.terms.len() as u64).to_le_bytes().to_vec();
+        for (root, coef) in self.terms.iter() {
+            bytes.extend_from_slice(root);
+            bytes.extend_from_slice(&coef.to_bytes());
+        }
+        for coef in self.public_coefs.iter() {
+            bytes.extend_from_slice(&coef.to_bytes());
+        }
+        bytes
+    }
+}
+
+impl<F: Scalar> HomomorphicPolyComElem for FRICommitment<F> {
+    type Scalar = F;
+
+    fn get_base() -> Self {
+        Self {
+            terms: vec![],
+            public_coefs: vec![F::one()],
+        }
+    }
+
+    fn get_identity() -> Self {
+        Self::default()
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        let mut new = self.clone();
+        new.add_assign(other);
+        new
+    }
+
+    fn add_assign(&mut self, other: &Self) {
+        let mut terms = Vec::with_capacity(self.terms.len() + other.terms.len());
+        let (mut i, mut j) = (0, 0);
+        while i < self.terms.len() && j < other.terms.len() {
+            match self.terms[i].0.cmp(&other.terms[j].0) {
+                Ordering::Less => {
+                    terms.push(self.terms[i]);
+                    i += 1;
+                }
+                Ordering::Greater => {
+                    terms.push(other.terms[j]);
+                    j += 1;
+                }
+                Ordering::Equal => {
+                    terms.push((self.terms[i].0, self.terms[i].1.add(&other.terms[j].1)));
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        terms.extend_from_slice(&self.terms[i..]);
+        terms.extend_from_slice(&other.terms[j..]);
+        self.terms = terms;
+
+        if self.public_coefs.len() < other.public_coefs.len() {
+            self.public_coefs
+                .resize(other.public_coefs.len(), F::zero());
+        }
+        for (self_coef, other_coef) in self.public_coefs.iter_mut().zip(other.public_coefs.iter()) {
+            self_coef.add_assign(other_coef);
+        }
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        self.add(&other.mul(&F::one().neg()))
+    }
+
+    fn sub_assign(&mut self, other: &Self) {
+        self.add_assign(&other.mul(&F::one().neg()))
+    }
+
+    fn mul(&self, scalar: &F) -> Self {
+        let mut new = self.clone();
+        new.mul_assign(scalar);
+        new
+    }
+
+    fn mul_assign(&mut self, scalar: &F) {
+        for (_, coef) in self.terms.iter_mut() {
+            coef.mul_assign(scalar);
+        }
+        for coef in self.public_coefs.iter_mut() {
+            coef.mul_assign(scalar);
+        }
+    }
+}
+
+/// The opening of one leaf of a Merkle tree,
+/// which stores the evaluations at the points `x` and `-x`.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct FRILeafOpening<F> {
+    /// The evaluations at `x` and `-x`.
+    pub values: [F; 2],
+    /// The authentication path, from the leaf to the root.
+    pub path: Vec<MerkleDigest>,
+}
+
+/// The openings for one query of the FRI protocol.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct FRIQueryProof<F> {
+    /// The openings of the committed polynomials.
+    pub poly_openings: Vec<FRILeafOpening<F>>,
+    /// The openings of the intermediate folding layers.
+    pub layer_openings: Vec<FRILeafOpening<F>>,
+}
+
+/// A batch evaluation proof of the FRI scheme.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct FRIBatchEvalProof<F> {
+    /// The bound on the degrees of the polynomials that the proof enforces.
+    pub degree: usize,
+    /// The evaluations at the point of the polynomials under the Merkle roots, in their order.
+    pub evals: Vec<F>,
+    /// The Merkle roots of the intermediate folding layers.
+    pub layer_roots: Vec<MerkleDigest>,
+    /// The constant that the quotient polynomial folds into.
+    pub final_value: F,
+    /// The openings for each query.
+    pub queries: Vec<FRIQueryProof<F>>,
+}
+
+/// A transparent polynomial commitment scheme based on FRI, with Merkle tree commitments.
+///
+/// An evaluation proof opens every polynomial under the Merkle roots of a commitment at the point,
+/// and the verifier checks a FRI low-degree proof of the random linear combination of their
+/// quotients, together with the evaluation of the linear combination. Each quotient also enters
+/// the combination multiplied by a power of `X` that brings the degree bound of the proof up to
+/// the one of the low-degree test, so that the degree bound is enforced exactly.
+///
+/// Lagrange-basis commitments are not supported, so `lagrange_pcs` should not be given to the
+/// Plonk prover.
+///
+/// The scheme is not hiding: the queries reveal evaluations of the committed polynomials outside
+/// the domain, which the blinding of Plonk does not cover. Plonk proofs over this scheme are
+/// therefore not zero-knowledge, and it should only be used where the witness is not secret.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FRIPolyComScheme<F> {
+    /// The maximal degree of the committed polynomials.
+    max_degree: usize,
+    /// The ratio between the size of the evaluation domain and the degree bound.
+    blowup_factor: usize,
+    /// The number of queries made by the verifier.
+    num_queries: usize,
+    phantom: PhantomData<F>,
+}
+
+impl<F: Domain> FRIPolyComScheme<F> {
+    /// Create a new instance of the FRI polynomial commitment scheme.
+    /// `max_degree` - max degree of the polynomial,
+    /// `blowup_factor` - the rate inverse, a power of two at least 2,
+    /// `num_queries` - the number of queries for the proximity test.
+    pub fn new(max_degree: usize, blowup_factor: usize, num_queries: usize) -> Self {
+        assert!(blowup_factor >= 2 && blowup_factor.is_power_of_two());
+        assert!(num_queries > 0);
+        Self {
+            max_degree,
+            blowup_factor,
+            num_queries,
+            phantom: PhantomData,
+        }
+    }
+
+    /// The number of coefficients of the quotient polynomial, rounded up to a power of two.
+    fn degree_bound(&self) -> usize {
+        self.max_degree.next_power_of_two()
+    }
+
+    /// The number of folding rounds.
+    fn num_rounds(&self) -> usize {
+        self.degree_bound().trailing_zeros() as usize
+    }
+
+    /// The evaluation domain k * H of size `degree_bound * blowup_factor`.
+    fn domain_params(&self) -> Result<(usize, F, F)> {
+        let size = self.degree_bound() * self.blowup_factor;
+        let domain =
+            FpPolynomial::<F>::evaluation_domain(size).c(d!(PolyComSchemeError::DegreeError))?;
+        let root = F::from_field(domain.group_gen);
+        Ok((size, F::multiplicative_generator(), root))
+    }
+
+    /// Evaluate the polynomial, of degree less than the degree bound, over the evaluation domain.
+    fn evaluate_on_domain(&self, polynomial: &FpPolynomial<F>) -> Result<Vec<F>> {
+        if polynomial.degree() >= self.degree_bound() {
+            return Err(eg!(PolyComSchemeError::DegreeError));
+        }
+        let size = self.degree_bound() * self.blowup_factor;
+        let domain =
+            FpPolynomial::<F>::evaluation_domain(size).c(d!(PolyComSchemeError::DegreeError))?;
+        Ok(polynomial.coset_fft_with_domain(&domain, &F::multiplicative_generator()))
+    }
+
+    /// Initialize the transcript for the evaluation proof of the polynomials under `roots`.
+    fn init_eval_transcript(
+        &self,
+        degree: usize,
+        roots: &[MerkleDigest],
+        point: &F,
+        evals: &[F],
+    ) -> Transcript {
+        let mut transcript = Transcript::new(b"FRI-PCS-Eval Protocol");
+        transcript.append_message(b"field size", &F::get_field_size_le_bytes());
+        transcript.append_u64(b"max_degree", self.max_degree as u64);
+        transcript.append_u64(b"degree", degree as u64);
+        transcript.append_u64(b"blowup_factor", self.blowup_factor as u64);
+        transcript.append_u64(b"num_queries", self.num_queries as u64);
+        for root in roots.iter() {
+            transcript.append_message(b"append commitment", root);
+        }
+        transcript.append_field_elem(point);
+        for eval in evals.iter() {
+            transcript.append_field_elem(eval);
+        }
+        transcript
+    }
+
+    /// Sample the indices of the queries, each pointing to a pair `(x, -x)` of the domain.
+    fn sample_queries(&self, transcript: &mut Transcript, half_size: usize) -> Vec<usize> {
+        (0..self.num_queries)
+            .map(|_| {
+                let mut buf = [0u8; 8];
+                transcript.challenge_bytes(b"query", &mut buf);
+                (u64::from_le_bytes(buf) % half_size as u64) as usize
+            })
+            .collect()
+    }
+
+    /// Prove the evaluations at `point` of the polynomials of degree at most `degree`,
+    /// which are opened in the increasing order of their distinct Merkle roots.
+    fn prove_evals(
+        &self,
+        polys: &[&FpPolynomial<F>],
+        point: &F,
+        degree: usize,
+    ) -> Result<FRIBatchEvalProof<F>> {
+        assert!(!polys.is_empty());
+        if degree > self.max_degree || polys.iter().any(|poly| poly.degree() > degree) {
+            return Err(eg!(PolyComSchemeError::DegreeError));
+        }
+
+        let (size, shift, root) = self.domain_params().c(d!())?;
+        let mut poly_trees = polys
+            .iter()
+            .map(|poly| {
+                Ok((
+                    *poly,
+                    MerkleTree::new(&self.evaluate_on_domain(poly).c(d!())?),
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        poly_trees.sort_by(|a, b| a.1.root().cmp(&b.1.root()));
+        poly_trees.dedup_by(|a, b| a.1.root() == b.1.root());
+        let (polys, poly_trees): (Vec<_>, Vec<_>) = poly_trees.into_iter().unzip();
+
+        let roots = poly_trees
+            .iter()
+            .map(|tree| tree.root())
+            .collect::<Vec<_>>();
+        let values = polys
+            .iter()
+            .map(|poly| poly.eval(point))
+            .collect::<Vec<_>>();
+        let mut transcript = self.init_eval_transcript(degree, &roots, point, &values);
+
+        // h = sum_i (alpha^{2i} + alpha^{2i+1} * X^shift) * (f_i(X) - f_i(point)), where the
+        // quotient of h has degree less than the degree bound only if each f_i has degree at
+        // most `degree`.
+        let shift = self.degree_bound() - degree;
+        let alpha = transcript.get_challenge_field_elem::<F>(b"alpha");
+        let mut h = FpPolynomial::<F>::zero();
+        let mut multiplier = F::one();
+        for (poly, value) in polys.iter().zip(values.iter()) {
+            let mut poly = (*poly).clone();
+            poly.sub_assign(&FpPolynomial::from_coefs(vec![*value]));
+            let mut shifted_coefs = vec![F::zero(); shift];
+            shifted_coefs.extend_from_slice(poly.get_coefs_ref());
+
+            h.add_assign(&poly.mul_scalar(&multiplier));
+            multiplier.mul_assign(&alpha);
+            h.add_assign(&FpPolynomial::from_coefs(shifted_coefs).mul_scalar(&multiplier));
+            multiplier.mul_assign(&alpha);
+        }
+        let (q, rem) = h.div_rem(&FpPolynomial::from_zeroes(&[*point]));
+        if !rem.is_zero() {
+            return Err(eg!(PolyComSchemeError::PCSProveEvalError));
+        }
+
+        // Fold the evaluations of the quotient polynomial until it becomes a constant.
+        let two_inv = F::from(2u32).inv().c(d!())?;
+        let mut evals = self.evaluate_on_domain(&q).c(d!())?;
+        let mut shift = shift;
+        let mut root = root;
+        let mut layer_trees = vec![];
+        for round in 0..self.num_rounds() {
+            if round > 0 {
+                let tree = MerkleTree::new(&evals);
+                transcript.append_message(b"append commitment", &tree.root());
+                layer_trees.push(tree);
+            }
+            let beta = transcript.get_challenge_field_elem::<F>(b"beta");
+
+            let half = evals.len() / 2;
+            let root_inv = root.inv().c(d!())?;
+            let mut x_inv = shift.inv().c(d!())?;
+            let mut folded = Vec::with_capacity(half);
+            for i in 0..half {
+                folded.push(fold(&evals[i], &evals[i + half], &x_inv, &beta, &two_inv));
+                x_inv.mul_assign(&root_inv);
+            }
+            evals = folded;
+            shift = shift.square();
+            root = root.square();
+        }
+        let final_value = evals[0];
+        transcript.append_field_elem(&final_value);
+
+        let queries = self
+            .sample_queries(&mut transcript, size / 2)
+            .into_iter()
+            .map(|index| {
+                let poly_openings = poly_trees.iter().map(|tree| tree.open(index)).collect();
+                let mut half = size / 2;
+                let layer_openings = layer_trees
+                    .iter()
+                    .map(|tree| {
+                        half /= 2;
+                        tree.open(index % half)
+                    })
+                    .collect();
+                FRIQueryProof {
+                    poly_openings,
+                    layer_openings,
+                }
+            })
+            .collect();
+
+        Ok(FRIBatchEvalProof {
+            degree,
+            evals: values,
+            layer_roots: layer_trees.iter().map(|tree| tree.root()).collect(),
+            final_value,
+            queries,
+        })
+    }
+
+    /// Verify the proof of the evaluations at `point` of the polynomials under `roots`.
+    fn verify_evals(
+        &self,
+        roots: &[MerkleDigest],
+        point: &F,
+        proof: &FRIBatchEvalProof<F>,
+    ) -> Result<()> {
+        let num_rounds = self.num_rounds();
+        let values = &proof.evals;
+        if proof.degree > self.max_degree
+            || roots.is_empty()
+            || roots.len() != values.len()
+            || proof.layer_roots.len() != num_rounds.saturating_sub(1)
+            || proof.queries.len() != self.num_queries
+        {
+            return Err(eg!(PolyComSchemeError::PCSProveEvalError));
+        }
+
+        let (size, shift, root) = self.domain_params().c(d!())?;
+        let mut transcript = self.init_eval_transcript(proof.degree, roots, point, values);
+        let shift = self.degree_bound() - proof.degree;
+        let alpha = transcript.get_challenge_field_elem::<F>(b"alpha");
+        let mut betas = Vec::with_capacity(num_rounds);
+        for round in 0..num_rounds {
+            if round > 0 {
+                transcript.append_message(b"append commitment", &proof.layer_roots[round - 1]);
+            }
+            betas.push(transcript.get_challenge_field_elem::<F>(b"beta"));
+        }
+        transcript.append_field_elem(&proof.final_value);
+        let indices = self.sample_queries(&mut transcript, size / 2);
+
+        let two_inv = F::from(2u32).inv().c(d!())?;
+        for (index, query) in indices.into_iter().zip(proof.queries.iter()) {
+            if query.poly_openings.len() != roots.len()
+                || query.layer_openings.len() != proof.layer_roots.len()
+            {
+                return Err(eg!(PolyComSchemeError::PCSProveEvalError));
+            }
+
+            // Recompute the quotient polynomial at `x` and `-x` from the openings.
+            let x = shift.mul(&root.pow(&[index as u64]));
+            let x_minus_point_inv = x.sub(point).inv().c(d!())?;
+            let neg_x_minus_point_inv = x.neg().sub(point).inv().c(d!())?;
+            let x_shift = x.pow(&[shift as u64]);
+            let neg_x_shift = x.neg().pow(&[shift as u64]);
+            let mut a = F::zero();
+            let mut b = F::zero();
+            let mut multiplier = F::one();
+            for ((poly_root, value), opening) in roots
+                .iter()
+                .zip(values.iter())
+                .zip(query.poly_openings.iter())
+            {
+                MerkleTree::verify(poly_root, index, opening).c(d!())?;
+                let shifted_multiplier = multiplier.mul(&alpha);
+                a.add_assign(
+                    &opening.values[0]
+                        .sub(value)
+                        .mul(&multiplier.add(&shifted_multiplier.mul(&x_shift))),
+                );
+                b.add_assign(
+                    &opening.values[1]
+                        .sub(value)
+                        .mul(&multiplier.add(&shifted_multiplier.mul(&neg_x_shift))),
+                );
+                multiplier = shifted_multiplier.mul(&alpha);
+            }
+            a.mul_assign(&x_minus_point_inv);
+            b.mul_assign(&neg_x_minus_point_inv);
+
+            let mut x = x;
+            let mut position = index;
+            let mut half = size / 2;
+            for (round, beta) in betas.iter().enumerate() {
+                let folded = fold(&a, &b, &x.inv().c(d!())?, beta, &two_inv);
+                x = x.square();
+                half /= 2;
+
+                if round + 1 == num_rounds {
+                    a = folded;
+                    b = folded;
+                } else {
+                    let opening = &query.layer_openings[round];
+                    MerkleTree::verify(&proof.layer_roots[round], position % half, opening)
+                        .c(d!())?;
+                    // The leaf stores the evaluations at `x^2` and `-x^2`, in some order.
+                    let expected = if position < half {
+                        opening.values[0]
+                    } else {
+                        x = x.neg();
+                        opening.values[1]
+                    };
+                    if expected != folded {
+                        return Err(eg!(PolyComSchemeError::PCSProveEvalError));
+                    }
+                    a = opening.values[0];
+                    b = opening.values[1];
+                    position %= half;
+                }
+            }
+
+            if a != proof.final_value || b != proof.final_value {
+                return Err(eg!(PolyComSchemeError::PCSProveEvalError));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<F: Domain + Debug + Sync + Send> PolyComScheme for FRIPolyComScheme<F> {
+    type Field = F;
+    type Commitment = FRICommitment<F>;
+    type EvalProof = FRIBatchEvalProof<F>;
+
+    fn max_degree(&self) -> usize {
+        self.max_degree
+    }
+
+    fn commit(&self, polynomial: &FpPolynomial<F>) -> Result<Self::Commitment> {
+        if polynomial.degree() > self.max_degree {
+            return Err(eg!(PolyComSchemeError::DegreeError));
+        }
+        let evals = self.evaluate_on_domain(polynomial).c(d!())?;
+        Ok(FRICommitment {
+            terms: vec![(MerkleTree::new(&evals).root(), F::one())],
+            public_coefs: vec![],
+        })
+    }
+
+    fn eval(&self, polynomial: &FpPolynomial<F>, point: &F) -> F {
+        polynomial.eval(point)
+    }
+
+    fn apply_blind_factors(
+        &self,
+        commitment: &Self::Commitment,
+        blinds: &[F],
+        zeroing_degree: usize,
+    ) -> Self::Commitment {
+        let mut public_coefs = vec![F::zero(); zeroing_degree + blinds.len()];
+        for (i, blind) in blinds.iter().enumerate() {
+            public_coefs[i].add_assign(blind);
+            public_coefs[zeroing_degree + i].sub_assign(blind);
+        }
+        commitment.add(&FRICommitment {
+            terms: vec![],
+            public_coefs,
+        })
+    }
+
+    fn prove<R: CryptoRng + RngCore>(
+        &self,
+        _prng: &mut R,
+        poly: &FpPolynomial<F>,
+        point: &F,
+        max_degree: usize,
+    ) -> Result<Self::EvalProof> {
+        self.prove_evals(&[poly], point, max_degree).c(d!())
+    }
+
+    /// The proof must enforce a degree bound of at most `degree` on the polynomials under the
+    /// Merkle roots, and the public polynomial must also have degree at most `degree`.
+    fn verify(
+        &self,
+        cm: &Self::Commitment,
+        degree: usize,
+        point: &F,
+        value: &F,
+        proof: &Self::EvalProof,
+    ) -> Result<()> {
+        if proof.degree > degree || cm.public_coefs.len() > degree + 1 {
+            return Err(eg!(PolyComSchemeError::DegreeError));
+        }
+        if cm.terms.len() != proof.evals.len() {
+            return Err(eg!(PolyComSchemeError::PCSProveEvalError));
+        }
+
+        let mut combined = cm
+            .public_coefs
+            .iter()
+            .rev()
+            .fold(F::zero(), |acc, coef| acc.mul(point).add(coef));
+        for ((_, coef), eval) in cm.terms.iter().zip(proof.evals.iter()) {
+            combined.add_assign(&coef.mul(eval));
+        }
+        if combined != *value {
+            return Err(eg!(PolyComSchemeError::PCSProveEvalError));
+        }
+
+        let roots = cm.terms.iter().map(|(root, _)| *root).collect::<Vec<_>>();
+        self.verify_evals(&roots, point, proof).c(d!())
+    }
+
+    /// The proof opens each of the polynomials, and the verifier checks the random linear
+    /// combination computed in `batch` against their evaluations.
+    /// Since no new commitment is produced, `lagrange_pcs` is not used.
+    fn batch_prove<R: CryptoRng + RngCore>(
+        &self,
+        _prng: &mut R,
+        transcript: &mut Transcript,
+        _lagrange_pcs: Option<&Self>,
+        polys: &[&FpPolynomial<F>],
+        point: &F,
+        max_degree: usize,
+    ) -> Result<Self::EvalProof> {
+        Self::init_pcs_batch_eval_transcript(transcript, max_degree, point);
+        self.prove_evals(polys, point, max_degree).c(d!())
+    }
+
+    /// The combined polynomial is not opened, its constituents are opened instead,
+    /// as they are the ones under the Merkle roots of the combined commitment.
+    fn batch_prove_with_linear_combination<R: CryptoRng + RngCore>(
+        &self,
+        prng: &mut R,
+        transcript: &mut Transcript,
+        lagrange_pcs: Option<&Self>,
+        polys: &[&FpPolynomial<F>],
+        lc_polys: &[&FpPolynomial<F>],
+        _lc_coefs: &[F],
+        point: &F,
+        max_degree: usize,
+    ) -> Result<Self::EvalProof> {
+        let polys = polys
+            .iter()
+            .chain(lc_polys.iter())
+            .copied()
+            .collect::<Vec<_>>();
+        self.batch_prove(prng, transcript, lagrange_pcs, &polys, point, max_degree)
+    }
+
+    /// Only the verifier needs the combination challenge, so it is drawn from a copy of the
+    /// transcript that also binds the evaluations, which leaves the transcript as in `batch_prove`.
+    fn batch(
+        &self,
+        transcript: &mut Transcript,
+        cm_vec: &[&Self::Commitment],
+        max_degree: usize,
+        point: &F,
+        evals: &[F],
+    ) -> (Self::Commitment, F) {
+        Self::init_pcs_batch_eval_transcript(transcript, max_degree, point);
+        let mut challenge_transcript = transcript.clone();
+        for eval in evals.iter() {
+            challenge_transcript.append_field_elem(eval);
+        }
+        let alpha = challenge_transcript.get_challenge_field_elem::<F>(b"alpha");
+
+        let mut multiplier = F::one();
+        let mut cm_combined = Self::Commitment::get_identity();
+        let mut eval_combined = F::zero();
+        for (eval, cm) in evals.iter().zip(cm_vec) {
+            cm_combined.add_assign(&cm.mul(&multiplier));
+            eval_combined.add_assign(&eval.mul(multiplier));
+            multiplier.mul_assign(&alpha);
+        }
+        (cm_combined, eval_combined)
+    }
+
+    fn batch_verify_diff_points(
+        &self,
+        _transcript: &mut Transcript,
+        cm_vec: &[Self::Commitment],
+        degree: usize,
+        point_vec: &[F],
+        eval_vec: &[F],
+        proofs: &[Self::EvalProof],
+        _challenge: &F,
+    ) -> Result<()> {
+        assert!(!proofs.is_empty());
+        assert_eq!(proofs.len(), point_vec.len());
+        assert_eq!(proofs.len(), eval_vec.len());
+        assert_eq!(proofs.len(), cm_vec.len());
+
+        for (((cm, point), eval), proof) in cm_vec
+            .iter()
+            .zip(point_vec.iter())
+            .zip(eval_vec.iter())
+            .zip(proofs.iter())
+        {
+            self.verify(cm, degree, point, eval, proof).c(d!())?;
+        }
+        Ok(())
+    }
+
+    fn shrink_to_verifier_only(&self) -> Result<Self> {
+        Ok(self.clone())
+    }
+}
+
+/// Compute `f'(x^2) = (f(x) + f(-x)) / 2 + beta * (f(x) - f(-x)) / (2 * x)`.
+fn fold<F: Scalar>(f_x: &F, f_neg_x: &F, x_inv: &F, beta: &F, two_inv: &F) -> F {
+    let even = f_x.add(f_neg_x);
+    let odd = f_x.sub(f_neg_x).mul(x_inv).mul(beta);
+    even.add(&odd).mul(two_inv)
+}
+
+/// A Merkle tree whose i-th leaf stores the evaluations at the i-th point `x` and at `-x`.
+struct MerkleTree<F> {
+    leaves: Vec<[F; 2]>,
+    /// The layers of the tree, from the leaves to the root.
+    nodes: Vec<Vec<MerkleDigest>>,
+}
+
+impl<F: Scalar> MerkleTree<F> {
+    /// Build the tree from the evaluations over a domain, whose size is a power of two.
+    fn new(evals: &[F]) -> Self {
+        let half = evals.len() / 2;
+        let leaves = (0..half)
+            .map(|i| [evals[i], evals[i + half]])
+            .collect::<Vec<_>>();
+
+        let mut nodes = vec![leaves.iter().map(hash_leaf).collect::<Vec<_>>()];
+        while nodes.last().unwrap().len() > 1 {
+            let next = nodes
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| hash_node(&pair[0], &pair[1]))
+                .collect();
+            nodes.push(next);
+        }
+        Self { leaves, nodes }
+    }
+
+    /// Return the root of the tree.
+    fn root(&self) -> MerkleDigest {
+        self.nodes.last().unwrap()[0]
+    }
+
+    /// Open the leaf at `index`.
+    fn open(&self, index: usize) -> FRILeafOpening<F> {
+        let mut position = index;
+        let mut path = vec![];
+        for layer in &self.nodes[..self.nodes.len() - 1] {
+            path.push(layer[position ^ 1]);
+            position /= 2;
+        }
+        FRILeafOpening {
+            values: self.leaves[index],
+            path,
+        }
+    }
+
+    /// Verify the opening of the leaf at `index` against the root.
+    fn verify(root: &MerkleDigest, index: usize, opening: &FRILeafOpening<F>) -> Result<()> {
+        let mut position = index;
+        let mut digest = hash_leaf(&opening.values);
+        for sibling in opening.path.iter() {
+            digest = if position % 2 == 0 {
+                hash_node(&digest, sibling)
+            } else {
+                hash_node(sibling, &digest)
+            };
+            position /= 2;
+        }
+        if position != 0 || digest != *root {
+            return Err(eg!(PolyComSchemeError::PCSProveEvalError));
+        }
+        Ok(())
+    }
+}
+
+fn hash_leaf<F: Scalar>(values: &[F; 2]) -> MerkleDigest {
+    let mut hasher = Sha3_256::new();
+    hasher.update([0u8]);
+    hasher.update(values[0].to_bytes());
+    hasher.update(values[1].to_bytes());
+    hasher.finalize().into()
+}
+
+fn hash_node(left: &MerkleDigest, right: &MerkleDigest) -> MerkleDigest {
+    let mut hasher = Sha3_256::new();
+    hasher.update([1u8]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::poly_commit::{
+        field_polynomial::FpPolynomial,
+        fri_poly_com::FRIPolyComScheme,
+        pcs::{HomomorphicPolyComElem, PolyComScheme},
+    };
+    use ark_std::test_rng;
+    use merlin::Transcript;
+    use noah_algebra::{bls12_381::BLSScalar, bn254::BN254Scalar, prelude::*, traits::Domain};
+    use std::fmt::Debug;
+
+    fn check_batch_eval<F: Domain + Debug + Sync + Send>(max_degree: usize) {
+        let mut prng = test_rng();
+        let pcs = FRIPolyComScheme::<F>::new(max_degree, 4, 16);
+        let polys = (0..3)
+            .map(|_| FpPolynomial::<F>::random(&mut prng, max_degree))
+            .collect::<Vec<_>>();
+        let commitments = polys
+            .iter()
+            .map(|poly| pcs.commit(poly).unwrap())
+            .collect::<Vec<_>>();
+        let point = F::random(&mut prng);
+        let values = polys
+            .iter()
+            .map(|poly| pcs.eval(poly, &point))
+            .collect::<Vec<_>>();
+
+        let proof = {
+            let mut transcript = Transcript::new(b"TestFRI");
+            pcs.batch_prove(
+                &mut prng,
+                &mut transcript,
+                None,
+                &polys.iter().collect_vec(),
+                &point,
+                max_degree,
+            )
+            .unwrap()
+        };
+
+        let commitments_ref = commitments.iter().collect_vec();
+        {
+            let mut transcript = Transcript::new(b"TestFRI");
+            pnk!(pcs.batch_verify(
+                &mut transcript,
+                &commitments_ref,
+                max_degree,
+                &point,
+                &values,
+                &proof
+            ));
+        }
+
+        // A wrong evaluation is rejected.
+        {
+            let mut wrong_values = values.clone();
+            wrong_values[1].add_assign(&F::one());
+            let mut transcript = Transcript::new(b"TestFRI");
+            assert!(pcs
+                .batch_verify(
+                    &mut transcript,
+                    &commitments_ref,
+                    max_degree,
+                    &point,
+                    &wrong_values,
+                    &proof
+                )
+                .is_err());
+        }
+
+        // A tampered proof is rejected.
+        {
+            let mut wrong_proof = proof.clone();
+            wrong_proof.final_value.add_assign(&F::one());
+            let mut transcript = Transcript::new(b"TestFRI");
+            assert!(pcs
+                .batch_verify(
+                    &mut transcript,
+                    &commitments_ref,
+                    max_degree,
+                    &point,
+                    &values,
+                    &wrong_proof
+                )
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_batch_eval() {
+        check_batch_eval::<BLSScalar>(1);
+        check_batch_eval::<BLSScalar>(2);
+        check_batch_eval::<BLSScalar>(20);
+        check_batch_eval::<BN254Scalar>(32);
+    }
+
+    #[test]
+    fn test_degree_error() {
+        let mut prng = test_rng();
+        let pcs = FRIPolyComScheme::<BLSScalar>::new(8, 2, 8);
+        let poly = FpPolynomial::<BLSScalar>::random(&mut prng, 9);
+        assert!(pcs.commit(&poly).is_err());
+    }
+
+    #[test]
+    fn test_degree_bound() {
+        let mut prng = test_rng();
+        let pcs = FRIPolyComScheme::<BLSScalar>::new(20, 4, 16);
+        let poly = FpPolynomial::<BLSScalar>::random(&mut prng, 12);
+        let cm = pcs.commit(&poly).unwrap();
+        let point = BLSScalar::random(&mut prng);
+        let value = pcs.eval(&poly, &point);
+
+        assert!(pcs.prove(&mut prng, &poly, &point, 11).is_err());
+
+        let proof = pcs.prove(&mut prng, &poly, &point, 12).unwrap();
+        pnk!(pcs.verify(&cm, 12, &point, &value, &proof));
+        assert!(pcs.verify(&cm, 11, &point, &value, &proof).is_err());
+
+        // A proof that claims a smaller degree bound is rejected by the low-degree test.
+        let mut wrong_proof = proof.clone();
+        wrong_proof.degree = 11;
+        assert!(pcs.verify(&cm, 11, &point, &value, &wrong_proof).is_err());
+    }
+
+    #[test]
+    fn test_linear_combination() {
+        let mut prng = test_rng();
+        let pcs = FRIPolyComScheme::<BLSScalar>::new(20, 4, 16);
+        let poly1 = FpPolynomial::<BLSScalar>::random(&mut prng, 12);
+        let poly2 = FpPolynomial::<BLSScalar>::random(&mut prng, 15);
+        let cm1 = pcs.commit(&poly1).unwrap();
+        let cm2 = pcs.commit(&poly2).unwrap();
+        let exponent = BLSScalar::random(&mut prng);
+        let blinds = vec![BLSScalar::random(&mut prng), BLSScalar::random(&mut prng)];
+
+        // poly1 + exponent * poly2 - poly1 + poly2, with blinds over X^16 - 1.
+        let cm = pcs.apply_blind_factors(
+            &cm1.add(&cm2.mul(&exponent)).sub(&cm1).add(&cm2),
+            &blinds,
+            16,
+        );
+        let mut poly = poly2.mul_scalar(&exponent.add(&BLSScalar::one()));
+        for (i, blind) in blinds.iter().enumerate() {
+            poly.add_coef_assign(blind, i);
+            poly.add_coef_assign(&blind.neg(), 16 + i);
+        }
+
+        let point = BLSScalar::random(&mut prng);
+        let value = pcs.eval(&poly, &point);
+        let proof = {
+            let mut transcript = Transcript::new(b"TestFRI");
+            pcs.batch_prove_with_linear_combination(
+                &mut prng,
+                &mut transcript,
+                None,
+                &[],
+                &[&poly1, &poly2],
+                &[BLSScalar::zero(), exponent.add(&BLSScalar::one())],
+                &point,
+                20,
+            )
+            .unwrap()
+        };
+        {
+            let mut transcript = Transcript::new(b"TestFRI");
+            pnk!(pcs.batch_verify(&mut transcript, &[&cm], 20, &point, &[value], &proof));
+        }
+        {
+            let mut transcript = Transcript::new(b"TestFRI");
+            let wrong_value = value.add(&BLSScalar::one());
+            assert!(pcs
+                .batch_verify(&mut transcript, &[&cm], 20, &point, &[wrong_value], &proof)
+                .is_err());
+        }
+    }
+}
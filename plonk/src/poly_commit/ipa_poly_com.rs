@@ -0,0 +1,557 @@
+use crate::poly_commit::{
+    errors::PolyComSchemeError,
+    field_polynomial::FpPolynomial,
+    pcs::{HomomorphicPolyComElem, PolyComScheme, ToBytes},
+    transcript::PolyComTranscript,
+};
+use merlin::Transcript;
+use noah_algebra::{bn254::BN254G1, prelude::*, traits::Domain};
+use sha3::{Digest, Sha3_512};
+
+/// IPA commitment scheme over the `Group`.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Default)]
+pub struct IPACommitment<G>(pub G);
+
+impl<G: Group> ToBytes for IPACommitment<G> {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_compressed_bytes()
+    }
+}
+
+impl<G: Group + Sync + Send> HomomorphicPolyComElem for IPACommitment<G> {
+    type Scalar = G::ScalarType;
+    fn get_base() -> Self {
+        IPACommitment(G::get_base())
+    }
+
+    fn get_identity() -> Self {
+        IPACommitment(G::get_identity())
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        IPACommitment(self.0.add(&other.0))
+    }
+
+    fn add_assign(&mut self, other: &Self) {
+        self.0.add_assign(&other.0)
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        IPACommitment(self.0.sub(&other.0))
+    }
+
+    fn sub_assign(&mut self, other: &Self) {
+        self.0.sub_assign(&other.0)
+    }
+
+    fn mul(&self, exp: &G::ScalarType) -> Self {
+        IPACommitment(self.0.mul(exp))
+    }
+
+    fn mul_assign(&mut self, exp: &G::ScalarType) {
+        self.0 = self.0.mul(exp)
+    }
+}
+
+/// IPA evaluation proof.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct IPAEvalProof<G, F> {
+    /// The left cross terms, one for each round, blinded with `H`.
+    pub l_vec: Vec<G>,
+    /// The right cross terms, one for each round, blinded with `H`.
+    pub r_vec: Vec<G>,
+    /// The commitment to the random values masking the final opening.
+    pub delta: G,
+    /// The coefficient after the last round of folding, masked.
+    pub z1: F,
+    /// The blinding factor accumulated over the rounds, masked.
+    pub z2: F,
+}
+
+/// Inner-product-argument (Bulletproofs-style) polynomial commitment scheme over the `Group`.
+///
+/// It needs no trusted setup and no pairing, but the verifier runs in linear time.
+/// The cross terms of each round are blinded with the generator `H`, and the final opening
+/// is a Schnorr-style proof of knowledge, so that the evaluation proof reveals nothing
+/// beyond the evaluation.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IPAPolyComScheme<G> {
+    /// The generators for the coefficients, the number of which is a power of two.
+    pub generators: Vec<G>,
+    /// The generator for the inner product.
+    pub generator_u: G,
+    /// The generator for the blinding factors.
+    pub generator_h: G,
+}
+
+impl<G: Group> IPAPolyComScheme<G> {
+    /// Create a new instance of an IPA polynomial commitment scheme,
+    /// with the generators sampled by hashing.
+    /// `max_degree` - max degree of the polynomial.
+    pub fn new(max_degree: usize) -> IPAPolyComScheme<G> {
+        let n = (max_degree + 1).next_power_of_two();
+        let generators = (0..n)
+            .map(|i| {
+                G::from_hash(
+                    Sha3_512::new()
+                        .chain_update(b"IPA PCS generator")
+                        .chain_update((i as u64).to_le_bytes()),
+                )
+            })
+            .collect();
+        let generator_u = G::from_hash(Sha3_512::new().chain_update(b"IPA PCS generator u"));
+        let generator_h = G::from_hash(Sha3_512::new().chain_update(b"IPA PCS generator h"));
+
+        IPAPolyComScheme {
+            generators,
+            generator_u,
+            generator_h,
+        }
+    }
+
+    /// Create a new instance of an IPA polynomial commitment scheme from existing generators,
+    /// such as the Bulletproofs generators.
+    pub fn from_generators(
+        generators: Vec<G>,
+        generator_u: G,
+        generator_h: G,
+    ) -> Result<IPAPolyComScheme<G>> {
+        if !generators.len().is_power_of_two() {
+            return Err(eg!(PolyComSchemeError::DegreeError));
+        }
+        Ok(IPAPolyComScheme {
+            generators,
+            generator_u,
+            generator_h,
+        })
+    }
+
+    /// Commit to the coefficients of a polynomial.
+    pub fn commit_coefs(&self, coefs: &[G::ScalarType]) -> Result<G> {
+        if coefs.len() > self.generators.len() {
+            return Err(eg!(PolyComSchemeError::DegreeError));
+        }
+        let scalars = coefs.iter().collect_vec();
+        let points = self.generators[..coefs.len()].iter().collect_vec();
+        Ok(G::multi_exp(&scalars, &points))
+    }
+
+    /// Initialize the transcript of the inner product argument.
+    fn init_ipa_transcript(
+        &self,
+        cm: &G,
+        point: &G::ScalarType,
+        value: &G::ScalarType,
+    ) -> Transcript {
+        let mut transcript = Transcript::new(b"IPA PCS Evaluation Proof");
+        transcript.append_u64(b"num_generators", self.generators.len() as u64);
+        transcript.append_message(b"commitment", &cm.to_compressed_bytes());
+        transcript.append_field_elem(point);
+        transcript.append_field_elem(value);
+        transcript
+    }
+
+    /// Prove that the polynomial with the coefficients `coefs`, committed in `cm`,
+    /// evaluates to `value` on input `point`, with the blinds sampled from `prng`.
+    pub fn prove_coefs<R: CryptoRng + RngCore>(
+        &self,
+        prng: &mut R,
+        cm: &G,
+        coefs: &[G::ScalarType],
+        point: &G::ScalarType,
+    ) -> Result<IPAEvalProof<G, G::ScalarType>> {
+        let n = self.generators.len();
+        if coefs.len() > n {
+            return Err(eg!(PolyComSchemeError::DegreeError));
+        }
+
+        let mut a = coefs.to_vec();
+        a.resize(n, G::ScalarType::zero());
+        let mut b = powers(point, n);
+        let value = inner_product(&a, &b);
+
+        let mut transcript = self.init_ipa_transcript(cm, point, &value);
+        let u = self
+            .generator_u
+            .mul(&transcript.get_challenge_field_elem::<G::ScalarType>(b"u"));
+
+        let mut g = self.generators.clone();
+        let mut blind = G::ScalarType::zero();
+        let mut l_vec = Vec::new();
+        let mut r_vec = Vec::new();
+        while a.len() > 1 {
+            let half = a.len() / 2;
+            let (a_lo, a_hi) = a.split_at(half);
+            let (b_lo, b_hi) = b.split_at(half);
+            let (g_lo, g_hi) = g.split_at(half);
+
+            let l_blind = G::ScalarType::random(prng);
+            let r_blind = G::ScalarType::random(prng);
+            let l = G::multi_exp(&a_lo.iter().collect_vec(), &g_hi.iter().collect_vec())
+                .add(&u.mul(&inner_product(a_lo, b_hi)))
+                .add(&self.generator_h.mul(&l_blind));
+            let r = G::multi_exp(&a_hi.iter().collect_vec(), &g_lo.iter().collect_vec())
+                .add(&u.mul(&inner_product(a_hi, b_lo)))
+                .add(&self.generator_h.mul(&r_blind));
+            transcript.append_message(b"L", &l.to_compressed_bytes());
+            transcript.append_message(b"R", &r.to_compressed_bytes());
+            let x = transcript.get_challenge_field_elem::<G::ScalarType>(b"x");
+            let x_inv = x.inv().c(d!(PolyComSchemeError::PCSProveEvalError))?;
+            blind.add_assign(&l_blind.mul(&x.square()));
+            blind.add_assign(&r_blind.mul(&x_inv.square()));
+
+            a = (0..half)
+                .map(|i| a_lo[i].mul(&x).add(&a_hi[i].mul(&x_inv)))
+                .collect();
+            b = (0..half)
+                .map(|i| b_lo[i].mul(&x_inv).add(&b_hi[i].mul(&x)))
+                .collect();
+            g = (0..half)
+                .map(|i| g_lo[i].mul(&x_inv).add(&g_hi[i].mul(&x)))
+                .collect();
+            l_vec.push(l);
+            r_vec.push(r);
+        }
+
+        // Prove knowledge of `a` and `blind` such that the folded commitment is
+        // `a * (G + b * U) + blind * H`, without revealing them.
+        let d = G::ScalarType::random(prng);
+        let s = G::ScalarType::random(prng);
+        let delta = g[0]
+            .add(&u.mul(&b[0]))
+            .mul(&d)
+            .add(&self.generator_h.mul(&s));
+        transcript.append_message(b"delta", &delta.to_compressed_bytes());
+        let c = transcript.get_challenge_field_elem::<G::ScalarType>(b"c");
+
+        Ok(IPAEvalProof {
+            l_vec,
+            r_vec,
+            delta,
+            z1: a[0].mul(&c).add(&d),
+            z2: blind.mul(&c).add(&s),
+        })
+    }
+
+    /// Verify that the polynomial committed in `cm` evaluates to `value` on input `point`.
+    pub fn verify_coefs(
+        &self,
+        cm: &G,
+        point: &G::ScalarType,
+        value: &G::ScalarType,
+        proof: &IPAEvalProof<G, G::ScalarType>,
+    ) -> Result<()> {
+        let n = self.generators.len();
+        let rounds = n.trailing_zeros() as usize;
+        if proof.l_vec.len() != rounds || proof.r_vec.len() != rounds {
+            return Err(eg!(PolyComSchemeError::PCSProveEvalError));
+        }
+
+        let mut transcript = self.init_ipa_transcript(cm, point, value);
+        let u = self
+            .generator_u
+            .mul(&transcript.get_challenge_field_elem::<G::ScalarType>(b"u"));
+
+        let mut cm = cm.add(&u.mul(value));
+        let mut challenges = Vec::with_capacity(rounds);
+        for (l, r) in proof.l_vec.iter().zip(proof.r_vec.iter()) {
+            transcript.append_message(b"L", &l.to_compressed_bytes());
+            transcript.append_message(b"R", &r.to_compressed_bytes());
+            let x = transcript.get_challenge_field_elem::<G::ScalarType>(b"x");
+            let x_inv = x.inv().c(d!(PolyComSchemeError::PCSProveEvalError))?;
+
+            cm = cm.add(&l.mul(&x.square())).add(&r.mul(&x_inv.square()));
+            challenges.push((x, x_inv));
+        }
+
+        // The folded generator is `\sum_i s_i G_i`, where `s_i` takes `x` in the rounds
+        // where `i` falls into the upper half, and `x^{-1}` otherwise.
+        let mut s = vec![G::ScalarType::one(); n];
+        for (round, (x, x_inv)) in challenges.iter().enumerate() {
+            let bit = 1 << (rounds - 1 - round);
+            for (i, s_i) in s.iter_mut().enumerate() {
+                s_i.mul_assign(if i & bit != 0 { x } else { x_inv });
+            }
+        }
+        let g = G::multi_exp(
+            &s.iter().collect_vec(),
+            &self.generators.iter().collect_vec(),
+        );
+        let b = inner_product(&s, &powers(point, n));
+
+        transcript.append_message(b"delta", &proof.delta.to_compressed_bytes());
+        let c = transcript.get_challenge_field_elem::<G::ScalarType>(b"c");
+        if cm.mul(&c).add(&proof.delta)
+            == g.add(&u.mul(&b))
+                .mul(&proof.z1)
+                .add(&self.generator_h.mul(&proof.z2))
+        {
+            Ok(())
+        } else {
+            Err(eg!(PolyComSchemeError::PCSProveEvalError))
+        }
+    }
+}
+
+/// IPA commitment scheme over the BN254 curve
+pub type IPAPolyComSchemeBN254 = IPAPolyComScheme<BN254G1>;
+
+impl<G: Group + Sync + Send> PolyComScheme for IPAPolyComScheme<G>
+where
+    G::ScalarType: Domain,
+{
+    type Field = G::ScalarType;
+    type Commitment = IPACommitment<G>;
+    type EvalProof = IPAEvalProof<G, G::ScalarType>;
+
+    fn max_degree(&self) -> usize {
+        self.generators.len() - 1
+    }
+
+    fn commit(&self, polynomial: &FpPolynomial<Self::Field>) -> Result<Self::Commitment> {
+        let cm = self.commit_coefs(polynomial.get_coefs_ref()).c(d!())?;
+        Ok(IPACommitment(cm))
+    }
+
+    fn eval(&self, poly: &FpPolynomial<Self::Field>, point: &Self::Field) -> Self::Field {
+        poly.eval(point)
+    }
+
+    fn apply_blind_factors(
+        &self,
+        commitment: &Self::Commitment,
+        blinds: &[Self::Field],
+        zeroing_degree: usize,
+    ) -> Self::Commitment {
+        let mut commitment = commitment.0.clone();
+        for (i, blind) in blinds.iter().enumerate() {
+            let mut blind = blind.clone();
+            commitment = commitment + &(self.generators[i] * &blind);
+            blind = blind.neg();
+            commitment = commitment + &(self.generators[zeroing_degree + i] * &blind);
+        }
+        IPACommitment(commitment)
+    }
+
+    fn prove<R: CryptoRng + RngCore>(
+        &self,
+        prng: &mut R,
+        poly: &FpPolynomial<Self::Field>,
+        x: &Self::Field,
+        max_degree: usize,
+    ) -> Result<Self::EvalProof> {
+        if poly.degree() > max_degree {
+            return Err(eg!(PolyComSchemeError::DegreeError));
+        }
+        let cm = self.commit(poly).c(d!())?;
+        self.prove_coefs(prng, &cm.0, poly.get_coefs_ref(), x)
+            .c(d!())
+    }
+
+    fn verify(
+        &self,
+        cm: &Self::Commitment,
+        _degree: usize,
+        point: &Self::Field,
+        eval: &Self::Field,
+        proof: &Self::EvalProof,
+    ) -> Result<()> {
+        self.verify_coefs(&cm.0, point, eval, proof).c(d!())
+    }
+
+    /// The proof opens the random linear combination of the polynomials, which matches the
+    /// combined commitment computed by the verifier in `batch`.
+    /// Since no new commitment is produced, `lagrange_pcs` is not used.
+    fn batch_prove<R: CryptoRng + RngCore>(
+        &self,
+        prng: &mut R,
+        transcript: &mut Transcript,
+        _lagrange_pcs: Option<&Self>,
+        polys: &[&FpPolynomial<Self::Field>],
+        point: &Self::Field,
+        max_degree: usize,
+    ) -> Result<Self::EvalProof> {
+        assert!(!polys.is_empty());
+
+        Self::init_pcs_batch_eval_transcript(transcript, max_degree, point);
+
+        let alpha = transcript.get_challenge_field_elem(b"alpha");
+        let mut h = FpPolynomial::<Self::Field>::zero();
+        let mut multiplier = Self::Field::one();
+        for poly in polys.iter() {
+            h.add_assign(&poly.mul_scalar(&multiplier));
+            multiplier.mul_assign(&alpha);
+        }
+
+        let cm = self.commit(&h).c(d!())?;
+        self.prove_coefs(prng, &cm.0, h.get_coefs_ref(), point)
+            .c(d!())
+    }
+
+    fn batch_verify_diff_points(
+        &self,
+        _transcript: &mut Transcript,
+        cm_vec: &[Self::Commitment],
+        _degree: usize,
+        point_vec: &[Self::Field],
+        eval_vec: &[Self::Field],
+        proofs: &[Self::EvalProof],
+        _challenge: &Self::Field,
+    ) -> Result<()> {
+        assert!(!proofs.is_empty());
+        assert_eq!(proofs.len(), point_vec.len());
+        assert_eq!(proofs.len(), eval_vec.len());
+        assert_eq!(proofs.len(), cm_vec.len());
+
+        for (((cm, point), eval), proof) in cm_vec
+            .iter()
+            .zip(point_vec.iter())
+            .zip(eval_vec.iter())
+            .zip(proofs.iter())
+        {
+            self.verify_coefs(&cm.0, point, eval, proof).c(d!())?;
+        }
+        Ok(())
+    }
+
+    fn shrink_to_verifier_only(&self) -> Result<Self> {
+        Ok(Self {
+            generators: self.generators.clone(),
+            generator_u: self.generator_u,
+            generator_h: self.generator_h,
+        })
+    }
+}
+
+/// Compute `(1, x, x^2, ..., x^{n-1})`.
+fn powers<F: Scalar>(x: &F, n: usize) -> Vec<F> {
+    let mut res = Vec::with_capacity(n);
+    let mut cur = F::one();
+    for _ in 0..n {
+        res.push(cur);
+        cur.mul_assign(x);
+    }
+    res
+}
+
+/// Compute the inner product of two vectors.
+fn inner_product<F: Scalar>(a: &[F], b: &[F]) -> F {
+    a.iter().zip(b.iter()).map(|(x, y)| x.mul(y)).sum()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::poly_commit::{
+        field_polynomial::FpPolynomial,
+        ipa_poly_com::{IPAPolyComScheme, IPAPolyComSchemeBN254},
+        pcs::{HomomorphicPolyComElem, PolyComScheme},
+    };
+    use ark_std::test_rng;
+    use merlin::Transcript;
+    use noah_algebra::{
+        bn254::BN254Scalar,
+        prelude::*,
+        secq256k1::{SECQ256K1Scalar, SECQ256K1G1},
+    };
+
+    #[test]
+    fn test_ipa_secq256k1() {
+        let mut prng = test_rng();
+        let pcs = IPAPolyComScheme::<SECQ256K1G1>::new(15);
+        let coefs = (0..13)
+            .map(|_| SECQ256K1Scalar::random(&mut prng))
+            .collect_vec();
+        let point = SECQ256K1Scalar::random(&mut prng);
+        let value = coefs
+            .iter()
+            .rev()
+            .fold(SECQ256K1Scalar::zero(), |acc, c| acc.mul(&point).add(c));
+
+        let cm = pcs.commit_coefs(&coefs).unwrap();
+        let proof = pcs.prove_coefs(&mut prng, &cm, &coefs, &point).unwrap();
+        pnk!(pcs.verify_coefs(&cm, &point, &value, &proof));
+
+        let wrong_value = value.add(&SECQ256K1Scalar::one());
+        assert!(pcs.verify_coefs(&cm, &point, &wrong_value, &proof).is_err());
+
+        let mut wrong_proof = proof.clone();
+        wrong_proof.z2.add_assign(&SECQ256K1Scalar::one());
+        assert!(pcs.verify_coefs(&cm, &point, &value, &wrong_proof).is_err());
+    }
+
+    #[test]
+    fn test_homomorphic_poly_com_elem() {
+        let mut prng = test_rng();
+        let pcs = IPAPolyComSchemeBN254::new(10);
+        let poly1 = FpPolynomial::<BN254Scalar>::random(&mut prng, 5);
+        let poly2 = FpPolynomial::<BN254Scalar>::random(&mut prng, 7);
+        let cm1 = pcs.commit(&poly1).unwrap();
+        let cm2 = pcs.commit(&poly2).unwrap();
+        assert_eq!(pcs.commit(&poly1.add(&poly2)).unwrap(), cm1.add(&cm2));
+
+        let exponent = BN254Scalar::random(&mut prng);
+        assert_eq!(
+            pcs.commit(&poly1.mul_scalar(&exponent)).unwrap(),
+            cm1.mul(&exponent)
+        );
+    }
+
+    #[test]
+    fn test_eval() {
+        let mut prng = test_rng();
+        let pcs = IPAPolyComSchemeBN254::new(10);
+        let poly = FpPolynomial::<BN254Scalar>::random(&mut prng, 10);
+        let point = BN254Scalar::random(&mut prng);
+        let eval = pcs.eval(&poly, &point);
+        let cm = pcs.commit(&poly).unwrap();
+
+        assert!(pcs.prove(&mut prng, &poly, &point, 9).is_err());
+        let proof = pcs.prove(&mut prng, &poly, &point, 10).unwrap();
+        pnk!(pcs.verify(&cm, 10, &point, &eval, &proof));
+
+        // The blinds are sampled afresh for each proof.
+        let other_proof = pcs.prove(&mut prng, &poly, &point, 10).unwrap();
+        assert_ne!(proof, other_proof);
+        pnk!(pcs.verify(&cm, 10, &point, &eval, &other_proof));
+
+        let new_pcs = pcs.shrink_to_verifier_only().unwrap();
+        pnk!(new_pcs.verify(&cm, 10, &point, &eval, &proof));
+
+        let wrong_eval = eval.add(&BN254Scalar::one());
+        assert!(pcs.verify(&cm, 10, &point, &wrong_eval, &proof).is_err());
+    }
+
+    #[test]
+    fn test_batch_eval() {
+        let mut prng = test_rng();
+        let pcs = IPAPolyComSchemeBN254::new(20);
+        let polys = (0..3)
+            .map(|_| FpPolynomial::<BN254Scalar>::random(&mut prng, 20))
+            .collect_vec();
+        let cms = polys.iter().map(|p| pcs.commit(p).unwrap()).collect_vec();
+        let point = BN254Scalar::random(&mut prng);
+        let evals = polys.iter().map(|p| pcs.eval(p, &point)).collect_vec();
+
+        let proof = {
+            let mut transcript = Transcript::new(b"TestPCS");
+            pcs.batch_prove(
+                &mut prng,
+                &mut transcript,
+                None,
+                &polys.iter().collect_vec(),
+                &point,
+                20,
+            )
+            .unwrap()
+        };
+        let mut transcript = Transcript::new(b"TestPCS");
+        pnk!(pcs.batch_verify(
+            &mut transcript,
+            &cms.iter().collect_vec(),
+            20,
+            &point,
+            &evals,
+            &proof,
+        ));
+    }
+}
@@ -2,10 +2,12 @@ use crate::poly_commit::{
     errors::PolyComSchemeError,
     field_polynomial::FpPolynomial,
     pcs::{HomomorphicPolyComElem, PolyComScheme, ToBytes},
+    transcript::PolyComTranscript,
 };
 use merlin::Transcript;
 use noah_algebra::{
-    bls12_381::{BLSPairingEngine, BLSScalar, BLSG1},
+    bls12_381::BLSPairingEngine,
+    bn254::BN254PairingEngine,
     prelude::*,
     traits::{Domain, Pairing},
 };
@@ -23,14 +25,14 @@ where
     }
 }
 
-impl HomomorphicPolyComElem for KZGCommitment<BLSG1> {
-    type Scalar = BLSScalar;
+impl<G: Group + Sync + Send> HomomorphicPolyComElem for KZGCommitment<G> {
+    type Scalar = G::ScalarType;
     fn get_base() -> Self {
-        KZGCommitment(BLSG1::get_base())
+        KZGCommitment(G::get_base())
     }
 
     fn get_identity() -> Self {
-        KZGCommitment(BLSG1::get_identity())
+        KZGCommitment(G::get_identity())
     }
 
     fn add(&self, other: &Self) -> Self {
@@ -49,12 +51,12 @@ impl HomomorphicPolyComElem for KZGCommitment<BLSG1> {
         self.0.sub_assign(&other.0)
     }
 
-    fn mul(&self, exp: &BLSScalar) -> Self {
+    fn mul(&self, exp: &G::ScalarType) -> Self {
         KZGCommitment(self.0.mul(exp))
     }
 
-    fn mul_assign(&mut self, exp: &BLSScalar) {
-        self.0.mul_assign(&exp)
+    fn mul_assign(&mut self, exp: &G::ScalarType) {
+        self.0 = self.0.mul(exp)
     }
 }
 
@@ -204,15 +206,23 @@ impl<P: Pairing> KZGCommitmentScheme<P> {
 /// KZG commitment scheme over the BLS12-381 curve
 pub type KZGCommitmentSchemeBLS = KZGCommitmentScheme<BLSPairingEngine>;
 
-impl<'b> PolyComScheme for KZGCommitmentSchemeBLS {
-    type Field = BLSScalar;
-    type Commitment = KZGCommitment<BLSG1>;
+/// KZG commitment scheme over the BN254 curve
+pub type KZGCommitmentSchemeBN254 = KZGCommitmentScheme<BN254PairingEngine>;
+
+impl<P: Pairing> PolyComScheme for KZGCommitmentScheme<P>
+where
+    P::ScalarField: Domain,
+    P::G1: Sync + Send,
+{
+    type Field = P::ScalarField;
+    type Commitment = KZGCommitment<P::G1>;
+    type EvalProof = KZGCommitment<P::G1>;
 
     fn max_degree(&self) -> usize {
         self.public_parameter_group_1.len() - 1
     }
 
-    fn commit(&self, polynomial: &FpPolynomial<BLSScalar>) -> Result<Self::Commitment> {
+    fn commit(&self, polynomial: &FpPolynomial<Self::Field>) -> Result<Self::Commitment> {
         let coefs = polynomial.get_coefs_ref();
 
         let degree = polynomial.degree();
@@ -221,15 +231,13 @@ impl<'b> PolyComScheme for KZGCommitmentSchemeBLS {
             return Err(eg!(PolyComSchemeError::DegreeError));
         }
 
-        let coefs_poly_bls_scalar_ref: Vec<&BLSScalar> = coefs.iter().collect();
-        let pub_param_group_1_as_ref: Vec<&BLSG1> = self.public_parameter_group_1[0..degree + 1]
+        let coefs_poly_scalar_ref: Vec<&Self::Field> = coefs.iter().collect();
+        let pub_param_group_1_as_ref: Vec<&P::G1> = self.public_parameter_group_1[0..degree + 1]
             .iter()
             .collect();
 
-        let commitment_value = BLSG1::multi_exp_unsafe(
-            &coefs_poly_bls_scalar_ref[..],
-            &pub_param_group_1_as_ref[..],
-        );
+        let commitment_value =
+            P::G1::multi_exp_unsafe(&coefs_poly_scalar_ref[..], &pub_param_group_1_as_ref[..]);
 
         Ok(KZGCommitment(commitment_value))
     }
@@ -254,12 +262,13 @@ impl<'b> PolyComScheme for KZGCommitmentSchemeBLS {
         KZGCommitment(commitment)
     }
 
-    fn prove(
+    fn prove<R: CryptoRng + RngCore>(
         &self,
+        _prng: &mut R,
         poly: &FpPolynomial<Self::Field>,
         x: &Self::Field,
         max_degree: usize,
-    ) -> Result<Self::Commitment> {
+    ) -> Result<Self::EvalProof> {
         let eval = poly.eval(x);
 
         if poly.degree() > max_degree {
@@ -295,7 +304,7 @@ impl<'b> PolyComScheme for KZGCommitmentSchemeBLS {
         _degree: usize,
         point: &Self::Field,
         eval: &Self::Field,
-        proof: &Self::Commitment,
+        proof: &Self::EvalProof,
     ) -> Result<()> {
         let g1_0 = self.public_parameter_group_1[0].clone();
         let g2_0 = self.public_parameter_group_2[0].clone();
@@ -304,13 +313,12 @@ impl<'b> PolyComScheme for KZGCommitmentSchemeBLS {
         let x_minus_point_group_element_group_2 = &g2_1.sub(&g2_0.mul(point));
 
         let left_pairing_eval = if eval.is_zero() {
-            BLSPairingEngine::pairing(&cm.0, &g2_0)
+            P::pairing(&cm.0, &g2_0)
         } else {
-            BLSPairingEngine::pairing(&cm.0.sub(&g1_0.mul(eval)), &g2_0)
+            P::pairing(&cm.0.sub(&g1_0.mul(eval)), &g2_0)
         };
 
-        let right_pairing_eval =
-            BLSPairingEngine::pairing(&proof.0, &x_minus_point_group_element_group_2);
+        let right_pairing_eval = P::pairing(&proof.0, &x_minus_point_group_element_group_2);
 
         if left_pairing_eval == right_pairing_eval {
             Ok(())
@@ -319,6 +327,69 @@ impl<'b> PolyComScheme for KZGCommitmentSchemeBLS {
         }
     }
 
+    fn batch_prove<R: CryptoRng + RngCore>(
+        &self,
+        _prng: &mut R,
+        transcript: &mut Transcript,
+        lagrange_pcs: Option<&Self>,
+        polys: &[&FpPolynomial<Self::Field>],
+        point: &Self::Field,
+        max_degree: usize,
+    ) -> Result<Self::EvalProof> {
+        assert!(polys.len() > 0);
+
+        Self::init_pcs_batch_eval_transcript(transcript, max_degree, point);
+
+        let alpha = transcript.get_challenge_field_elem(b"alpha");
+        let mut h = FpPolynomial::<Self::Field>::zero();
+        let mut multiplier = Self::Field::one();
+        let z = FpPolynomial::from_zeroes(&[point.clone()]);
+
+        for poly in polys.iter() {
+            let mut poly = (*poly).clone();
+            let eval_value = poly.eval(point);
+            poly.sub_assign(&FpPolynomial::from_coefs(vec![eval_value]));
+            poly.mul_scalar_assign(&multiplier);
+            h.add_assign(&poly);
+            multiplier.mul_assign(&alpha);
+        }
+
+        let (q, rem) = h.div_rem(&z);
+        if !rem.is_zero() {
+            return Err(eg!());
+        }
+
+        if let Some(lagrange_pcs) = lagrange_pcs {
+            let degree = q.degree();
+            let mut max_power_of_2 = degree;
+            for i in (0..=degree).rev() {
+                if (i & (i - 1)) == 0 {
+                    max_power_of_2 = i;
+                    break;
+                }
+            }
+
+            let mut blinds = vec![];
+            for i in &q.coefs[max_power_of_2..] {
+                blinds.push(i.neg());
+            }
+
+            let mut new_coefs = q.coefs[..max_power_of_2].to_vec();
+            for (i, v) in blinds.iter().enumerate() {
+                new_coefs[i] = new_coefs[i] - v;
+            }
+
+            let sub_q = FpPolynomial::from_coefs(new_coefs);
+            let (_domain, q_eval) = FpPolynomial::fft(&sub_q, max_power_of_2).c(d!())?;
+            let q_eval = FpPolynomial::from_coefs(q_eval);
+
+            let cm = lagrange_pcs.commit(&q_eval).c(d!())?;
+            Ok(self.apply_blind_factors(&cm, &blinds, max_power_of_2))
+        } else {
+            self.commit(&q)
+        }
+    }
+
     fn batch_verify_diff_points(
         &self,
         _transcript: &mut Transcript,
@@ -326,7 +397,7 @@ impl<'b> PolyComScheme for KZGCommitmentSchemeBLS {
         _degree: usize,
         point_vec: &[Self::Field],
         eval_vec: &[Self::Field],
-        proofs: &[Self::Commitment],
+        proofs: &[Self::EvalProof],
         challenge: &Self::Field,
     ) -> Result<()> {
         assert!(proofs.len() > 0);
@@ -360,8 +431,8 @@ impl<'b> PolyComScheme for KZGCommitmentSchemeBLS {
         right_first.sub_assign(&g1_0.mul(&right_first_val));
         right_first.add_assign(&right_first_comm);
 
-        let left_pairing_eval = BLSPairingEngine::pairing(&left_first, &left_second);
-        let right_pairing_eval = BLSPairingEngine::pairing(&right_first, &right_second);
+        let left_pairing_eval = P::pairing(&left_first, &left_second);
+        let right_pairing_eval = P::pairing(&right_first, &right_second);
 
         if left_pairing_eval == right_pairing_eval {
             Ok(())
@@ -391,8 +462,9 @@ mod tests_kzg_impl {
     use ark_std::test_rng;
     use noah_algebra::{
         bls12_381::{BLSPairingEngine, BLSScalar, BLSG1},
+        bn254::BN254PairingEngine,
         prelude::*,
-        traits::Pairing,
+        traits::{Domain, Pairing},
     };
 
     fn check_public_parameters_generation<P: Pairing>() {
@@ -471,11 +543,13 @@ mod tests_kzg_impl {
     #[test]
     fn test_public_parameters() {
         check_public_parameters_generation::<BLSPairingEngine>();
+        check_public_parameters_generation::<BN254PairingEngine>();
     }
 
     #[test]
     fn test_generation_of_crs() {
         generation_of_crs::<BLSPairingEngine>();
+        generation_of_crs::<BN254PairingEngine>();
     }
 
     #[test]
@@ -504,10 +578,18 @@ mod tests_kzg_impl {
 
     #[test]
     fn test_eval() {
+        check_eval::<BLSPairingEngine>();
+        check_eval::<BN254PairingEngine>();
+    }
+
+    fn check_eval<P: Pairing>()
+    where
+        P::ScalarField: Domain,
+        P::G1: Sync + Send,
+    {
         let mut prng = test_rng();
-        let pcs = KZGCommitmentSchemeBLS::new(10, &mut prng);
-        type Field = BLSScalar;
-        let one = Field::one();
+        let pcs = KZGCommitmentScheme::<P>::new(10, &mut prng);
+        let one = P::ScalarField::one();
         let two = one.add(&one);
         let three = two.add(&one);
         let four = three.add(&one);
@@ -522,10 +604,10 @@ mod tests_kzg_impl {
 
         // Check that an error is returned if the degree of the polynomial exceeds the maximum degree.
         let wrong_max_degree = 1;
-        let res = pcs.prove(&fq_poly, &point, wrong_max_degree);
+        let res = pcs.prove(&mut prng, &fq_poly, &point, wrong_max_degree);
         assert!(res.is_err());
 
-        let proof = pcs.prove(&fq_poly, &point, max_degree).unwrap();
+        let proof = pcs.prove(&mut prng, &fq_poly, &point, max_degree).unwrap();
 
         let res = pcs.verify(&commitment_value, degree, &point, &seven, &proof);
         pnk!(res);
@@ -4,6 +4,12 @@ pub mod field_polynomial;
 /// Module for KZG polynomial commitment scheme.
 pub mod kzg_poly_com;
 
+/// Module for FRI polynomial commitment scheme.
+pub mod fri_poly_com;
+
+/// Module for IPA polynomial commitment scheme.
+pub mod ipa_poly_com;
+
 /// Module for polynomial commitment traits.
 pub mod pcs;
 
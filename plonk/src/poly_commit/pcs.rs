@@ -57,6 +57,16 @@ pub trait PolyComScheme: Sized {
         + Sync
         + for<'de> Deserialize<'de>;
 
+    /// Type of the evaluation proof.
+    type EvalProof: Debug
+        + PartialEq
+        + Eq
+        + Clone
+        + Serialize
+        + Sync
+        + Send
+        + for<'de> Deserialize<'de>;
+
     /// Return maximal supported degree
     fn max_degree(&self) -> usize;
 
@@ -67,12 +77,14 @@ pub trait PolyComScheme: Sized {
     fn eval(&self, polynomial: &FpPolynomial<Self::Field>, point: &Self::Field) -> Self::Field;
 
     /// Evaluate the polynomial producing a proof for it.
-    fn prove(
+    /// `prng` provides the randomness of hiding schemes.
+    fn prove<R: CryptoRng + RngCore>(
         &self,
+        prng: &mut R,
         polynomial: &FpPolynomial<Self::Field>,
         point: &Self::Field,
         max_degree: usize,
-    ) -> Result<Self::Commitment>;
+    ) -> Result<Self::EvalProof>;
 
     /// Verify an evaluation proof that polynomial inside commitment
     /// evaluates to `value` on input `point `.
@@ -82,7 +94,7 @@ pub trait PolyComScheme: Sized {
         degree: usize,
         point: &Self::Field,
         value: &Self::Field,
-        proof: &Self::Commitment,
+        proof: &Self::EvalProof,
     ) -> Result<()>;
 
     /// Apply blind factors over the vanishing part
@@ -97,66 +109,39 @@ pub trait PolyComScheme: Sized {
     /// `param` stores the instance parameters to be appended to the transcript.
     /// When `param` is `None`, our function assumes `params` are implicit
     /// in the transcript already.
-    fn batch_prove(
+    /// `prng` provides the randomness of hiding schemes.
+    fn batch_prove<R: CryptoRng + RngCore>(
         &self,
+        prng: &mut R,
         transcript: &mut Transcript,
         lagrange_pcs: Option<&Self>,
         polys: &[&FpPolynomial<Self::Field>],
         point: &Self::Field,
         max_degree: usize,
-    ) -> Result<Self::Commitment> {
-        assert!(polys.len() > 0);
-
-        Self::init_pcs_batch_eval_transcript(transcript, max_degree, point);
+    ) -> Result<Self::EvalProof>;
 
-        let alpha = transcript.get_challenge_field_elem(b"alpha");
-        let mut h = FpPolynomial::<Self::Field>::zero();
-        let mut multiplier = Self::Field::one();
-        let z = FpPolynomial::from_zeroes(&[point.clone()]);
-
-        for poly in polys.iter() {
-            let mut poly = (*poly).clone();
-            let eval_value = poly.eval(point);
-            poly.sub_assign(&FpPolynomial::from_coefs(vec![eval_value]));
-            poly.mul_scalar_assign(&multiplier);
-            h.add_assign(&poly);
-            multiplier.mul_assign(&alpha);
-        }
-
-        let (q, rem) = h.div_rem(&z);
-        if !rem.is_zero() {
-            return Err(eg!());
-        }
-
-        if let Some(lagrange_pcs) = lagrange_pcs {
-            let degree = q.degree();
-            let mut max_power_of_2 = degree;
-            for i in (0..=degree).rev() {
-                if (i & (i - 1)) == 0 {
-                    max_power_of_2 = i;
-                    break;
-                }
-            }
-
-            let mut blinds = vec![];
-            for i in &q.coefs[max_power_of_2..] {
-                blinds.push(i.neg());
-            }
-
-            let mut new_coefs = q.coefs[..max_power_of_2].to_vec();
-            for (i, v) in blinds.iter().enumerate() {
-                new_coefs[i] = new_coefs[i] - v;
-            }
-
-            let sub_q = FpPolynomial::from_coefs(new_coefs);
-            let (_domain, q_eval) = FpPolynomial::fft(&sub_q, max_power_of_2).c(d!())?;
-            let q_eval = FpPolynomial::from_coefs(q_eval);
-
-            let cm = lagrange_pcs.commit(&q_eval).c(d!())?;
-            Ok(self.apply_blind_factors(&cm, &blinds, max_power_of_2))
-        } else {
-            self.commit(&q)
+    /// Batch proof for polynomial evaluation, where the last polynomial opened is the
+    /// linear combination of `lc_polys` with coefficients `lc_coefs`, and the verifier
+    /// checks it against the same combination of their commitments.
+    /// By default, the combined polynomial is computed and opened with the others.
+    fn batch_prove_with_linear_combination<R: CryptoRng + RngCore>(
+        &self,
+        prng: &mut R,
+        transcript: &mut Transcript,
+        lagrange_pcs: Option<&Self>,
+        polys: &[&FpPolynomial<Self::Field>],
+        lc_polys: &[&FpPolynomial<Self::Field>],
+        lc_coefs: &[Self::Field],
+        point: &Self::Field,
+        max_degree: usize,
+    ) -> Result<Self::EvalProof> {
+        let mut combined = FpPolynomial::<Self::Field>::zero();
+        for (poly, coef) in lc_polys.iter().zip(lc_coefs.iter()) {
+            combined.add_assign(&poly.mul_scalar(coef));
         }
+        let mut polys = polys.to_vec();
+        polys.push(&combined);
+        self.batch_prove(prng, transcript, lagrange_pcs, &polys, point, max_degree)
     }
 
     /// Combine multiple commitments into one commitment.
@@ -190,7 +175,7 @@ pub trait PolyComScheme: Sized {
         max_degree: usize,
         point: &Self::Field,
         values: &[Self::Field],
-        proof: &Self::Commitment,
+        proof: &Self::EvalProof,
     ) -> Result<()> {
         let (cm_combined, eval_combined) =
             self.batch(transcript, commitments, max_degree, point, values);
@@ -207,7 +192,7 @@ pub trait PolyComScheme: Sized {
         _degree: usize,
         point_vec: &[Self::Field],
         eval_vec: &[Self::Field],
-        proof: &[Self::Commitment],
+        proof: &[Self::EvalProof],
         challenge: &Self::Field,
     ) -> Result<()>;
 
@@ -240,7 +225,7 @@ pub trait PolyComScheme: Sized {
 #[allow(non_snake_case)]
 mod test {
     use crate::poly_commit::{
-        field_polynomial::FpPolynomial, kzg_poly_com::KZGCommitmentScheme, pcs::PolyComScheme,
+        field_polynomial::FpPolynomial, kzg_poly_com::KZGCommitmentSchemeBLS, pcs::PolyComScheme,
     };
     use ark_std::test_rng;
     use merlin::Transcript;
@@ -255,10 +240,10 @@ mod test {
 
         let poly = FpPolynomial::from_zeroes(&[zero, one, two]);
         let degree = poly.degree();
-        let pcs = KZGCommitmentScheme::new(degree, &mut prng);
+        let pcs = KZGCommitmentSchemeBLS::new(degree, &mut prng);
         let com = pcs.commit(&poly).unwrap();
         let point = BLSScalar::random(&mut prng);
-        let proof = pcs.prove(&poly, &point, degree).unwrap();
+        let proof = pcs.prove(&mut prng, &poly, &point, degree).unwrap();
         let eval = pcs.eval(&poly, &point);
         assert!(pcs.verify(&com, degree, &point, &eval, &proof).is_ok());
     }
@@ -275,7 +260,7 @@ mod test {
         let poly2 = FpPolynomial::from_coefs(vec![one, zero, three]);
         let poly3 = FpPolynomial::from_coefs(vec![two, two, two, two]);
         let degree = poly3.degree();
-        let pcs = KZGCommitmentScheme::new(degree + 1, &mut prng);
+        let pcs = KZGCommitmentSchemeBLS::new(degree + 1, &mut prng);
         let com1 = pcs.commit(&poly1).unwrap();
         let com2 = pcs.commit(&poly2).unwrap();
         let com3 = pcs.commit(&poly3).unwrap();
@@ -283,6 +268,7 @@ mod test {
         let proof = {
             let mut transcript = Transcript::new(b"TestPCS");
             pcs.batch_prove(
+                &mut prng,
                 &mut transcript,
                 None,
                 &[&poly1, &poly2, &poly3],